@@ -1,7 +1,8 @@
 use crate::syntax_pos::{BytePos, Span};
-use chashmap::{CHashMap, ReadGuard};
+use hashbrown::HashMap;
+use parking_lot::{MappedRwLockReadGuard, RwLock, RwLockReadGuard};
 
-type CommentMap = CHashMap<BytePos, Vec<Comment>>;
+type CommentMap = RwLock<HashMap<BytePos, Vec<Comment>>>;
 
 #[derive(Debug, Default)]
 pub struct Comments {
@@ -11,26 +12,60 @@ pub struct Comments {
 
 impl Comments {
     pub fn add_leading(&self, pos: BytePos, cmt: Vec<Comment>) {
-        self.leading.insert(pos, cmt);
+        self.leading.write().insert(pos, cmt);
     }
 
     pub fn add_trailing(&self, pos: BytePos, cmt: Comment) {
-        self.trailing.alter(pos, |v| match v {
-            Some(mut value) => {
-                value.push(cmt);
-                Some(value)
-            }
-            None => Some(vec![cmt]),
-        });
+        self.trailing.write().entry(pos).or_default().push(cmt);
     }
 
-    pub fn trailing_comments(&self, pos: BytePos) -> Option<ReadGuard<'_, BytePos, Vec<Comment>>> {
-        self.trailing.get(&pos)
+    pub fn trailing_comments(&self, pos: BytePos) -> Option<ReadGuard<'_>> {
+        get(&self.trailing, pos)
     }
 
-    pub fn leading_comments(&self, pos: BytePos) -> Option<ReadGuard<'_, BytePos, Vec<Comment>>> {
-        self.leading.get(&pos)
+    pub fn leading_comments(&self, pos: BytePos) -> Option<ReadGuard<'_>> {
+        get(&self.leading, pos)
     }
+
+    /// Returns all leading comments along with the position of the token they
+    /// precede, ordered by position.
+    pub fn all_leading(&self) -> Vec<(BytePos, Vec<Comment>)> {
+        let mut cmts: Vec<_> = self
+            .leading
+            .read()
+            .iter()
+            .map(|(pos, cmts)| (*pos, cmts.clone()))
+            .collect();
+        cmts.sort_by_key(|&(pos, _)| pos);
+        cmts
+    }
+
+    /// Returns all comments matching `pred`, ordered by position.
+    ///
+    /// Only the matching comments are cloned.
+    pub fn matching<F>(&self, mut pred: F) -> Vec<Comment>
+    where
+        F: FnMut(&Comment) -> bool,
+    {
+        let leading = self.leading.read();
+        let trailing = self.trailing.read();
+
+        let mut cmts: Vec<_> = leading
+            .values()
+            .chain(trailing.values())
+            .flatten()
+            .filter(|cmt| pred(*cmt))
+            .cloned()
+            .collect();
+        cmts.sort_by_key(|cmt| cmt.span.lo());
+        cmts
+    }
+}
+
+pub type ReadGuard<'a> = MappedRwLockReadGuard<'a, Vec<Comment>>;
+
+fn get(map: &CommentMap, pos: BytePos) -> Option<ReadGuard<'_>> {
+    RwLockReadGuard::try_map(map.read(), |map| map.get(&pos)).ok()
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -145,6 +145,16 @@ impl SourceMap {
         }
     }
 
+    /// Rewrites `prefix` of paths of real files registered afterwards to `to`.
+    ///
+    /// The original path is kept as `unmapped_path` of the file. This is
+    /// useful to make paths in source maps portable, like
+    /// `--remap-path-prefix` of rustc.
+    pub fn with_path_remapping(mut self, prefix: PathBuf, to: PathBuf) -> Self {
+        self.path_mapping.mapping.push((prefix, to));
+        self
+    }
+
     pub fn path_mapping(&self) -> &FilePathMapping {
         &self.path_mapping
     }
@@ -386,8 +396,13 @@ impl SourceMap {
         )
     }
 
+    /// Returns the name of the file containing the start of `sp`, or
+    /// [FileName::Anon] if it's not in a file of this source map.
     pub fn span_to_filename(&self, sp: Span) -> FileName {
-        self.lookup_char_pos(sp.lo()).file.name.clone()
+        match self.lookup_source_file(sp.lo()) {
+            Some(sf) => sf.name.clone(),
+            None => FileName::Anon,
+        }
     }
 
     pub fn span_to_unmapped_path(&self, sp: Span) -> FileName {
@@ -806,6 +821,22 @@ impl SourceMap {
         CharPos(bpos.to_usize() - map.start_pos.to_usize() - total_extra_bytes as usize)
     }
 
+    /// Returns the file containing `pos`, or `None` if it's not in a file of
+    /// this source map.
+    pub fn lookup_source_file(&self, pos: BytePos) -> Option<Arc<SourceFile>> {
+        if self.files.borrow().source_files.is_empty() {
+            return None;
+        }
+
+        let idx = self.lookup_source_file_idx(pos);
+        let sf = self.files.borrow().source_files[idx].clone();
+        if sf.contains(pos) {
+            Some(sf)
+        } else {
+            None
+        }
+    }
+
     // Return the index of the source_file (in self.files) which contains pos.
     pub fn lookup_source_file_idx(&self, pos: BytePos) -> usize {
         let files = self.files.borrow();
@@ -1178,6 +1209,78 @@ mod tests {
         assert!(sm.merge_spans(span1, span2).is_none());
     }
 
+    #[test]
+    fn span_to_filename() {
+        let sm = init_source_map();
+
+        let span = Span::new(BytePos(12), BytePos(23), NO_EXPANSION);
+        assert_eq!(sm.span_to_filename(span), PathBuf::from("blork.rs").into());
+
+        let span = Span::new(BytePos(25), BytePos(30), NO_EXPANSION);
+        assert_eq!(sm.span_to_filename(span), PathBuf::from("blork2.rs").into());
+
+        // The file of `lo` is used for a span crossing files.
+        let span = Span::new(BytePos(20), BytePos(30), NO_EXPANSION);
+        assert_eq!(sm.span_to_filename(span), PathBuf::from("blork.rs").into());
+    }
+
+    #[test]
+    fn span_to_filename_unmapped() {
+        let sm = init_source_map();
+        let span = Span::new(BytePos(100), BytePos(101), NO_EXPANSION);
+        assert_eq!(sm.span_to_filename(span), FileName::Anon);
+
+        let sm = SourceMap::new(FilePathMapping::empty());
+        let span = Span::new(BytePos(0), BytePos(1), NO_EXPANSION);
+        assert_eq!(sm.span_to_filename(span), FileName::Anon);
+    }
+
+    #[test]
+    fn lookup_source_file() {
+        let sm = init_source_map();
+        let name = |pos| {
+            sm.lookup_source_file(BytePos(pos))
+                .map(|sf| sf.name.clone())
+        };
+
+        assert_eq!(name(0), Some(PathBuf::from("blork.rs").into()));
+        assert_eq!(name(12), Some(PathBuf::from("blork.rs").into()));
+        // The end of a file.
+        assert_eq!(name(23), Some(PathBuf::from("blork.rs").into()));
+        assert_eq!(name(24), Some(PathBuf::from("empty.rs").into()));
+        assert_eq!(name(25), Some(PathBuf::from("blork2.rs").into()));
+        assert_eq!(name(48), Some(PathBuf::from("blork2.rs").into()));
+        assert_eq!(name(49), None);
+    }
+
+    #[test]
+    fn lookup_source_file_empty() {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        assert!(sm.lookup_source_file(BytePos(0)).is_none());
+    }
+
+    #[test]
+    fn path_remapping() {
+        let sm = SourceMap::default()
+            .with_path_remapping(PathBuf::from("/home/user/project"), PathBuf::from("src"));
+
+        let fm = sm.new_source_file(
+            PathBuf::from("/home/user/project/lib/a.js").into(),
+            "a();".to_string(),
+        );
+        assert_eq!(fm.name, FileName::Real(PathBuf::from("src/lib/a.js")));
+        assert!(fm.name_was_remapped);
+        assert_eq!(
+            fm.unmapped_path,
+            Some(FileName::Real(PathBuf::from("/home/user/project/lib/a.js")))
+        );
+
+        let fm = sm.new_source_file(PathBuf::from("/tmp/b.js").into(), "b();".to_string());
+        assert_eq!(fm.name, FileName::Real(PathBuf::from("/tmp/b.js")));
+        assert!(!fm.name_was_remapped);
+        assert_eq!(fm.unmapped_path, Some(fm.name.clone()));
+    }
+
     /// Returns the span corresponding to the `n`th occurrence of
     /// `substring` in `source_text`.
     trait SourceMapExtension {
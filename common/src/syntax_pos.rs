@@ -157,8 +157,26 @@ impl SpanData {
     pub fn with_ctxt(&self, ctxt: SyntaxContext) -> Span {
         Span::new(self.lo, self.hi, ctxt)
     }
+    /// Returns the position of the span, without the syntax context.
+    #[inline]
+    pub fn pos_key(&self) -> PosKey {
+        PosKey(self.lo, self.hi)
+    }
+    /// Return true if the spans have the same position, regardless of their
+    /// syntax contexts.
+    #[inline]
+    pub fn loc_eq(&self, other: &SpanData) -> bool {
+        self.pos_key() == other.pos_key()
+    }
 }
 
+/// Position of a span, which ignores its syntax context.
+///
+/// Unlike [Span], this can be used as a key of a map to find nodes at the
+/// same position in two versions of an AST, like the outputs of two passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PosKey(pub BytePos, pub BytePos);
+
 impl PartialOrd for Span {
     fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
         PartialOrd::partial_cmp(&self.data(), &rhs.data())
@@ -245,6 +263,22 @@ impl Span {
         span.lo <= other.lo && other.hi <= span.hi
     }
 
+    /// Returns the position of the span, without the syntax context.
+    #[inline]
+    pub fn pos_key(self) -> PosKey {
+        self.data().pos_key()
+    }
+
+    /// Return true if the spans have the same position, regardless of their
+    /// syntax contexts.
+    ///
+    /// `==` compares syntax contexts too, so it's false for spans of
+    /// identifiers renamed by hygiene.
+    #[inline]
+    pub fn loc_eq(self, other: Span) -> bool {
+        self.pos_key() == other.pos_key()
+    }
+
     /// Return true if the spans are equal with regards to the source text.
     ///
     /// Use this instead of `==` when either span could be generated code,
@@ -374,6 +408,30 @@ impl Span {
         )
     }
 
+    /// Splits the span into `[lo, lo + offset)` and `[lo + offset, hi)`.
+    ///
+    /// Both halves keep the syntax context of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is larger than the length of the span.
+    pub fn split_at(self, offset: usize) -> (Span, Span) {
+        let span = self.data();
+        let len = (span.hi - span.lo).to_usize();
+        assert!(
+            offset <= len,
+            "Span::split_at: offset ({}) is out of bounds (len = {})",
+            offset,
+            len
+        );
+
+        let mid = span.lo + BytePos::from_usize(offset);
+        (
+            Span::new(span.lo, mid, span.ctxt),
+            Span::new(mid, span.hi, span.ctxt),
+        )
+    }
+
     #[inline]
     pub fn apply_mark(self, mark: Mark) -> Span {
         let span = self.data();
@@ -748,15 +806,15 @@ impl SourceFile {
     /// Get a line from the list of pre-computed line-beginnings.
     /// The line number here is 0-based.
     pub fn get_line(&self, line_number: usize) -> Option<Cow<'_, str>> {
-        fn get_until_newline(src: &str, begin: usize) -> &str {
+        fn get_until_newline(src: &str, begin: usize) -> Option<&str> {
             // We can't use `lines.get(line_number+1)` because we might
             // be parsing when we call this function and thus the current
             // line is the last one we have line info for.
-            let slice = &src[begin..];
-            match slice.find('\n') {
+            let slice = src.get(begin..)?;
+            Some(match slice.find('\n') {
                 Some(e) => &slice[..e],
                 None => slice,
-            }
+            })
         }
 
         let begin = {
@@ -765,11 +823,12 @@ impl SourceFile {
             } else {
                 return None;
             };
-            let begin: BytePos = *line - self.start_pos;
+            // The source is unavailable if the line is out of this file.
+            let begin: BytePos = line.checked_sub(self.start_pos)?;
             begin.to_usize()
         };
 
-        Some(Cow::from(get_until_newline(&self.src, begin)))
+        get_until_newline(&self.src, begin).map(Cow::from)
     }
 
     pub fn is_real_file(&self) -> bool {
@@ -818,6 +877,68 @@ impl SourceFile {
     pub fn contains(&self, byte_pos: BytePos) -> bool {
         byte_pos >= self.start_pos && byte_pos <= self.end_pos
     }
+
+    /// Detects the line terminator used by this file.
+    ///
+    /// Files without any line terminator are treated as using `\n`.
+    pub fn line_ending(&self) -> LineEnding {
+        let src = self.src.as_bytes();
+        let (mut lf, mut crlf) = (false, false);
+
+        // Each line except the first one starts right after a `\n`.
+        for &line in self.lines.iter().skip(1) {
+            let idx = line.to_usize() - self.start_pos.to_usize();
+            if idx >= 2 && src[idx - 2] == b'\r' {
+                crlf = true;
+            } else {
+                lf = true;
+            }
+        }
+
+        match (lf, crlf) {
+            (_, false) => LineEnding::Lf,
+            (false, true) => LineEnding::CrLf,
+            (true, true) => LineEnding::Mixed,
+        }
+    }
+}
+
+/// Line terminator style of a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// Both `\n` and `\r\n` are used.
+    Mixed,
+}
+
+/// Converts all line terminators of `src` to `ending`.
+///
+/// `src` is returned as-is if `ending` is `Mixed`.
+pub fn normalize_line_endings(src: &str, ending: LineEnding) -> Cow<'_, str> {
+    match ending {
+        LineEnding::Lf if src.contains("\r\n") => Cow::Owned(src.replace("\r\n", "\n")),
+        LineEnding::CrLf => {
+            let bytes = src.as_bytes();
+            let needs_cr = |i: usize| bytes[i] == b'\n' && (i == 0 || bytes[i - 1] != b'\r');
+            if !(0..bytes.len()).any(needs_cr) {
+                return Cow::Borrowed(src);
+            }
+
+            let mut buf = String::with_capacity(src.len() + src.len() / 16);
+            let mut last = 0;
+            for i in (0..bytes.len()).filter(|&i| needs_cr(i)) {
+                buf.push_str(&src[last..i]);
+                buf.push_str("\r\n");
+                last = i + 1;
+            }
+            buf.push_str(&src[last..]);
+            Cow::Owned(buf)
+        }
+        _ => Cow::Borrowed(src),
+    }
 }
 
 /// Remove utf-8 BOM if any.
@@ -893,6 +1014,17 @@ impl Sub for BytePos {
     }
 }
 
+impl BytePos {
+    /// Returns `None` instead of panicking if `rhs` is greater than `self`.
+    ///
+    /// Code rendering diagnostics should use this, because a corrupted span
+    /// should not crash error reporting.
+    #[inline]
+    pub fn checked_sub(self, rhs: BytePos) -> Option<BytePos> {
+        self.0.checked_sub(rhs.0).map(BytePos)
+    }
+}
+
 impl Pos for CharPos {
     #[inline(always)]
     fn from_usize(n: usize) -> CharPos {
@@ -1040,7 +1172,11 @@ fn lookup_line(lines: &[BytePos], pos: BytePos) -> isize {
 
 #[cfg(test)]
 mod tests {
-    use super::{lookup_line, BytePos};
+    use super::{
+        lookup_line, normalize_line_endings, BytePos, FileName, LineEnding, Mark, PosKey,
+        SourceFile, Span, SyntaxContext,
+    };
+    use std::collections::HashMap;
 
     #[test]
     fn test_lookup_line() {
@@ -1057,4 +1193,142 @@ mod tests {
         assert_eq!(lookup_line(lines, BytePos(28)), 2);
         assert_eq!(lookup_line(lines, BytePos(29)), 2);
     }
+
+    #[test]
+    fn checked_sub() {
+        assert_eq!(BytePos(5).checked_sub(BytePos(3)), Some(BytePos(2)));
+        assert_eq!(BytePos(3).checked_sub(BytePos(5)), None);
+    }
+
+    #[test]
+    fn get_line_before_start_pos() {
+        let mut sf = SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            "first\nsecond".into(),
+            BytePos(10),
+        );
+        assert_eq!(sf.get_line(1).as_ref().map(|s| &**s), Some("second"));
+
+        // Simulate a corrupted line table.
+        sf.lines[1] = BytePos(3);
+        assert_eq!(sf.get_line(1), None);
+
+        sf.lines[1] = BytePos(100);
+        assert_eq!(sf.get_line(1), None);
+    }
+
+    fn line_ending(src: &str) -> LineEnding {
+        SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            src.into(),
+            BytePos(10),
+        )
+        .line_ending()
+    }
+
+    #[test]
+    fn line_ending_lf() {
+        assert_eq!(line_ending("a;\nb;\n"), LineEnding::Lf);
+        assert_eq!(line_ending("a;"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn line_ending_crlf() {
+        assert_eq!(line_ending("a;\r\nb;\r\n"), LineEnding::CrLf);
+        assert_eq!(line_ending("\r\n"), LineEnding::CrLf);
+    }
+
+    #[test]
+    fn line_ending_mixed() {
+        assert_eq!(line_ending("a;\r\nb;\nc;"), LineEnding::Mixed);
+        // A lone `\r` does not end a line.
+        assert_eq!(line_ending("a;\rb;\nc;"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn normalize_crlf() {
+        let src = "a;\r\nb;\nc;\n";
+
+        assert_eq!(normalize_line_endings(src, LineEnding::Lf), "a;\nb;\nc;\n");
+        assert_eq!(
+            normalize_line_endings(src, LineEnding::CrLf),
+            "a;\r\nb;\r\nc;\r\n"
+        );
+        assert_eq!(normalize_line_endings(src, LineEnding::Mixed), src);
+        assert_eq!(normalize_line_endings("\nx", LineEnding::CrLf), "\r\nx");
+    }
+
+    #[test]
+    fn loc_eq_ignores_ctxt() {
+        crate::GLOBALS.set(&crate::Globals::new(), || {
+            let ctxt = SyntaxContext::empty().apply_mark(Mark::fresh(Mark::root()));
+            let a = Span::new(BytePos(1), BytePos(4), SyntaxContext::empty());
+            let b = a.with_ctxt(ctxt);
+
+            assert_ne!(a, b);
+            assert!(a.loc_eq(b));
+            assert!(a.data().loc_eq(&b.data()));
+            assert_eq!(a.pos_key(), b.pos_key());
+            assert_eq!(a.pos_key(), PosKey(BytePos(1), BytePos(4)));
+
+            let c = Span::new(BytePos(1), BytePos(5), ctxt);
+            assert!(!b.loc_eq(c));
+            assert_ne!(b.pos_key(), c.pos_key());
+        })
+    }
+
+    #[test]
+    fn pos_key_as_map_key() {
+        crate::GLOBALS.set(&crate::Globals::new(), || {
+            let ctxt = SyntaxContext::empty().apply_mark(Mark::fresh(Mark::root()));
+            let a = Span::new(BytePos(1), BytePos(4), SyntaxContext::empty());
+
+            let mut by_span = HashMap::new();
+            by_span.insert(a, "a");
+            assert_eq!(by_span.get(&a.with_ctxt(ctxt)), None);
+
+            let mut by_pos = HashMap::new();
+            by_pos.insert(a.pos_key(), "a");
+            assert_eq!(by_pos.get(&a.with_ctxt(ctxt).pos_key()), Some(&"a"));
+        })
+    }
+
+    #[test]
+    fn split_at_mid() {
+        let span = Span::new(BytePos(10), BytePos(20), SyntaxContext::empty());
+        let (first, second) = span.split_at(4);
+
+        assert_eq!(
+            first,
+            Span::new(BytePos(10), BytePos(14), SyntaxContext::empty())
+        );
+        assert_eq!(
+            second,
+            Span::new(BytePos(14), BytePos(20), SyntaxContext::empty())
+        );
+    }
+
+    #[test]
+    fn split_at_boundary() {
+        let span = Span::new(BytePos(10), BytePos(20), SyntaxContext::empty());
+
+        let (first, second) = span.split_at(0);
+        assert_eq!(first, span.shrink_to_lo());
+        assert_eq!(second, span);
+
+        let (first, second) = span.split_at(10);
+        assert_eq!(first, span);
+        assert_eq!(second, span.shrink_to_hi());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_at_out_of_bounds() {
+        let span = Span::new(BytePos(10), BytePos(20), SyntaxContext::empty());
+        span.split_at(11);
+    }
 }
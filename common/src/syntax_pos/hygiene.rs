@@ -84,6 +84,13 @@ impl Mark {
         })
     }
 
+    /// Returns the number of marks allocated so far, without allocating a new
+    /// one like `Mark::fresh` does.
+    #[inline]
+    pub fn count() -> u32 {
+        HygieneData::with(|data| data.marks.len() as u32)
+    }
+
     /// The mark of the theoretical expansion that generates freshly parsed,
     /// unexpanded AST.
     #[inline]
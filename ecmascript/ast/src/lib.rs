@@ -26,7 +26,7 @@ pub use self::{
         JSXFragment, JSXMemberExpr, JSXNamespacedName, JSXObject, JSXOpeningElement,
         JSXOpeningFragment, JSXSpreadChild, JSXText,
     },
-    lit::{Bool, Lit, Null, Number, Regex, RegexFlags, Str},
+    lit::{BigInt, Bool, Lit, Null, Number, Regex, RegexFlags, Str},
     module::{Module, ModuleItem, Program, Script},
     module_decl::{
         DefaultDecl, DefaultExportSpecifier, ExportAll, ExportDecl, ExportDefaultDecl,
@@ -17,6 +17,9 @@ pub enum Lit {
     #[tag("NumericLiteral")]
     Num(Number),
 
+    #[tag("BigIntLiteral")]
+    BigInt(BigInt),
+
     #[tag("RegExpLiteral")]
     Regex(Regex),
 
@@ -39,6 +42,24 @@ impl Str {
     pub fn is_empty(&self) -> bool {
         self.value.is_empty()
     }
+
+    /// Returns true if the value is a property key which can be written as a
+    /// number without changing it, like `0` or `42`.
+    ///
+    /// Other numbers, like `1.5` or `00`, don't round trip or are printed
+    /// differently.
+    pub fn is_index_key(&self) -> bool {
+        // Integers above 2^53 lose precision.
+        const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+        let key = &*self.value;
+        let canonical =
+            key == "0" || (!key.starts_with('0') && key.bytes().all(|b| b.is_ascii_digit()));
+        canonical
+            && key
+                .parse::<u64>()
+                .map_or(false, |value| value <= MAX_SAFE_INTEGER)
+    }
 }
 
 #[ast_node("BooleanLiteral")]
@@ -67,6 +88,16 @@ pub struct Regex {
 
 pub type RegexFlags = Str;
 
+#[ast_node("BigIntLiteral")]
+pub struct BigInt {
+    pub span: Span,
+
+    /// Digits without the `n` suffix and numeric separators.
+    ///
+    /// This includes the prefix of non-decimal literals, like `0x1f`.
+    pub value: JsWord,
+}
+
 #[ast_node("NumericLiteral")]
 #[derive(Copy)]
 pub struct Number {
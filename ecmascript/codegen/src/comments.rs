@@ -1,5 +1,9 @@
 use super::*;
-use swc_common::comments::CommentKind;
+use crate::config::PreserveComments;
+use hashbrown::HashMap;
+use regex::Regex;
+use std::cell::RefCell;
+use swc_common::comments::{Comment, CommentKind};
 
 macro_rules! write_comments {
     ($e:expr, $prefix_space:expr, $cmts:expr) => {{
@@ -9,6 +13,14 @@ macro_rules! write_comments {
         };
 
         for cmt in cmts.iter() {
+            // Annotations are hoisted by `emit_annotations`.
+            if $e.cfg.preserve_annotations && ($e.cfg.minify || is_annotation(cmt)) {
+                continue;
+            }
+            if !$e.should_keep_comment(cmt)? {
+                continue;
+            }
+
             match cmt.kind {
                 CommentKind::Line => {
                     if $prefix_space {
@@ -33,7 +45,81 @@ macro_rules! write_comments {
     }};
 }
 
+/// Returns true for comments which should be kept even in minified output.
+fn is_annotation(cmt: &Comment) -> bool {
+    cmt.text.starts_with('!') || cmt.text.contains("@license") || cmt.text.contains("@preserve")
+}
+
+/// Maximum number of patterns in `COMMENT_REGEXES`.
+const MAX_COMMENT_REGEXES: usize = 16;
+
+thread_local! {
+    /// Compiled patterns of [PreserveComments::Regex].
+    static COMMENT_REGEXES: RefCell<HashMap<String, Regex>> = Default::default();
+}
+
+/// Returns true if `text` matches `pattern`. `pattern` is compiled only once
+/// per thread.
+fn matches_comment_regex(pattern: &str, text: &str) -> io::Result<bool> {
+    COMMENT_REGEXES.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(re) = cache.get(pattern) {
+            return Ok(re.is_match(text));
+        }
+
+        let re =
+            Regex::new(pattern).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let is_match = re.is_match(text);
+        if cache.len() >= MAX_COMMENT_REGEXES {
+            cache.clear();
+        }
+        cache.insert(pattern.to_string(), re);
+
+        Ok(is_match)
+    })
+}
+
 impl<'a> Emitter<'a> {
+    /// Returns true if `cmt` should be printed in place.
+    fn should_keep_comment(&self, cmt: &Comment) -> io::Result<bool> {
+        match self.cfg.preserve_comments {
+            PreserveComments::All => Ok(true),
+            PreserveComments::None => Ok(false),
+            PreserveComments::License => Ok(is_annotation(cmt)),
+            PreserveComments::Regex(pattern) => matches_comment_regex(pattern.as_str(), &cmt.text),
+        }
+    }
+
+    /// Emits all annotation comments, regardless of whether the code they
+    /// were attached to still exists.
+    pub(super) fn emit_annotations(&mut self) -> Result {
+        if !self.cfg.preserve_annotations {
+            return Ok(());
+        }
+
+        let comments = match self.comments {
+            Some(ref comments) => comments,
+            None => return Ok(()),
+        };
+
+        for cmt in comments.matching(is_annotation) {
+            match cmt.kind {
+                CommentKind::Line => {
+                    self.wr.write_comment(cmt.span, "//")?;
+                    self.wr.write_comment(cmt.span, &cmt.text)?;
+                }
+                CommentKind::Block => {
+                    self.wr.write_comment(cmt.span, "/*")?;
+                    self.wr.write_comment(cmt.span, &cmt.text)?;
+                    self.wr.write_comment(cmt.span, "*/")?;
+                }
+            }
+            self.wr.write_line()?;
+        }
+
+        Ok(())
+    }
+
     pub(super) fn emit_trailing_comments_of_pos(
         &mut self,
         pos: BytePos,
@@ -1,4 +1,229 @@
-#[derive(Debug, Default, Clone, Copy)]
+use hashbrown::HashSet;
+use std::cell::RefCell;
+use swc_common::{SourceMap, Span};
+
+#[derive(Debug, Clone, Copy)]
 pub struct Config {
     pub minify: bool,
+    /// Hoist license comments (`/*! ... */`, `@license` and `@preserve`) to
+    /// the top of the output, so that they survive even if the code they were
+    /// attached to is removed.
+    ///
+    /// If `minify` is also enabled, other comments are dropped.
+    ///
+    /// License comments are hoisted regardless of `preserve_comments`, which
+    /// only decides which of the other comments are printed in place.
+    pub preserve_annotations: bool,
+    /// Escape `</script`, `<!--` and `-->` in string literals, so that the
+    /// output can be embedded in a `<script>` element of a html document.
+    ///
+    /// Values of the strings are not changed.
+    pub script_safe_strings: bool,
+    /// Escape control characters in string literals which are otherwise
+    /// printed as is, like `U+007F`, using the shortest escape.
+    pub minify_control_chars: bool,
+    pub number_style: NumberStyle,
+    /// Comments to print in place.
+    ///
+    /// License comments hoisted by `preserve_annotations` are not affected.
+    pub preserve_comments: PreserveComments,
+    /// Blank lines to print between statements and other items on their own
+    /// lines, like class members.
+    ///
+    /// Ignored if `minify` is enabled.
+    pub blank_lines: BlankLines,
+    /// Print `export { a as default }` as `export default a` if `a` is a
+    /// top-level `const` variable.
+    ///
+    /// The former exports a live binding while the latter exports the value
+    /// at the time it's evaluated, so other bindings are printed as is.
+    pub collapse_default_export: bool,
+    /// End the output of a module or a script with a line break.
+    ///
+    /// If disabled, the output ends with its last token. Enabled by default.
+    pub final_newline: bool,
+    /// Line terminator of the output.
+    ///
+    /// Unless this is [LineEnding::FromWriter], this overrides the terminator
+    /// a [JsWriter](crate::text_writer::JsWriter) is created with.
+    pub line_ending: LineEnding,
+    /// Wrap every binary and conditional expression in parentheses, like
+    /// `(a + (b * c))`, regardless of precedence.
+    ///
+    /// Parentheses in the source around such expressions are not doubled.
+    pub fully_parenthesize: bool,
+    /// Escape non-ASCII characters in identifiers, like `\u00e9`.
+    ///
+    /// Other tokens, like string literals, are printed as is.
+    pub ascii_only: bool,
+    /// Append the syntax context to identifiers which have one, like `ref#3`,
+    /// so that identifiers created by transforms can be told apart.
+    ///
+    /// The output is not valid javascript, so this is only for debugging.
+    pub debug_syntax_contexts: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            minify: false,
+            preserve_annotations: false,
+            script_safe_strings: false,
+            minify_control_chars: false,
+            number_style: Default::default(),
+            preserve_comments: Default::default(),
+            blank_lines: Default::default(),
+            collapse_default_export: false,
+            final_newline: true,
+            line_ending: Default::default(),
+            fully_parenthesize: false,
+            ascii_only: false,
+            debug_syntax_contexts: false,
+        }
+    }
+}
+
+/// Line terminator of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Use the line terminator the writer is created with.
+    FromWriter,
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// Use the line terminator of the source file.
+    ///
+    /// `\n` is used if the source file uses both or is unknown.
+    FromSource,
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        LineEnding::FromWriter
+    }
+}
+
+impl LineEnding {
+    /// Returns the line terminator for emitting the code at `span`, like the
+    /// span of a module.
+    ///
+    /// Returns `None` for [LineEnding::FromWriter].
+    pub fn new_line(self, cm: &SourceMap, span: Span) -> Option<&'static str> {
+        match self {
+            LineEnding::FromWriter => None,
+            LineEnding::Lf => Some("\n"),
+            LineEnding::CrLf => Some("\r\n"),
+            LineEnding::FromSource => {
+                match cm.lookup_source_file(span.lo()).map(|fm| fm.line_ending()) {
+                    Some(swc_common::LineEnding::CrLf) => Some("\r\n"),
+                    _ => Some("\n"),
+                }
+            }
+        }
+    }
+}
+
+/// How blank lines in the source are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlankLines {
+    /// Print each statement on its own line, without blank lines.
+    None,
+    /// Print a single blank line where the source has one or more.
+    Collapse,
+    /// Print blank lines as in the source.
+    ///
+    /// Blank lines around comments are printed before the comments.
+    Preserve,
+}
+
+impl Default for BlankLines {
+    fn default() -> Self {
+        BlankLines::None
+    }
+}
+
+/// Which comments are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreserveComments {
+    All,
+    None,
+    /// Keep license comments (`/*! ... */`, `@license` and `@preserve`).
+    License,
+    /// Keep comments whose text matches the regular expression.
+    ///
+    /// Emitting fails with [std::io::ErrorKind::InvalidInput] if the
+    /// expression is invalid.
+    Regex(CommentPattern),
+}
+
+/// A regular expression of [PreserveComments::Regex].
+///
+/// Patterns are interned, so that [Config] can be copied. Each distinct
+/// pattern is allocated once per thread and never freed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CommentPattern(&'static str);
+
+thread_local! {
+    static COMMENT_PATTERNS: RefCell<HashSet<&'static str>> = Default::default();
+}
+
+impl CommentPattern {
+    pub fn new(pattern: &str) -> Self {
+        COMMENT_PATTERNS.with(|patterns| {
+            let mut patterns = patterns.borrow_mut();
+            if let Some(&interned) = patterns.get(pattern) {
+                return CommentPattern(interned);
+            }
+
+            let interned: &'static str = Box::leak(pattern.into());
+            patterns.insert(interned);
+            CommentPattern(interned)
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl From<&'_ str> for CommentPattern {
+    fn from(pattern: &str) -> Self {
+        CommentPattern::new(pattern)
+    }
+}
+
+impl Default for PreserveComments {
+    fn default() -> Self {
+        PreserveComments::All
+    }
+}
+
+/// How number literals are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberStyle {
+    /// Print numbers in the shortest form for the value, so `1.0` becomes
+    /// `1`.
+    IntegerCompact,
+    /// Print numbers as written in the source, so `0.0` stays `0.0`.
+    ///
+    /// Numbers without a source, or with a value changed by a transform, are
+    /// printed like `IntegerCompact`. This takes precedence over `minify`.
+    Preserve,
+}
+
+impl Default for NumberStyle {
+    fn default() -> Self {
+        NumberStyle::IntegerCompact
+    }
+}
+
+/// Options of
+/// [Emitter::emit_with_inline_source_map](crate::Emitter::emit_with_inline_source_map).
+#[derive(Debug, Default, Clone)]
+pub struct SourceMapConfig {
+    /// `sourceRoot` of the source map.
+    pub source_root: Option<String>,
+    /// Embed the source code of referenced files as `sourcesContent`.
+    pub sources_content: bool,
 }
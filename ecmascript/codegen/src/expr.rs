@@ -16,6 +16,34 @@ mod tests {
         assert_min(r#" 'foobar' "#, r#"'foobar';"#);
     }
 
+    #[test]
+    fn shortest_number() {
+        assert_min("10", "10;");
+        assert_min("100", "100;");
+        assert_min("1000", "1e3;");
+        assert_min("1000000", "1e6;");
+        assert_min("1500000", "15e5;");
+        assert_min("123456", "123456;");
+        assert_min("255", "255;");
+        assert_min("281474976710655", "0xffffffffffff;");
+        assert_min("1e21", "1e21;");
+        assert_min("1.5e300", "15e299;");
+        assert_min("0.5", ".5;");
+        assert_min("0.001", ".001;");
+        assert_min("0.0001", "1e-4;");
+        assert_min("1.5e-7", "15e-8;");
+        assert_min("123.456", "123.456;");
+        assert_min("1000000..toFixed()", "1e6.toFixed();");
+        assert_min("281474976710655..toFixed()", "0xffffffffffff.toFixed();");
+        assert_min("10..toFixed()", "10..toFixed();");
+    }
+
+    #[test]
+    fn shortest_number_pretty() {
+        assert_pretty("1000000", "1000000;");
+        assert_pretty("0.5", "0.5;");
+    }
+
     #[test]
     fn template_expression() {
         assert_min("``", "``;");
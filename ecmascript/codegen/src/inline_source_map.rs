@@ -0,0 +1,54 @@
+use crate::{text_writer::JsWriter, Config, Emitter, Handlers, SourceMapConfig};
+use sourcemap::SourceMapBuilder;
+use std::{io, sync::Arc};
+use swc_common::{comments::Comments, SourceMap};
+use swc_ecma_ast::Module;
+
+impl<'a> Emitter<'a> {
+    /// Emits `module` and appends its source map as a
+    /// `//# sourceMappingURL=data:application/json;base64,...` comment.
+    pub fn emit_with_inline_source_map(
+        cfg: Config,
+        cm: Arc<SourceMap>,
+        comments: Option<&'a Comments>,
+        handlers: Box<dyn 'a + Handlers>,
+        module: &Module,
+        map_cfg: &SourceMapConfig,
+    ) -> io::Result<String> {
+        let mut builder = SourceMapBuilder::new(None);
+        builder.set_source_root(map_cfg.source_root.as_ref().map(|s| &**s));
+
+        let new_line = cfg.line_ending.new_line(&cm, module.span).unwrap_or("\n");
+        let mut buf = vec![];
+        {
+            let mut emitter = Emitter {
+                cfg,
+                cm: cm.clone(),
+                comments,
+                wr: Box::new(
+                    JsWriter::new(cm, new_line, &mut buf, Some(&mut builder))
+                        .with_sources_content(map_cfg.sources_content),
+                ),
+                handlers,
+                pos_of_leading_comments: Default::default(),
+            };
+            emitter.emit_module(module)?;
+        }
+
+        let mut map = vec![];
+        builder
+            .into_sourcemap()
+            .to_writer(&mut map)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+
+        let mut code = String::from_utf8(buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if !code.ends_with('\n') {
+            code.push_str(new_line);
+        }
+        code.push_str("//# sourceMappingURL=data:application/json;base64,");
+        code.push_str(&base64::encode(&map));
+
+        Ok(code)
+    }
+}
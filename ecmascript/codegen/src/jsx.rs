@@ -38,7 +38,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_jsx_element_name(&mut self, node: &JSXElementName) -> Result {
         match *node {
-            JSXElementName::Ident(ref n) => emit!(n),
+            JSXElementName::Ident(ref n) => self.emit_jsx_name(n)?,
             JSXElementName::JSXMemberExpr(ref n) => emit!(n),
             JSXElementName::JSXNamespacedName(ref n) => emit!(n),
         }
@@ -57,7 +57,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_jsx_attr_name(&mut self, node: &JSXAttrName) -> Result {
         match *node {
-            JSXAttrName::Ident(ref n) => emit!(n),
+            JSXAttrName::Ident(ref n) => self.emit_jsx_name(n)?,
             JSXAttrName::JSXNamespacedName(ref n) => emit!(n),
         }
     }
@@ -136,9 +136,16 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_jsx_namespaced_name(&mut self, node: &JSXNamespacedName) -> Result {
-        emit!(node.ns);
+        self.emit_jsx_name(&node.ns)?;
         punct!(":");
-        emit!(node.name);
+        self.emit_jsx_name(&node.name)?;
+    }
+
+    /// Names of elements and attributes can contain `-`, like `data-foo`, so
+    /// they are printed without validation.
+    fn emit_jsx_name(&mut self, n: &Ident) -> Result {
+        self.emit_leading_comments_of_pos(n.span.lo())?;
+        self.wr.write_symbol(n.span, &n.sym)
     }
 
     #[emitter]
@@ -1,7 +1,9 @@
 #![recursion_limit = "1024"]
 #![allow(unused_variables)]
 
-pub use self::config::Config;
+pub use self::config::{
+    BlankLines, CommentPattern, Config, LineEnding, NumberStyle, PreserveComments, SourceMapConfig,
+};
 use self::{
     list::ListFormat,
     text_writer::WriteJs,
@@ -9,10 +11,11 @@ use self::{
 };
 use hashbrown::HashSet;
 use std::{io, sync::Arc};
-use swc_atoms::JsWord;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{comments::Comments, BytePos, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_codegen_macros::emitter;
+use unicode_xid::UnicodeXID;
 
 #[macro_use]
 pub mod macros;
@@ -20,9 +23,11 @@ mod comments;
 mod config;
 mod decl;
 mod expr;
+mod inline_source_map;
 mod jsx;
 pub mod list;
 mod stmt;
+mod stream;
 #[cfg(test)]
 mod tests;
 pub mod text_writer;
@@ -87,26 +92,62 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_module(&mut self, node: &Module) -> Result {
+        if let Some(new_line) = self.cfg.line_ending.new_line(&self.cm, node.span) {
+            self.wr.set_new_line(new_line);
+        }
+
         if let Some(ref shebang) = node.shebang {
             punct!("#!");
             self.wr.write_str_lit(DUMMY_SP, &*shebang)?;
             self.wr.write_line()?;
         }
+        self.emit_annotations()?;
+        let mut prev: Option<Span> = None;
+        // `const` variables declared so far.
+        let mut consts = HashSet::new();
         for stmt in &node.body {
-            emit!(stmt);
+            if let Some(prev) = prev {
+                self.emit_blank_lines(prev, stmt.span())?;
+            }
+            let collapsed = if self.cfg.collapse_default_export {
+                add_const_decls(&mut consts, stmt);
+                collapsible_default_export(&consts, stmt)
+            } else {
+                None
+            };
+            match collapsed {
+                Some(orig) => emit!(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                    span: stmt.span(),
+                    expr: Box::new(Expr::Ident(orig.clone())),
+                })),
+                None => emit!(stmt),
+            }
+            prev = Some(stmt.span());
         }
+        self.wr.finish(self.cfg.final_newline)?;
     }
 
     #[emitter]
     pub fn emit_script(&mut self, node: &Script) -> Result {
+        if let Some(new_line) = self.cfg.line_ending.new_line(&self.cm, node.span) {
+            self.wr.set_new_line(new_line);
+        }
+
         if let Some(ref shebang) = node.shebang {
             punct!("#!");
             self.wr.write_str_lit(DUMMY_SP, &*shebang)?;
             self.wr.write_line()?;
         }
+        self.emit_annotations()?;
+        let mut prev: Option<Span> = None;
         for stmt in &node.body {
+            if let Some(prev) = prev {
+                self.emit_blank_lines(prev, stmt.span())?;
+            }
             emit!(stmt);
+            prev = Some(stmt.span());
         }
+        self.wr.finish(self.cfg.final_newline)?;
     }
 
     #[emitter]
@@ -319,6 +360,7 @@ impl<'a> Emitter<'a> {
             Lit::Null(Null { span }) => keyword!(span, "null"),
             Lit::Str(ref s) => emit!(s),
             Lit::Num(ref n) => emit!(n),
+            Lit::BigInt(ref n) => emit!(n),
             Lit::Regex(ref n) => {
                 punct!("/");
                 self.emit_js_word(n.exp.span, &n.exp.value)?;
@@ -345,40 +387,21 @@ impl<'a> Emitter<'a> {
         //     self.wr.write_str_lit(node.span, &s)?;
         //     return Ok(());
         // }
-        let value = node
-            .value
-            .replace("\\", "\\\\")
-            .replace('\u{0008}', "\\b")
-            .replace('\u{000C}', "\\f")
-            .replace("\n", "\\n")
-            .replace("\r", "\\r")
-            .replace("\t", "\\t")
-            .replace('\u{000B}', "\\v")
-            .replace("\00", "\\x000")
-            .replace("\01", "\\x001")
-            .replace("\02", "\\x002")
-            .replace("\03", "\\x003")
-            .replace("\04", "\\x004")
-            .replace("\05", "\\x005")
-            .replace("\06", "\\x006")
-            .replace("\07", "\\x007")
-            .replace("\08", "\\x008")
-            .replace("\09", "\\x009")
-            .replace("\0", "\\0");
-        // let value = node.value.replace("\n", "\\n");
-
-        if !node.value.contains('\'') {
-            punct!("'");
-            self.wr.write_str_lit(node.span, &value)?;
-            punct!("'");
-        } else if !node.value.contains('\"') {
+        let quote = choose_quote(&node.value);
+        let value = escape_str(&node.value, quote, self.cfg.minify_control_chars);
+        let value = if self.cfg.script_safe_strings {
+            escape_script_sequences(&value)
+        } else {
+            value
+        };
+
+        if quote == '"' {
             punct!("\"");
             self.wr.write_str_lit(node.span, &value)?;
             punct!("\"");
         } else {
             punct!("'");
-            self.wr
-                .write_str_lit(node.span, &value.replace("'", "\\'"))?;
+            self.wr.write_str_lit(node.span, &value)?;
             punct!("'");
         }
     }
@@ -394,7 +417,47 @@ impl<'a> Emitter<'a> {
             }
             self.wr.write_str_lit(num.span, "Infinity")?;
         } else {
-            self.wr.write_str_lit(num.span, &format!("{}", num.value))?;
+            let s = match self.original_number_text(num) {
+                Some(s) => s,
+                None => self.format_number(num.value),
+            };
+            self.wr.write_str_lit(num.span, &s)?;
+        }
+    }
+
+    #[emitter]
+    pub fn emit_big_int_lit(&mut self, num: &BigInt) -> Result {
+        self.emit_leading_comments_of_pos(num.span().lo())?;
+
+        self.wr.write_str_lit(num.span, &num.value)?;
+        self.wr.write_str_lit(num.span, "n")?;
+    }
+
+    /// Returns the source code of `num` if `number_style` is `Preserve` and
+    /// the code still has the value of `num`.
+    fn original_number_text(&self, num: &Number) -> Option<String> {
+        if self.cfg.number_style != NumberStyle::Preserve {
+            return None;
+        }
+        if num.span.is_dummy() || num.span.ctxt() != SyntaxContext::empty() {
+            return None;
+        }
+
+        let s = self.cm.span_to_snippet(num.span).ok()?;
+        // Other forms like `0x10` are not parsed here, and printed as usual.
+        match s.parse::<f64>() {
+            Ok(value) if value == num.value => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Formats a finite number. The shortest form is used if `minify` is
+    /// enabled.
+    fn format_number(&self, value: f64) -> String {
+        if self.cfg.minify {
+            minify_number(value)
+        } else {
+            format_number(value)
         }
     }
 
@@ -534,9 +597,21 @@ impl<'a> Emitter<'a> {
         match *expr {
             ExprOrSuper::Expr(ref expr) => {
                 match **expr {
-                    Expr::Lit(Lit::Num(Number { span, value })) => {
+                    Expr::Lit(Lit::Num(ref num)) => {
+                        let Number { span, value } = *num;
+                        // Decide from the text which is actually emitted.
+                        if let Some(text) = self.original_number_text(num) {
+                            return !text.contains(|c| match c {
+                                '.' | 'e' | 'E' | 'x' | 'X' => true,
+                                _ => false,
+                            });
+                        }
                         if value.fract() == 0.0 {
-                            return true;
+                            // `1e3.toString` and `0xff.toString` are valid.
+                            return self
+                                .format_number(value)
+                                .bytes()
+                                .all(|c| c.is_ascii_digit() || c == b'-');
                         }
                         // check if numeric literal is a decimal literal that was originally written
                         // with a dot
@@ -624,6 +699,9 @@ impl<'a> Emitter<'a> {
             _ => false,
         };
 
+        if self.cfg.fully_parenthesize {
+            punct!("(");
+        }
         emit!(node.left);
 
         let need_pre_space = need_space
@@ -641,6 +719,8 @@ impl<'a> Emitter<'a> {
         let need_post_space = need_space
             || match *node.right {
                 Expr::Unary(..) | Expr::Update(UpdateExpr { prefix: true, .. }) => true,
+                // `a - -0`
+                Expr::Lit(Lit::Num(Number { value, .. })) => value.is_sign_negative(),
                 _ => false,
             };
         if need_post_space {
@@ -649,6 +729,9 @@ impl<'a> Emitter<'a> {
             formatting_space!();
         }
         emit!(node.right);
+        if self.cfg.fully_parenthesize {
+            punct!(")");
+        }
     }
 
     #[emitter]
@@ -808,6 +891,10 @@ impl<'a> Emitter<'a> {
     pub fn emit_prop_name(&mut self, node: &PropName) -> Result {
         match *node {
             PropName::Ident(ref n) => emit!(n),
+            PropName::Str(ref n) if self.cfg.minify && n.is_index_key() => {
+                self.emit_leading_comments_of_pos(n.span().lo())?;
+                self.wr.write_str_lit(n.span, &n.value)?;
+            }
             PropName::Str(ref n) => emit!(n),
             PropName::Num(ref n) => emit!(n),
             PropName::Computed(ref n) => emit!(n),
@@ -825,6 +912,9 @@ impl<'a> Emitter<'a> {
     pub fn emit_cond_expr(&mut self, node: &CondExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
+        if self.cfg.fully_parenthesize {
+            punct!("(");
+        }
         emit!(node.test);
         formatting_space!();
         punct!("?");
@@ -834,6 +924,9 @@ impl<'a> Emitter<'a> {
         punct!(":");
         formatting_space!();
         emit!(node.alt);
+        if self.cfg.fully_parenthesize {
+            punct!(")");
+        }
     }
 
     #[emitter]
@@ -1144,6 +1237,17 @@ impl<'a> Emitter<'a> {
     pub fn emit_paren_expr(&mut self, node: &ParenExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
+        if self.cfg.fully_parenthesize {
+            match *node.expr {
+                // Parenthesized by `emit_bin_expr` and `emit_cond_expr`.
+                Expr::Bin(..) | Expr::Cond(..) => {
+                    emit!(node.expr);
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
         punct!("(");
         emit!(node.expr);
         punct!(")");
@@ -1162,6 +1266,15 @@ impl<'a> Emitter<'a> {
         // TODO: Use write_symbol when ident is a symbol.
         self.emit_leading_comments_of_pos(ident.span.lo())?;
 
+        // Transforms may create identifiers from arbitrary strings, like
+        // property names.
+        if !is_valid_ident(&ident.sym) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("`{}` is not a valid identifier", ident.sym),
+            ));
+        }
+
         let symbol: Option<String> = None;
         if let Some(sym) = symbol {
             //            self.wr.write_symbol(
@@ -1171,7 +1284,16 @@ impl<'a> Emitter<'a> {
             unimplemented!()
         } else {
             // TODO: span
-            self.wr.write_symbol(ident.span, &ident.sym)?
+            if self.cfg.ascii_only && !ident.sym.is_ascii() {
+                self.wr
+                    .write_symbol(ident.span, &escape_ident(&ident.sym))?
+            } else {
+                self.wr.write_symbol(ident.span, &ident.sym)?
+            }
+            if self.cfg.debug_syntax_contexts && ident.span.ctxt() != SyntaxContext::empty() {
+                self.wr
+                    .write_symbol(ident.span, &format!("{:?}", ident.span.ctxt()))?
+            }
 
             // self.wr
             //     .write(get_text_of_node(&self.cm, &ident, /* includeTrivia */
@@ -1184,6 +1306,39 @@ impl<'a> Emitter<'a> {
         // emitList(node, node.typeArguments, ListFormat::TypeParameters);
     }
 
+    /// Writes blank lines between `prev` and `next` as configured by
+    /// `blank_lines`.
+    fn emit_blank_lines(&mut self, prev: Span, next: Span) -> Result {
+        let max = match self.cfg.blank_lines {
+            BlankLines::None => return Ok(()),
+            BlankLines::Collapse => 1,
+            BlankLines::Preserve => usize::max_value(),
+        };
+        if self.cfg.minify || prev.is_dummy() || next.is_dummy() || prev.hi() >= next.lo() {
+            return Ok(());
+        }
+
+        let gap = Span::new(prev.hi(), next.lo(), Default::default());
+        let between = match self.cm.span_to_snippet(gap) {
+            Ok(between) => between,
+            Err(..) => return Ok(()),
+        };
+        // The first and the last line are the ones `prev` and `next` are on.
+        let lines = between.split('\n').count();
+        let blank = between
+            .split('\n')
+            .skip(1)
+            .take(lines.saturating_sub(2))
+            .filter(|line| line.trim().is_empty())
+            .count();
+
+        for _ in 0..blank.min(max) {
+            self.wr.write_blank_line()?;
+        }
+
+        Ok(())
+    }
+
     pub fn emit_list<N: Node>(
         &mut self,
         parent_node: Span,
@@ -1318,6 +1473,7 @@ impl<'a> Emitter<'a> {
                         if !self.cfg.minify {
                             self.wr.write_line()?;
                         }
+                        self.emit_blank_lines(previous_sibling, child.span())?;
                         should_emit_intervening_comments = false;
                     } else if format.contains(ListFormat::SpaceBetweenSiblings) {
                         formatting_space!(self);
@@ -1851,7 +2007,44 @@ impl<'a> Emitter<'a> {
 
         keyword!("for");
         punct!("(");
-        opt!(node.init);
+        match node.init {
+            // `for (a in b;;)` is parsed as a `for in` statement.
+            Some(VarDeclOrExpr::Expr(ref init)) if contains_in_operator(init) => {
+                punct!("(");
+                emit!(init);
+                punct!(")");
+            }
+            Some(VarDeclOrExpr::VarDecl(ref init))
+                if init
+                    .decls
+                    .iter()
+                    .any(|d| d.init.as_ref().map_or(false, |e| contains_in_operator(e))) =>
+            {
+                let init = VarDecl {
+                    decls: init
+                        .decls
+                        .iter()
+                        .cloned()
+                        .map(|d| VarDeclarator {
+                            init: d.init.map(|e| {
+                                if contains_in_operator(&e) {
+                                    Box::new(Expr::Paren(ParenExpr {
+                                        span: e.span(),
+                                        expr: e,
+                                    }))
+                                } else {
+                                    e
+                                }
+                            }),
+                            ..d
+                        })
+                        .collect(),
+                    ..init.clone()
+                };
+                emit!(init);
+            }
+            _ => opt!(node.init),
+        }
         semi!();
         opt_leading_space!(node.test);
         semi!();
@@ -1930,6 +2123,55 @@ impl<'a> Emitter<'a> {
     }
 }
 
+/// Adds the names of the top-level `const` variables declared by `item` to
+/// `consts`.
+fn add_const_decls<'a>(consts: &mut HashSet<(&'a JsWord, SyntaxContext)>, item: &'a ModuleItem) {
+    let decl = match *item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(ref decl)))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Var(ref decl),
+            ..
+        })) if decl.kind == VarDeclKind::Const => decl,
+        _ => return,
+    };
+
+    for d in &decl.decls {
+        if let Pat::Ident(ref i) = d.name {
+            consts.insert((&i.sym, i.span.ctxt()));
+        }
+    }
+}
+
+/// Returns `a` if `item` is `export { a as default }` and `a` is in `consts`,
+/// the top-level `const` variables declared before `item`. This makes it
+/// equivalent to `export default a`.
+fn collapsible_default_export<'a>(
+    consts: &HashSet<(&JsWord, SyntaxContext)>,
+    item: &'a ModuleItem,
+) -> Option<&'a Ident> {
+    let orig = match *item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+            src: None,
+            ref specifiers,
+            ..
+        })) if specifiers.len() == 1 => match specifiers[0] {
+            ExportSpecifier::Named(NamedExportSpecifier {
+                ref orig,
+                exported: Some(ref exported),
+                ..
+            }) if exported.sym == js_word!("default") => orig,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if consts.contains(&(&orig.sym, orig.span.ctxt())) {
+        Some(orig)
+    } else {
+        None
+    }
+}
+
 #[allow(dead_code)]
 fn get_text_of_node<T: Spanned>(
     cm: &Arc<SourceMap>,
@@ -1996,10 +2238,267 @@ fn should_emit_whitespace_before_operand(node: &UnaryExpr) -> bool {
             op: op!(unary, "-"),
             ..
         }) if node.op == op!(unary, "-") => true,
+        Expr::Lit(Lit::Num(Number { value, .. })) if node.op == op!(unary, "-") => {
+            value.is_sign_negative()
+        }
         _ => false,
     }
 }
 
+/// Formats a finite number like `Number.prototype.toString`, except that the
+/// sign of negative numbers (including `-0`) is preserved and `+` is omitted
+/// from positive exponents.
+///
+/// The shortest digits which round-trip to the same value are used, so `0.1`
+/// is emitted as `0.1`. Integers are emitted without an exponent below
+/// `1e21`.
+fn format_number(value: f64) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    if value.is_sign_negative() {
+        return format!("-{}", format_number(-value));
+    }
+    if value == 0.0 {
+        return String::from("0");
+    }
+
+    let (digits, n) = shortest_digits(value);
+    let k = digits.len() as i32;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else if k == 1 {
+        format!("{}e{}", digits, n - 1)
+    } else {
+        format!("{}.{}e{}", &digits[..1], &digits[1..], n - 1)
+    }
+}
+
+/// Picks the shortest of the decimal, exponent and hexadecimal forms of a
+/// finite number, preferring them in that order.
+///
+/// e.g. `1000000` becomes `1e6`, `0.5` becomes `.5` and `281474976710655`
+/// becomes `0xffffffffffff`.
+fn minify_number(value: f64) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    if value.is_sign_negative() {
+        return format!("-{}", minify_number(-value));
+    }
+    if value == 0.0 {
+        return String::from("0");
+    }
+
+    let mut best = format_number(value);
+    if best.starts_with("0.") {
+        best.remove(0);
+    }
+
+    // `{digits}e{exp}`
+    let (digits, n) = shortest_digits(value);
+    let exp = n - digits.len() as i32;
+    if exp != 0 {
+        let s = format!("{}e{}", digits, exp);
+        if s.len() < best.len() {
+            best = s;
+        }
+    }
+
+    // Integers are exact below 2^53.
+    if value.fract() == 0.0 && value < 9_007_199_254_740_992.0 {
+        let s = format!("0x{:x}", value as u64);
+        if s.len() < best.len() {
+            best = s;
+        }
+    }
+
+    best
+}
+
+/// Returns the quote which requires fewer escapes in a string literal of
+/// `value`, preferring single quotes.
+fn choose_quote(value: &str) -> char {
+    let (single, double) = value.chars().fold((0, 0), |(single, double), c| match c {
+        '\'' => (single + 1, double),
+        '"' => (single, double + 1),
+        _ => (single, double),
+    });
+
+    if double < single {
+        '"'
+    } else {
+        '\''
+    }
+}
+
+/// Escapes `value` for a string literal quoted with `quote`, using the
+/// shortest escape for each character which can't be printed as is.
+///
+/// Other control characters are escaped too if `control_chars` is true.
+fn escape_str(value: &str, quote: char, control_chars: bool) -> String {
+    let mut buf = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => buf.push_str("\\\\"),
+            '\u{0008}' => buf.push_str("\\b"),
+            '\u{000C}' => buf.push_str("\\f"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\u{000B}' => buf.push_str("\\v"),
+            // `\0` followed by a digit is an octal escape.
+            '\0' => match chars.peek() {
+                Some(c) if c.is_ascii_digit() => buf.push_str("\\x00"),
+                _ => buf.push_str("\\0"),
+            },
+            // Line terminators are not allowed in string literals before es2019.
+            '\u{2028}' => buf.push_str("\\u2028"),
+            '\u{2029}' => buf.push_str("\\u2029"),
+            _ if c == quote => {
+                buf.push('\\');
+                buf.push(c);
+            }
+            '\u{0001}'..='\u{001F}' | '\u{007F}'..='\u{009F}' if control_chars => {
+                buf.push_str(&format!("\\x{:02X}", c as u32))
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+/// Returns true if `e` contains an `in` operator which is not in brackets or
+/// parentheses, which is not allowed in the initializer of a `for` statement.
+fn contains_in_operator(e: &Expr) -> bool {
+    match *e {
+        Expr::Bin(BinExpr { op: op!("in"), .. }) => true,
+        Expr::Bin(BinExpr {
+            ref left,
+            ref right,
+            ..
+        }) => contains_in_operator(left) || contains_in_operator(right),
+        Expr::Assign(AssignExpr {
+            ref left,
+            ref right,
+            ..
+        }) => {
+            let left = match *left {
+                PatOrExpr::Expr(ref e) => contains_in_operator(e),
+                PatOrExpr::Pat(..) => false,
+            };
+            left || contains_in_operator(right)
+        }
+        Expr::Seq(SeqExpr { ref exprs, .. }) => exprs.iter().any(|e| contains_in_operator(e)),
+        // `cons` allows `in`.
+        Expr::Cond(CondExpr {
+            ref test, ref alt, ..
+        }) => contains_in_operator(test) || contains_in_operator(alt),
+        Expr::Unary(UnaryExpr { ref arg, .. })
+        | Expr::Update(UpdateExpr { ref arg, .. })
+        | Expr::Await(AwaitExpr { ref arg, .. })
+        | Expr::Yield(YieldExpr {
+            arg: Some(ref arg), ..
+        }) => contains_in_operator(arg),
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref e),
+            ..
+        })
+        | Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(ref e),
+            ..
+        })
+        | Expr::New(NewExpr { callee: ref e, .. })
+        | Expr::TaggedTpl(TaggedTpl { tag: ref e, .. })
+        | Expr::Arrow(ArrowExpr {
+            body: BlockStmtOrExpr::Expr(ref e),
+            ..
+        })
+        | Expr::TsAs(TsAsExpr { expr: ref e, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr: ref e, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr: ref e, .. }) => contains_in_operator(e),
+        _ => false,
+    }
+}
+
+/// Returns true if `s` can be printed as an identifier.
+fn is_valid_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(c) if c == '$' || c == '_' || UnicodeXID::is_xid_start(c) => chars.all(|c| {
+            c == '$' || c == '\u{200c}' || c == '\u{200d}' || UnicodeXID::is_xid_continue(c)
+        }),
+        _ => false,
+    }
+}
+
+/// Escapes non-ASCII characters of an identifier, using `\u{...}` for
+/// characters outside of the basic multilingual plane.
+fn escape_ident(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c as u32 {
+            0..=0x7f => buf.push(c),
+            v @ 0x80..=0xffff => buf.push_str(&format!("\\u{:04x}", v)),
+            v => buf.push_str(&format!("\\u{{{:x}}}", v)),
+        }
+    }
+
+    buf
+}
+
+/// Escapes sequences which end a `<script>` element or start or end a html
+/// comment.
+fn escape_script_sequences(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        if rest
+            .get(..8)
+            .map_or(false, |p| p.eq_ignore_ascii_case("</script"))
+        {
+            buf.push_str("<\\/");
+            rest = &rest[2..];
+        } else if rest.starts_with("<!--") {
+            buf.push_str("\\x3C!--");
+            rest = &rest[4..];
+        } else if rest.starts_with("-->") {
+            buf.push_str("--\\x3E");
+            rest = &rest[3..];
+        } else {
+            buf.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    buf
+}
+
+/// Returns the shortest digits which round-trip to `value` and the position of
+/// the decimal point, so that `value` is `0.{digits} * 10^n`.
+///
+/// `value` should be a positive finite number.
+fn shortest_digits(value: f64) -> (String, i32) {
+    // `{:e}` uses the shortest representation, like `1.2345e-7`.
+    let s = format!("{:e}", value);
+    let e = s.find('e').unwrap();
+    let digits = s[..e].chars().filter(|&c| c != '.').collect();
+    let exp: i32 = s[e + 1..].parse().unwrap();
+
+    (digits, exp + 1)
+}
+
 impl<N> Node for Option<N>
 where
     N: Node,
@@ -0,0 +1,41 @@
+use crate::{text_writer::JsWriter, Config, Emitter, Handlers};
+use sourcemap::SourceMapBuilder;
+use std::{
+    io::{self, BufWriter, Write},
+    sync::Arc,
+};
+use swc_common::{comments::Comments, SourceMap};
+use swc_ecma_ast::Module;
+
+impl<'a> Emitter<'a> {
+    /// Emits `module` into `wr` while generating it, so the whole output is
+    /// never held in memory.
+    ///
+    /// Writes to `wr` are buffered, and `wr` is flushed when the module is
+    /// emitted. Mappings are added to `srcmap` as tokens are written, using
+    /// the number of bytes written so far.
+    pub fn emit_module_to<W>(
+        cfg: Config,
+        cm: Arc<SourceMap>,
+        comments: Option<&'a Comments>,
+        handlers: Box<dyn 'a + Handlers>,
+        module: &Module,
+        wr: W,
+        srcmap: Option<&'a mut SourceMapBuilder>,
+    ) -> io::Result<()>
+    where
+        W: 'a + Write,
+    {
+        let new_line = cfg.line_ending.new_line(&cm, module.span).unwrap_or("\n");
+
+        let mut emitter = Emitter {
+            cfg,
+            cm: cm.clone(),
+            comments,
+            wr: Box::new(JsWriter::new(cm, new_line, BufWriter::new(wr), srcmap)),
+            handlers,
+            pos_of_leading_comments: Default::default(),
+        };
+        emitter.emit_module(module)
+    }
+}
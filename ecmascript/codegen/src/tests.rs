@@ -1,9 +1,9 @@
 use self::swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
 use super::*;
-use crate::config::Config;
+use crate::config::{BlankLines, Config, LineEnding, NumberStyle, PreserveComments};
 use std::{
     fmt::{self, Debug, Display, Formatter},
-    io::Write,
+    io::{self, Write},
     sync::{Arc, RwLock},
 };
 use swc_common::{comments::Comments, FileName, SourceMap};
@@ -50,6 +50,15 @@ impl Builder {
 }
 
 fn parse_then_emit(from: &str, cfg: Config) -> String {
+    parse_then_emit_with(from, cfg, |_| {})
+}
+
+/// Same as `parse_then_emit`, but allows modifying the module before emitting
+/// it.
+fn parse_then_emit_with<F>(from: &str, cfg: Config, op: F) -> String
+where
+    F: FnOnce(&mut Module),
+{
     ::testing::run_test(false, |cm, handler| {
         let src = cm.new_source_file(FileName::Real("custom.js".into()), from.to_string());
         println!(
@@ -58,7 +67,7 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
         );
 
         let comments = Default::default();
-        let res = {
+        let mut res = {
             let mut parser = Parser::new(
                 Session { handler: &handler },
                 Syntax::default(),
@@ -69,6 +78,7 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
                 e.emit();
             })?
         };
+        op(&mut res);
 
         let out = Builder { cfg, cm, comments }.text(from, |e| e.emit_module(&res).unwrap());
         Ok(out)
@@ -77,13 +87,25 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
 }
 
 pub(crate) fn assert_min(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: true });
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+    );
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
 }
 
 pub(crate) fn assert_pretty(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: false });
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: false,
+            ..Default::default()
+        },
+    );
 
     assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
 }
@@ -152,6 +174,273 @@ a;",
     );
 }
 
+fn assert_script_safe(from: &str, to: &str) {
+    let out = parse_then_emit(
+        from,
+        Config {
+            script_safe_strings: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to));
+}
+
+#[test]
+fn script_safe_strings_script_tag() {
+    assert_script_safe("'</script>';", r"'<\/script>';");
+    assert_script_safe("\"</SCRIPT>'\";", r#""<\/SCRIPT>'";"#);
+}
+
+#[test]
+fn script_safe_strings_comment_start() {
+    assert_script_safe("'a<!--b';", r"'a\x3C!--b';");
+    assert_script_safe("\"a<!--b'\";", r#""a\x3C!--b'";"#);
+}
+
+#[test]
+fn script_safe_strings_comment_end() {
+    assert_script_safe("'a-->b';", r"'a--\x3Eb';");
+    assert_script_safe("\"a-->b'\";", r#""a--\x3Eb'";"#);
+}
+
+#[test]
+fn script_safe_strings_disabled() {
+    test_from_to("'</script><!-- -->';", "'</script><!-- -->';");
+}
+
+fn emit_numbers(from: &str, number_style: NumberStyle) -> String {
+    let out = parse_then_emit(
+        from,
+        Config {
+            number_style,
+            ..Default::default()
+        },
+    );
+
+    out.trim().to_string()
+}
+
+#[test]
+fn number_style_integer_compact() {
+    let style = NumberStyle::IntegerCompact;
+
+    assert_eq!(emit_numbers("0.0;", style), "0;");
+    assert_eq!(emit_numbers("1.0;", style), "1;");
+    assert_eq!(emit_numbers("1;", style), "1;");
+}
+
+#[test]
+fn number_style_preserve() {
+    let style = NumberStyle::Preserve;
+
+    assert_eq!(emit_numbers("0.0;", style), "0.0;");
+    assert_eq!(emit_numbers("1.0;", style), "1.0;");
+    assert_eq!(emit_numbers("1;", style), "1;");
+    // Not a decimal literal.
+    assert_eq!(emit_numbers("0x10;", style), "16;");
+}
+
+#[test]
+fn number_style_preserve_property_access() {
+    let style = NumberStyle::Preserve;
+
+    assert_eq!(emit_numbers("1.0.toString();", style), "1.0.toString();");
+    assert_eq!(emit_numbers("1e3.toString();", style), "1e3.toString();");
+    assert_eq!(emit_numbers("1..toString();", style), "1..toString();");
+}
+
+#[test]
+fn number_style_preserve_changed_value() {
+    let out = parse_then_emit_with(
+        "1.0;",
+        Config {
+            number_style: NumberStyle::Preserve,
+            ..Default::default()
+        },
+        |m| match m.body[0] {
+            ModuleItem::Stmt(Stmt::Expr(ref mut e)) => match **e {
+                Expr::Lit(Lit::Num(ref mut num)) => num.value = 2.0,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        },
+    );
+
+    assert_eq!(out.trim(), "2;");
+}
+
+fn emit_comments(preserve_comments: PreserveComments) -> String {
+    parse_then_emit(
+        "// @custom-keep first
+foo();
+/* drop me */
+bar();
+/*! license */
+baz();
+/* @custom-keep second */
+qux();",
+        Config {
+            preserve_comments,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn preserve_comments_regex() {
+    let out = emit_comments(PreserveComments::Regex("@custom-keep".into()));
+
+    assert!(out.contains("// @custom-keep first"), "{}", out);
+    assert!(out.contains("/* @custom-keep second */"), "{}", out);
+    assert!(!out.contains("drop me"), "{}", out);
+    assert!(!out.contains("license"), "{}", out);
+}
+
+#[test]
+fn preserve_comments_regex_alternating() {
+    for _ in 0..2 {
+        let out = emit_comments(PreserveComments::Regex("first".into()));
+        assert!(out.contains("// @custom-keep first"), "{}", out);
+        assert!(!out.contains("second"), "{}", out);
+
+        let out = emit_comments(PreserveComments::Regex("second".into()));
+        assert!(out.contains("/* @custom-keep second */"), "{}", out);
+        assert!(!out.contains("first"), "{}", out);
+    }
+}
+
+#[test]
+fn preserve_comments_license() {
+    let out = emit_comments(PreserveComments::License);
+
+    assert!(out.contains("/*! license */"), "{}", out);
+    assert!(!out.contains("@custom-keep"), "{}", out);
+    assert!(!out.contains("drop me"), "{}", out);
+}
+
+#[test]
+fn preserve_comments_none_and_all() {
+    let out = emit_comments(PreserveComments::None);
+    assert!(!out.contains("/*") && !out.contains("//"), "{}", out);
+
+    let out = emit_comments(PreserveComments::All);
+    assert!(out.contains("/* drop me */"), "{}", out);
+    assert!(out.contains("// @custom-keep first"), "{}", out);
+}
+
+fn emit_blank_lines(blank_lines: BlankLines) -> String {
+    parse_then_emit(
+        "a();
+
+
+
+b();
+function f() {
+    c();
+
+
+    // comment
+    d();
+}",
+        Config {
+            blank_lines,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn blank_lines_none() {
+    assert_eq!(
+        DebugUsingDisplay(emit_blank_lines(BlankLines::None).trim()),
+        DebugUsingDisplay("a();\nb();\nfunction f() {\n    c();\n    // comment\n    d();\n}")
+    );
+}
+
+#[test]
+fn blank_lines_collapse() {
+    assert_eq!(
+        DebugUsingDisplay(emit_blank_lines(BlankLines::Collapse).trim()),
+        DebugUsingDisplay("a();\n\nb();\nfunction f() {\n    c();\n\n    // comment\n    d();\n}")
+    );
+}
+
+#[test]
+fn blank_lines_preserve() {
+    assert_eq!(
+        DebugUsingDisplay(emit_blank_lines(BlankLines::Preserve).trim()),
+        DebugUsingDisplay(
+            "a();\n\n\n\nb();\nfunction f() {\n    c();\n\n\n    // comment\n    d();\n}"
+        )
+    );
+}
+
+#[test]
+fn preserve_annotations_minify() {
+    let out = parse_then_emit(
+        "/*! banner */
+foo();
+/* normal */
+bar();",
+        Config {
+            minify: true,
+            preserve_annotations: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("/*! banner */\nfoo();bar();")
+    );
+}
+
+#[test]
+fn preserve_annotations_removed_code() {
+    let out = parse_then_emit_with(
+        "/** @license MIT */
+unused();
+// normal
+foo();",
+        Config {
+            minify: false,
+            preserve_annotations: true,
+            ..Default::default()
+        },
+        |m| {
+            m.body.remove(0);
+        },
+    );
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("/** @license MIT */\n// normal\nfoo();")
+    );
+}
+
+#[test]
+fn preserve_annotations_with_preserve_comments() {
+    let out = parse_then_emit(
+        "/*! banner */
+foo();
+/* @keep */
+bar();
+/* normal */
+baz();",
+        Config {
+            preserve_annotations: true,
+            preserve_comments: PreserveComments::Regex("@keep".into()),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("/*! banner */\nfoo();\n/* @keep */\nbar();\nbaz();")
+    );
+}
+
 #[test]
 fn no_octal_escape() {
     test_from_to(
@@ -166,6 +455,122 @@ fn no_octal_escape() {
     );
 }
 
+#[test]
+fn str_quote() {
+    test_from_to(
+        r#"'a';
+"it's";
+'say "hi"';
+'\'\'\'"';
+"'\"\"\"";
+'\'"';"#,
+        r#"'a';
+"it's";
+'say "hi"';
+"'''\"";
+'\'"""';
+'\'"';"#,
+    );
+}
+
+#[test]
+fn str_escape() {
+    test_from_to(
+        r#"'a\
+b\nc\u000a\x0d\u0009';
+'\u0000\x001\0';
+'\u2028\u{2029}';
+'\x41é\\';"#,
+        r#"'ab\nc\n\r\t';
+'\0\x001\0';
+'\u2028\u2029';
+'Aé\\';"#,
+    );
+}
+
+#[test]
+fn index_keys_min() {
+    assert_min("a = { '0': 1, \"42\": 2 };", "a={0:1,42:2};");
+}
+
+#[test]
+fn non_index_keys_min() {
+    assert_min("a = { '1.5': 1 };", "a={'1.5':1};");
+    assert_min("a = { '00': 1, '01': 2 };", "a={'00':1,'01':2};");
+    assert_min(
+        "a = { '9007199254740993': 1, '': 2 };",
+        "a={'9007199254740993':1,'':2};",
+    );
+}
+
+#[test]
+fn index_keys_pretty() {
+    test_from_to("a = { '0': 1 };", "a = {\n    '0': 1\n};");
+}
+
+fn emit_control_chars(from: &str, minify_control_chars: bool) -> String {
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify_control_chars,
+            ..Default::default()
+        },
+    );
+
+    out.trim().to_string()
+}
+
+#[test]
+fn minify_control_chars() {
+    assert_eq!(emit_control_chars(r"'\u00001';", true), r"'\x001';");
+    assert_eq!(emit_control_chars(r"'\u0000a';", true), r"'\0a';");
+    assert_eq!(emit_control_chars(r"'\u0009';", true), r"'\t';");
+    assert_eq!(emit_control_chars(r"'\u007f\u001b';", true), r"'\x7F\x1B';");
+}
+
+#[test]
+fn minify_control_chars_disabled() {
+    assert_eq!(emit_control_chars(r"'\u00001';", false), r"'\x001';");
+    assert_eq!(emit_control_chars(r"'\u0009';", false), r"'\t';");
+    assert_eq!(
+        emit_control_chars(r"'\u007f\u001b';", false),
+        "'\u{7f}\u{1b}';"
+    );
+}
+
+fn emit_final_newline(from: &str, minify: bool, final_newline: bool) -> String {
+    parse_then_emit(
+        from,
+        Config {
+            minify,
+            final_newline,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn final_newline() {
+    assert_eq!(emit_final_newline("a;\nb;", false, true), "a;\nb;\n");
+    assert_eq!(emit_final_newline("a;\nb;", true, true), "a;b;\n");
+    assert_eq!(emit_final_newline("", false, true), "");
+}
+
+#[test]
+fn final_newline_by_default() {
+    assert_eq!(parse_then_emit("a;\nb;", Default::default()), "a;\nb;\n");
+}
+
+#[test]
+fn final_newline_disabled() {
+    assert_eq!(emit_final_newline("a;\nb;", false, false), "a;\nb;");
+    assert_eq!(
+        emit_final_newline("export const a = 1;", false, false),
+        "export const a = 1;"
+    );
+    assert_eq!(emit_final_newline("a;\nb;", true, false), "a;b;");
+}
+
 #[test]
 fn issue_450() {
     test_from_to(
@@ -182,6 +587,630 @@ fn issue_450() {
     );
 }
 
+/// Emits `value` by replacing the number of `0;`.
+fn emit_num(value: f64) -> String {
+    let out = parse_then_emit_with("0;", Default::default(), |m| match m.body[0] {
+        ModuleItem::Stmt(Stmt::Expr(ref mut e)) => match **e {
+            Expr::Lit(Lit::Num(ref mut num)) => num.value = value,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    });
+
+    out.trim().to_string()
+}
+
+#[test]
+fn number_negative_zero() {
+    assert_eq!(emit_num(-0.0), "-0;");
+    assert_eq!(emit_num(0.0), "0;");
+    assert_eq!(emit_num(-1.5), "-1.5;");
+}
+
+#[test]
+fn number_negative_zero_operand() {
+    let out = parse_then_emit_with(
+        "a - 0; -0;",
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+        |m| {
+            for item in &mut m.body {
+                let e = match *item {
+                    ModuleItem::Stmt(Stmt::Expr(ref mut e)) => e,
+                    _ => unreachable!(),
+                };
+                let num = match **e {
+                    Expr::Bin(BinExpr { ref mut right, .. }) => right,
+                    Expr::Unary(UnaryExpr { ref mut arg, .. }) => arg,
+                    _ => unreachable!(),
+                };
+                match **num {
+                    Expr::Lit(Lit::Num(ref mut num)) => num.value = -0.0,
+                    _ => unreachable!(),
+                }
+            }
+        },
+    );
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("a- -0;- -0;")
+    );
+}
+
+#[test]
+fn number_large() {
+    test_from_to("1e21", "1e21;");
+    test_from_to("1000000000000000000000", "1e21;");
+    test_from_to("1e20", "100000000000000000000;");
+    test_from_to("1.5e300", "1.5e300;");
+    // Not representable, so the closest value is emitted.
+    test_from_to("9007199254740993", "9007199254740992;");
+    test_from_to("9007199254740992", "9007199254740992;");
+}
+
+#[test]
+fn number_fraction() {
+    test_from_to("0.1", "0.1;");
+    test_from_to("0.1 + 0.2", "0.1 + 0.2;");
+    test_from_to("0.000001", "0.000001;");
+    test_from_to("1e-7", "1e-7;");
+    test_from_to("1.25e-10", "1.25e-10;");
+    test_from_to("123.456", "123.456;");
+}
+
+#[test]
+fn inline_source_map() {
+    let src = "var a = 1;\nfoo(a);\n";
+
+    let code = ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Real("input.js".into()), src.to_string());
+        let module = Parser::new(
+            Session { handler: &handler },
+            Syntax::default(),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_module()
+        .map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let code = Emitter::emit_with_inline_source_map(
+            Default::default(),
+            cm,
+            None,
+            Box::new(Noop),
+            &module,
+            &SourceMapConfig {
+                source_root: Some("/src".into()),
+                sources_content: true,
+            },
+        )
+        .unwrap();
+
+        Ok(code)
+    })
+    .unwrap();
+
+    const PREFIX: &str = "//# sourceMappingURL=data:application/json;base64,";
+    let idx = code.rfind(PREFIX).expect("should have inline source map");
+    let (code, url) = code.split_at(idx);
+    assert_eq!(code, "var a = 1;\nfoo(a);\n");
+
+    let json = base64::decode(&url[PREFIX.len()..]).unwrap();
+    assert!(String::from_utf8_lossy(&json).contains(r#""sourceRoot":"/src""#));
+
+    let map = sourcemap::SourceMap::from_slice(&json).unwrap();
+    assert_eq!(map.get_source(0), Some("input.js"));
+    assert_eq!(map.get_source_contents(0), Some(src));
+    assert!(map.get_token_count() > 0);
+
+    let lines = code.lines().collect::<Vec<_>>();
+    for token in map.tokens() {
+        let line = lines[token.get_dst_line() as usize];
+        assert!((token.get_dst_col() as usize) < line.len());
+        assert!((token.get_src_line() as usize) < src.lines().count());
+    }
+}
+
+/// Emits `src` with a source map with `sourcesContent`.
+fn emit_with_sources_content(name: FileName, src: &str) -> sourcemap::SourceMap {
+    ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(name, src.to_string());
+        let module = Parser::new(
+            Session { handler: &handler },
+            Syntax::default(),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_module()
+        .map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let mut builder = sourcemap::SourceMapBuilder::new(None);
+        let mut buf = vec![];
+        {
+            let mut e = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                wr: Box::new(
+                    text_writer::JsWriter::new(cm.clone(), "\n", &mut buf, Some(&mut builder))
+                        .with_sources_content(true),
+                ),
+                comments: None,
+                handlers: Box::new(Noop),
+                pos_of_leading_comments: Default::default(),
+            };
+            e.emit_module(&module).unwrap();
+        }
+
+        Ok(builder.into_sourcemap())
+    })
+    .unwrap()
+}
+
+#[test]
+fn sources_content() {
+    let src = "var a = 1;\nfoo(a);\n";
+    let map = emit_with_sources_content(FileName::Real("input.js".into()), src);
+
+    assert_eq!(map.get_source_count(), 1);
+    assert_eq!(map.get_source(0), Some("input.js"));
+    assert_eq!(map.get_source_contents(0), Some(src));
+}
+
+#[test]
+fn sources_content_anon() {
+    let map = emit_with_sources_content(FileName::Anon, "foo();");
+
+    assert_eq!(map.get_source_count(), 0);
+}
+
+/// Emits `src` using the line terminator selected by `line_ending`, with a
+/// writer created with `writer_new_line`.
+fn emit_with_line_ending(
+    src: &str,
+    line_ending: LineEnding,
+    writer_new_line: &'static str,
+) -> (String, sourcemap::SourceMap) {
+    ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Real("input.js".into()), src.to_string());
+        let module = Parser::new(
+            Session { handler: &handler },
+            Syntax::default(),
+            SourceFileInput::from(&*fm),
+            None,
+        )
+        .parse_module()
+        .map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let cfg = Config {
+            line_ending,
+            ..Default::default()
+        };
+
+        let mut builder = sourcemap::SourceMapBuilder::new(None);
+        let mut buf = vec![];
+        {
+            let mut e = Emitter {
+                cfg,
+                cm: cm.clone(),
+                wr: Box::new(text_writer::JsWriter::new(
+                    cm.clone(),
+                    writer_new_line,
+                    &mut buf,
+                    Some(&mut builder),
+                )),
+                comments: None,
+                handlers: Box::new(Noop),
+                pos_of_leading_comments: Default::default(),
+            };
+            e.emit_module(&module).unwrap();
+        }
+
+        Ok((String::from_utf8(buf).unwrap(), builder.into_sourcemap()))
+    })
+    .unwrap()
+}
+
+#[test]
+fn line_ending_crlf() {
+    let (code, map) = emit_with_line_ending("var a = 1;\nfoo(a);\nbar(a);", LineEnding::CrLf, "\n");
+
+    assert_eq!(code, "var a = 1;\r\nfoo(a);\r\nbar(a);\r\n");
+
+    // `\r` is not counted as a column of the next line.
+    let token = map
+        .lookup_token(2, 4)
+        .expect("should have a mapping for `a`");
+    assert_eq!(token.get_dst_col(), 4);
+    assert_eq!((token.get_src_line(), token.get_src_col()), (2, 4));
+}
+
+#[test]
+fn line_ending_from_source() {
+    let src = "var a = 1;\r\nfoo(a);\r\nbar(a);\r\n";
+    let (code, map) = emit_with_line_ending(src, LineEnding::FromSource, "\n");
+
+    assert_eq!(code, src);
+    for token in map.tokens() {
+        let line = code
+            .split("\r\n")
+            .nth(token.get_dst_line() as usize)
+            .unwrap();
+        assert!((token.get_dst_col() as usize) <= line.len());
+        assert_eq!(token.get_dst_line(), token.get_src_line());
+    }
+    let token = map
+        .lookup_token(1, 4)
+        .expect("should have a mapping for `a`");
+    assert_eq!((token.get_src_line(), token.get_src_col()), (1, 4));
+
+    let (code, _) = emit_with_line_ending("var a = 1;\nfoo(a);", LineEnding::FromSource, "\r\n");
+    assert_eq!(code, "var a = 1;\nfoo(a);\n");
+}
+
+#[test]
+fn line_ending_overrides_writer() {
+    let out = parse_then_emit(
+        "a;\nb;",
+        Config {
+            line_ending: LineEnding::CrLf,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(out, "a;\r\nb;\r\n");
+}
+
+#[test]
+fn line_ending_from_writer() {
+    let (code, _) = emit_with_line_ending("a;\nb;", LineEnding::FromWriter, "\r\n");
+    assert_eq!(code, "a;\r\nb;\r\n");
+
+    let (code, _) = emit_with_line_ending("a;\r\nb;", LineEnding::default(), "\n");
+    assert_eq!(code, "a;\nb;\n");
+}
+
+/// Accepts at most one byte per call to `write`.
+struct Trickle<'a>(&'a mut Vec<u8>);
+
+impl Write for Trickle<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(&buf[..buf.len().min(1)])
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn positions(map: &sourcemap::SourceMap) -> Vec<(u32, u32, u32, u32)> {
+    map.tokens()
+        .map(|t| {
+            (
+                t.get_dst_line(),
+                t.get_dst_col(),
+                t.get_src_line(),
+                t.get_src_col(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn emit_module_to_writer() {
+    let src = "var a = 1;\nfunction foo(b) {\n    return a + b;\n}\nfoo(`x\ny`);\n";
+
+    let (buffered, buffered_map, streamed, streamed_map) =
+        ::testing::run_test(false, |cm, handler| {
+            let fm = cm.new_source_file(FileName::Real("input.js".into()), src.to_string());
+            let module = Parser::new(
+                Session { handler: &handler },
+                Syntax::default(),
+                SourceFileInput::from(&*fm),
+                None,
+            )
+            .parse_module()
+            .map_err(|mut e| {
+                e.emit();
+            })?;
+
+            let mut buffered_map = sourcemap::SourceMapBuilder::new(None);
+            let mut buffered = vec![];
+            {
+                let mut e = Emitter {
+                    cfg: Default::default(),
+                    cm: cm.clone(),
+                    wr: Box::new(text_writer::JsWriter::new(
+                        cm.clone(),
+                        "\n",
+                        &mut buffered,
+                        Some(&mut buffered_map),
+                    )),
+                    comments: None,
+                    handlers: Box::new(Noop),
+                    pos_of_leading_comments: Default::default(),
+                };
+                e.emit_module(&module).unwrap();
+            }
+
+            let mut streamed_map = sourcemap::SourceMapBuilder::new(None);
+            let mut streamed = vec![];
+            Emitter::emit_module_to(
+                Default::default(),
+                cm.clone(),
+                None,
+                Box::new(Noop),
+                &module,
+                Trickle(&mut streamed),
+                Some(&mut streamed_map),
+            )
+            .unwrap();
+
+            Ok((
+                buffered,
+                buffered_map.into_sourcemap(),
+                streamed,
+                streamed_map.into_sourcemap(),
+            ))
+        })
+        .unwrap();
+
+    assert_eq!(
+        DebugUsingDisplay(&String::from_utf8(streamed).unwrap()),
+        DebugUsingDisplay(&String::from_utf8(buffered).unwrap())
+    );
+    assert!(buffered_map.get_token_count() > 0);
+    assert_eq!(positions(&streamed_map), positions(&buffered_map));
+}
+
+fn fully_parenthesize(src: &str) -> String {
+    parse_then_emit(
+        src,
+        Config {
+            fully_parenthesize: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Removes parentheses around binary and conditional expressions.
+fn unwrap_parens(e: &mut Expr) {
+    match *e {
+        Expr::Paren(ParenExpr { ref expr, .. }) => {
+            let expr = (**expr).clone();
+            *e = expr;
+            unwrap_parens(e);
+        }
+        Expr::Bin(BinExpr {
+            ref mut left,
+            ref mut right,
+            ..
+        }) => {
+            unwrap_parens(left);
+            unwrap_parens(right);
+        }
+        Expr::Cond(CondExpr {
+            ref mut test,
+            ref mut cons,
+            ref mut alt,
+            ..
+        }) => {
+            unwrap_parens(test);
+            unwrap_parens(cons);
+            unwrap_parens(alt);
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn fully_parenthesize_round_trip() {
+    let src = "a + b * c - d ? e : f;";
+    let out = fully_parenthesize(src);
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("(((a + (b * c)) - d) ? e : f);")
+    );
+
+    // Parsing the output gives the same expression, except for parentheses.
+    let reparsed = parse_then_emit_with(&out, Default::default(), |m| {
+        for item in &mut m.body {
+            if let ModuleItem::Stmt(Stmt::Expr(ref mut e)) = *item {
+                unwrap_parens(e);
+            }
+        }
+    });
+    assert_eq!(reparsed, parse_then_emit(src, Default::default()));
+
+    // Existing parentheses are not doubled.
+    assert_eq!(fully_parenthesize(&out), out);
+}
+
+#[test]
+fn fully_parenthesize_logical() {
+    let out = fully_parenthesize("x = a && b || c;");
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("x = ((a && b) || c);")
+    );
+}
+
+fn collapse_default_export(src: &str) -> String {
+    parse_then_emit(
+        src,
+        Config {
+            collapse_default_export: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn collapse_default_export_const() {
+    let out = collapse_default_export("const a = 1;\nexport { a as default };");
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("const a = 1;\nexport default a;")
+    );
+}
+
+#[test]
+fn collapse_default_export_live_binding() {
+    assert_eq!(
+        DebugUsingDisplay(
+            collapse_default_export("let b = 1;\nexport { b as default };\nb = 2;").trim()
+        ),
+        DebugUsingDisplay("let b = 1;\nexport { b as default }\nb = 2;")
+    );
+    assert_eq!(
+        DebugUsingDisplay(collapse_default_export("var c = 1;\nexport { c as default };").trim()),
+        DebugUsingDisplay("var c = 1;\nexport { c as default }")
+    );
+}
+
+#[test]
+fn collapse_default_export_before_decl() {
+    let out = collapse_default_export("export { a as default };\nconst a = 1;");
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("export { a as default }\nconst a = 1;")
+    );
+}
+
+#[test]
+fn collapse_default_export_disabled() {
+    let out = parse_then_emit("const a = 1;\nexport { a as default };", Default::default());
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("const a = 1;\nexport { a as default }")
+    );
+}
+
+fn in_expr() -> Box<Expr> {
+    Box::new(Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        left: Box::new(Expr::Ident(Ident::new("x".into(), DUMMY_SP))),
+        op: op!("in"),
+        right: Box::new(Expr::Ident(Ident::new("y".into(), DUMMY_SP))),
+    }))
+}
+
+#[test]
+fn for_init_in_operator() {
+    let out = parse_then_emit_with("for(a;;);", Default::default(), |m| match m.body[0] {
+        ModuleItem::Stmt(Stmt::For(ForStmt {
+            init: Some(VarDeclOrExpr::Expr(ref mut init)),
+            ..
+        })) => {
+            *init = Box::new(Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(Box::new(Pat::Ident(Ident::new("a".into(), DUMMY_SP)))),
+                op: op!("="),
+                right: in_expr(),
+            }))
+        }
+        _ => unreachable!(),
+    });
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("for((a = x in y);;);")
+    );
+}
+
+#[test]
+fn for_init_var_in_operator() {
+    let out = parse_then_emit_with(
+        "for(var a = 1, b = 2;;);",
+        Default::default(),
+        |m| match m.body[0] {
+            ModuleItem::Stmt(Stmt::For(ForStmt {
+                init: Some(VarDeclOrExpr::VarDecl(ref mut init)),
+                ..
+            })) => init.decls[0].init = Some(in_expr()),
+            _ => unreachable!(),
+        },
+    );
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay("for(var a = (x in y), b = 2;;);")
+    );
+}
+
+#[test]
+fn for_init_in_operator_in_brackets() {
+    test_from_to("for(a = [x in y];;);", "for(a = [x in y];;);");
+    test_from_to("for(a = f(x in y);;);", "for(a = f(x in y);;);");
+}
+
+/// Emits `sym;`, where `sym` is an identifier created by a transform.
+fn emit_synthesized_ident(sym: &str, ascii_only: bool) -> io::Result<String> {
+    ::testing::run_test(false, |cm, _| {
+        let module = Module {
+            span: DUMMY_SP,
+            body: vec![ModuleItem::Stmt(Stmt::Expr(Box::new(Expr::Ident(
+                Ident::new(sym.into(), DUMMY_SP),
+            ))))],
+            shebang: None,
+        };
+        let cfg = Config {
+            ascii_only,
+            ..Default::default()
+        };
+
+        let mut buf = vec![];
+        let res = Builder {
+            cfg,
+            cm,
+            comments: Default::default(),
+        }
+        .with("", &mut buf, |e| e.emit_module(&module));
+
+        Ok(res.map(|()| String::from_utf8(buf).unwrap()))
+    })
+    .unwrap()
+}
+
+#[test]
+fn synthesized_ident_invalid() {
+    let err = emit_synthesized_ident("foo bar", false).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    assert!(emit_synthesized_ident("1foo", false).is_err());
+    assert!(emit_synthesized_ident("", false).is_err());
+    assert!(emit_synthesized_ident("$foo_1", false).is_ok());
+}
+
+#[test]
+fn synthesized_ident_non_ascii() {
+    assert_eq!(
+        DebugUsingDisplay(emit_synthesized_ident("名前", false).unwrap().trim()),
+        DebugUsingDisplay("名前;")
+    );
+    assert_eq!(
+        DebugUsingDisplay(emit_synthesized_ident("名前", true).unwrap().trim()),
+        DebugUsingDisplay("\\u540d\\u524d;")
+    );
+    assert_eq!(
+        DebugUsingDisplay(emit_synthesized_ident("a\u{10480}", true).unwrap().trim()),
+        DebugUsingDisplay("a\\u{10480};")
+    );
+}
+
 #[derive(Debug, Clone)]
 struct Buf(Arc<RwLock<Vec<u8>>>);
 impl Write for Buf {
@@ -202,3 +1231,51 @@ impl<'a> Debug for DebugUsingDisplay<'a> {
         Display::fmt(self.0, f)
     }
 }
+
+#[test]
+fn debug_syntax_contexts() {
+    let out = ::testing::run_test(false, |cm, _| {
+        let marked = || {
+            let span = DUMMY_SP.apply_mark(swc_common::Mark::fresh(swc_common::Mark::root()));
+            ModuleItem::Stmt(Stmt::Expr(Box::new(Expr::Ident(Ident::new(
+                "ref".into(),
+                span,
+            )))))
+        };
+        let module = Module {
+            span: DUMMY_SP,
+            body: vec![
+                marked(),
+                marked(),
+                ModuleItem::Stmt(Stmt::Expr(Box::new(Expr::Ident(Ident::new(
+                    "a".into(),
+                    DUMMY_SP,
+                ))))),
+            ],
+            shebang: None,
+        };
+        let cfg = Config {
+            debug_syntax_contexts: true,
+            ..Default::default()
+        };
+
+        let mut buf = vec![];
+        Builder {
+            cfg,
+            cm,
+            comments: Default::default(),
+        }
+        .with("", &mut buf, |e| e.emit_module(&module))
+        .unwrap();
+
+        Ok(String::from_utf8(buf).unwrap())
+    })
+    .unwrap();
+
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3, "{}", out);
+    assert!(lines[0].starts_with("ref#"), "{}", out);
+    assert!(lines[1].starts_with("ref#"), "{}", out);
+    assert_ne!(lines[0], lines[1]);
+    assert_eq!(lines[2], "a;");
+}
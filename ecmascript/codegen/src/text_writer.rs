@@ -26,6 +26,12 @@ pub trait WriteJs {
 
     fn write_line(&mut self) -> Result;
 
+    /// Sets the line terminator written by `write_line`.
+    fn set_new_line(&mut self, new_line: &'static str);
+
+    /// Writes an empty line, after ending the current line if it's not empty.
+    fn write_blank_line(&mut self) -> Result;
+
     fn write_lit(&mut self, span: Span, s: &str) -> Result;
     fn write_comment(&mut self, span: Span, s: &str) -> Result;
 
@@ -34,6 +40,11 @@ pub trait WriteJs {
     fn write_symbol(&mut self, span: Span, s: &str) -> Result;
 
     fn write_punct(&mut self, s: &'static str) -> Result;
+
+    /// Ends the output, with a line break if `final_newline` is true.
+    ///
+    /// Line breaks written after the last token are dropped otherwise.
+    fn finish(&mut self, final_newline: bool) -> Result;
 }
 
 impl<W> WriteJs for Box<W>
@@ -70,6 +81,14 @@ where
         (**self).write_line()
     }
 
+    fn set_new_line(&mut self, new_line: &'static str) {
+        (**self).set_new_line(new_line)
+    }
+
+    fn write_blank_line(&mut self) -> Result {
+        (**self).write_blank_line()
+    }
+
     fn write_lit(&mut self, span: Span, s: &str) -> Result {
         (**self).write_lit(span, s)
     }
@@ -89,4 +108,8 @@ where
     fn write_punct(&mut self, s: &'static str) -> Result {
         (**self).write_punct(s)
     }
+
+    fn finish(&mut self, final_newline: bool) -> Result {
+        (**self).finish(final_newline)
+    }
 }
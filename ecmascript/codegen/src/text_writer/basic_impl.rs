@@ -1,4 +1,5 @@
 use super::{Result, WriteJs};
+use hashbrown::HashSet;
 use sourcemap::SourceMapBuilder;
 use std::{
     io::{self, Write},
@@ -19,7 +20,14 @@ pub struct JsWriter<'a, W: Write> {
     line_count: usize,
     line_pos: usize,
     new_line: &'a str,
+    /// Line breaks which are written before the next token, so that the
+    /// output can end without them.
+    pending_new_lines: usize,
     srcmap: Option<&'a mut SourceMapBuilder>,
+    /// Embed the source code of referenced files as `sourcesContent`.
+    sources_content: bool,
+    /// Ids of sources which have their content set.
+    sources_with_content: HashSet<u32>,
     wr: W,
     written_bytes: usize,
 }
@@ -38,12 +46,25 @@ impl<'a, W: Write> JsWriter<'a, W> {
             line_count: 0,
             line_pos: Default::default(),
             new_line,
+            pending_new_lines: 0,
             srcmap,
+            sources_content: false,
+            sources_with_content: Default::default(),
             wr,
             written_bytes: 0,
         }
     }
 
+    /// Sets `sourcesContent` of the source map to the source code of files
+    /// referenced by emitted tokens.
+    ///
+    /// Files without a path (like `FileName::Anon`) are not added to the
+    /// source map, so they don't have content either.
+    pub fn with_sources_content(mut self, enabled: bool) -> Self {
+        self.sources_content = enabled;
+        self
+    }
+
     fn write_indent_string(&mut self) -> io::Result<usize> {
         const INDENT: &[u8] = b"    ";
 
@@ -55,11 +76,26 @@ impl<'a, W: Write> JsWriter<'a, W> {
         Ok(cnt)
     }
 
+    fn write_pending_new_lines(&mut self) -> io::Result<usize> {
+        let mut cnt = 0;
+        if self.pending_new_lines > 0 {
+            for _ in 0..self.pending_new_lines {
+                cnt += self.raw_write(self.new_line.as_bytes())?;
+            }
+            self.pending_new_lines = 0;
+            self.line_pos = 0;
+        }
+
+        Ok(cnt)
+    }
+
     fn raw_write(&mut self, data: &[u8]) -> io::Result<usize> {
-        let written = self.wr.write(data)?;
-        self.written_bytes += written;
-        self.line_pos += written;
-        Ok(written)
+        // `write` may write only a part of `data` if `wr` is a file or a
+        // socket.
+        self.wr.write_all(data)?;
+        self.written_bytes += data.len();
+        self.line_pos += data.len();
+        Ok(data.len())
     }
 
     fn write(&mut self, span: Option<Span>, data: &str) -> io::Result<usize> {
@@ -74,7 +110,7 @@ impl<'a, W: Write> JsWriter<'a, W> {
                         FileName::Real(ref p) => Some(p.display().to_string()),
                         _ => None,
                     };
-                    srcmap.add(
+                    let token = srcmap.add(
                         self.line_count as _,
                         self.line_pos as _,
                         (loc.line - 1) as _,
@@ -82,11 +118,20 @@ impl<'a, W: Write> JsWriter<'a, W> {
                         src.as_ref().map(|s| &**s),
                         None,
                     );
+
+                    if self.sources_content
+                        && src.is_some()
+                        && self.sources_with_content.insert(token.src_id)
+                    {
+                        srcmap.set_source_contents(token.src_id, Some(&loc.file.src));
+                    }
                 }
             }};
         }
 
         if !data.is_empty() {
+            cnt += self.write_pending_new_lines()?;
+
             if let Some(span) = span {
                 if !span.is_dummy() {
                     srcmap!(span.lo())
@@ -151,7 +196,7 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
 
     fn write_line(&mut self) -> Result {
         if !self.line_start {
-            self.raw_write(self.new_line.as_bytes())?;
+            self.pending_new_lines += 1;
             self.line_count += 1;
             self.line_pos = 0;
             self.line_start = true;
@@ -160,6 +205,29 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
         Ok(())
     }
 
+    fn set_new_line(&mut self, new_line: &'static str) {
+        self.new_line = new_line;
+    }
+
+    fn write_blank_line(&mut self) -> Result {
+        self.write_line()?;
+        self.pending_new_lines += 1;
+        self.line_count += 1;
+
+        Ok(())
+    }
+
+    fn finish(&mut self, final_newline: bool) -> Result {
+        if final_newline && self.written_bytes != 0 {
+            self.pending_new_lines = 1;
+            self.write_pending_new_lines()?;
+        }
+        self.pending_new_lines = 0;
+        self.wr.flush()?;
+
+        Ok(())
+    }
+
     fn write_lit(&mut self, span: Span, s: &str) -> Result {
         if !s.is_empty() {
             self.write(Some(span), s)?;
@@ -49,10 +49,19 @@ impl<W: WriteJs> WriteJs for OmitTrailingSemi<W> {
     with_semi!(write_param(s: &str));
     with_semi!(write_property(s: &str));
     with_semi!(write_line());
+    with_semi!(write_blank_line());
     with_semi!(write_lit(span: Span, s: &str));
     with_semi!(write_str_lit(span: Span, s: &str));
     with_semi!(write_symbol(span: Span, s: &str));
     with_semi!(write_punct(s: &'static str));
+
+    fn set_new_line(&mut self, new_line: &'static str) {
+        self.inner.set_new_line(new_line)
+    }
+
+    fn finish(&mut self, final_newline: bool) -> Result {
+        self.inner.finish(final_newline)
+    }
 }
 
 impl<W: WriteJs> OmitTrailingSemi<W> {
@@ -8,10 +8,13 @@ use std::{
 };
 use swc_atoms::JsWord;
 use swc_common::{
-    errors::{DiagnosticBuilder, Handler},
-    Span,
+    errors::{DiagnosticBuilder, DiagnosticId, Handler},
+    Span, SpanLabel,
 };
 
+/// Code of the error emitted for an unexpected end of file.
+const EOF_CODE: &str = "SWC2000";
+
 #[derive(Copy, Clone)]
 pub(crate) struct Eof<'a> {
     pub last: Span,
@@ -22,6 +25,7 @@ impl<'a> From<Eof<'a>> for DiagnosticBuilder<'a> {
     fn from(Eof { handler, last }: Eof<'a>) -> Self {
         let mut db = handler.struct_err("Unexpected eof");
         db.set_span(last);
+        db.code(DiagnosticId::Error(EOF_CODE.into()));
         db
     }
 }
@@ -74,6 +78,7 @@ pub enum SyntaxError {
         count: u8,
     },
     NumLitTerminatedWithExp,
+    NumericSeparatorIsAllowedOnlyBetweenTwoDigits,
     LegacyCommentInModule,
 
     /// "implements", "interface", "let", "package",\
@@ -130,6 +135,7 @@ pub enum SyntaxError {
     YieldParamInGen,
 
     AwaitForStmt,
+    TopLevelAwaitInScript,
 
     UnterminatedJSXContents,
     EmptyJSXAttr,
@@ -140,6 +146,7 @@ pub enum SyntaxError {
     },
     InvalidLeadingDecorator,
     DecoratorOnExport,
+    DecoratorAfterExport,
 
     TsNonLastRest,
     TsRequiredAfterOptional,
@@ -161,6 +168,10 @@ pub enum SyntaxError {
 
     DotsWithoutIdentifier,
 
+    MaxDepthExceeded {
+        max_depth: usize,
+    },
+
     TS1003,
     TS1005,
     TS1009,
@@ -216,10 +227,143 @@ impl<'a> From<ErrorToDiag<'a>> for Error {
     }
 }
 
-impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
-    #[cold]
-    fn from(e: ErrorToDiag<'a>) -> Self {
-        let msg: Cow<'static, _> = match e.error {
+impl SyntaxError {
+    /// Returns a stable code of the error, like `SWC1006`.
+    ///
+    /// Errors of the lexer are `SWC1xxx` and errors of the parser are
+    /// `SWC2xxx`. Errors reported by typescript have the code used by tsc.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            LegacyDecimal => "SWC1001",
+            LegacyOctal => "SWC1002",
+            InvalidIdentChar => "SWC1003",
+            ExpectedDigit { .. } => "SWC1004",
+            UnterminatedBlockComment => "SWC1005",
+            UnterminatedStrLit => "SWC1006",
+            ExpectedUnicodeEscape => "SWC1007",
+            EscapeInReservedWord { .. } => "SWC1008",
+            UnterminatedRegxp => "SWC1009",
+            UnterminatedTpl => "SWC1010",
+            IdentAfterNum => "SWC1011",
+            UnexpectedChar { .. } => "SWC1012",
+            InvalidStrEscape => "SWC1013",
+            InvalidUnicodeEscape => "SWC1014",
+            InvalidCodePoint => "SWC1015",
+            ExpectedHexChars { .. } => "SWC1016",
+            NumLitTerminatedWithExp => "SWC1017",
+            NumericSeparatorIsAllowedOnlyBetweenTwoDigits => "SWC1018",
+            LegacyCommentInModule => "SWC1019",
+            UnterminatedJSXContents => "SWC1020",
+
+            RestPatInSetter => "SWC2001",
+            InvalidIdentInStrict => "SWC2002",
+            EvalAndArgumentsInStrict => "SWC2003",
+            UnaryInExp { .. } => "SWC2004",
+            Hash => "SWC2005",
+            LineBreakInThrow => "SWC2006",
+            LineBreakBeforeArrow => "SWC2007",
+            Unexpected { .. } => "SWC2008",
+            ReservedWordInImport => "SWC2009",
+            AssignProperty => "SWC2010",
+            Expected(..) => "SWC2011",
+            ExpectedSemiForExprStmt { .. } => "SWC2012",
+            AwaitStar => "SWC2013",
+            ReservedWordInObjShorthandOrPat => "SWC2014",
+            MultipleDefault { .. } => "SWC2015",
+            CommaAfterRestElement => "SWC2016",
+            NonLastRestParam => "SWC2017",
+            SpreadInParenExpr => "SWC2018",
+            EmptyParenExpr => "SWC2019",
+            InvalidPat => "SWC2020",
+            InvalidExpr => "SWC2021",
+            NotSimpleAssign => "SWC2022",
+            ExpectedIdent => "SWC2023",
+            ExpctedSemi => "SWC2024",
+            DuplicateLabel(..) => "SWC2025",
+            AsyncGenerator => "SWC2026",
+            NonTopLevelImportExport => "SWC2027",
+            ImportExportInScript => "SWC2028",
+            PatVarWithoutInit => "SWC2029",
+            WithInStrict => "SWC2030",
+            ReturnNotAllowed => "SWC2031",
+            TooManyVarInForInHead => "SWC2032",
+            VarInitializerInForInHead => "SWC2033",
+            LabelledGenerator => "SWC2034",
+            YieldParamInGen => "SWC2035",
+            AwaitForStmt => "SWC2036",
+            EmptyJSXAttr => "SWC2037",
+            InvalidJSXValue => "SWC2038",
+            JSXExpectedClosingTagForLtGt => "SWC2039",
+            JSXExpectedClosingTag { .. } => "SWC2040",
+            InvalidLeadingDecorator => "SWC2041",
+            DecoratorOnExport => "SWC2042",
+            TsNonLastRest => "SWC2043",
+            TsRequiredAfterOptional => "SWC2044",
+            TsInvalidParamPropPat => "SWC2045",
+            SpaceBetweenHashAndIdent => "SWC2046",
+            AsyncConstructor => "SWC2047",
+            PropertyNamedConstructor => "SWC2048",
+            ClassProperty => "SWC2049",
+            ReadOnlyMethod => "SWC2050",
+            TsBindingPatCannotBeOptional => "SWC2051",
+            TrailingCommaInsideImport => "SWC2052",
+            DynamicImport => "SWC2053",
+            ExportDefaultWithOutFrom => "SWC2054",
+            ExportNamespaceFrom => "SWC2055",
+            DotsWithoutIdentifier => "SWC2056",
+            MaxDepthExceeded { .. } => "SWC2057",
+            DecoratorAfterExport => "SWC2058",
+            TopLevelAwaitInScript => "SWC2059",
+
+            TS1003 => "TS1003",
+            TS1005 => "TS1005",
+            TS1009 => "TS1009",
+            TS1014 => "TS1014",
+            TS1015 => "TS1015",
+            TS1031 => "TS1031",
+            TS1038 => "TS1038",
+            TS1042 => "TS1042",
+            TS1047 => "TS1047",
+            TS1048 => "TS1048",
+            TS1056 => "TS1056",
+            TS1085 => "TS1085",
+            TS1092 => "TS1092",
+            TS1096 => "TS1096",
+            TS1098 => "TS1098",
+            TS1102 => "TS1102",
+            TS1105 => "TS1105",
+            TS1107 => "TS1107",
+            TS1109 => "TS1109",
+            TS1114 => "TS1114",
+            TS1115 => "TS1115",
+            TS1116 => "TS1116",
+            TS1123 => "TS1123",
+            TS1162 => "TS1162",
+            TS1164 => "TS1164",
+            TS1171 => "TS1171",
+            TS1172 => "TS1172",
+            TS1174 => "TS1174",
+            TS1175 => "TS1175",
+            TS1183 => "TS1183",
+            TS1093 => "TS1093",
+            TS1094 => "TS1094",
+            TS1196 => "TS1196",
+            TS2369 => "TS2369",
+            TS2371 => "TS2371",
+            TS2406 => "TS2406",
+            TS2410 => "TS2410",
+            TS2414 => "TS2414",
+            TS2427 => "TS2427",
+            TS2452 => "TS2452",
+            TS2483 => "TS2483",
+            TS2491 => "TS2491",
+            TS2703 => "TS2703",
+        }
+    }
+
+    /// Returns the message of the error.
+    pub fn msg(&self) -> Cow<'static, str> {
+        match *self {
             LegacyDecimal => "Legacy decimal escape is not permitted in strict mode".into(),
             LegacyOctal => "Legacy octal escape is not permitted in strict mode".into(),
             InvalidIdentChar => "Invalid character in identifier".into(),
@@ -250,6 +394,9 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             ExpectedHexChars { count } => format!("Expected {} hex characters", count).into(),
             LegacyCommentInModule => "Legacy comments cannot be used in module code".into(),
             NumLitTerminatedWithExp => "Expected +, - or decimal digit after e".into(),
+            NumericSeparatorIsAllowedOnlyBetweenTwoDigits => {
+                "A numeric separator is only allowed between two digits".into()
+            }
 
             InvalidIdentInStrict => "'implements', 'interface', 'let', 'package', 'private', \
                                      'protected',  'public', 'static', or 'yield' cannot be used \
@@ -294,6 +441,9 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             ImportExportInScript => {
                 "'import', and 'export' cannot be used outside of module code".into()
             }
+            MaxDepthExceeded { max_depth } => {
+                format!("Maximum nesting depth of {} exceeded", max_depth).into()
+            }
 
             PatVarWithoutInit => "Destructuring bindings require initializers".into(),
             WithInStrict => "With statement are not allowed in strict mode".into(),
@@ -303,6 +453,9 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             LabelledGenerator => "Generator cannot be labelled".into(),
             YieldParamInGen => "'yield' cannot be used as a parameter within generator".into(),
             AwaitForStmt => "for await syntax is valid only for for-of statement".into(),
+            TopLevelAwaitInScript => "'await' is only allowed within async functions and at the \
+                                      top level of modules"
+                .into(),
 
             UnterminatedJSXContents => "Unterminated JSX contents".into(),
             EmptyJSXAttr => "JSX attributes must only be assigned a non-empty expression".into(),
@@ -319,6 +472,9 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             DecoratorOnExport => "Using the export keyword between a decorator and a class is not \
                                   allowed. Please use `export @dec class` instead."
                 .into(),
+            DecoratorAfterExport => "Decorators must be placed before the export keyword. Please \
+                                     use `@dec export class` instead."
+                .into(),
             TsNonLastRest => "A rest element must be last in a tuple type.".into(),
             TsRequiredAfterOptional => {
                 "A required element cannot follow an optional element.".into()
@@ -355,25 +511,97 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             TS1056 => "jsc.taraget should be es5 or upper to use getter / setter".into(),
 
             // TODO:
-            _ => format!("{:?}", e.error).into(),
-        };
-
-        let mut db = e.handler.struct_err(&msg);
-        db.set_span(e.span);
+            _ => format!("{:?}", self).into(),
+        }
+    }
 
-        match e.error {
-            ExpectedSemiForExprStmt { expr } => {
-                db.span_note(
-                    expr,
-                    "This is the expression part of an expression statement",
-                );
-            }
+    /// Returns notes attached to other spans.
+    fn notes(&self) -> Vec<(Span, &'static str)> {
+        match *self {
+            ExpectedSemiForExprStmt { expr } => vec![(
+                expr,
+                "This is the expression part of an expression statement",
+            )],
             MultipleDefault { previous } => {
-                db.span_note(previous, "previous default case is declared at here");
+                vec![(previous, "previous default case is declared at here")]
             }
-            _ => {}
+            _ => vec![],
+        }
+    }
+}
+
+impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
+    #[cold]
+    fn from(e: ErrorToDiag<'a>) -> Self {
+        let mut db = e.handler.struct_err(&e.error.msg());
+        db.set_span(e.span);
+        db.code(DiagnosticId::Error(e.error.code().into()));
+
+        for (span, note) in e.error.notes() {
+            db.span_note(span, note);
         }
 
         db
     }
 }
+
+/// A syntax error in a machine-readable form, for integration with other
+/// tools like IDEs.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Stable code of the error, like `SWC1006`.
+    pub code: String,
+    pub message: String,
+    pub span: Span,
+    /// Notes attached to other spans.
+    pub labels: Vec<SpanLabel>,
+}
+
+impl Diagnostic {
+    /// Converts an error returned by the parser.
+    ///
+    /// Returns `None` if `db` is not a syntax error.
+    pub fn from_builder(db: &DiagnosticBuilder) -> Option<Self> {
+        let code = match db.code {
+            Some(DiagnosticId::Error(ref code)) => code.clone(),
+            _ => return None,
+        };
+
+        Some(Diagnostic {
+            code,
+            message: db.message(),
+            span: db.span.primary_span()?,
+            labels: db
+                .children
+                .iter()
+                .filter_map(|note| {
+                    Some(SpanLabel {
+                        span: note.span.primary_span()?,
+                        is_primary: false,
+                        label: Some(note.message()),
+                    })
+                })
+                .collect(),
+        })
+    }
+}
+
+impl From<Error> for Diagnostic {
+    fn from(e: Error) -> Self {
+        Diagnostic {
+            code: e.error.code().into(),
+            message: e.error.msg().into_owned(),
+            span: e.span,
+            labels: e
+                .error
+                .notes()
+                .into_iter()
+                .map(|(span, note)| SpanLabel {
+                    span,
+                    is_primary: false,
+                    label: Some(note.into()),
+                })
+                .collect(),
+        }
+    }
+}
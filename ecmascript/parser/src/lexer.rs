@@ -159,7 +159,10 @@ impl<'a, I: Input> Lexer<'a, I> {
                     }
                 };
                 if '0' <= next && next <= '9' {
-                    return self.read_number(true).map(Token::Num).map(Some);
+                    return self
+                        .read_number(true)
+                        .map(|v| v.either(Num, BigInt))
+                        .map(Some);
                 }
 
                 self.input.bump(); // 1st `.`
@@ -174,6 +177,17 @@ impl<'a, I: Input> Lexer<'a, I> {
                 return Ok(Some(tok!('.')));
             }
 
+            '?' if self.syntax.logical_assignment()
+                && self.input.peek() == Some('?')
+                && self.input.peek_ahead() == Some('=') =>
+            {
+                self.input.bump();
+                self.input.bump();
+                self.input.bump();
+
+                return Ok(Some(AssignOp(NullishAssign)));
+            }
+
             '(' | ')' | ';' | ',' | '[' | ']' | '{' | '}' | '@' | '?' => {
                 // These tokens are emitted directly.
                 self.input.bump();
@@ -215,12 +229,25 @@ impl<'a, I: Input> Lexer<'a, I> {
                     Some('x') | Some('X') => 16,
                     Some('o') | Some('O') => 8,
                     Some('b') | Some('B') => 2,
-                    _ => return self.read_number(false).map(Num).map(Some),
+                    _ => {
+                        return self
+                            .read_number(false)
+                            .map(|v| v.either(Num, BigInt))
+                            .map(Some);
+                    }
                 };
 
-                return self.read_radix_number(radix).map(Num).map(Some);
+                return self
+                    .read_radix_number(radix)
+                    .map(|v| v.either(Num, BigInt))
+                    .map(Some);
+            }
+            '1'..='9' => {
+                return self
+                    .read_number(false)
+                    .map(|v| v.either(Num, BigInt))
+                    .map(Some);
             }
-            '1'..='9' => return self.read_number(false).map(Num).map(Some),
 
             '"' | '\'' => return self.read_str_lit().map(Some),
 
@@ -268,6 +295,17 @@ impl<'a, I: Input> Lexer<'a, I> {
                 // '||', '&&'
                 if self.input.cur() == Some(c) {
                     self.input.bump();
+
+                    // '||=', '&&='
+                    if self.syntax.logical_assignment() && self.input.cur() == Some('=') {
+                        self.input.bump();
+                        return Ok(Some(AssignOp(match token {
+                            BitAnd => AndAssign,
+                            BitOr => OrAssign,
+                            _ => unreachable!(),
+                        })));
+                    }
+
                     return Ok(Some(BinOp(match token {
                         BitAnd => LogicalAnd,
                         BitOr => LogicalOr,
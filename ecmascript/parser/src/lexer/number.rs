@@ -4,12 +4,14 @@
 //! See https://tc39.github.io/ecma262/#sec-literals-numeric-literals
 use super::*;
 use crate::error::SyntaxError;
+use either::Either;
 use log::trace;
 use std::{fmt::Write, iter::FusedIterator};
 
 impl<'a, I: Input> Lexer<'a, I> {
-    /// Reads an integer, octal integer, or floating-point number
-    pub(super) fn read_number(&mut self, starts_with_dot: bool) -> LexResult<f64> {
+    /// Reads an integer, octal integer, or floating-point number, or the
+    /// digits of a BigInt literal like `10n`.
+    pub(super) fn read_number(&mut self, starts_with_dot: bool) -> LexResult<Either<f64, JsWord>> {
         debug_assert!(self.cur().is_some());
         if starts_with_dot {
             debug_assert_eq!(
@@ -27,7 +29,17 @@ impl<'a, I: Input> Lexer<'a, I> {
             0f64
         } else {
             // Use read_number_no_dot to support long numbers.
-            let val = self.read_number_no_dot(10)?;
+            let mut raw = Raw(Some(String::new()));
+            let val = self.read_number_no_dot(10, &mut raw)?;
+            let raw = raw.0.unwrap();
+
+            // `0n` is a BigInt, but `01n` is not.
+            if self.cur() == Some('n') && (!starts_with_zero || raw == "0") {
+                self.bump();
+                self.ensure_not_ident()?;
+                return Ok(Either::Right(raw.into()));
+            }
+
             if starts_with_zero {
                 // TODO: I guess it would be okay if I don't use -ffast-math
                 // (or something like that), but needs review.
@@ -42,7 +54,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                     if start.0 != self.last_pos().0 - 1 {
                         // `-1` is utf 8 length of `0`
 
-                        return self.make_legacy_octal(start, 0f64);
+                        return self.make_legacy_octal(start, 0f64).map(Either::Left);
                     }
                 } else {
                     // strict mode hates non-zero decimals starting with zero.
@@ -64,7 +76,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                                 .to_string()
                                 .parse()
                                 .expect("failed to parse numeric value as f64");
-                            return self.make_legacy_octal(start, val);
+                            return self.make_legacy_octal(start, val).map(Either::Left);
                         }
                     }
                 }
@@ -122,7 +134,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 true
             };
 
-            let exp = self.read_number_no_dot(10)?;
+            let exp = self.read_number_no_dot(10, &mut Raw(None))?;
             let flag = if positive { '+' } else { '-' };
             // TODO:
             val = format!("{}e{}{}", val, flag, exp)
@@ -132,10 +144,10 @@ impl<'a, I: Input> Lexer<'a, I> {
 
         self.ensure_not_ident()?;
 
-        Ok(val)
+        Ok(Either::Left(val))
     }
 
-    pub(super) fn read_radix_number(&mut self, radix: u8) -> LexResult<f64> {
+    pub(super) fn read_radix_number(&mut self, radix: u8) -> LexResult<Either<f64, JsWord>> {
         debug_assert!(
             radix == 2 || radix == 8 || radix == 16,
             "radix should be one of 2, 8, 16, but got {}",
@@ -146,15 +158,29 @@ impl<'a, I: Input> Lexer<'a, I> {
         self.bump(); // 0
         self.bump(); // x
 
-        let val = self.read_number_no_dot(radix)?;
+        let mut raw = Raw(Some(String::new()));
+        let val = self.read_number_no_dot(radix, &mut raw)?;
+
+        if self.eat('n') {
+            self.ensure_not_ident()?;
+
+            let prefix = match radix {
+                2 => "0b",
+                8 => "0o",
+                _ => "0x",
+            };
+            return Ok(Either::Right(
+                format!("{}{}", prefix, raw.0.unwrap()).into(),
+            ));
+        }
         self.ensure_not_ident()?;
 
-        Ok(val)
+        Ok(Either::Left(val))
     }
 
     /// This can read long integers like
     /// "13612536612375123612312312312312312312312".
-    fn read_number_no_dot(&mut self, radix: u8) -> LexResult<f64> {
+    fn read_number_no_dot(&mut self, radix: u8, raw: &mut Raw) -> LexResult<f64> {
         debug_assert!(
             radix == 2 || radix == 8 || radix == 10 || radix == 16,
             "radix for read_number_no_dot should be one of 2, 8, 10, 16, but got {}",
@@ -170,7 +196,8 @@ impl<'a, I: Input> Lexer<'a, I> {
                 read_any = true;
                 (f64::mul_add(total, radix as f64, v as f64), true)
             },
-            &mut Raw(None),
+            raw,
+            true,
         );
 
         if !read_any {
@@ -204,6 +231,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 (Some(total), count != len)
             },
             raw,
+            true,
         )?;
         if len != 0 && count != len {
             Ok(None)
@@ -227,6 +255,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 (Some(total), count != len)
             },
             raw,
+            false,
         )?;
         if len != 0 && count != len {
             Ok(None)
@@ -236,7 +265,16 @@ impl<'a, I: Input> Lexer<'a, I> {
     }
 
     /// `op`- |total, radix, value| -> (total * radix + value, continue)
-    fn read_digits<F, Ret>(&mut self, radix: u8, mut op: F, raw: &mut Raw) -> LexResult<Ret>
+    ///
+    /// Numeric separators (e.g. `1_000`) are skipped if `allow_num_sep` is true
+    /// and the syntax supports them.
+    fn read_digits<F, Ret>(
+        &mut self,
+        radix: u8,
+        mut op: F,
+        raw: &mut Raw,
+        allow_num_sep: bool,
+    ) -> LexResult<Ret>
     where
         F: FnMut(Ret, u8, u32) -> (Ret, bool),
         Ret: Copy + Default,
@@ -248,30 +286,27 @@ impl<'a, I: Input> Lexer<'a, I> {
         );
         trace!("read_digits(radix = {}), cur = {:?}", radix, self.cur());
 
-        let start = self.cur_pos();
-
+        let allow_num_sep = allow_num_sep && self.syntax.num_sep();
+        let mut prev_is_digit = false;
         let mut total: Ret = Default::default();
 
         while let Some(c) = self.cur() {
-            if self.syntax.num_sep() {
-                // let prev: char = unimplemented!("prev");
-                // let next = self.input.peek();
-
-                // if c == '_' {
-                //     if !allowed_siblings.contains(&next) {
-                //         unimplemented!("Error(Invalid or unexpected token)");
-                //     }
-
-                // if forbidden_siblings.contains(&prev) ||
-                // forbidden_siblings.contains(&next) ||
-                // Number::is_nan(next)     {
-                //         unimplemented!("Error(Invalid or unexpected token)");
-                //     }
-
-                //     // Ignore this _ character
-                //     self.input.bump();
-                // }
-                unimplemented!("numeric separator")
+            if c == '_' && allow_num_sep {
+                let next_is_digit = self
+                    .peek()
+                    .map(|next| next.is_digit(radix as _))
+                    .unwrap_or(false);
+                if !prev_is_digit || !next_is_digit {
+                    let span = pos_span(self.cur_pos());
+                    self.error_span(
+                        span,
+                        SyntaxError::NumericSeparatorIsAllowedOnlyBetweenTwoDigits,
+                    )?
+                }
+
+                self.bump();
+                prev_is_digit = false;
+                continue;
             }
 
             // e.g. (val for a) = 10  where radix = 16
@@ -284,6 +319,7 @@ impl<'a, I: Input> Lexer<'a, I> {
             raw.push(c);
 
             self.bump();
+            prev_is_digit = true;
             let (t, cont) = op(total, radix, val);
             total = t;
             if !cont {
@@ -351,6 +387,7 @@ fn digits(value: u64, radix: u64) -> impl Iterator<Item = u64> + Clone + 'static
 #[cfg(test)]
 mod tests {
     use super::{input::SourceFileInput, *};
+    use crate::EsConfig;
     use std::{f64::INFINITY, panic};
 
     fn lex<F, Ret>(s: &'static str, f: F) -> Ret
@@ -365,7 +402,9 @@ mod tests {
     }
 
     fn num(s: &'static str) -> f64 {
-        lex(s, |l| l.read_number(s.starts_with('.')).unwrap())
+        lex(s, |l| {
+            l.read_number(s.starts_with('.')).unwrap().left().unwrap()
+        })
     }
 
     fn int(radix: u8, s: &'static str) -> u32 {
@@ -428,10 +467,69 @@ mod tests {
     fn read_radix_number() {
         debug_assert_eq!(
             0o73 as f64,
-            lex("0o73", |l| l.read_radix_number(8).unwrap())
+            lex("0o73", |l| l.read_radix_number(8).unwrap().left().unwrap())
+        );
+    }
+
+    #[test]
+    fn big_int() {
+        assert_eq!(
+            lex("10n", |l| l.read_number(false).unwrap()),
+            Either::Right("10".into())
+        );
+        assert_eq!(
+            lex("0n", |l| l.read_number(false).unwrap()),
+            Either::Right("0".into())
+        );
+        assert_eq!(
+            lex("0x1Fn", |l| l.read_radix_number(16).unwrap()),
+            Either::Right("0x1F".into())
         );
     }
 
+    #[test]
+    fn big_int_invalid() {
+        assert!(lex("1.5n", |l| l.read_number(false)).is_err());
+        assert!(lex("1e3n", |l| l.read_number(false)).is_err());
+    }
+
+    fn lex_num_sep(s: &'static str) -> Vec<Token> {
+        crate::with_test_sess(s, |sess, fm| {
+            let syntax = Syntax::Es(EsConfig {
+                num_sep: true,
+                ..Default::default()
+            });
+            let l = Lexer::new(sess, syntax, Default::default(), fm.into(), None);
+            Ok(l.map(|ts| ts.token).collect())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn num_sep() {
+        assert_eq!(lex_num_sep("1_000"), vec![Num(1000.0)]);
+        assert_eq!(lex_num_sep("1_000.0_1"), vec![Num(1000.01)]);
+        assert_eq!(lex_num_sep("0b1_0"), vec![Num(2.0)]);
+        assert_eq!(lex_num_sep("0xf_f"), vec![Num(255.0)]);
+        assert_eq!(lex_num_sep("1_000n"), vec![BigInt("1000".into())]);
+    }
+
+    #[test]
+    fn num_sep_invalid() {
+        for case in &["1__0", "1_", "0x_1", "1._0"] {
+            let tokens = lex_num_sep(case);
+            assert!(
+                tokens.iter().any(|t| match *t {
+                    Error(..) => true,
+                    _ => false,
+                }),
+                "{} should be invalid: {:?}",
+                case,
+                tokens
+            );
+        }
+    }
+
     /// Valid even on strict mode.
     const VALID_CASES: &[&str] = &[".0", "0.e-1", "0e8", ".8e1", "0.8e1", "1.18e1"];
     const INVALID_CASES_ON_STRICT: &[&str] = &["08e1", "08.1", "08.8e1", "08", "01"];
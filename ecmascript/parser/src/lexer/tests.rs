@@ -102,18 +102,53 @@ impl WithSpan for AssignOpToken {
     }
 }
 
-//#[test]
-//fn module_legacy_octal() {
-//    assert_eq!(
-//        lex_module(Syntax::default(), "01"),
-//        vec![Token::Error(Error {
-//            span: sp(0..2),
-//            error: SyntaxError::LegacyOctal,
-//        })
-//        .span(0..2)
-//        .lb(),]
-//    );
-//}
+#[test]
+fn script_legacy_octal() {
+    assert_eq!(
+        lex(Syntax::default(), "0777"),
+        vec![(0o777 as f64).span(0..4).lb()]
+    );
+}
+
+#[test]
+fn script_legacy_decimal() {
+    assert_eq!(lex(Syntax::default(), "08"), vec![8.span(0..2).lb()]);
+    assert_eq!(lex(Syntax::default(), "0719"), vec![719.span(0..4).lb()]);
+}
+
+#[test]
+fn unterminated_str_lit_diagnostic() {
+    let d = match lex_module(Syntax::default(), "'abc")[0].token {
+        Token::Error(ref e) => crate::Diagnostic::from(e.clone()),
+        ref token => panic!("expected an error, got {:?}", token),
+    };
+
+    assert_eq!(d.code, "SWC1006");
+    assert_eq!(d.span, sp(0..4));
+}
+
+#[test]
+fn module_legacy_octal() {
+    let stderr = with_lexer(Syntax::default(), "0777", |l| {
+        l.ctx.strict = true;
+        l.ctx.module = true;
+
+        let _: Vec<_> = l.by_ref().collect();
+        if l.session.handler.has_errors() {
+            Err(())
+        } else {
+            Ok(())
+        }
+    })
+    .expect_err("legacy octal should be rejected in module code")
+    .to_string();
+
+    assert!(
+        stderr.contains("Legacy octal escape is not permitted in strict mode"),
+        "{}",
+        stderr
+    );
+}
 
 #[test]
 fn module_legacy_decimal() {
@@ -1118,6 +1153,44 @@ fn issue_481() {
     );
 }
 
+#[test]
+fn logical_assignment() {
+    assert_eq!(
+        lex_tokens(
+            crate::Syntax::Es(crate::EsConfig {
+                logical_assignment: true,
+                ..Default::default()
+            }),
+            "a &&= b ||= c ??= d ?? e"
+        ),
+        vec![
+            Word(Word::Ident("a".into())),
+            AssignOp(AndAssign),
+            Word(Word::Ident("b".into())),
+            AssignOp(OrAssign),
+            Word(Word::Ident("c".into())),
+            AssignOp(NullishAssign),
+            Word(Word::Ident("d".into())),
+            QuestionMark,
+            QuestionMark,
+            Word(Word::Ident("e".into())),
+        ]
+    );
+}
+
+#[test]
+fn logical_assignment_disabled() {
+    assert_eq!(
+        lex_tokens(Syntax::default(), "a &&= b"),
+        vec![
+            Word(Word::Ident("a".into())),
+            BinOp(LogicalAnd),
+            AssignOp(Assign),
+            Word(Word::Ident("b".into())),
+        ]
+    );
+}
+
 #[bench]
 fn lex_colors_js(b: &mut Bencher) {
     b.bytes = include_str!("../../colors.js").len() as _;
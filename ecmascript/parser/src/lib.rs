@@ -98,8 +98,10 @@
 #![deny(unsafe_code)]
 
 pub use self::{
+    error::Diagnostic,
     lexer::input::{Input, SourceFileInput},
     parser::*,
+    token::TokenKind,
 };
 use serde::{Deserialize, Serialize};
 use swc_common::{errors::Handler, Span};
@@ -238,6 +240,16 @@ impl Syntax {
         }
     }
 
+    pub fn logical_assignment(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                logical_assignment: true,
+                ..
+            }) => true,
+            _ => false,
+        }
+    }
+
     pub fn export_namespace_from(self) -> bool {
         match self {
             Syntax::Es(EsConfig {
@@ -247,6 +259,37 @@ impl Syntax {
             _ => false,
         }
     }
+
+    pub fn top_level_await(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                top_level_await: true,
+                ..
+            })
+            | Syntax::Typescript(TsConfig {
+                top_level_await: true,
+                ..
+            }) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Kind of a program, which decides what is allowed at its top level.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProgramKind {
+    /// Classic script. Neither `await` nor `return` is allowed at the top
+    /// level.
+    #[serde(rename = "script")]
+    Script,
+    /// Es module, which is always in strict mode. `await` is allowed at the
+    /// top level if `topLevelAwait` is enabled.
+    #[serde(rename = "module")]
+    Module,
+    /// CommonJS module, which is a script wrapped in a function by the
+    /// runtime. `return` is allowed at the top level.
+    #[serde(rename = "commonjs")]
+    CommonJs,
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -260,6 +303,10 @@ pub struct TsConfig {
 
     #[serde(default)]
     pub dynamic_import: bool,
+
+    /// Allow `await` at the top level of modules.
+    #[serde(default)]
+    pub top_level_await: bool,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
@@ -278,6 +325,8 @@ pub enum JscTarget {
     Es2018,
     #[serde(rename = "es2019")]
     Es2019,
+    #[serde(rename = "es2020")]
+    Es2020,
 }
 
 impl Default for JscTarget {
@@ -319,6 +368,11 @@ pub struct EsConfig {
 
     /// babel: `decorators.decoratorsBeforeExport`
     ///
+    /// If true, decorators are placed before `export`, like
+    /// `@dec export class Foo {}`. Otherwise they are placed after `export`
+    /// or `export default`, like `export @dec class Foo {}`. The other form
+    /// is a syntax error.
+    ///
     /// Effective only if `decorator` is true.
     #[serde(rename = "decoratorsBeforeExport")]
     #[serde(default)]
@@ -332,6 +386,14 @@ pub struct EsConfig {
 
     #[serde(default)]
     pub dynamic_import: bool,
+
+    /// Support logical assignment operators (`&&=`, `||=` and `??=`).
+    #[serde(default)]
+    pub logical_assignment: bool,
+
+    /// Allow `await` at the top level of modules.
+    #[serde(default)]
+    pub top_level_await: bool,
 }
 
 /// Syntactic context.
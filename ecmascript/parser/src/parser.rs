@@ -5,10 +5,13 @@ use self::{input::Buffer, util::ParseObject};
 use crate::{
     error::{ErrorToDiag, SyntaxError},
     lexer::Lexer,
-    token::{Token, Word},
-    Context, JscTarget, Session, Syntax,
+    token::{Token, TokenKind, Word},
+    Context, JscTarget, ProgramKind, Session, Syntax,
+};
+use std::{
+    cmp::Ordering,
+    ops::{Deref, DerefMut},
 };
-use std::ops::{Deref, DerefMut};
 use swc_atoms::JsWord;
 use swc_common::{comments::Comments, errors::DiagnosticBuilder, input::Input, BytePos, Span};
 use swc_ecma_ast::*;
@@ -40,6 +43,10 @@ pub struct Parser<'a, I: Tokens> {
     session: Session<'a>,
     state: State,
     input: Buffer<I>,
+    /// Maximum nesting depth of expressions and statements.
+    max_depth: Option<usize>,
+    /// Current nesting depth of expressions and statements.
+    depth: usize,
 }
 
 #[derive(Clone, Default)]
@@ -74,13 +81,73 @@ impl<'a, I: Tokens> Parser<'a, I> {
             session,
             input: Buffer::new(input),
             state: Default::default(),
+            max_depth: None,
+            depth: 0,
         }
     }
 
+    /// Limits nesting depth of expressions and statements.
+    ///
+    /// Deeply nested input can overflow the stack because the parser is
+    /// recursive. If `max_depth` is set, the parser reports an error at the
+    /// point where the depth is exceeded instead.
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Records tokens produced by the lexer, so they can be looked up with
+    /// [Parser::token_at] after parsing.
+    pub fn with_token_recording(mut self, record: bool) -> Self {
+        self.input.set_token_recording(record);
+        self
+    }
+
+    /// Returns the token which contains `pos`.
+    ///
+    /// Returns `None` if `pos` is not in a token (e.g. in whitespace or a
+    /// comment), or tokens are not recorded.
+    pub fn token_at(&self, pos: BytePos) -> Option<(TokenKind, Span)> {
+        let tokens = self.input.tokens()?;
+
+        let idx = tokens
+            .binary_search_by(|&(_, span)| {
+                if span.hi() <= pos {
+                    Ordering::Less
+                } else if span.lo() > pos {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .ok()?;
+
+        Some(tokens[idx])
+    }
+
     pub(crate) fn target(&self) -> JscTarget {
         self.input.target()
     }
 
+    /// Parses a program of the given kind.
+    pub fn parse_program(&mut self, kind: ProgramKind) -> PResult<'a, Program> {
+        match kind {
+            ProgramKind::Script => self.parse_script().map(Program::Script),
+            ProgramKind::Module => self.parse_module().map(Program::Module),
+            ProgramKind::CommonJs => {
+                // The runtime wraps CommonJS modules in a function, so
+                // `return` is allowed at the top level.
+                let ctx = Context {
+                    in_function: true,
+                    ..self.ctx()
+                };
+                self.set_ctx(ctx);
+
+                self.parse_script().map(Program::Script)
+            }
+        }
+    }
+
     pub fn parse_script(&mut self) -> PResult<'a, Script> {
         let ctx = Context {
             module: false,
@@ -106,6 +173,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let ctx = Context {
             module: true,
             //            strict: true,
+            in_async: self.syntax().top_level_await(),
             ..self.ctx()
         };
         // Module code is always in strict mode
@@ -126,6 +194,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let ctx = Context {
             module: true,
             strict: true,
+            in_async: self.syntax().top_level_await(),
             ..self.ctx()
         };
         // Module code is always in strict mode
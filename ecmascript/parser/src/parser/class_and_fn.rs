@@ -199,6 +199,33 @@ impl<'a, I: Tokens> Parser<'a, I> {
         Ok(decorators)
     }
 
+    /// Parses decorators placed after `export` or `export default`, like
+    /// `export @dec class Foo {}`.
+    pub(super) fn parse_decorators_after_export(&mut self) -> PResult<'a, Vec<Decorator>> {
+        if !self.syntax().decorators() {
+            return Ok(vec![]);
+        }
+
+        let mut decorators = vec![];
+        let start = cur_pos!();
+
+        while is!('@') {
+            decorators.push(self.parse_decorator()?);
+        }
+        if decorators.is_empty() {
+            return Ok(decorators);
+        }
+
+        if self.syntax().decorators_before_export() {
+            syntax_error!(span!(start), SyntaxError::DecoratorAfterExport);
+        }
+        if !is!("class") {
+            syntax_error!(span!(start), SyntaxError::InvalidLeadingDecorator);
+        }
+
+        Ok(decorators)
+    }
+
     fn parse_decorator(&mut self) -> PResult<'a, Decorator> {
         let start = cur_pos!();
 
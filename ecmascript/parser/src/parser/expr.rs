@@ -32,6 +32,10 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     ///`parseMaybeAssign` (overrided)
     pub(super) fn parse_assignment_expr(&mut self) -> PResult<'a, Box<Expr>> {
+        self.nested(|p| p.parse_assignment_expr_inner())
+    }
+
+    fn parse_assignment_expr_inner(&mut self) -> PResult<'a, Box<Expr>> {
         if self.input.syntax().typescript() {
             // Note: When the JSX plugin is on, type assertions (`<T> x`) aren't valid
             // syntax.
@@ -254,6 +258,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             | Ok(&tok!("true"))
             | Ok(&tok!("false"))
             | Ok(&Token::Num(..))
+            | Ok(&Token::BigInt(..))
             | Ok(Token::Str { .. }) => true,
             _ => false,
         } {
@@ -729,11 +734,26 @@ impl<'a, I: Tokens> Parser<'a, I> {
         })
     }
 
-    fn parse_subscripts(&mut self, mut obj: ExprOrSuper, no_call: bool) -> PResult<'a, Box<Expr>> {
+    fn parse_subscripts(&mut self, obj: ExprOrSuper, no_call: bool) -> PResult<'a, Box<Expr>> {
+        // Each subscript nests the expression one level deeper.
+        let depth = self.depth;
+        let res = self.parse_subscripts_inner(obj, no_call);
+        self.depth = depth;
+        res
+    }
+
+    fn parse_subscripts_inner(
+        &mut self,
+        mut obj: ExprOrSuper,
+        no_call: bool,
+    ) -> PResult<'a, Box<Expr>> {
         loop {
             obj = match self.parse_subscript(obj, no_call)? {
                 (expr, false) => return Ok(expr),
-                (expr, true) => ExprOrSuper::Expr(expr),
+                (expr, true) => {
+                    self.enter_nested()?;
+                    ExprOrSuper::Expr(expr)
+                }
             }
         }
     }
@@ -1352,6 +1372,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }),
                 _ => unreachable!(),
             },
+            Token::BigInt(..) => match bump!() {
+                Token::BigInt(value) => Lit::BigInt(BigInt {
+                    span: span!(start),
+                    value,
+                }),
+                _ => unreachable!(),
+            },
             _ => unreachable!("parse_lit should not be called"),
         };
         Ok(v)
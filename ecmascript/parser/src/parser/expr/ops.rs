@@ -10,7 +10,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub(super) fn parse_bin_expr(&mut self) -> PResult<'a, Box<Expr>> {
         let ctx = self.ctx();
 
-        let left = match self.parse_unary_expr() {
+        let left = match self.parse_bin_operand(0) {
             Ok(v) => v,
             Err(mut err) => {
                 match {
@@ -143,7 +143,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
 
         let right = {
-            let left_of_right = self.parse_unary_expr()?;
+            let left_of_right = self.parse_bin_operand(op.precedence())?;
             self.parse_bin_op_recursively(
                 left_of_right,
                 if op == op!("**") {
@@ -166,10 +166,31 @@ impl<'a, I: Tokens> Parser<'a, I> {
         Ok(expr)
     }
 
+    /// Parses an operand of a binary operator with precedence `min_prec`.
+    ///
+    /// This is a unary expression, or a private name if it's the left operand
+    /// of `in` (e.g. `#x in obj`).
+    fn parse_bin_operand(&mut self, min_prec: u8) -> PResult<'a, Box<Expr>> {
+        if is!('#') && self.ctx().include_in_expr && op!("in").precedence() > min_prec {
+            let name = self.parse_private_name()?;
+            if !is!("in") {
+                unexpected!()
+            }
+
+            return Ok(Box::new(Expr::PrivateName(name)));
+        }
+
+        self.parse_unary_expr()
+    }
+
     /// Parse unary expression and update expression.
     ///
     /// spec: 'UnaryExpression'
     pub(in crate::parser) fn parse_unary_expr(&mut self) -> PResult<'a, Box<Expr>> {
+        self.nested(|p| p.parse_unary_expr_inner())
+    }
+
+    fn parse_unary_expr_inner(&mut self) -> PResult<'a, Box<Expr>> {
         let start = cur_pos!();
 
         if !self.input.syntax().jsx() && self.input.syntax().typescript() && eat!('<') {
@@ -261,8 +282,17 @@ impl<'a, I: Tokens> Parser<'a, I> {
             })));
         }
 
-        if self.ctx().in_async && is!("await") {
-            return self.parse_await_expr();
+        if is!("await") {
+            let ctx = self.ctx();
+            if ctx.in_async {
+                return self.parse_await_expr();
+            }
+
+            // `await` is an identifier in scripts, so `await foo` is a syntax
+            // error outside of async functions.
+            if !ctx.module && self.syntax().top_level_await() && self.is_await_with_operand() {
+                syntax_error!(self.input.cur_span(), SyntaxError::TopLevelAwaitInScript);
+            }
         }
 
         // UpdateExpression
@@ -310,6 +340,31 @@ impl<'a, I: Tokens> Parser<'a, I> {
             arg,
         })))
     }
+
+    /// Returns true if the current `await` is followed by an operand on the
+    /// same line, like `await foo`.
+    ///
+    /// Tokens like `(` and `+` are not operands, as `await (foo)` and
+    /// `await + foo` are valid when `await` is an identifier.
+    fn is_await_with_operand(&mut self) -> bool {
+        if self.input.has_linebreak_between_cur_and_peeked() {
+            return false;
+        }
+
+        let is_ts = self.syntax().typescript();
+        match self.input.peek() {
+            Some(&Token::Word(Word::Ident(ref w))) if *w == *"of" => false,
+            Some(&Token::Word(Word::Ident(ref w))) if *w == *"as" && is_ts => false,
+            Some(&tok!('('))
+            | Some(&tok!('['))
+            | Some(&Token::BackQuote)
+            | Some(&tok!("++"))
+            | Some(&tok!("--"))
+            | Some(&Token::BinOp(..)) => false,
+            Some(t) => t.starts_expr(),
+            None => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 extern crate test;
 
 use super::*;
-use crate::EsConfig;
+use crate::{EsConfig, TokenKind, TsConfig};
 use std::hint::black_box;
 use swc_common::DUMMY_SP as span;
 use test::Bencher;
@@ -312,6 +312,225 @@ fn max_integer() {
     )
 }
 
+#[test]
+fn big_int() {
+    assert_eq_ignore_span!(
+        expr("0x1fn"),
+        box Expr::Lit(Lit::BigInt(BigInt {
+            span,
+            value: "0x1f".into()
+        }))
+    )
+}
+
+/// Returns the error of parsing `src` as a module with `max_depth`.
+fn max_depth_err(src: &str, max_depth: usize) -> String {
+    crate::with_test_sess(src, |sess, input| {
+        Parser::new(sess, syntax(), input, None)
+            .with_max_depth(Some(max_depth))
+            .parse_module()
+            .map_err(|mut e| {
+                e.emit();
+            })
+    })
+    .expect_err("should fail")
+    .to_string()
+}
+
+#[test]
+fn max_depth_parens() {
+    let src = format!("{}a{}", "(".repeat(100_000), ")".repeat(100_000));
+    let stderr = max_depth_err(&src, 100);
+
+    assert!(
+        stderr.contains("Maximum nesting depth of 100 exceeded"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn max_depth_blocks() {
+    let src = format!("{}{}", "{".repeat(100_000), "}".repeat(100_000));
+    let stderr = max_depth_err(&src, 100);
+
+    assert!(
+        stderr.contains("Maximum nesting depth of 100 exceeded"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn max_depth_unary() {
+    let src = format!("{}a", "!".repeat(100_000));
+    let stderr = max_depth_err(&src, 100);
+
+    assert!(
+        stderr.contains("Maximum nesting depth of 100 exceeded"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn max_depth_subscripts() {
+    let src = format!("a{}", ".b()".repeat(100_000));
+    let stderr = max_depth_err(&src, 100);
+
+    assert!(
+        stderr.contains("Maximum nesting depth of 100 exceeded"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn max_depth_not_exceeded() {
+    crate::with_test_sess("[[[a]]]; { { b; } }", |sess, input| {
+        Parser::new(sess, syntax(), input, None)
+            .with_max_depth(Some(10))
+            .parse_module()
+            .map_err(|mut e| {
+                e.emit();
+            })
+    })
+    .unwrap();
+}
+
+#[test]
+fn token_at() {
+    crate::with_test_sess("foo  + bar", |sess, input| {
+        let mut p = Parser::new(sess, syntax(), input, None).with_token_recording(true);
+        p.parse_module().map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let sp = |lo, hi| Span::new(BytePos(lo), BytePos(hi), Default::default());
+        assert_eq!(p.token_at(BytePos(1)), Some((TokenKind::Ident, sp(0, 3))));
+        assert_eq!(p.token_at(BytePos(5)), Some((TokenKind::Punct, sp(5, 6))));
+        assert_eq!(p.token_at(BytePos(9)), Some((TokenKind::Ident, sp(7, 10))));
+        // Whitespace between tokens
+        assert_eq!(p.token_at(BytePos(3)), None);
+        assert_eq!(p.token_at(BytePos(4)), None);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn token_at_after_ts_lookahead() {
+    // `a < b` is parsed as type arguments first, which fails.
+    crate::with_test_sess("a < b; f<T>(c);", |sess, input| {
+        let syntax = Syntax::Typescript(Default::default());
+        let mut p = Parser::new(sess, syntax, input, None).with_token_recording(true);
+        p.parse_module().map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let sp = |lo, hi| Span::new(BytePos(lo), BytePos(hi), Default::default());
+        assert_eq!(p.token_at(BytePos(2)), Some((TokenKind::Punct, sp(2, 3))));
+        assert_eq!(p.token_at(BytePos(4)), Some((TokenKind::Ident, sp(4, 5))));
+        assert_eq!(p.token_at(BytePos(8)), Some((TokenKind::Punct, sp(8, 9))));
+        assert_eq!(
+            p.token_at(BytePos(12)),
+            Some((TokenKind::Ident, sp(12, 13)))
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn token_at_without_recording() {
+    crate::with_test_sess("foo", |sess, input| {
+        let mut p = Parser::new(sess, syntax(), input, None);
+        p.parse_module().map_err(|mut e| {
+            e.emit();
+        })?;
+
+        assert_eq!(p.token_at(BytePos(1)), None);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+fn expr_with(s: &'static str, syntax: Syntax) -> Box<Expr> {
+    test_parser(s, syntax, |p| {
+        p.parse_expr().map_err(|mut e| {
+            e.emit();
+        })
+    })
+}
+
+#[test]
+fn jsx_in_js() {
+    let syntax = Syntax::Es(EsConfig {
+        jsx: true,
+        ..Default::default()
+    });
+
+    match *expr_with("<T>x</T>", syntax) {
+        Expr::JSXElement(..) => {}
+        ref e => panic!("expected a jsx element, got {:?}", e),
+    }
+}
+
+#[test]
+fn type_assertion_in_ts() {
+    match *expr_with("<T>x", Syntax::Typescript(Default::default())) {
+        Expr::TsTypeAssertion(..) => {}
+        ref e => panic!("expected a type assertion, got {:?}", e),
+    }
+}
+
+#[test]
+fn type_assertion_in_tsx() {
+    let syntax = Syntax::Typescript(TsConfig {
+        tsx: true,
+        ..Default::default()
+    });
+
+    // `<T>` starts a jsx element in .tsx files.
+    let stderr = crate::with_test_sess("<T>x", |sess, input| {
+        Parser::new(sess, syntax, input, None)
+            .parse_expr()
+            .map_err(|mut e| {
+                e.emit();
+            })
+    })
+    .expect_err("type assertions should not be allowed in tsx")
+    .to_string();
+
+    assert!(stderr.contains("Unterminated JSX contents"), "{}", stderr);
+}
+
+#[test]
+fn unterminated_str_lit_diagnostic() {
+    let d = crate::with_test_sess("foo('abc", |sess, input| {
+        let mut err = Parser::new(sess, syntax(), input, None)
+            .parse_module()
+            .expect_err("should fail");
+        let d = crate::Diagnostic::from_builder(&err);
+        err.cancel();
+
+        Ok(d)
+    })
+    .unwrap()
+    .expect("syntax errors should have a code");
+
+    assert_eq!(d.code, "SWC1006");
+    assert_eq!(d.message, "Unterminated string constant");
+    assert_eq!(
+        d.span,
+        Span::new(BytePos(4), BytePos(8), Default::default())
+    );
+    assert!(d.labels.is_empty());
+}
+
 #[test]
 fn iife() {
     assert_eq_ignore_span!(
@@ -413,6 +632,57 @@ fn issue_380() {
     );
 }
 
+fn private_syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        class_private_props: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn private_name_in() {
+    assert_eq_ignore_span!(
+        test_parser("#x in obj && a", private_syntax(), |p| p
+            .parse_expr()
+            .map_err(|mut e| {
+                e.emit();
+            })),
+        box Expr::Bin(BinExpr {
+            span,
+            op: op!("&&"),
+            left: box Expr::Bin(BinExpr {
+                span,
+                op: op!("in"),
+                left: box Expr::PrivateName(PrivateName {
+                    span,
+                    id: Ident::new("x".into(), span),
+                }),
+                right: expr("obj"),
+            }),
+            right: expr("a"),
+        })
+    );
+}
+
+#[test]
+fn private_name_in_right_of_logical() {
+    test_parser("a || #x in obj", private_syntax(), |p| {
+        p.parse_expr().map_err(|mut e| {
+            e.emit();
+        })
+    });
+}
+
+#[test]
+#[should_panic(expected = "Unexpected")]
+fn private_name_without_in() {
+    test_parser("#x + 1", private_syntax(), |p| {
+        p.parse_expr().map_err(|mut e| {
+            e.emit();
+        })
+    });
+}
+
 #[bench]
 fn bench_new_expr_ts(b: &mut Bencher) {
     bench_parser(
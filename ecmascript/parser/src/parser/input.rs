@@ -5,7 +5,11 @@ use crate::{
     Context, JscTarget, Syntax,
 };
 use lexer::TokenContexts;
-use std::mem;
+use std::{
+    cell::{Ref, RefCell},
+    mem,
+    rc::Rc,
+};
 use swc_common::{BytePos, Span, DUMMY_SP};
 
 pub trait Tokens: Clone + Iterator<Item = TokenAndSpan> {
@@ -153,6 +157,11 @@ pub(super) struct Buffer<I: Tokens> {
     cur: Option<TokenAndSpan>,
     /// Peeked token
     next: Option<TokenAndSpan>,
+    /// Tokens produced by the lexer, if recording is enabled.
+    ///
+    /// This is shared with clones made for lookahead, so cloning the buffer
+    /// doesn't copy it.
+    tokens: Option<Rc<RefCell<Vec<(TokenKind, Span)>>>>,
 }
 
 impl<I: Tokens> Parser<'_, I> {
@@ -168,7 +177,39 @@ impl<I: Tokens> Buffer<I> {
             cur: None,
             prev_span: DUMMY_SP,
             next: None,
+            tokens: None,
+        }
+    }
+
+    /// Starts or stops recording tokens produced by the lexer.
+    pub fn set_token_recording(&mut self, record: bool) {
+        self.tokens = if record {
+            Some(Default::default())
+        } else {
+            None
+        };
+    }
+
+    /// Returns recorded tokens, sorted by their position.
+    pub fn tokens(&self) -> Option<Ref<Vec<(TokenKind, Span)>>> {
+        self.tokens.as_ref().map(|tokens| tokens.borrow())
+    }
+
+    fn next_token(&mut self) -> Option<TokenAndSpan> {
+        let next = self.iter.next();
+        if let (Some(tokens), Some(next)) = (self.tokens.as_ref(), next.as_ref()) {
+            let mut tokens = tokens.borrow_mut();
+            // Tokens after `next` were produced by a lookahead which was rolled
+            // back, and are lexed again from here.
+            while tokens
+                .last()
+                .map_or(false, |&(_, span)| span.lo() >= next.span.lo())
+            {
+                tokens.pop();
+            }
+            tokens.push((next.token.kind(), next.span));
         }
+        next
     }
 
     pub fn store(&mut self, token: Token) {
@@ -191,7 +232,7 @@ impl<I: Tokens> Buffer<I> {
         };
 
         // If we have peeked a token, take it instead of calling lexer.next()
-        self.cur = self.next.take().or_else(|| self.iter.next());
+        self.cur = self.next.take().or_else(|| self.next_token());
 
         prev.map(|it| it.token)
     }
@@ -226,7 +267,7 @@ impl<I: Tokens> Buffer<I> {
         );
 
         if self.next.is_none() {
-            self.next = self.iter.next();
+            self.next = self.next_token();
         }
 
         self.next.as_ref().map(|ts| &ts.token)
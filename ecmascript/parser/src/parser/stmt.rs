@@ -78,7 +78,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             return self.handle_import_export(top_level, decorators);
         }
 
-        self.parse_stmt_internal(include_decl, top_level, decorators)
+        self.nested(|p| p.parse_stmt_internal(include_decl, top_level, decorators))
             .map(From::from)
     }
 
@@ -1072,7 +1072,7 @@ impl<'a, I: Tokens> StmtLikeParser<'a, Stmt> for Parser<'a, I> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::EsConfig;
+    use crate::{EsConfig, ProgramKind};
     use swc_common::DUMMY_SP as span;
 
     fn stmt(s: &'static str) -> Stmt {
@@ -1591,4 +1591,173 @@ export default function waitUntil(callback, options = {}) {
             },
         );
     }
+
+    fn decorators(before_export: bool) -> Syntax {
+        Syntax::Es(EsConfig {
+            decorators: true,
+            decorators_before_export: before_export,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the class of the declaration exported by `src`.
+    fn exported_class(src: &'static str, before_export: bool) -> Class {
+        let m = test_parser(src, decorators(before_export), |p| {
+            p.parse_module().map_err(|mut e| {
+                e.emit();
+            })
+        });
+
+        match m.body.into_iter().next() {
+            Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Class(ClassDecl { class, .. }),
+                ..
+            })))
+            | Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::Class(ClassExpr { class, .. }),
+                ..
+            }))) => class,
+            item => panic!("expected an exported class, got {:?}", item),
+        }
+    }
+
+    /// Returns the diagnostic of parsing `src`, which should fail.
+    fn decorator_err(src: &'static str, before_export: bool) -> crate::Diagnostic {
+        crate::with_test_sess(src, |sess, input| {
+            let mut err = Parser::new(sess, decorators(before_export), input, None)
+                .parse_module()
+                .expect_err("should fail");
+            let d = crate::Diagnostic::from_builder(&err);
+            err.cancel();
+
+            Ok(d)
+        })
+        .unwrap()
+        .expect("should be a syntax error")
+    }
+
+    #[test]
+    fn decorators_before_export() {
+        let class = exported_class("@dec export class Foo {}", true);
+        assert_eq!(class.decorators.len(), 1);
+
+        let d = decorator_err("export @dec class Foo {}", true);
+        assert_eq!(d.code, "SWC2058");
+        assert_eq!(
+            d.span,
+            Span::new(BytePos(7), BytePos(11), Default::default())
+        );
+    }
+
+    #[test]
+    fn decorators_after_export() {
+        let class = exported_class("export @dec class Foo {}", false);
+        assert_eq!(class.decorators.len(), 1);
+        let class = exported_class("export default @a @b class {}", false);
+        assert_eq!(class.decorators.len(), 2);
+
+        let d = decorator_err("@dec export class Foo {}", false);
+        assert_eq!(d.code, "SWC2042");
+        assert_eq!(
+            d.span,
+            Span::new(BytePos(0), BytePos(4), Default::default())
+        );
+    }
+
+    #[test]
+    fn decorators_after_export_not_class() {
+        let d = decorator_err("export @dec function foo() {}", false);
+        assert_eq!(d.code, "SWC2041");
+    }
+
+    fn top_level_await() -> Syntax {
+        Syntax::Es(EsConfig {
+            top_level_await: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn top_level_await_in_module() {
+        let m = test_parser("await foo();", top_level_await(), |p| {
+            p.parse_module().map_err(|mut e| {
+                e.emit();
+            })
+        });
+
+        let is_await = match m.body[0] {
+            ModuleItem::Stmt(Stmt::Expr(ref e)) => match **e {
+                Expr::Await(..) => true,
+                _ => false,
+            },
+            _ => false,
+        };
+        assert!(
+            is_await,
+            "expected an await expression, got {:?}",
+            m.body[0]
+        );
+    }
+
+    #[test]
+    fn top_level_await_in_script() {
+        let d = crate::with_test_sess("let x = await foo();", |sess, input| {
+            let mut err = Parser::new(sess, top_level_await(), input, None)
+                .parse_script()
+                .expect_err("should fail");
+            let d = crate::Diagnostic::from_builder(&err);
+            err.cancel();
+
+            Ok(d)
+        })
+        .unwrap()
+        .expect("should be a syntax error");
+
+        assert_eq!(d.code, "SWC2059");
+        assert_eq!(
+            d.span,
+            Span::new(BytePos(8), BytePos(13), Default::default())
+        );
+    }
+
+    #[test]
+    fn await_ident_in_script() {
+        test_parser(
+            "await(foo); await[0]; await\nfoo;",
+            top_level_await(),
+            |p| {
+                p.parse_script().map_err(|mut e| {
+                    e.emit();
+                })
+            },
+        );
+    }
+
+    #[test]
+    fn top_level_return() {
+        test_parser("return;", Default::default(), |p| {
+            p.parse_program(ProgramKind::CommonJs).map_err(|mut e| {
+                e.emit();
+            })
+        });
+
+        let stderr = crate::with_test_sess("return;", |sess, input| {
+            Parser::new(sess, Default::default(), input, None)
+                .parse_program(ProgramKind::Script)
+                .map_err(|mut e| {
+                    e.emit();
+                })?;
+
+            // The error is recoverable.
+            Err(())
+        })
+        .expect_err("should fail")
+        .to_string();
+
+        assert!(
+            stderr.contains("Return statement is not allowed here"),
+            "{}",
+            stderr
+        );
+    }
 }
@@ -203,6 +203,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
             }
         }
 
+        // `export @dec class Foo {}`
+        let decorators = if is!('@') {
+            self.parse_decorators_after_export()?
+        } else {
+            decorators
+        };
+
         let mut has_star = false;
         let mut export_ns = None;
 
@@ -258,6 +265,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
+            // `export default @dec class {}`
+            let decorators = if is!('@') {
+                self.parse_decorators_after_export()?
+            } else {
+                decorators
+            };
+
             if is!("class") {
                 let decl = self.parse_default_class(decorators)?;
                 return Ok(ModuleDecl::ExportDefaultDecl(decl));
@@ -121,6 +121,34 @@ impl<'a, I: Tokens> Parser<'a, I> {
         f(self)
     }
 
+    /// Parses with given closure, one level deeper.
+    ///
+    /// Returns an error if this exceeds `max_depth`.
+    pub(super) fn nested<F, Ret>(&mut self, f: F) -> PResult<'a, Ret>
+    where
+        F: FnOnce(&mut Self) -> PResult<'a, Ret>,
+    {
+        self.enter_nested()?;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+
+    /// Goes one level deeper without returning.
+    ///
+    /// Returns an error if this exceeds `max_depth`. The caller is responsible
+    /// for restoring `depth`.
+    pub(super) fn enter_nested(&mut self) -> PResult<'a, ()> {
+        if let Some(max_depth) = self.max_depth {
+            if self.depth >= max_depth {
+                syntax_error!(self, SyntaxError::MaxDepthExceeded { max_depth })
+            }
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+
     /// Creates a span from `start` to current pos.
     pub(super) fn span(&mut self, start: BytePos) -> Span {
         let end = last_pos!(self);
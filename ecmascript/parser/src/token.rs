@@ -123,6 +123,10 @@ pub enum Token {
     #[kind(starts_expr)]
     Num(f64),
 
+    /// Digits of a BigInt literal, without the `n` suffix.
+    #[kind(starts_expr)]
+    BigInt(JsWord),
+
     JSXName {
         name: JsWord,
     },
@@ -554,6 +558,46 @@ impl Token {
     }
 }
 
+impl Token {
+    pub(crate) fn kind(&self) -> TokenKind {
+        match *self {
+            Token::Word(Word::Ident(..)) => TokenKind::Ident,
+            Token::Word(..) => TokenKind::Keyword,
+            Token::Template { .. } => TokenKind::Template,
+            Token::Str { .. } => TokenKind::Str,
+            Token::Regex(..) => TokenKind::Regex,
+            Token::Num(..) => TokenKind::Num,
+            Token::BigInt(..) => TokenKind::BigInt,
+            Token::JSXName { .. } => TokenKind::JSXName,
+            Token::JSXText { .. } => TokenKind::JSXText,
+            Token::Shebang(..) => TokenKind::Shebang,
+            Token::Error(..) => TokenKind::Error,
+            _ => TokenKind::Punct,
+        }
+    }
+}
+
+/// Kind of a token, for editor integrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Ident,
+    /// Keywords, including `null`, `true` and `false`.
+    Keyword,
+    /// Punctuators and operators.
+    Punct,
+    /// Raw text of a template literal.
+    Template,
+    Str,
+    Regex,
+    Num,
+    BigInt,
+    JSXName,
+    JSXText,
+    Shebang,
+    /// A token which failed to be lexed.
+    Error,
+}
+
 impl Word {
     pub(crate) fn cow(&self) -> Cow<JsWord> {
         match *self {
@@ -7,7 +7,7 @@ static GLOBAL: System = System;
 
 use swc_common::{FileName, FoldWith};
 use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
-use swc_ecma_transforms::{compat, helpers};
+use swc_ecma_transforms::{compat, helpers, pass};
 use test::Bencher;
 
 static SOURCE: &str = r#"
@@ -81,13 +81,29 @@ module.exports = {
     hexToInt,
 };"#;
 
+/// Patterns only appear in nested functions.
+static NESTED_PATTERN_SOURCE: &str = r#"
+module.exports = {
+    sum: function (values) {
+        return values.reduce(([a, b], { x, y = 0 }) => [a + x, b + y], [0, 0]);
+    },
+    first: (...args) => {
+        const [first] = args;
+        return first;
+    },
+};
+"#;
+
 /// Benchmark a folder
 macro_rules! tr {
     ($b:expr, $tr:expr) => {
-        $b.bytes = SOURCE.len() as _;
+        tr!($b, SOURCE, $tr)
+    };
+    ($b:expr, $src:expr, $tr:expr) => {
+        $b.bytes = $src.len() as _;
 
         let _ = ::testing::run_test(false, |cm, handler| {
-            let fm = cm.new_source_file(FileName::Anon, SOURCE.into());
+            let fm = cm.new_source_file(FileName::Anon, $src.into());
 
             let mut parser = Parser::new(
                 Session { handler: &handler },
@@ -127,12 +143,12 @@ fn all(b: &mut Bencher) {
 
 #[bench]
 fn es2018(b: &mut Bencher) {
-    tr!(b, || compat::es2018());
+    tr!(b, || compat::es2018(Default::default()));
 }
 
 #[bench]
 fn es2018_object_rest_spread(b: &mut Bencher) {
-    tr!(b, || compat::es2018::object_rest_spread());
+    tr!(b, || compat::es2018::object_rest_spread(Default::default()));
 }
 
 #[bench]
@@ -180,6 +196,15 @@ fn es2015_classes(b: &mut Bencher) {
     tr!(b, || compat::es2015::Classes);
 }
 
+/// `SOURCE` doesn't contain classes, so the classes pass is skipped.
+#[bench]
+fn es2015_classes_skipped(b: &mut Bencher) {
+    tr!(b, || pass::skip_unless(
+        pass::FeatureFlags::CLASSES,
+        compat::es2015::Classes
+    ));
+}
+
 #[bench]
 fn es2015_computed_props(b: &mut Bencher) {
     tr!(b, compat::es2015::computed_properties);
@@ -190,6 +215,21 @@ fn es2015_destructuring(b: &mut Bencher) {
     tr!(b, compat::es2015::destructuring);
 }
 
+#[bench]
+fn es2015_destructuring_nested(b: &mut Bencher) {
+    tr!(b, NESTED_PATTERN_SOURCE, || compat::es2015::destructuring(
+        Default::default()
+    ));
+}
+
+#[bench]
+fn es2015_destructuring_many_stmts(b: &mut Bencher) {
+    let src = "var [a, b] = f(), { c, d = 1 } = g();\n[a, b] = [b, a];\n".repeat(1000);
+    tr!(b, &*src, || compat::es2015::destructuring(
+        Default::default()
+    ));
+}
+
 #[bench]
 fn es2015_duplicate_keys(b: &mut Bencher) {
     tr!(b, || compat::es2015::duplicate_keys());
@@ -0,0 +1,7 @@
+pub use self::{
+    duplicate_keys::{check_duplicate_keys, DuplicateKeysChecker},
+    tdz::{check_tdz, TdzChecker},
+};
+
+mod duplicate_keys;
+mod tdz;
@@ -0,0 +1,240 @@
+use crate::util::HANDLER;
+use ast::*;
+use hashbrown::{hash_map::Entry, HashMap};
+use swc_atoms::JsWord;
+use swc_common::{MultiSpan, Span, Visit, VisitWith};
+
+/// Reports duplicate keys of object literals.
+///
+/// # Example
+///
+/// ```js
+/// var a = { b: 1, b: 2 }; // reported
+/// var c = { get d() {}, set d(v) {} }; // not reported
+/// var e = { __proto__: f, __proto__: g }; // error
+/// ```
+///
+/// A getter and a setter with the same key define a single property, so they
+/// are not reported. Other duplicates are valid, but only the last one takes
+/// effect, so they are reported as warnings pointing at both keys.
+///
+/// `__proto__: value` sets the prototype of the object instead of defining a
+/// property, and using it twice is a syntax error. It's reported as an error,
+/// and does not conflict with other forms like `__proto__() {}`.
+///
+/// Computed keys are ignored.
+pub fn check_duplicate_keys<N>(node: &N)
+where
+    N: VisitWith<DuplicateKeysChecker>,
+{
+    node.visit_with(&mut DuplicateKeysChecker);
+}
+
+pub struct DuplicateKeysChecker;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Data,
+    Getter,
+    Setter,
+    /// A getter and a setter.
+    Accessors,
+}
+
+struct Seen {
+    span: Span,
+    kind: Kind,
+}
+
+impl Visit<ObjectLit> for DuplicateKeysChecker {
+    fn visit(&mut self, obj: &ObjectLit) {
+        obj.visit_children(self);
+
+        let mut seen = HashMap::<JsWord, Seen>::default();
+        let mut proto: Option<Span> = None;
+
+        for prop in &obj.props {
+            let prop = match *prop {
+                PropOrSpread::Prop(ref prop) => &**prop,
+                PropOrSpread::Spread(..) => continue,
+            };
+
+            let (key, kind) = match *prop {
+                Prop::Shorthand(ref i) => {
+                    check(&mut seen, i.sym.clone(), i.span, Kind::Data);
+                    continue;
+                }
+                Prop::KeyValue(KeyValueProp { ref key, .. }) => {
+                    match key_name(key) {
+                        Some((ref sym, span)) if *sym == *"__proto__" => {
+                            if let Some(prev) = proto {
+                                report_proto(prev, span);
+                            }
+                            proto = Some(span);
+                            continue;
+                        }
+                        _ => {}
+                    }
+
+                    (key, Kind::Data)
+                }
+                Prop::Method(MethodProp { ref key, .. }) => (key, Kind::Data),
+                Prop::Getter(GetterProp { ref key, .. }) => (key, Kind::Getter),
+                Prop::Setter(SetterProp { ref key, .. }) => (key, Kind::Setter),
+                Prop::Assign(..) => continue,
+            };
+
+            if let Some((sym, span)) = key_name(key) {
+                check(&mut seen, sym, span, kind);
+            }
+        }
+    }
+}
+
+fn check(seen: &mut HashMap<JsWord, Seen>, sym: JsWord, span: Span, kind: Kind) {
+    match seen.entry(sym) {
+        Entry::Vacant(e) => {
+            e.insert(Seen { span, kind });
+        }
+        Entry::Occupied(mut e) => match (e.get().kind, kind) {
+            (Kind::Getter, Kind::Setter) | (Kind::Setter, Kind::Getter) => {
+                e.get_mut().kind = Kind::Accessors;
+            }
+            _ => {
+                report(e.key(), e.get().span, span);
+                e.insert(Seen { span, kind });
+            }
+        },
+    }
+}
+
+fn report(sym: &JsWord, prev: Span, span: Span) {
+    let mut spans = MultiSpan::from_span(span);
+    spans.push_span_label(prev, "first defined here".into());
+    spans.push_span_label(span, "redefined here".into());
+
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(spans, &format!("duplicate key `{}` in object literal", sym))
+            .emit()
+    });
+}
+
+fn report_proto(prev: Span, span: Span) {
+    let mut spans = MultiSpan::from_span(span);
+    spans.push_span_label(prev, "first defined here".into());
+    spans.push_span_label(span, "redefined here".into());
+
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(
+                spans,
+                "duplicate `__proto__` fields are not allowed in object literals",
+            )
+            .emit()
+    });
+}
+
+/// Returns the name of a non-computed key.
+fn key_name(key: &PropName) -> Option<(JsWord, Span)> {
+    match *key {
+        PropName::Ident(ref i) => Some((i.sym.clone(), i.span)),
+        PropName::Str(ref s) => Some((s.value.clone(), s.span)),
+        // `1` and `'1'` are the same key. Other numbers are ignored, as they
+        // are formatted differently by javascript.
+        PropName::Num(Number { span, value })
+            if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 =>
+        {
+            Some(((value as i64).to_string().into(), span))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+
+    /// Returns the stderr of checking `src`, parsed as a module.
+    fn run(src: &str) -> Option<String> {
+        ::testing::run_test(false, |cm, handler| {
+            HANDLER.set(handler, || {
+                let mut tester = Tester {
+                    cm,
+                    handler,
+                    comments: Default::default(),
+                };
+
+                let module = tester.parse_module("input.js", src)?;
+                check_duplicate_keys(&module);
+
+                // Diagnostics are returned only on failure.
+                Err(())
+            })
+        })
+        .err()
+        .map(|stderr| stderr.to_string())
+        .filter(|stderr| !stderr.trim().is_empty())
+    }
+
+    #[test]
+    fn plain_duplicate() {
+        let stderr = run("var a = { b: 1, c: 2, b: 3 };").expect("should warn");
+
+        assert!(
+            stderr.contains("duplicate key `b` in object literal"),
+            "{}",
+            stderr
+        );
+        assert!(stderr.contains("first defined here"), "{}", stderr);
+        assert!(stderr.contains("redefined here"), "{}", stderr);
+        assert!(stderr.contains("input.js:1:23"), "{}", stderr);
+    }
+
+    #[test]
+    fn mixed_key_kinds() {
+        let stderr = run("var a = { 'b': 1, b() {} };").expect("should warn");
+        assert!(stderr.contains("duplicate key `b`"), "{}", stderr);
+
+        let stderr = run("var a = { 1: 1, '1': 2 };").expect("should warn");
+        assert!(stderr.contains("duplicate key `1`"), "{}", stderr);
+
+        let stderr = run("var a = { b: 1, get b() {} };").expect("should warn");
+        assert!(stderr.contains("duplicate key `b`"), "{}", stderr);
+    }
+
+    #[test]
+    fn getter_and_setter() {
+        assert_eq!(run("var a = { get b() {}, set b(v) {} };"), None);
+
+        let stderr = run("var a = { get b() {}, set b(v) {}, get b() {} };").expect("should warn");
+        assert!(stderr.contains("duplicate key `b`"), "{}", stderr);
+    }
+
+    #[test]
+    fn computed() {
+        assert_eq!(run("var a = { [b]: 1, [b]: 2, c: 3 };"), None);
+    }
+
+    #[test]
+    fn proto() {
+        let stderr = run("var a = { __proto__: b, '__proto__': c };").expect("should fail");
+        assert!(
+            stderr.contains("duplicate `__proto__` fields are not allowed"),
+            "{}",
+            stderr
+        );
+
+        assert_eq!(
+            run("var a = { __proto__: b, ['__proto__']: c, __proto__() {} };"),
+            None
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let stderr = run("f({ a: { b: 1, b: 2 } });").expect("should warn");
+        assert!(stderr.contains("duplicate key `b`"), "{}", stderr);
+    }
+}
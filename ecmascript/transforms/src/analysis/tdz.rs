@@ -0,0 +1,477 @@
+use crate::util::{DestructuringFinder, HANDLER};
+use ast::*;
+use hashbrown::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{MultiSpan, Span, Visit, VisitWith};
+
+/// Reports uses of `let`, `const` and class bindings in their temporal dead
+/// zone, i.e. before their declaration is evaluated.
+///
+/// # Example
+///
+/// ```js
+/// foo(a); // reported
+/// function f() { return a; } // not reported
+/// let a = 1;
+/// ```
+///
+/// Code in functions, arrows and class properties is evaluated later, so uses
+/// in them are not reported. Function declarations are hoisted and never in
+/// the temporal dead zone.
+///
+/// Each diagnostic is a warning pointing at both the use and the declaration.
+pub fn check_tdz<N>(node: &N)
+where
+    N: VisitWith<TdzChecker>,
+{
+    let mut v = TdzChecker {
+        scopes: vec![],
+        fn_depth: 0,
+    };
+    node.visit_with(&mut v);
+}
+
+pub struct TdzChecker {
+    scopes: Vec<Scope>,
+    /// Number of functions we are in.
+    fn_depth: usize,
+}
+
+#[derive(Default)]
+struct Scope {
+    bindings: HashMap<JsWord, Binding>,
+}
+
+struct Binding {
+    span: Span,
+    initialized: bool,
+    /// `fn_depth` of the declaration.
+    fn_depth: usize,
+}
+
+impl TdzChecker {
+    fn with_scope<F>(&mut self, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.scopes.push(Scope::default());
+        op(self);
+        self.scopes.pop();
+    }
+
+    /// Runs `op` as code which is not evaluated immediately.
+    fn deferred<F>(&mut self, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.fn_depth += 1;
+        op(self);
+        self.fn_depth -= 1;
+    }
+
+    fn declare(&mut self, sym: JsWord, span: Span, initialized: bool) {
+        let fn_depth = self.fn_depth;
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.bindings.insert(
+                sym,
+                Binding {
+                    span,
+                    initialized,
+                    fn_depth,
+                },
+            );
+        }
+    }
+
+    fn declare_pat<N>(&mut self, pat: &N, initialized: bool)
+    where
+        N: for<'a> VisitWith<DestructuringFinder<'a>>,
+    {
+        for (sym, span) in find_ids(pat) {
+            self.declare(sym, span, initialized);
+        }
+    }
+
+    /// Declares bindings of `decl` without initializing lexical ones.
+    fn declare_decl(&mut self, decl: &Decl) {
+        match *decl {
+            Decl::Var(VarDecl {
+                kind: VarDeclKind::Var,
+                ..
+            }) => {}
+            Decl::Var(ref v) => self.declare_pat(&v.decls, false),
+            Decl::Class(ref c) => self.declare(c.ident.sym.clone(), c.ident.span, false),
+            Decl::Fn(ref f) => self.declare(f.ident.sym.clone(), f.ident.span, true),
+            _ => {}
+        }
+    }
+
+    fn declare_stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            match *stmt {
+                Stmt::Decl(ref decl) => self.declare_decl(decl),
+                _ => {}
+            }
+        }
+    }
+
+    fn initialize(&mut self, sym: &JsWord) {
+        if let Some(b) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.bindings.get_mut(sym))
+        {
+            b.initialized = true;
+        }
+    }
+
+    fn initialize_pat<N>(&mut self, pat: &N)
+    where
+        N: for<'a> VisitWith<DestructuringFinder<'a>>,
+    {
+        for (sym, _) in find_ids(pat) {
+            self.initialize(&sym);
+        }
+    }
+
+    fn check(&self, sym: &JsWord, span: Span) {
+        let b = match self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.bindings.get(sym))
+        {
+            Some(b) => b,
+            None => return,
+        };
+        if b.initialized || b.fn_depth != self.fn_depth {
+            return;
+        }
+
+        let mut spans = MultiSpan::from_span(span);
+        spans.push_span_label(span, "used here".into());
+        spans.push_span_label(b.span, "declared here".into());
+
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_warn(spans, &format!("`{}` is used before its declaration", sym))
+                .emit()
+        });
+    }
+
+    /// Checks the identifiers assigned by `pat`.
+    fn check_pat<N>(&self, pat: &N)
+    where
+        N: for<'a> VisitWith<DestructuringFinder<'a>>,
+    {
+        for (sym, span) in find_ids(pat) {
+            self.check(&sym, span);
+        }
+    }
+
+    /// Visits a loop with lexical bindings in its head.
+    fn visit_for_in_of(&mut self, left: &VarDeclOrPat, right: &Expr, body: &Stmt) {
+        self.with_scope(|v| {
+            match *left {
+                VarDeclOrPat::VarDecl(ref decl) if decl.kind != VarDeclKind::Var => {
+                    v.declare_pat(&decl.decls, false)
+                }
+                _ => {}
+            }
+            right.visit_with(v);
+
+            match *left {
+                VarDeclOrPat::VarDecl(ref decl) => v.initialize_pat(&decl.decls),
+                VarDeclOrPat::Pat(ref pat) => v.check_pat(pat),
+            }
+            left.visit_with(v);
+            body.visit_with(v);
+        })
+    }
+}
+
+fn find_ids<N>(node: &N) -> Vec<(JsWord, Span)>
+where
+    N: for<'a> VisitWith<DestructuringFinder<'a>>,
+{
+    let mut found = vec![];
+    node.visit_with(&mut DestructuringFinder { found: &mut found });
+    found
+}
+
+impl Visit<Module> for TdzChecker {
+    fn visit(&mut self, m: &Module) {
+        self.with_scope(|v| {
+            for item in &m.body {
+                match *item {
+                    ModuleItem::Stmt(Stmt::Decl(ref decl))
+                    | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ref decl)) => {
+                        v.declare_decl(decl)
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(ref import)) => {
+                        for s in &import.specifiers {
+                            let local = match *s {
+                                ImportSpecifier::Specific(ref s) => &s.local,
+                                ImportSpecifier::Default(ref s) => &s.local,
+                                ImportSpecifier::Namespace(ref s) => &s.local,
+                            };
+                            v.declare(local.sym.clone(), local.span, true);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            m.body.visit_with(v)
+        })
+    }
+}
+
+impl Visit<Script> for TdzChecker {
+    fn visit(&mut self, s: &Script) {
+        self.with_scope(|v| {
+            v.declare_stmts(&s.body);
+            s.body.visit_with(v)
+        })
+    }
+}
+
+impl Visit<BlockStmt> for TdzChecker {
+    fn visit(&mut self, b: &BlockStmt) {
+        self.with_scope(|v| {
+            v.declare_stmts(&b.stmts);
+            b.stmts.visit_with(v)
+        })
+    }
+}
+
+impl Visit<SwitchStmt> for TdzChecker {
+    fn visit(&mut self, s: &SwitchStmt) {
+        s.discriminant.visit_with(self);
+
+        // All cases share a scope.
+        self.with_scope(|v| {
+            for case in &s.cases {
+                v.declare_stmts(&case.cons);
+            }
+            s.cases.visit_with(v)
+        })
+    }
+}
+
+impl Visit<CatchClause> for TdzChecker {
+    fn visit(&mut self, c: &CatchClause) {
+        self.with_scope(|v| {
+            if let Some(ref param) = c.param {
+                v.declare_pat(param, true);
+            }
+            c.visit_children(v)
+        })
+    }
+}
+
+impl Visit<ForStmt> for TdzChecker {
+    fn visit(&mut self, s: &ForStmt) {
+        self.with_scope(|v| {
+            match s.init {
+                Some(VarDeclOrExpr::VarDecl(ref decl)) if decl.kind != VarDeclKind::Var => {
+                    v.declare_pat(&decl.decls, false)
+                }
+                _ => {}
+            }
+            s.visit_children(v)
+        })
+    }
+}
+
+impl Visit<ForInStmt> for TdzChecker {
+    fn visit(&mut self, s: &ForInStmt) {
+        self.visit_for_in_of(&s.left, &s.right, &s.body)
+    }
+}
+
+impl Visit<ForOfStmt> for TdzChecker {
+    fn visit(&mut self, s: &ForOfStmt) {
+        self.visit_for_in_of(&s.left, &s.right, &s.body)
+    }
+}
+
+impl Visit<VarDecl> for TdzChecker {
+    fn visit(&mut self, decl: &VarDecl) {
+        for d in &decl.decls {
+            d.init.visit_with(self);
+            d.name.visit_with(self);
+
+            if decl.kind != VarDeclKind::Var {
+                self.initialize_pat(&d.name);
+            }
+        }
+    }
+}
+
+impl Visit<ClassDecl> for TdzChecker {
+    fn visit(&mut self, c: &ClassDecl) {
+        // `class A extends A {}` is in the temporal dead zone.
+        c.class.visit_with(self);
+        self.initialize(&c.ident.sym);
+    }
+}
+
+impl Visit<Expr> for TdzChecker {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Ident(ref i) => self.check(&i.sym, i.span),
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Prop> for TdzChecker {
+    fn visit(&mut self, p: &Prop) {
+        match *p {
+            Prop::Shorthand(ref i) => self.check(&i.sym, i.span),
+            Prop::Getter(..) | Prop::Setter(..) => self.deferred(|v| p.visit_children(v)),
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<MemberExpr> for TdzChecker {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<AssignExpr> for TdzChecker {
+    fn visit(&mut self, e: &AssignExpr) {
+        match e.left {
+            PatOrExpr::Pat(ref pat) => self.check_pat(pat),
+            PatOrExpr::Expr(..) => {}
+        }
+        e.visit_children(self)
+    }
+}
+
+impl Visit<Function> for TdzChecker {
+    fn visit(&mut self, f: &Function) {
+        self.deferred(|v| {
+            v.with_scope(|v| {
+                v.declare_pat(&f.params, true);
+                f.visit_children(v)
+            })
+        })
+    }
+}
+
+impl Visit<ArrowExpr> for TdzChecker {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.deferred(|v| {
+            v.with_scope(|v| {
+                v.declare_pat(&f.params, true);
+                f.visit_children(v)
+            })
+        })
+    }
+}
+
+impl Visit<Constructor> for TdzChecker {
+    fn visit(&mut self, c: &Constructor) {
+        self.deferred(|v| {
+            v.with_scope(|v| {
+                v.declare_pat(&c.params, true);
+                c.visit_children(v)
+            })
+        })
+    }
+}
+
+impl Visit<ClassProp> for TdzChecker {
+    fn visit(&mut self, p: &ClassProp) {
+        if p.computed {
+            p.key.visit_with(self);
+        }
+        self.deferred(|v| p.value.visit_with(v))
+    }
+}
+
+impl Visit<PrivateProp> for TdzChecker {
+    fn visit(&mut self, p: &PrivateProp) {
+        self.deferred(|v| p.value.visit_with(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+
+    /// Returns the stderr of checking `src`, parsed as a module.
+    fn run(src: &str) -> Option<String> {
+        ::testing::run_test(false, |cm, handler| {
+            HANDLER.set(handler, || {
+                let mut tester = Tester {
+                    cm,
+                    handler,
+                    comments: Default::default(),
+                };
+
+                let module = tester.parse_module("input.js", src)?;
+                check_tdz(&module);
+
+                // Diagnostics are returned only on failure.
+                Err(())
+            })
+        })
+        .err()
+        .map(|stderr| stderr.to_string())
+        .filter(|stderr| !stderr.trim().is_empty())
+    }
+
+    #[test]
+    fn direct_use() {
+        let stderr = run("foo(a);\nlet a = 1;").expect("should warn");
+
+        assert!(
+            stderr.contains("`a` is used before its declaration"),
+            "{}",
+            stderr
+        );
+        assert!(stderr.contains("used here"), "{}", stderr);
+        assert!(stderr.contains("declared here"), "{}", stderr);
+        assert!(stderr.contains("input.js:1:5"), "{}", stderr);
+    }
+
+    #[test]
+    fn use_in_initializer() {
+        let stderr = run("const a = a + 1;").expect("should warn");
+
+        assert!(stderr.contains("`a` is used before its declaration"));
+    }
+
+    #[test]
+    fn closure() {
+        assert_eq!(
+            run("function f() { return a; }
+const g = () => a;
+let a = 1;
+f(); g();"),
+            None
+        );
+    }
+
+    #[test]
+    fn hoisted_function() {
+        assert_eq!(run("f();\nfunction f() {}\nlet a = f();"), None);
+    }
+
+    #[test]
+    fn shadowed() {
+        let stderr = run("let a = 1;\n{ foo(a); let a = 2; }").expect("should warn");
+
+        assert!(stderr.contains("input.js:2:7"), "{}", stderr);
+    }
+}
@@ -0,0 +1,6 @@
+//! Utilities for bundling multiple modules into one.
+
+pub use self::{rename::rename, tree_shake::tree_shake};
+
+mod rename;
+mod tree_shake;
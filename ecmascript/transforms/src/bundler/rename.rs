@@ -0,0 +1,263 @@
+use crate::{
+    hygiene::ops::{Operator, ScopeOp},
+    util::DestructuringFinder,
+};
+use ast::*;
+use hashbrown::HashSet;
+use swc_atoms::JsWord;
+use swc_common::{FoldWith, SyntaxContext, Visit, VisitWith};
+
+/// Renames top-level bindings which collide with those of other modules, so
+/// that `modules` can be concatenated into a single scope.
+///
+/// The syntax context paired with each module should be the one of its
+/// top-level bindings, e.g. a context marked with the mark passed to
+/// [resolver_with_mark](crate::resolver_with_mark).
+///
+/// Bindings of the first module keep their names, and clashing bindings of
+/// later modules get a numeric suffix like `helper1`. References to renamed
+/// bindings are renamed too. A binding is also renamed if its name is used as
+/// a global by any module, so that the global is not shadowed.
+pub fn rename(modules: Vec<(Module, SyntaxContext)>) -> Vec<Module> {
+    let mut used = HashSet::default();
+    let mut declared = HashSet::default();
+    for (module, _) in &modules {
+        module.visit_with(&mut UsedNames {
+            used: &mut used,
+            globals: &mut declared,
+        });
+    }
+
+    modules
+        .into_iter()
+        .map(|(module, ctxt)| {
+            let mut v = TopLevelDecls {
+                ctxt,
+                found: vec![],
+            };
+            module.visit_with(&mut v);
+
+            let mut ops = vec![];
+            for sym in v.found {
+                if declared.insert(sym.clone()) {
+                    continue;
+                }
+
+                let renamed: JsWord = (1..)
+                    .map(|i| JsWord::from(format!("{}{}", sym, i)))
+                    .find(|renamed| !used.contains(renamed))
+                    .unwrap();
+                used.insert(renamed.clone());
+                declared.insert(renamed.clone());
+
+                ops.push(ScopeOp::Rename {
+                    from: (sym, ctxt),
+                    to: renamed,
+                });
+            }
+
+            if ops.is_empty() {
+                module
+            } else {
+                module.fold_with(&mut Operator(&ops))
+            }
+        })
+        .collect()
+}
+
+/// Collects all names used in a module and names of globals.
+struct UsedNames<'a> {
+    used: &'a mut HashSet<JsWord>,
+    /// Identifiers which are not resolved to a binding.
+    globals: &'a mut HashSet<JsWord>,
+}
+
+impl Visit<Ident> for UsedNames<'_> {
+    fn visit(&mut self, i: &Ident) {
+        self.used.insert(i.sym.clone());
+
+        if i.span.ctxt() == SyntaxContext::empty() {
+            self.globals.insert(i.sym.clone());
+        }
+    }
+}
+
+impl Visit<MemberExpr> for UsedNames<'_> {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for UsedNames<'_> {
+    fn visit(&mut self, n: &PropName) {
+        match *n {
+            PropName::Computed(ref e) => e.visit_with(self),
+            PropName::Ident(ref i) => {
+                self.used.insert(i.sym.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects names of bindings with `ctxt`, in declaration order.
+struct TopLevelDecls {
+    ctxt: SyntaxContext,
+    found: Vec<JsWord>,
+}
+
+impl TopLevelDecls {
+    fn add(&mut self, i: &Ident) {
+        if i.span.ctxt() == self.ctxt && !self.found.contains(&i.sym) {
+            self.found.push(i.sym.clone());
+        }
+    }
+}
+
+impl Visit<VarDeclarator> for TopLevelDecls {
+    fn visit(&mut self, d: &VarDeclarator) {
+        let mut found = vec![];
+        d.name
+            .visit_with(&mut DestructuringFinder { found: &mut found });
+        for (sym, span) in found {
+            self.add(&Ident::new(sym, span));
+        }
+
+        d.init.visit_with(self);
+    }
+}
+
+impl Visit<FnDecl> for TopLevelDecls {
+    fn visit(&mut self, f: &FnDecl) {
+        self.add(&f.ident);
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassDecl> for TopLevelDecls {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.add(&c.ident);
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<ImportSpecifier> for TopLevelDecls {
+    fn visit(&mut self, s: &ImportSpecifier) {
+        match *s {
+            ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+            | ImportSpecifier::Default(ImportDefault { ref local, .. })
+            | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => self.add(local),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{resolver_with_mark, tests::Tester};
+    use swc_common::{Mark, DUMMY_SP};
+
+    fn test(inputs: &[&str], expected: &str) {
+        Tester::run(|tester| {
+            let mut modules = vec![];
+            for (i, src) in inputs.iter().enumerate() {
+                let mark = Mark::fresh(Mark::root());
+                let module = tester
+                    .parse_module(&format!("input{}.js", i), src)?
+                    .fold_with(&mut resolver_with_mark(mark));
+
+                modules.push((module, SyntaxContext::empty().apply_mark(mark)));
+            }
+
+            let module = Module {
+                span: DUMMY_SP,
+                body: rename(modules)
+                    .into_iter()
+                    .flat_map(|module| module.body)
+                    .collect(),
+                shebang: None,
+            };
+
+            let actual = tester.print(&module);
+            let expected = {
+                let module = tester.parse_module("expected.js", expected)?;
+                tester.print(&module)
+            };
+            assert_eq!(actual, expected);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn colliding_fn() {
+        test(
+            &[
+                "function helper() { return 1; } use(helper());",
+                "function helper() { return 2; } use(helper());",
+            ],
+            "function helper() { return 1; } use(helper());
+            function helper1() { return 2; } use(helper1());",
+        );
+    }
+
+    #[test]
+    fn colliding_var() {
+        test(
+            &[
+                "var helper = 1; use(helper);",
+                "var helper = 2; var o = { helper, helper: helper }; use(o.helper);",
+                "var { helper } = obj; use(helper);",
+            ],
+            "var helper = 1; use(helper);
+            var helper1 = 2; var o = { helper: helper1, helper: helper1 }; use(o.helper);
+            var { helper: helper2 } = obj; use(helper2);",
+        );
+    }
+
+    #[test]
+    fn skips_used_names() {
+        test(
+            &[
+                "function helper() {} function helper1() {}",
+                "function helper() {} helper();",
+            ],
+            "function helper() {} function helper1() {}
+            function helper2() {} helper2();",
+        );
+    }
+
+    #[test]
+    fn nested_bindings() {
+        test(
+            &[
+                "function helper() {}",
+                "function helper() {} function foo(helper) { return helper; } helper();",
+            ],
+            "function helper() {}
+            function helper1() {} function foo(helper) { return helper; } helper1();",
+        );
+    }
+
+    #[test]
+    fn global() {
+        test(
+            &["var helper = 1; use(helper);", "helper();"],
+            "var helper1 = 1; use(helper1);
+            helper();",
+        );
+    }
+
+    #[test]
+    fn no_collision() {
+        test(
+            &["function a() {} a();", "function b() {} b();"],
+            "function a() {} a();
+            function b() {} b();",
+        );
+    }
+}
@@ -0,0 +1,537 @@
+use crate::{
+    pass::Pass,
+    util::{DestructuringFinder, ExprExt, Id},
+};
+use ast::*;
+use hashbrown::HashSet;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{comments::Comments, util::move_map::MoveMap, BytePos, Fold, Visit, VisitWith};
+
+/// Removes exports which are not in `used_exports`, and top-level
+/// declarations which are not used by remaining code.
+///
+/// A declaration is removed only if evaluating it does not have side effects.
+/// If `comments` is given, calls and `new` expressions annotated with
+/// `/*#__PURE__*/` are assumed to be side-effect free, except their arguments.
+///
+/// Statements other than declarations, imports and re-exports are always
+/// retained, as are declarations used by them.
+///
+/// # Example
+///
+/// ## In (with `used_exports` = `["a"]`)
+///
+/// ```js
+/// function helper() {}
+/// export function a() { return helper(); }
+/// export function b() {}
+/// export const c = /*#__PURE__*/ create();
+/// export const d = register();
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function helper() {}
+/// export function a() { return helper(); }
+/// export const d = register();
+/// ```
+pub fn tree_shake(used_exports: HashSet<JsWord>, comments: Option<&Comments>) -> impl Pass + '_ {
+    TreeShaker {
+        used_exports,
+        comments,
+    }
+}
+
+struct TreeShaker<'a> {
+    used_exports: HashSet<JsWord>,
+    comments: Option<&'a Comments>,
+}
+
+/// A removable part of a module, e.g. a declarator or an export specifier.
+#[derive(Debug, Default)]
+struct Unit {
+    decls: Vec<Id>,
+    refs: Vec<Id>,
+    /// `true` if the unit should be retained even if its bindings are not
+    /// used.
+    root: bool,
+}
+
+impl Fold<Module> for TreeShaker<'_> {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut units = vec![];
+        for item in &module.body {
+            self.add_units(item, &mut units);
+        }
+
+        let mut live = mark_live(&units).into_iter();
+
+        let body = module.body.move_flat_map(|item| shake(item, &mut live));
+        debug_assert_eq!(live.next(), None);
+
+        Module { body, ..module }
+    }
+}
+
+impl TreeShaker<'_> {
+    fn is_used_export(&self, sym: &JsWord) -> bool {
+        self.used_exports.contains(sym)
+    }
+
+    /// Adds units of `item`. [shake] should consume the same number of units.
+    fn add_units(&self, item: &ModuleItem, units: &mut Vec<Unit>) {
+        match *item {
+            ModuleItem::Stmt(Stmt::Decl(ref decl)) => self.add_decl_units(decl, false, units),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                self.add_decl_units(decl, true, units)
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                ref specifiers,
+                src: None,
+                ..
+            })) => {
+                for s in specifiers {
+                    units.push(match *s {
+                        ExportSpecifier::Named(NamedExportSpecifier {
+                            ref orig,
+                            ref exported,
+                            ..
+                        }) => Unit {
+                            decls: vec![],
+                            refs: vec![id(orig)],
+                            root: self.is_used_export(&exported.as_ref().unwrap_or(orig).sym),
+                        },
+                        _ => Unit {
+                            root: true,
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ref expr)) => units.push(Unit {
+                decls: vec![],
+                refs: refs(&expr.expr),
+                root: self.is_used_export(&js_word!("default")) || !self.is_pure(&expr.expr),
+            }),
+
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ref export)) => {
+                let used = self.is_used_export(&js_word!("default"));
+
+                units.push(match export.decl {
+                    DefaultDecl::Fn(FnExpr {
+                        ref ident,
+                        ref function,
+                    }) => Unit {
+                        decls: ident.iter().map(id).collect(),
+                        refs: refs(function),
+                        root: used,
+                    },
+                    DefaultDecl::Class(ClassExpr {
+                        ref ident,
+                        ref class,
+                    }) => Unit {
+                        decls: ident.iter().map(id).collect(),
+                        refs: refs(class),
+                        root: used || self.class_has_side_effects(class),
+                    },
+                    _ => Unit {
+                        root: true,
+                        ..Default::default()
+                    },
+                })
+            }
+
+            _ => units.push(Unit {
+                decls: vec![],
+                refs: refs(item),
+                root: true,
+            }),
+        }
+    }
+
+    fn add_decl_units(&self, decl: &Decl, exported: bool, units: &mut Vec<Unit>) {
+        match *decl {
+            Decl::Fn(FnDecl {
+                ref ident,
+                ref function,
+                ..
+            }) => units.push(Unit {
+                decls: vec![id(ident)],
+                refs: refs(function),
+                root: exported && self.is_used_export(&ident.sym),
+            }),
+
+            Decl::Class(ClassDecl {
+                ref ident,
+                ref class,
+                ..
+            }) => units.push(Unit {
+                decls: vec![id(ident)],
+                refs: refs(class),
+                root: (exported && self.is_used_export(&ident.sym))
+                    || self.class_has_side_effects(class),
+            }),
+
+            Decl::Var(VarDecl { ref decls, .. }) => {
+                for decl in decls {
+                    let mut found = vec![];
+                    decl.name
+                        .visit_with(&mut DestructuringFinder { found: &mut found });
+
+                    let is_pure = match decl.name {
+                        // Destructuring may invoke getters.
+                        Pat::Ident(..) => decl.init.as_ref().map_or(true, |e| self.is_pure(e)),
+                        _ => false,
+                    };
+
+                    units.push(Unit {
+                        root: !is_pure
+                            || (exported && found.iter().any(|(sym, _)| self.is_used_export(sym))),
+                        decls: found
+                            .into_iter()
+                            .map(|(sym, span)| (sym, span.ctxt()))
+                            .collect(),
+                        refs: refs(&decl.init),
+                    })
+                }
+            }
+
+            _ => units.push(Unit {
+                decls: vec![],
+                refs: refs(decl),
+                root: true,
+            }),
+        }
+    }
+
+    fn is_pure(&self, e: &Expr) -> bool {
+        match *e {
+            Expr::Call(CallExpr { span, ref args, .. })
+            | Expr::New(NewExpr {
+                span,
+                args: Some(ref args),
+                ..
+            }) if self.has_pure_annotation(span.lo()) => args
+                .iter()
+                .all(|arg| arg.spread.is_none() && self.is_pure(&arg.expr)),
+            Expr::New(NewExpr {
+                span, args: None, ..
+            }) if self.has_pure_annotation(span.lo()) => true,
+
+            Expr::Paren(ParenExpr { ref expr, .. }) => self.is_pure(expr),
+
+            _ => !e.may_have_side_effects(),
+        }
+    }
+
+    fn class_has_side_effects(&self, class: &Class) -> bool {
+        if !class.decorators.is_empty() {
+            return true;
+        }
+        if let Some(ref super_class) = class.super_class {
+            if !self.is_pure(super_class) {
+                return true;
+            }
+        }
+
+        class.body.iter().any(|member| match *member {
+            ClassMember::Method(ClassMethod {
+                key: PropName::Computed(ref e),
+                ..
+            }) => !self.is_pure(&e.expr),
+            ClassMember::ClassProp(ClassProp {
+                ref key,
+                computed,
+                is_static,
+                ref value,
+                ..
+            }) => {
+                (computed && !self.is_pure(key))
+                    || (is_static && value.as_ref().map_or(false, |v| !self.is_pure(v)))
+            }
+            _ => false,
+        })
+    }
+
+    fn has_pure_annotation(&self, pos: BytePos) -> bool {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return false,
+        };
+
+        comments.leading_comments(pos).map_or(false, |cmts| {
+            cmts.iter().any(|cmt| {
+                let text = cmt.text.trim();
+                text == "#__PURE__" || text == "@__PURE__"
+            })
+        })
+    }
+}
+
+/// Removes dead units of `item`.
+fn shake<I>(item: ModuleItem, live: &mut I) -> Option<ModuleItem>
+where
+    I: Iterator<Item = bool>,
+{
+    let mut next = || live.next().unwrap();
+
+    match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => {
+            let decls = shake_declarators(var.decls, &mut next);
+            if decls.is_empty() {
+                return None;
+            }
+
+            Some(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                decls,
+                ..var
+            }))))
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span,
+            decl: Decl::Var(var),
+        })) => {
+            let decls = shake_declarators(var.decls, &mut next);
+            if decls.is_empty() {
+                return None;
+            }
+
+            Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Var(VarDecl { decls, ..var }),
+            })))
+        }
+
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+            span,
+            specifiers,
+            src: None,
+        })) => {
+            if specifiers.is_empty() {
+                return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                    NamedExport {
+                        span,
+                        specifiers,
+                        src: None,
+                    },
+                )));
+            }
+
+            let specifiers: Vec<_> = specifiers.into_iter().filter(|_| next()).collect();
+            if specifiers.is_empty() {
+                return None;
+            }
+
+            Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                NamedExport {
+                    span,
+                    specifiers,
+                    src: None,
+                },
+            )))
+        }
+
+        _ => {
+            if next() {
+                Some(item)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn shake_declarators<F>(decls: Vec<VarDeclarator>, next: &mut F) -> Vec<VarDeclarator>
+where
+    F: FnMut() -> bool,
+{
+    decls.into_iter().filter(|_| next()).collect()
+}
+
+/// Returns liveness of each unit.
+fn mark_live(units: &[Unit]) -> Vec<bool> {
+    let mut live = vec![false; units.len()];
+    let mut used: HashSet<&Id> = HashSet::default();
+
+    loop {
+        let mut changed = false;
+
+        for (i, unit) in units.iter().enumerate() {
+            if live[i] || !(unit.root || unit.decls.iter().any(|id| used.contains(id))) {
+                continue;
+            }
+
+            live[i] = true;
+            changed = true;
+            used.extend(unit.refs.iter());
+        }
+
+        if !changed {
+            return live;
+        }
+    }
+}
+
+fn id(i: &Ident) -> Id {
+    (i.sym.clone(), i.span.ctxt())
+}
+
+/// Returns identifiers referenced by `node`.
+fn refs<N>(node: &N) -> Vec<Id>
+where
+    N: VisitWith<RefFinder>,
+{
+    let mut v = RefFinder { found: vec![] };
+    node.visit_with(&mut v);
+    v.found
+}
+
+struct RefFinder {
+    found: Vec<Id>,
+}
+
+impl Visit<Ident> for RefFinder {
+    fn visit(&mut self, i: &Ident) {
+        self.found.push(id(i));
+    }
+}
+
+impl Visit<MemberExpr> for RefFinder {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for RefFinder {
+    fn visit(&mut self, n: &PropName) {
+        if let PropName::Computed(ref e) = *n {
+            e.visit_with(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use swc_common::FoldWith;
+
+    fn test(used_exports: &[&str], src: &str, expected: &str) {
+        Tester::run(|tester| {
+            let module = tester.parse_module("input.js", src)?;
+            let module = module.fold_with(&mut tree_shake(
+                used_exports.iter().map(|&s| s.into()).collect(),
+                Some(&tester.comments),
+            ));
+
+            let actual = tester.print(&module);
+            let expected = {
+                let module = tester.parse_module("expected.js", expected)?;
+                tester.print(&module)
+            };
+            assert_eq!(actual, expected);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn unused_pure_export() {
+        test(
+            &["a"],
+            "export const a = 1; export const b = 2; export function c() {}",
+            "export const a = 1;",
+        );
+    }
+
+    #[test]
+    fn unused_export_with_side_effects() {
+        test(
+            &["a"],
+            "export const a = 1; export const b = register(); export const c = foo.bar;",
+            "export const a = 1; export const b = register(); export const c = foo.bar;",
+        );
+    }
+
+    #[test]
+    fn pure_annotation() {
+        test(
+            &[],
+            "export const a = /*#__PURE__*/ create(); export const b = /*#__PURE__*/ create(f());
+            export const c = /*#__PURE__*/ new Foo(1);",
+            "export const b = /*#__PURE__*/ create(f());",
+        );
+    }
+
+    #[test]
+    fn transitive_dependencies() {
+        test(
+            &["a"],
+            "function helper() { return dep(); }
+            function dep() { return 1; }
+            function unused() { return helper(); }
+            const value = helper();
+            export function a() { return helper(); }
+            export function b() { return unused(); }",
+            "function helper() { return dep(); }
+            function dep() { return 1; }
+            const value = helper();
+            export function a() { return helper(); }",
+        );
+    }
+
+    #[test]
+    fn dependency_of_side_effect() {
+        test(
+            &[],
+            "function helper() {} function unused() {} helper();",
+            "function helper() {} helper();",
+        );
+    }
+
+    #[test]
+    fn export_specifiers() {
+        test(
+            &["x"],
+            "const a = 1, b = 2; const c = 3; export { a as x, b as y, c };",
+            "const a = 1; export { a as x };",
+        );
+    }
+
+    #[test]
+    fn export_default() {
+        test(&[], "export default function () {}", "");
+        test(&[], "export default 1;", "");
+        test(&[], "export default foo();", "export default foo();");
+        test(
+            &["default"],
+            "export default function () {}",
+            "export default function () {}",
+        );
+    }
+
+    #[test]
+    fn classes() {
+        test(
+            &[],
+            "export class A {} export class B extends foo() {} class C { [foo()]() {} }",
+            "export class B extends foo() {} class C { [foo()]() {} }",
+        );
+    }
+
+    #[test]
+    fn imports_are_retained() {
+        test(
+            &[],
+            "import foo from 'foo'; export const a = foo;",
+            "import foo from 'foo';",
+        );
+    }
+}
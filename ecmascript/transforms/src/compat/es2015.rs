@@ -1,11 +1,25 @@
 pub use self::{
-    arrow::arrow, block_scoped_fn::BlockScopedFns, block_scoping::block_scoping, classes::Classes,
-    computed_props::computed_properties, destructuring::destructuring,
-    duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::InstanceOf, parameters::parameters, shorthand_property::Shorthand, spread::spread,
-    sticky_regex::StickyRegex, template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
+    arrow::arrow,
+    block_scoped_fn::BlockScopedFns,
+    block_scoping::block_scoping,
+    classes::{classes, Classes},
+    computed_props::computed_properties,
+    destructuring::destructuring,
+    duplicate_keys::duplicate_keys,
+    for_of::for_of,
+    function_name::function_name,
+    instanceof::InstanceOf,
+    new_target::new_target,
+    object_super::object_super,
+    parameters::parameters,
+    shorthand_property::Shorthand,
+    spread::spread,
+    sticky_regex::StickyRegex,
+    template_literal::{template_literal, TemplateLiteral},
+    typeof_symbol::TypeOfSymbol,
+    unicode_regex::UnicodeRegex,
 };
-use crate::pass::Pass;
+use crate::pass::{FeatureFlags, Optional, Pass, SharedFeatures};
 use ast::{Expr, Module};
 use serde::Deserialize;
 #[cfg(test)]
@@ -21,19 +35,23 @@ mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
+mod new_target;
+mod object_super;
 mod parameters;
 mod shorthand_property;
 pub mod spread;
 mod sticky_regex;
-mod template_literal;
+pub mod template_literal;
 mod typeof_symbol;
+mod unicode_regex;
 
-fn exprs() -> impl Pass {
+fn exprs(keep_unicode_regex: bool, keep_sticky_regex: bool) -> impl Pass {
     chain_at!(
         Expr,
         arrow(),
         duplicate_keys(),
-        StickyRegex,
+        Optional::new(UnicodeRegex, !keep_unicode_regex),
+        Optional::new(StickyRegex, !keep_sticky_regex),
         InstanceOf,
         TypeOfSymbol,
         Shorthand,
@@ -42,23 +60,32 @@ fn exprs() -> impl Pass {
 
 /// Compiles es2015 to es5.
 pub fn es2015(c: Config) -> impl Pass {
-    chain_at!(
+    // Passes in this chain don't introduce the features checked below.
+    let features = SharedFeatures::default();
+
+    features.scope(chain_at!(
         Module,
         BlockScopedFns,
-        TemplateLiteral::default(),
-        Classes::default(),
-        spread(c.spread),
+        features.skip_unless(
+            FeatureFlags::TEMPLATE_LITERALS,
+            template_literal(c.template_literal)
+        ),
+        new_target(),
+        object_super(),
+        features.skip_unless(FeatureFlags::CLASSES, classes(c.classes)),
+        features.skip_unless(FeatureFlags::SPREAD, spread(c.spread)),
         function_name(),
-        exprs(),
+        exprs(c.keep_unicode_regex, c.keep_sticky_regex),
         parameters(),
-        for_of(c.for_of),
+        features.skip_unless(FeatureFlags::FOR_OF, for_of(c.for_of)),
         computed_properties(),
-        destructuring(c.destructuring),
+        features.skip_unless(FeatureFlags::DESTRUCTURING, destructuring(c.destructuring)),
         block_scoping(),
-    )
+    ))
 }
 
 #[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Config {
     #[serde(flatten)]
     pub for_of: for_of::Config,
@@ -68,6 +95,20 @@ pub struct Config {
 
     #[serde(flatten)]
     pub spread: spread::Config,
+
+    #[serde(flatten)]
+    pub classes: classes::Config,
+
+    #[serde(flatten)]
+    pub template_literal: template_literal::Config,
+
+    /// Keep regex literals with the `u` flag, for targets which support it.
+    #[serde(default)]
+    pub keep_unicode_regex: bool,
+
+    /// Keep regex literals with the `y` flag, for targets which support it.
+    #[serde(default)]
+    pub keep_sticky_regex: bool,
 }
 
 #[cfg(test)]
@@ -95,8 +136,8 @@ export var Foo = function() {
 
     _createClass(Foo, [{
             key: 'func',
-            value: function func(a, param) {
-                var b = param === void 0 ? Date.now() : param;
+            value: function func(a) {
+                var b = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : Date.now();
                 return {
                     a: a
                 };
@@ -194,6 +235,26 @@ function foo(scope) {
     //         ""
     //     );
 
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| es2015(Default::default()),
+        regex_flags,
+        r"var a = /x/y, b = /\u{1F600}/u;",
+        r"var a = new RegExp('x', 'y'), b = /(?:\uD83D\uDE00)/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| es2015(Config {
+            keep_unicode_regex: true,
+            keep_sticky_regex: true,
+            ..Default::default()
+        }),
+        regex_flags_supported,
+        r"var a = /x/y, b = /\u{1F600}/u;",
+        r"var a = /x/y, b = /\u{1F600}/u;"
+    );
+
     test!(
         ::swc_ecma_parser::Syntax::default(),
         |_| es2015(Default::default()),
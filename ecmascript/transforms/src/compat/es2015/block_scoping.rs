@@ -1,22 +1,269 @@
-use crate::{pass::Pass, util::undefined};
+use crate::{
+    pass::Pass,
+    util::{undefined, DestructuringFinder, ExprFactory, StmtLike},
+};
 use ast::*;
-use swc_common::{Fold, FoldWith, Spanned};
-
+use std::mem;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, Span, Spanned, SyntaxContext, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-transform-block-scoping`
+///
+/// If a closure in a loop captures a block scoped variable, the loop body is
+/// moved into a function so that each iteration gets its own binding.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// outer: for (let i = 0; i < 3; i++) {
+///     fns.push(() => i);
+///     if (i === 1) continue outer;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _loop = function (i) {
+///     fns.push(() => i);
+///     if (i === 1) return;
+/// };
+/// outer: for (var i = 0; i < 3; i++) _loop(i);
+/// ```
+///
+/// `break`, `continue` and `return` in the moved body are converted to
+/// return values of the function, which are dispatched after the call.
 pub fn block_scoping() -> impl Pass {
-    BlockScoping {
-        in_loop_body: false,
-    }
+    BlockScoping::new(false)
 }
 
 struct BlockScoping {
     in_loop_body: bool,
+    /// Declarations of loop functions which should be inserted before the
+    /// current statement.
+    pending: Vec<Stmt>,
+}
+
+impl BlockScoping {
+    fn new(in_loop_body: bool) -> Self {
+        BlockScoping {
+            in_loop_body,
+            pending: vec![],
+        }
+    }
+
+    fn fold_loop_body(&mut self, body: Box<Stmt>) -> Box<Stmt> {
+        let mut folder = BlockScoping::new(true);
+        let body = body.fold_with(&mut folder);
+
+        if folder.pending.is_empty() {
+            return body;
+        }
+
+        let mut stmts = folder.pending;
+        stmts.push(*body);
+        box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        })
+    }
+
+    /// `labels`: Labels of `stmt`.
+    fn fold_loop(&mut self, stmt: Stmt, labels: &mut Vec<JsWord>) -> Stmt {
+        match stmt {
+            Stmt::Labeled(LabeledStmt { span, label, body }) => {
+                labels.push(label.sym.clone());
+                let body = box self.fold_loop(*body, labels);
+
+                Stmt::Labeled(LabeledStmt { span, label, body })
+            }
+
+            Stmt::For(..)
+            | Stmt::ForIn(..)
+            | Stmt::ForOf(..)
+            | Stmt::While(..)
+            | Stmt::DoWhile(..) => {
+                let params = captured_loop_vars(&stmt);
+                let mut stmt = stmt.fold_children(self);
+
+                if let Some(params) = params {
+                    let body = match stmt {
+                        Stmt::For(ForStmt { ref mut body, .. })
+                        | Stmt::ForIn(ForInStmt { ref mut body, .. })
+                        | Stmt::ForOf(ForOfStmt { ref mut body, .. })
+                        | Stmt::While(WhileStmt { ref mut body, .. })
+                        | Stmt::DoWhile(DoWhileStmt { ref mut body, .. }) => body,
+                        _ => unreachable!(),
+                    };
+
+                    let old = mem::replace(&mut **body, Stmt::Empty(EmptyStmt { span: DUMMY_SP }));
+                    **body = self.convert_loop_body(old, params, labels);
+                }
+
+                stmt
+            }
+
+            _ => stmt.fold_children(self),
+        }
+    }
+
+    /// Moves `body` into a function and returns the statement which calls it.
+    fn convert_loop_body(&mut self, body: Stmt, params: Vec<Ident>, labels: &[JsWord]) -> Stmt {
+        let span = body.span();
+
+        let mut flow = FlowRewriter {
+            labels,
+            inner_labels: vec![],
+            in_loop: false,
+            in_switch: false,
+            has_break: false,
+            has_return: false,
+            jumps: vec![],
+        };
+        let stmts = match body {
+            Stmt::Block(BlockStmt { stmts, .. }) => stmts,
+            body => vec![body],
+        }
+        .fold_with(&mut flow);
+
+        let loop_fn = private_ident!("_loop");
+        self.pending.push(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(loop_fn.clone()),
+                init: Some(box Expr::Fn(FnExpr {
+                    ident: None,
+                    function: Function {
+                        span: DUMMY_SP,
+                        params: params.iter().cloned().map(Pat::Ident).collect(),
+                        decorators: Default::default(),
+                        body: Some(BlockStmt { span, stmts }),
+                        is_generator: false,
+                        is_async: false,
+                        type_params: Default::default(),
+                        return_type: Default::default(),
+                    },
+                })),
+                definite: false,
+            }],
+        })));
+
+        let call = box Expr::Call(CallExpr {
+            span,
+            callee: loop_fn.as_callee(),
+            args: params.into_iter().map(|i| i.as_arg()).collect(),
+            type_args: Default::default(),
+        });
+
+        if !flow.has_break && !flow.has_return && flow.jumps.is_empty() {
+            return Stmt::Expr(call);
+        }
+
+        let ret = private_ident!("_ret");
+        let mut stmts = vec![Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ret.clone()),
+                init: Some(call),
+                definite: false,
+            }],
+        }))];
+
+        // if (_ret === "break") break;
+        if flow.has_break {
+            stmts.push(Stmt::If(IfStmt {
+                span: DUMMY_SP,
+                test: box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: box Expr::Ident(ret.clone()),
+                    op: op!("==="),
+                    right: box Expr::Lit(Lit::Str(quote_str!("break"))),
+                }),
+                cons: box Stmt::Break(BreakStmt {
+                    span: DUMMY_SP,
+                    label: None,
+                }),
+                alt: None,
+            }));
+        }
+
+        // switch (_ret) { case "continue|outer": continue outer; }
+        if !flow.jumps.is_empty() {
+            stmts.push(Stmt::Switch(SwitchStmt {
+                span: DUMMY_SP,
+                discriminant: box Expr::Ident(ret.clone()),
+                cases: flow
+                    .jumps
+                    .into_iter()
+                    .map(|jump| SwitchCase {
+                        span: DUMMY_SP,
+                        test: Some(box Expr::Lit(Lit::Str(quote_str!(jump.sentinel())))),
+                        cons: vec![jump.into_stmt()],
+                    })
+                    .collect(),
+            }));
+        }
+
+        // if (typeof _ret === "object") return _ret.v;
+        if flow.has_return {
+            stmts.push(Stmt::If(IfStmt {
+                span: DUMMY_SP,
+                test: box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: box Expr::Unary(UnaryExpr {
+                        span: DUMMY_SP,
+                        op: op!("typeof"),
+                        arg: box Expr::Ident(ret.clone()),
+                    }),
+                    op: op!("==="),
+                    right: box Expr::Lit(Lit::Str(quote_str!("object"))),
+                }),
+                cons: box Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box ret.member(quote_ident!("v"))),
+                }),
+                alt: None,
+            }));
+        }
+
+        Stmt::Block(BlockStmt { span, stmts })
+    }
+}
+
+impl<T> Fold<Vec<T>> for BlockScoping
+where
+    T: StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+            buf.extend(self.pending.drain(..).map(T::from_stmt));
+            buf.push(stmt);
+        }
+
+        buf
+    }
+}
+
+impl Fold<Stmt> for BlockScoping {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        self.fold_loop(stmt, &mut vec![])
+    }
 }
 
 impl Fold<DoWhileStmt> for BlockScoping {
     fn fold(&mut self, node: DoWhileStmt) -> DoWhileStmt {
-        let body = node
-            .body
-            .fold_with(&mut BlockScoping { in_loop_body: true });
+        let body = self.fold_loop_body(node.body);
 
         let test = node.test.fold_with(self);
 
@@ -26,13 +273,9 @@ impl Fold<DoWhileStmt> for BlockScoping {
 
 impl Fold<WhileStmt> for BlockScoping {
     fn fold(&mut self, node: WhileStmt) -> WhileStmt {
-        let body = node
-            .body
-            .fold_with(&mut BlockScoping { in_loop_body: true });
+        let body = self.fold_loop_body(node.body);
 
-        let test = node.test.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
+        let test = node.test.fold_with(&mut BlockScoping::new(false));
 
         WhileStmt { body, test, ..node }
     }
@@ -40,19 +283,11 @@ impl Fold<WhileStmt> for BlockScoping {
 
 impl Fold<ForStmt> for BlockScoping {
     fn fold(&mut self, node: ForStmt) -> ForStmt {
-        let body = node
-            .body
-            .fold_with(&mut BlockScoping { in_loop_body: true });
+        let body = self.fold_loop_body(node.body);
 
-        let init = node.init.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
-        let test = node.test.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
-        let update = node.update.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
+        let init = node.init.fold_with(&mut BlockScoping::new(false));
+        let test = node.test.fold_with(&mut BlockScoping::new(false));
+        let update = node.update.fold_with(&mut BlockScoping::new(false));
 
         ForStmt {
             init,
@@ -66,16 +301,10 @@ impl Fold<ForStmt> for BlockScoping {
 
 impl Fold<ForOfStmt> for BlockScoping {
     fn fold(&mut self, node: ForOfStmt) -> ForOfStmt {
-        let body = node
-            .body
-            .fold_with(&mut BlockScoping { in_loop_body: true });
+        let body = self.fold_loop_body(node.body);
 
-        let left = node.left.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
-        let right = node.right.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
+        let left = node.left.fold_with(&mut BlockScoping::new(false));
+        let right = node.right.fold_with(&mut BlockScoping::new(false));
 
         ForOfStmt {
             left,
@@ -88,16 +317,10 @@ impl Fold<ForOfStmt> for BlockScoping {
 
 impl Fold<ForInStmt> for BlockScoping {
     fn fold(&mut self, node: ForInStmt) -> ForInStmt {
-        let body = node
-            .body
-            .fold_with(&mut BlockScoping { in_loop_body: true });
+        let body = self.fold_loop_body(node.body);
 
-        let left = node.left.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
-        let right = node.right.fold_with(&mut BlockScoping {
-            in_loop_body: false,
-        });
+        let left = node.left.fold_with(&mut BlockScoping::new(false));
+        let right = node.right.fold_with(&mut BlockScoping::new(false));
 
         ForInStmt {
             left,
@@ -110,9 +333,7 @@ impl Fold<ForInStmt> for BlockScoping {
 
 impl Fold<Function> for BlockScoping {
     fn fold(&mut self, f: Function) -> Function {
-        let f = f.fold_children(&mut BlockScoping {
-            in_loop_body: false,
-        });
+        let f = f.fold_children(&mut BlockScoping::new(false));
 
         f
     }
@@ -120,9 +341,7 @@ impl Fold<Function> for BlockScoping {
 
 impl Fold<ArrowExpr> for BlockScoping {
     fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
-        let f = f.fold_children(&mut BlockScoping {
-            in_loop_body: false,
-        });
+        let f = f.fold_children(&mut BlockScoping::new(false));
 
         f
     }
@@ -159,6 +378,438 @@ impl Fold<Module> for BlockScoping {
     }
 }
 
+/// Returns parameters of the loop function if `stmt` is a loop which should be
+/// converted, that is, a closure in the body captures a block scoped variable.
+fn captured_loop_vars(stmt: &Stmt) -> Option<Vec<Ident>> {
+    let (head, body) = match *stmt {
+        Stmt::For(ForStmt {
+            init: Some(VarDeclOrExpr::VarDecl(ref v)),
+            ref body,
+            ..
+        })
+        | Stmt::ForIn(ForInStmt {
+            left: VarDeclOrPat::VarDecl(ref v),
+            ref body,
+            ..
+        })
+        | Stmt::ForOf(ForOfStmt {
+            left: VarDeclOrPat::VarDecl(ref v),
+            ref body,
+            ..
+        }) if v.kind != VarDeclKind::Var => (Some(v), body),
+        Stmt::For(ForStmt { ref body, .. })
+        | Stmt::ForIn(ForInStmt { ref body, .. })
+        | Stmt::ForOf(ForOfStmt { ref body, .. })
+        | Stmt::While(WhileStmt { ref body, .. })
+        | Stmt::DoWhile(DoWhileStmt { ref body, .. }) => (None, body),
+        _ => return None,
+    };
+
+    let mut params = vec![];
+    if let Some(head) = head {
+        head.decls
+            .visit_with(&mut DestructuringFinder { found: &mut params });
+    }
+
+    let mut decls = BodyDecls {
+        found: vec![],
+        has_var: false,
+        in_nested_loop: false,
+    };
+    body.visit_with(&mut decls);
+    // Moving `var` into a function changes its scope.
+    if decls.has_var {
+        return None;
+    }
+
+    let mut v = BodyAnalyzer {
+        names: params
+            .iter()
+            .chain(decls.found.iter())
+            .map(|&(ref sym, span)| (sym.clone(), span.ctxt()))
+            .collect(),
+        params: params
+            .iter()
+            .map(|&(ref sym, span)| (sym.clone(), span.ctxt()))
+            .collect(),
+        in_closure: false,
+        in_fn: false,
+        captured: false,
+        bail: false,
+    };
+    body.visit_with(&mut v);
+
+    if !v.captured || v.bail {
+        return None;
+    }
+
+    Some(
+        params
+            .into_iter()
+            .map(|(sym, span)| Ident::new(sym, span))
+            .collect(),
+    )
+}
+
+/// Collects block scoped variables declared in a loop body.
+///
+/// Variables of nested loops are ignored as those loops are converted
+/// separately.
+struct BodyDecls {
+    found: Vec<(JsWord, Span)>,
+    has_var: bool,
+    in_nested_loop: bool,
+}
+
+impl Visit<Stmt> for BodyDecls {
+    fn visit(&mut self, stmt: &Stmt) {
+        match *stmt {
+            Stmt::For(..)
+            | Stmt::ForIn(..)
+            | Stmt::ForOf(..)
+            | Stmt::While(..)
+            | Stmt::DoWhile(..) => {
+                let in_nested_loop = self.in_nested_loop;
+                self.in_nested_loop = true;
+                stmt.visit_children(self);
+                self.in_nested_loop = in_nested_loop;
+            }
+            _ => stmt.visit_children(self),
+        }
+    }
+}
+
+impl Visit<VarDecl> for BodyDecls {
+    fn visit(&mut self, v: &VarDecl) {
+        if v.kind == VarDeclKind::Var {
+            self.has_var = true;
+        } else if !self.in_nested_loop {
+            v.decls.visit_with(&mut DestructuringFinder {
+                found: &mut self.found,
+            });
+        }
+
+        for decl in &v.decls {
+            decl.init.visit_with(self);
+        }
+    }
+}
+
+impl Visit<Function> for BodyDecls {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for BodyDecls {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+struct BodyAnalyzer {
+    /// Block scoped variables of the loop.
+    names: Vec<(JsWord, SyntaxContext)>,
+    /// Variables declared in the head of the loop.
+    params: Vec<(JsWord, SyntaxContext)>,
+    in_closure: bool,
+    /// True if `this` and `arguments` refer to a nested function.
+    in_fn: bool,
+    /// Set if a closure uses a block scoped variable.
+    captured: bool,
+    /// Set if the body cannot be moved into a function.
+    bail: bool,
+}
+
+impl BodyAnalyzer {
+    fn is_param(&self, pat: &Pat) -> bool {
+        let mut found = vec![];
+        pat.visit_with(&mut DestructuringFinder { found: &mut found });
+        found
+            .iter()
+            .any(|&(ref sym, span)| self.params.contains(&(sym.clone(), span.ctxt())))
+    }
+
+    fn is_param_ident(&self, i: &Ident) -> bool {
+        self.params.contains(&(i.sym.clone(), i.span.ctxt()))
+    }
+}
+
+impl Visit<Ident> for BodyAnalyzer {
+    fn visit(&mut self, i: &Ident) {
+        if self.in_closure && self.names.contains(&(i.sym.clone(), i.span.ctxt())) {
+            self.captured = true;
+        }
+
+        if !self.in_fn && i.sym == js_word!("arguments") {
+            self.bail = true;
+        }
+    }
+}
+
+impl Visit<MemberExpr> for BodyAnalyzer {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<Function> for BodyAnalyzer {
+    fn visit(&mut self, f: &Function) {
+        let (in_closure, in_fn) = (self.in_closure, self.in_fn);
+        self.in_closure = true;
+        self.in_fn = true;
+        f.visit_children(self);
+        self.in_closure = in_closure;
+        self.in_fn = in_fn;
+    }
+}
+
+impl Visit<ArrowExpr> for BodyAnalyzer {
+    fn visit(&mut self, f: &ArrowExpr) {
+        let in_closure = self.in_closure;
+        self.in_closure = true;
+        f.visit_children(self);
+        self.in_closure = in_closure;
+    }
+}
+
+impl Visit<ThisExpr> for BodyAnalyzer {
+    fn visit(&mut self, _: &ThisExpr) {
+        if !self.in_fn {
+            self.bail = true;
+        }
+    }
+}
+
+impl Visit<Super> for BodyAnalyzer {
+    fn visit(&mut self, _: &Super) {
+        if !self.in_fn {
+            self.bail = true;
+        }
+    }
+}
+
+impl Visit<MetaPropExpr> for BodyAnalyzer {
+    fn visit(&mut self, _: &MetaPropExpr) {
+        if !self.in_fn {
+            self.bail = true;
+        }
+    }
+}
+
+impl Visit<YieldExpr> for BodyAnalyzer {
+    fn visit(&mut self, e: &YieldExpr) {
+        if !self.in_fn {
+            self.bail = true;
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<AwaitExpr> for BodyAnalyzer {
+    fn visit(&mut self, e: &AwaitExpr) {
+        if !self.in_closure {
+            self.bail = true;
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<AssignExpr> for BodyAnalyzer {
+    fn visit(&mut self, e: &AssignExpr) {
+        // Changes to the variable would not be visible to the next iteration.
+        let reassigned = match e.left {
+            PatOrExpr::Pat(ref pat) => self.is_param(pat),
+            PatOrExpr::Expr(box Expr::Ident(ref i)) => self.is_param_ident(i),
+            _ => false,
+        };
+        if reassigned {
+            self.bail = true;
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UpdateExpr> for BodyAnalyzer {
+    fn visit(&mut self, e: &UpdateExpr) {
+        match *e.arg {
+            Expr::Ident(ref i) if self.is_param_ident(i) => self.bail = true,
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+/// A jump to a label outside of the loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Jump {
+    Break(JsWord),
+    Continue(JsWord),
+}
+
+impl Jump {
+    /// Value returned from the loop function.
+    fn sentinel(&self) -> String {
+        match *self {
+            Jump::Break(ref label) => format!("break|{}", label),
+            Jump::Continue(ref label) => format!("continue|{}", label),
+        }
+    }
+
+    fn into_stmt(self) -> Stmt {
+        match self {
+            Jump::Break(label) => Stmt::Break(BreakStmt {
+                span: DUMMY_SP,
+                label: Some(quote_ident!(label)),
+            }),
+            Jump::Continue(label) => Stmt::Continue(ContinueStmt {
+                span: DUMMY_SP,
+                label: Some(quote_ident!(label)),
+            }),
+        }
+    }
+}
+
+/// Converts `break`, `continue` and `return` in a loop body into return values
+/// of the loop function.
+struct FlowRewriter<'a> {
+    /// Labels of the loop.
+    labels: &'a [JsWord],
+    /// Labels defined in the body.
+    inner_labels: Vec<JsWord>,
+    in_loop: bool,
+    in_switch: bool,
+    has_break: bool,
+    has_return: bool,
+    jumps: Vec<Jump>,
+}
+
+impl<'a> FlowRewriter<'a> {
+    fn is_own_label(&self, label: &Ident) -> bool {
+        !self.inner_labels.contains(&label.sym) && self.labels.contains(&label.sym)
+    }
+
+    fn is_outer_label(&self, label: &Ident) -> bool {
+        !self.inner_labels.contains(&label.sym) && !self.labels.contains(&label.sym)
+    }
+
+    fn jump(&mut self, span: Span, jump: Jump) -> Stmt {
+        let sentinel = jump.sentinel();
+        if !self.jumps.contains(&jump) {
+            self.jumps.push(jump);
+        }
+
+        Stmt::Return(ReturnStmt {
+            span,
+            arg: Some(box Expr::Lit(Lit::Str(quote_str!(span, sentinel)))),
+        })
+    }
+}
+
+impl<'a> Fold<Stmt> for FlowRewriter<'a> {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        match stmt {
+            Stmt::Break(BreakStmt { span, ref label })
+                if match *label {
+                    None => !self.in_loop && !self.in_switch,
+                    Some(ref label) => self.is_own_label(label),
+                } =>
+            {
+                self.has_break = true;
+                Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(box Expr::Lit(Lit::Str(quote_str!(span, "break")))),
+                })
+            }
+            Stmt::Break(BreakStmt {
+                span,
+                label: Some(ref label),
+            }) if self.is_outer_label(label) => self.jump(span, Jump::Break(label.sym.clone())),
+
+            Stmt::Continue(ContinueStmt { span, ref label })
+                if match *label {
+                    None => !self.in_loop,
+                    Some(ref label) => self.is_own_label(label),
+                } =>
+            {
+                Stmt::Return(ReturnStmt { span, arg: None })
+            }
+            Stmt::Continue(ContinueStmt {
+                span,
+                label: Some(ref label),
+            }) if self.is_outer_label(label) => self.jump(span, Jump::Continue(label.sym.clone())),
+
+            Stmt::Return(ReturnStmt { span, arg }) => {
+                self.has_return = true;
+                let value = arg.unwrap_or_else(|| undefined(span));
+
+                Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(box Expr::Object(ObjectLit {
+                        span,
+                        props: vec![PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: PropName::Ident(quote_ident!("v")),
+                            value,
+                        }))],
+                    })),
+                })
+            }
+
+            Stmt::Labeled(LabeledStmt { span, label, body }) => {
+                self.inner_labels.push(label.sym.clone());
+                let body = body.fold_with(self);
+                self.inner_labels.pop();
+
+                Stmt::Labeled(LabeledStmt { span, label, body })
+            }
+
+            Stmt::For(..)
+            | Stmt::ForIn(..)
+            | Stmt::ForOf(..)
+            | Stmt::While(..)
+            | Stmt::DoWhile(..) => {
+                let in_loop = self.in_loop;
+                self.in_loop = true;
+                let stmt = stmt.fold_children(self);
+                self.in_loop = in_loop;
+                stmt
+            }
+
+            Stmt::Switch(..) => {
+                let in_switch = self.in_switch;
+                self.in_switch = true;
+                let stmt = stmt.fold_children(self);
+                self.in_switch = in_switch;
+                stmt
+            }
+
+            _ => stmt.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<Function> for FlowRewriter<'a> {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl<'a> Fold<ArrowExpr> for FlowRewriter<'a> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+impl<'a> Fold<Class> for FlowRewriter<'a> {
+    fn fold(&mut self, c: Class) -> Class {
+        c
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::block_scoping;
@@ -198,4 +849,90 @@ mod tests {
             baz(key, qux, fog);
         }"
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(),
+        closure_in_loop,
+        "for (let i = 0; i < 3; i++) {
+            fns.push(() => i);
+        }",
+        "var _loop = function (i) {
+            fns.push(() => i);
+        };
+        for (var i = 0; i < 3; i++) _loop(i);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(),
+        continue_outer_label,
+        "outer: for (let i = 0; i < 3; i++) {
+            for (let j = 0; j < 3; j++) {
+                if (j === 1) continue outer;
+                fns.push(() => i);
+            }
+        }",
+        "var _loop = function (i) {
+            for (var j = 0; j < 3; j++) {
+                if (j === 1) return;
+                fns.push(() => i);
+            }
+        };
+        outer: for (var i = 0; i < 3; i++) _loop(i);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(),
+        jump_to_outer_label,
+        "outer: for (let i = 0; i < 3; i++) {
+            for (let j = 0; j < 3; j++) {
+                fns.push(() => j);
+                if (j === i) continue outer;
+                if (j > 1) break outer;
+            }
+        }",
+        "outer: for (var i = 0; i < 3; i++) {
+            var _loop = function (j) {
+                fns.push(() => j);
+                if (j === i) return 'continue|outer';
+                if (j > 1) return 'break|outer';
+            };
+            for (var j = 0; j < 3; j++) {
+                var _ret = _loop(j);
+                switch (_ret) {
+                    case 'continue|outer':
+                        continue outer;
+                    case 'break|outer':
+                        break outer;
+                }
+            }
+        }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(),
+        break_and_return,
+        "function foo() {
+            for (let i = 0; i < 3; i++) {
+                fns.push(() => i);
+                if (i === 1) break;
+                if (i === 2) return i;
+            }
+        }",
+        "function foo() {
+            var _loop = function (i) {
+                fns.push(() => i);
+                if (i === 1) return 'break';
+                if (i === 2) return { v: i };
+            };
+            for (var i = 0; i < 3; i++) {
+                var _ret = _loop(i);
+                if (_ret === 'break') break;
+                if (typeof _ret === 'object') return _ret.v;
+            }
+        }"
+    );
 }
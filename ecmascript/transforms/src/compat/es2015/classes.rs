@@ -7,12 +7,16 @@ use self::{
     native::is_native,
     prop_name::HashKey,
 };
-use crate::util::{
-    alias_if_required, default_constructor, prepend, prop_name_to_expr, ExprFactory, IsDirective,
-    ModuleItemLike, StmtLike,
+use crate::{
+    pass::Pass,
+    util::{
+        alias_if_required, default_constructor, prepend, prop_name_to_expr, ExprFactory,
+        IsDirective, ModuleItemLike, StmtLike,
+    },
 };
 use ast::*;
 use fxhash::FxBuildHasher;
+use serde::Deserialize;
 use std::iter;
 use swc_common::{Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP};
 
@@ -60,9 +64,43 @@ type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 /// ```
 #[derive(Default, Clone, Copy)]
 pub struct Classes {
+    c: Config,
     in_strict: bool,
 }
 
+pub fn classes(c: Config) -> impl Pass {
+    Classes {
+        c,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Define methods with `_createClass`, which makes them non-enumerable as
+    /// the spec requires.
+    ///
+    /// If false, methods are assigned to the prototype (e.g.
+    /// `Foo.prototype.bar = function bar() {}`), which is smaller but makes
+    /// them enumerable. Getters and setters are always defined with
+    /// `_createClass`.
+    #[serde(default = "default_spec_method_enumerability")]
+    pub spec_method_enumerability: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            spec_method_enumerability: default_spec_method_enumerability(),
+        }
+    }
+}
+
+const fn default_spec_method_enumerability() -> bool {
+    true
+}
+
 struct Data {
     key_prop: Box<Prop>,
     method: Option<Box<Expr>>,
@@ -350,7 +388,9 @@ impl Classes {
         class: Class,
     ) -> Vec<Stmt> {
         let is_named = class_name.is_some();
-        let class_name = class_name.unwrap_or_else(|| quote_ident!("_class"));
+        // The name of an anonymous class must not shadow a binding used in
+        // the class, like its super class.
+        let class_name = class_name.unwrap_or_else(|| private_ident!("_class"));
         let mut stmts = vec![];
 
         let mut priv_methods = vec![];
@@ -713,6 +753,37 @@ impl Classes {
             }))
         }
 
+        /// Foo.prototype.bar = function bar() {};
+        fn mk_assign_stmt(
+            class_name: &Ident,
+            is_static: bool,
+            key: PropName,
+            value: Box<Expr>,
+        ) -> Stmt {
+            let obj = if is_static {
+                box Expr::Ident(class_name.clone())
+            } else {
+                box class_name.clone().member(quote_ident!("prototype"))
+            };
+            let computed = match key {
+                PropName::Ident(..) => false,
+                _ => true,
+            };
+
+            Stmt::Expr(box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: ExprOrSuper::Expr(obj),
+                    prop: box prop_name_to_expr(key),
+                    computed,
+                })),
+                right: value,
+            }))
+        }
+
+        let mut stmts = vec![];
         let (mut props, mut static_props) = (IndexMap::default(), IndexMap::default());
 
         for m in methods {
@@ -722,7 +793,7 @@ impl Classes {
                 PropName::Computed(..) => true,
                 _ => false,
             };
-            let prop_name = prop_name_to_expr(m.key);
+            let prop_name = prop_name_to_expr(m.key.clone());
 
             let append_to: &mut IndexMap<_, _> = if m.is_static {
                 &mut static_props
@@ -787,23 +858,40 @@ impl Classes {
                 function,
             });
 
+            if m.kind == MethodKind::Method && !self.c.spec_method_enumerability {
+                stmts.push(mk_assign_stmt(&class_name, m.is_static, m.key, value));
+                continue;
+            }
+
             let data = append_to.entry(key).or_insert_with(|| Data {
                 key_prop,
                 get: None,
                 set: None,
                 method: None,
             });
+            // A later definition replaces a method with an accessor and vice versa,
+            // while a getter and a setter are merged into one descriptor.
             match m.kind {
-                MethodKind::Getter => data.get = Some(value),
-                MethodKind::Setter => data.set = Some(value),
-                MethodKind::Method => data.method = Some(value),
+                MethodKind::Getter => {
+                    data.method = None;
+                    data.get = Some(value)
+                }
+                MethodKind::Setter => {
+                    data.method = None;
+                    data.set = Some(value)
+                }
+                MethodKind::Method => {
+                    data.get = None;
+                    data.set = None;
+                    data.method = Some(value)
+                }
             }
         }
 
         if props.is_empty() && static_props.is_empty() {
-            return vec![];
+            return stmts;
         }
-        vec![mk_create_class_call(
+        stmts.push(mk_create_class_call(
             class_name,
             mk_arg_obj_for_create_class(props),
             if static_props.is_empty() {
@@ -811,7 +899,8 @@ impl Classes {
             } else {
                 Some(mk_arg_obj_for_create_class(static_props))
             },
-        )]
+        ));
+        stmts
     }
 }
 
@@ -820,7 +909,7 @@ impl Classes {
 /// ```js
 /// Child.__proto__ || Object.getPrototypeOf(Child)
 /// ```
-fn get_prototype_of(obj: &Expr) -> Expr {
+pub(super) fn get_prototype_of(obj: &Expr) -> Expr {
     Expr::Call(CallExpr {
         span: DUMMY_SP,
         callee: helper!(get_prototype_of, "getPrototypeOf"),
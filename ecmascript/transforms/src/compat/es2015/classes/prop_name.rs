@@ -5,7 +5,7 @@ use swc_common::{Span, Spanned};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum HashKey {
-    Ident(JsWord),
+    /// Identifiers are stored as strings, as `a` and `'a'` are the same key.
     Str(JsWord),
     Num(OrderedFloat<f64>),
     /// Not for key merging
@@ -15,7 +15,7 @@ pub enum HashKey {
 impl From<&PropName> for HashKey {
     fn from(p: &PropName) -> Self {
         match *p {
-            PropName::Ident(Ident { ref sym, .. }) => HashKey::Ident(sym.clone()),
+            PropName::Ident(Ident { ref sym, .. }) => HashKey::Str(sym.clone()),
             PropName::Str(Str { ref value, .. }) => HashKey::Str(value.clone()),
             PropName::Num(Number { value, .. }) => HashKey::Num(value.into()),
             PropName::Computed(ref expr) => HashKey::Computed(expr.span()),
@@ -109,6 +109,7 @@ impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
                             span: super_token, ..
                         }),
                     prop,
+                    computed,
                     ..
                 }) => {
                     let op = match op {
@@ -120,6 +121,7 @@ impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
                         super_token,
                         true,
                         prop,
+                        computed,
                         op,
                         box Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
@@ -148,6 +150,7 @@ impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
                             span: super_token, ..
                         }),
                     prop,
+                    computed,
                     ..
                 }))
                 | PatOrExpr::Pat(box Pat::Expr(box Expr::Member(MemberExpr {
@@ -156,8 +159,9 @@ impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
                             span: super_token, ..
                         }),
                     prop,
+                    computed,
                     ..
-                }))) => self.super_to_set_call(super_token, false, prop, op, right),
+                }))) => self.super_to_set_call(super_token, false, prop, computed, op, right),
                 _ => Expr::Assign(AssignExpr {
                     span,
                     left: left.fold_children(self),
@@ -239,11 +243,22 @@ impl<'a> SuperCalleeFolder<'a> {
         super_token: Span,
         is_update: bool,
         prop: Box<Expr>,
+        computed: bool,
         op: AssignOp,
         rhs: Box<Expr>,
     ) -> Expr {
         self.inject_set = true;
 
+        // `super.x` is `super['x']`.
+        let prop = match *prop {
+            Expr::Ident(Ident { ref sym, span, .. }) if !computed => box Expr::Lit(Lit::Str(Str {
+                span,
+                value: sym.clone(),
+                has_escape: false,
+            })),
+            _ => prop,
+        };
+
         let mut ref_ident = alias_ident_for(&rhs, "_ref");
         ref_ident.span = ref_ident.span.apply_mark(Mark::fresh(Mark::root()));
 
@@ -269,23 +284,37 @@ impl<'a> SuperCalleeFolder<'a> {
             }
         }
 
+        // `super.x ||= v` is `_get(proto, _ref = x, this) || _set(proto, _ref, v, this,
+        // true)`, so `v` is evaluated only if required.
+        let (prop, logical) = match op {
+            op!("&&=") | op!("||=") | op!("??=") => {
+                let get = self.super_to_get_call(
+                    super_token,
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(ref_ident.clone())),
+                        op: op!("="),
+                        right: prop,
+                    }),
+                    true,
+                );
+                let op = match op {
+                    op!("&&=") => op!("&&"),
+                    op!("||=") => op!("||"),
+                    _ => op!("??"),
+                };
+
+                (box Expr::Ident(ref_ident.clone()), Some((op, get)))
+            }
+            _ => (prop, None),
+        };
+
         let proto_arg =
             get_prototype_of(&self.class_name.clone().member(quote_ident!("prototype"))).as_arg();
 
-        let prop_arg = match *prop {
-            Expr::Ident(Ident {
-                sym: ref value,
-                span,
-                ..
-            }) => Expr::Lit(Lit::Str(Str {
-                span,
-                value: value.clone(),
-                has_escape: false,
-            })),
-            ref e => e.clone(),
-        };
         let prop_arg = match op {
-            op!("=") => prop_arg.as_arg(),
+            op!("=") => prop.as_arg(),
+            _ if logical.is_some() => prop.as_arg(),
             _ => AssignExpr {
                 span: DUMMY_SP,
                 left: PatOrExpr::Pat(box Pat::Ident(ref_ident.clone())),
@@ -297,6 +326,7 @@ impl<'a> SuperCalleeFolder<'a> {
 
         let rhs_arg = match op {
             op!("=") => rhs.as_arg(),
+            _ if logical.is_some() => rhs.as_arg(),
             _ => {
                 let left =
                     box self.super_to_get_call(super_token, box Expr::Ident(ref_ident), true);
@@ -334,6 +364,7 @@ impl<'a> SuperCalleeFolder<'a> {
                         op!("&=") => op!("&"),
                         op!("^=") => op!("^"),
                         op!("**=") => op!("**"),
+                        op!("&&=") | op!("||=") | op!("??=") => unreachable!(),
                     },
                     right: rhs,
                 }
@@ -366,6 +397,13 @@ impl<'a> SuperCalleeFolder<'a> {
                 span: DUMMY_SP,
                 exprs: vec![box expr, box Expr::Ident(update_ident)],
             })
+        } else if let Some((op, get)) = logical {
+            Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: box get,
+                op,
+                right: box expr,
+            })
         } else {
             expr
         }
@@ -4278,6 +4278,51 @@ expect(obj[1]).toBe(2);
 "#
 );
 
+test!(
+    Syntax::Es(EsConfig {
+        logical_assignment: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    get_set_logical_assign,
+    r#"
+class Obj extends Base {
+  assign(k) {
+    super.x ||= 1;
+    super.y &&= 2;
+    super[k] ??= 3;
+  }
+}
+"#,
+    r#"
+let Obj =
+/*#__PURE__*/
+function (Base) {
+  'use strict';
+  _inherits(Obj, Base);
+
+  function Obj() {
+    _classCallCheck(this, Obj);
+
+    return _possibleConstructorReturn(this, _getPrototypeOf(Obj).apply(this, arguments));
+  }
+
+  _createClass(Obj, [{
+    key: "assign",
+    value: function assign(k) {
+      var _ref, _ref1, _ref2;
+
+      _get(_getPrototypeOf(Obj.prototype), _ref = "x", this) || _set(_getPrototypeOf(Obj.prototype), _ref, 1, this, true);
+      _get(_getPrototypeOf(Obj.prototype), _ref1 = "y", this) && _set(_getPrototypeOf(Obj.prototype), _ref1, 2, this, true);
+      _get(_getPrototypeOf(Obj.prototype), _ref2 = k, this) ?? _set(_getPrototypeOf(Obj.prototype), _ref2, 3, this, true);
+    }
+  }]);
+
+  return Obj;
+}(Base);
+"#
+);
+
 // spec_nested_class_super_property_in_key_exec
 test_exec!(
     syntax(),
@@ -6101,3 +6146,313 @@ with (env) {
 
 "#
 );
+
+test_script!(
+    syntax(),
+    |_| tr(),
+    script_class_decl,
+    "class Foo {
+  constructor() {}
+}
+new Foo();",
+    "let Foo = function Foo() {
+  'use strict';
+  _classCallCheck(this, Foo);
+};
+new Foo();"
+);
+
+test!(
+    syntax(),
+    |_| classes(Config {
+        spec_method_enumerability: true
+    }),
+    spec_method_enumerability,
+    r#"
+class Foo {
+  bar() {}
+  get baz() { return 1; }
+  set baz(v) {}
+  static qux() {}
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  _createClass(Foo, [{
+    key: 'bar',
+    value: function bar() {}
+  }, {
+    key: 'baz',
+    get: function() { return 1; },
+    set: function(v) {}
+  }], [{
+    key: 'qux',
+    value: function qux() {}
+  }]);
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| classes(Config {
+        spec_method_enumerability: false
+    }),
+    loose_method_enumerability,
+    r#"
+class Foo {
+  bar() {}
+  get baz() { return 1; }
+  set baz(v) {}
+  static qux() {}
+  ['a' + b]() {}
+  'quux'() {}
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  Foo.prototype.bar = function bar() {};
+  Foo.qux = function qux() {};
+  Foo.prototype['a' + b] = function() {};
+  Foo.prototype['quux'] = function quux() {};
+  _createClass(Foo, [{
+    key: 'baz',
+    get: function() { return 1; },
+    set: function(v) {}
+  }]);
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| classes(Config {
+        spec_method_enumerability: false
+    }),
+    loose_method_enumerability_no_accessor,
+    r#"
+class Foo {
+  bar() {}
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  Foo.prototype.bar = function bar() {};
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    accessor_pair,
+    r#"
+class Foo {
+  get a() { return this._a; }
+  bar() {}
+  set 'a'(v) { this._a = v; }
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  _createClass(Foo, [{
+    key: 'a',
+    get: function() { return this._a; },
+    set: function(v) { this._a = v; }
+  }, {
+    key: 'bar',
+    value: function bar() {}
+  }]);
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    static_getter,
+    r#"
+class Foo {
+  get a() { return 1; }
+  static get a() { return 2; }
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  _createClass(Foo, [{
+    key: 'a',
+    get: function() { return 1; }
+  }], [{
+    key: 'a',
+    get: function() { return 2; }
+  }]);
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    computed_accessor,
+    r#"
+class Foo {
+  get [key()]() { return 1; }
+  set [key()](v) {}
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  _createClass(Foo, [{
+    key: key(),
+    get: function() { return 1; }
+  }, {
+    key: key(),
+    set: function(v) {}
+  }]);
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    accessor_replaces_method,
+    r#"
+class Foo {
+  a() {}
+  get a() { return 1; }
+}
+"#,
+    r#"
+let Foo = function() {
+  'use strict';
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+  _createClass(Foo, [{
+    key: 'a',
+    get: function() { return 1; }
+  }]);
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| spec_tr(),
+    use_asm_directive,
+    r#"
+class Foo {
+  constructor() {
+    "use asm";
+    this.a = 1;
+  }
+}
+"#,
+    r#"
+var Foo = function Foo() {
+  "use asm";
+  'use strict';
+  _classCallCheck(this, Foo);
+  this.a = 1;
+};
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    class_expr_anonymous,
+    r#"
+const C = class extends _class {
+  constructor() {
+    super();
+    this.x = 1;
+  }
+};
+"#,
+    r#"
+const C = function(_class) {
+    'use strict';
+    _inherits(_class1, _class);
+    function _class1() {
+        _classCallCheck(this, _class1);
+        var _this;
+        _this = _possibleConstructorReturn(this, _getPrototypeOf(_class1).call(this));
+        _this.x = 1;
+        return _this;
+    }
+    return _class1;
+}(_class);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    class_expr_named,
+    r#"
+const C = class Foo extends A {
+  constructor() {
+    super();
+    this.self = Foo;
+  }
+  static create() {
+    return new Foo();
+  }
+};
+Foo;
+"#,
+    r#"
+const C = function(A) {
+    'use strict';
+    _inherits(Foo, A);
+    function Foo() {
+        _classCallCheck(this, Foo);
+        var _this;
+        _this = _possibleConstructorReturn(this, _getPrototypeOf(Foo).call(this));
+        _this.self = Foo;
+        return _this;
+    }
+    _createClass(Foo, null, [{
+        key: 'create',
+        value: function create() {
+            return new Foo();
+        }
+    }]);
+    return Foo;
+}(A);
+Foo;
+"#
+);
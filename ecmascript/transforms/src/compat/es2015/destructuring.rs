@@ -1,13 +1,13 @@
 use crate::{
     pass::Pass,
     util::{
-        alias_ident_for, alias_if_required, has_rest_pat, is_literal, prop_name_to_expr, undefined,
-        ExprFactory, StmtLike,
+        alias_ident_for, alias_if_required, has_rest_pat, is_literal, prepend_stmts,
+        prop_name_to_expr, undefined, ExprExt, ExprFactory, StmtLike, HANDLER,
     },
 };
 use ast::*;
 use serde::Deserialize;
-use std::iter;
+use std::{iter, mem};
 use swc_common::{Fold, FoldWith, Spanned, SyntaxContext, Visit, VisitWith, DUMMY_SP};
 
 #[cfg(test)]
@@ -42,10 +42,57 @@ struct Destructuring {
     c: Config,
 }
 
-#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub loose: bool,
+
+    /// How a value is compared with `undefined` to decide whether the default
+    /// value is used.
+    #[serde(default)]
+    pub default_check: DefaultCheck,
+
+    /// Declare temporary variables in the `var` or `let` declaration which
+    /// uses them, instead of a separate `var` statement before it.
+    #[serde(default)]
+    pub inline_temps: bool,
+
+    /// Throw a `TypeError` if the value of an empty object pattern, like
+    /// `let {} = x`, is `null`. If disabled, the value is only evaluated.
+    #[serde(default = "default_check_empty_destructuring")]
+    pub check_empty_destructuring: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            loose: false,
+            default_check: Default::default(),
+            inline_temps: false,
+            check_empty_destructuring: default_check_empty_destructuring(),
+        }
+    }
+}
+
+const fn default_check_empty_destructuring() -> bool {
+    true
+}
+
+/// Comparison used for default values. `null` never triggers the default
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DefaultCheck {
+    /// `tmp === void 0`
+    StrictVoid0,
+    /// `tmp === undefined`
+    StrictUndefined,
+}
+
+impl Default for DefaultCheck {
+    fn default() -> Self {
+        DefaultCheck::StrictVoid0
+    }
 }
 
 macro_rules! impl_for_for_stmt {
@@ -175,6 +222,13 @@ impl Fold<Stmt> for AssignFolder {
 }
 
 impl AssignFolder {
+    /// Prepares the folder for the next statement.
+    fn reset(&mut self) {
+        self.exporting = false;
+        self.vars.clear();
+        self.ignore_return_value = None;
+    }
+
     fn fold_var_decl(&mut self, decls: &mut Vec<VarDeclarator>, decl: VarDeclarator) {
         match decl.name {
             Pat::Ident(..) => decls.push(decl),
@@ -296,6 +350,16 @@ impl AssignFolder {
                 }
             }
             Pat::Object(ObjectPat { span, props, .. }) if props.is_empty() => {
+                if !self.c.check_empty_destructuring {
+                    decls.push(VarDeclarator {
+                        span,
+                        name: Pat::Ident(private_ident!(span, "ref")),
+                        init: decl.init,
+                        definite: false,
+                    });
+                    return;
+                }
+
                 let (ident, aliased) = alias_if_required(&decl.init.as_ref().unwrap(), "ref");
                 if aliased {
                     decls.push(VarDeclarator {
@@ -411,7 +475,11 @@ impl AssignFolder {
                                     let var_decl = VarDeclarator {
                                         span: prop_span,
                                         name: Pat::Ident(key.clone()),
-                                        init: Some(box make_cond_expr(ref_ident, value)),
+                                        init: Some(box make_cond_expr(
+                                            self.c,
+                                            ref_ident.into(),
+                                            value,
+                                        )),
                                         definite: false,
                                     };
                                     decls.extend(vec![var_decl].fold_with(self));
@@ -449,20 +517,18 @@ impl AssignFolder {
                     "desturcturing pattern binding requires initializer"
                 );
 
-                let tmp_ident = match decl.init {
-                    Some(box Expr::Ident(ref i)) if i.span.ctxt() != SyntaxContext::empty() => {
-                        i.clone()
-                    }
-                    _ => {
+                let tmp = match decl.init {
+                    Some(init) if can_be_reused(&init) => *init,
+                    init => {
                         let tmp_ident = private_ident!(span, "tmp");
                         decls.push(VarDeclarator {
                             span: DUMMY_SP,
                             name: Pat::Ident(tmp_ident.clone()),
-                            init: decl.init,
+                            init,
                             definite: false,
                         });
 
-                        tmp_ident
+                        Expr::Ident(tmp_ident)
                     }
                 };
 
@@ -470,13 +536,21 @@ impl AssignFolder {
                     span,
                     name: *left,
                     // tmp === void 0 ? def_value : tmp
-                    init: Some(box make_cond_expr(tmp_ident, def_value)),
+                    init: Some(box make_cond_expr(self.c, tmp, def_value)),
                     definite: false,
                 };
                 decls.extend(vec![var_decl].fold_with(self))
             }
 
-            _ => unimplemented!("Pattern {:?}", decl),
+            _ => {
+                // e.g. an invalid pattern recovered by the parser
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(decl.name.span(), "destructuring: unexpected pattern")
+                        .emit()
+                });
+                decls.push(decl)
+            }
         }
     }
 }
@@ -510,17 +584,38 @@ impl Destructuring {
         let stmts = if decls.is_empty() {
             body.stmts
         } else {
-            iter::once(
-                Stmt::Decl(Decl::Var(VarDecl {
+            // The body is already folded, so patterns of parameters are lowered
+            // here instead of by the statement list containing the function.
+            let mut folder = AssignFolder {
+                c: self.c,
+                exporting: false,
+                vars: vec![],
+                ignore_return_value: None,
+            };
+            let decl = Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Let,
+                decls,
+                declare: false,
+            }))
+            .fold_with(&mut folder);
+
+            let mut injected = vec![];
+            if !folder.vars.is_empty() {
+                injected.push(Stmt::Decl(Decl::Var(VarDecl {
                     span: DUMMY_SP,
-                    kind: VarDeclKind::Let,
-                    decls,
+                    kind: VarDeclKind::Var,
+                    decls: folder.vars,
                     declare: false,
-                }))
-                .fold_with(self),
-            )
-            .chain(body.stmts)
-            .collect()
+                })));
+            }
+            injected.push(decl);
+
+            // Declarations are inserted after the directive prologue, as a
+            // directive like `"use strict"` must be the first statement.
+            let mut stmts = body.stmts;
+            prepend_stmts(&mut stmts, injected.into_iter());
+            stmts
         };
 
         (params, BlockStmt { stmts, ..body })
@@ -628,23 +723,43 @@ impl Fold<Expr> for AssignFolder {
                             }
                         }
 
+                        let elem_cnt = if has_rest_pat(&elems) {
+                            std::usize::MAX
+                        } else {
+                            elems.len()
+                        };
+
+                        // `[a, b] = set` evaluates to `set`, not to the array created from it.
+                        let mut value_ident = None;
+                        let right = if !ignore_return_value
+                            && needs_iterable_to_array(self.c, &right)
+                        {
+                            let value = make_ref_ident(self.c, &mut self.vars, None);
+                            exprs.push(box Expr::Assign(AssignExpr {
+                                span: DUMMY_SP,
+                                op: op!("="),
+                                left: PatOrExpr::Pat(box Pat::Ident(value.clone())),
+                                right,
+                            }));
+                            value_ident = Some(value.clone());
+                            box Expr::Ident(value)
+                        } else {
+                            right
+                        };
+
                         // initialized by first element of sequence expression
                         let ref_ident = make_ref_ident_for_array(
                             self.c,
                             &mut self.vars,
                             None,
-                            Some(if has_rest_pat(&elems) {
-                                std::usize::MAX
-                            } else {
-                                elems.len()
-                            }),
+                            Some(elem_cnt),
                         );
 
                         exprs.push(box Expr::Assign(AssignExpr {
                             span: DUMMY_SP,
                             op: op!("="),
                             left: PatOrExpr::Pat(box Pat::Ident(ref_ident.clone())),
-                            right,
+                            right: iterable_to_array(self.c, right, Some(elem_cnt)),
                         }));
 
                         for (i, elem) in elems.into_iter().enumerate() {
@@ -675,7 +790,11 @@ impl Fold<Expr> for AssignFolder {
                                             span,
                                             left: PatOrExpr::Pat(left),
                                             op: op!("="),
-                                            right: box make_cond_expr(assign_ref_ident, right),
+                                            right: box make_cond_expr(
+                                                self.c,
+                                                assign_ref_ident.into(),
+                                                right,
+                                            ),
                                         })
                                         .fold_with(self),
                                     );
@@ -709,8 +828,8 @@ impl Fold<Expr> for AssignFolder {
                             }
                         }
 
-                        // last one should be `ref`
-                        exprs.push(box Expr::Ident(ref_ident));
+                        // last one should be the value of the assignment
+                        exprs.push(box Expr::Ident(value_ident.unwrap_or(ref_ident)));
 
                         Expr::Seq(SeqExpr {
                             span: DUMMY_SP,
@@ -774,7 +893,11 @@ impl Fold<Expr> for AssignFolder {
                                                 span,
                                                 left: PatOrExpr::Pat(box Pat::Ident(key.clone())),
                                                 op: op!("="),
-                                                right: box make_cond_expr(prop_ident, value),
+                                                right: box make_cond_expr(
+                                                    self.c,
+                                                    prop_ident.into(),
+                                                    value,
+                                                ),
                                             }));
                                         }
                                         None => {
@@ -829,57 +952,62 @@ where
     T: FoldWith<AssignFolder>,
 {
     fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        // Nested functions and blocks are handled by their own invocation.
+        let stmts = stmts.fold_children(self);
+
         // fast path
         if !has_destruturing(&stmts) {
             return stmts;
         }
 
-        let stmts = stmts.fold_children(self);
-
         let mut buf = Vec::with_capacity(stmts.len());
 
-        for stmt in stmts {
-            let mut folder = AssignFolder {
-                c: self.c,
-                exporting: false,
-                vars: vec![],
-                ignore_return_value: None,
-            };
-
-            match stmt.try_into_stmt() {
-                Err(item) => {
-                    let item = item.fold_with(&mut folder);
+        // Reused for all statements. `vars` is moved into the declaration of
+        // temporaries, so it's only allocated for statements which need one.
+        let mut folder = AssignFolder {
+            c: self.c,
+            exporting: false,
+            vars: vec![],
+            ignore_return_value: None,
+        };
 
-                    // Add variable declaration
-                    // e.g. var ref
-                    if !folder.vars.is_empty() {
-                        buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
-                            span: DUMMY_SP,
-                            kind: VarDeclKind::Var,
-                            decls: folder.vars,
-                            declare: false,
-                        }))));
-                    }
+        for stmt in stmts {
+            folder.reset();
 
-                    buf.push(item)
-                }
+            let stmt = match stmt.try_into_stmt() {
+                Err(item) => item.fold_with(&mut folder),
                 Ok(stmt) => {
-                    let stmt = stmt.fold_with(&mut folder);
-
-                    // Add variable declaration
-                    // e.g. var ref
-                    if !folder.vars.is_empty() {
-                        buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
-                            span: DUMMY_SP,
-                            kind: VarDeclKind::Var,
-                            decls: folder.vars,
-                            declare: false,
-                        }))));
+                    let mut stmt = stmt.fold_with(&mut folder);
+
+                    if self.c.inline_temps {
+                        match stmt {
+                            // Temporaries don't have an initializer, so they can't be
+                            // added to `const`.
+                            Stmt::Decl(Decl::Var(ref mut var))
+                                if var.kind != VarDeclKind::Const =>
+                            {
+                                var.decls.splice(0..0, folder.vars.drain(..));
+                            }
+                            _ => {}
+                        }
                     }
 
-                    buf.push(T::from_stmt(stmt));
+                    T::from_stmt(stmt)
                 }
+            };
+
+            // Add variable declaration
+            // e.g. var ref
+            if !folder.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: mem::replace(&mut folder.vars, vec![]),
+                    declare: false,
+                }))));
             }
+
+            buf.push(stmt);
         }
 
         buf
@@ -923,41 +1051,7 @@ fn make_ref_ident_for_array(
                 decls.push(VarDeclarator {
                     span,
                     name: Pat::Ident(ref_ident.clone()),
-                    init: init.map(|v| {
-                        if c.loose
-                            || match *v {
-                                Expr::Array(..) => true,
-                                _ => false,
-                            }
-                        {
-                            v
-                        } else {
-                            match elem_cnt {
-                                None => v,
-                                Some(std::usize::MAX) => box CallExpr {
-                                    span: DUMMY_SP,
-                                    callee: helper!(to_array, "toArray"),
-                                    args: vec![v.as_arg()],
-                                    type_args: Default::default(),
-                                }
-                                .into(),
-                                Some(value) => box CallExpr {
-                                    span: DUMMY_SP,
-                                    callee: helper!(sliced_to_array, "slicedToArray"),
-                                    args: vec![
-                                        v.as_arg(),
-                                        Lit::Num(Number {
-                                            span: DUMMY_SP,
-                                            value: value as _,
-                                        })
-                                        .as_arg(),
-                                    ],
-                                    type_args: Default::default(),
-                                }
-                                .into(),
-                            }
-                        }
-                    }),
+                    init: init.map(|v| iterable_to_array(c, v, elem_cnt)),
                     definite: false,
                 });
             }
@@ -967,6 +1061,51 @@ fn make_ref_ident_for_array(
     }
 }
 
+/// Returns true if `v` should be converted to an array before destructuring
+/// it with an array pattern.
+fn needs_iterable_to_array(c: Config, v: &Expr) -> bool {
+    match *v {
+        Expr::Array(..) => false,
+        _ => !c.loose,
+    }
+}
+
+/// Converts `v` to an array with `_slicedToArray` or `_toArray`, so that
+/// iterables other than arrays can be destructured.
+///
+/// `elem_cnt` is the number of elements of the array pattern, or
+/// `usize::MAX` if the pattern has a rest element.
+fn iterable_to_array(c: Config, v: Box<Expr>, elem_cnt: Option<usize>) -> Box<Expr> {
+    if !needs_iterable_to_array(c, &v) {
+        return v;
+    }
+
+    match elem_cnt {
+        None => v,
+        Some(std::usize::MAX) => box CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(to_array, "toArray"),
+            args: vec![v.as_arg()],
+            type_args: Default::default(),
+        }
+        .into(),
+        Some(value) => box CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(sliced_to_array, "slicedToArray"),
+            args: vec![
+                v.as_arg(),
+                Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: value as _,
+                })
+                .as_arg(),
+            ],
+            type_args: Default::default(),
+        }
+        .into(),
+    }
+}
+
 fn make_ref_prop_expr(ref_ident: &Ident, prop: Box<Expr>, mut computed: bool) -> Expr {
     computed |= match *prop {
         Expr::Lit(Lit::Num(..)) | Expr::Lit(Lit::Str(..)) => true,
@@ -982,27 +1121,49 @@ fn make_ref_prop_expr(ref_ident: &Ident, prop: Box<Expr>, mut computed: bool) ->
 }
 
 /// Creates `tmp === void 0 ? def_value : tmp`
-fn make_cond_expr(tmp: Ident, def_value: Box<Expr>) -> Expr {
+fn make_cond_expr(c: Config, tmp: Expr, def_value: Box<Expr>) -> Expr {
+    let undefined = match c.default_check {
+        DefaultCheck::StrictVoid0 => box Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: op!("void"),
+            arg: box Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: 0.0,
+            })),
+        }),
+        DefaultCheck::StrictUndefined => box Expr::Ident(quote_ident!("undefined")),
+    };
+
     Expr::Cond(CondExpr {
         span: DUMMY_SP,
         test: box Expr::Bin(BinExpr {
             span: DUMMY_SP,
-            left: box Expr::Ident(tmp.clone()),
+            left: box tmp.clone(),
             op: op!("==="),
-            right: box Expr::Unary(UnaryExpr {
-                span: DUMMY_SP,
-                op: op!("void"),
-                arg: box Expr::Lit(Lit::Num(Number {
-                    span: DUMMY_SP,
-                    value: 0.0,
-                })),
-            }),
+            right: undefined,
         }),
         cons: def_value,
-        alt: box Expr::Ident(tmp),
+        alt: box tmp,
     })
 }
 
+/// Returns true if `e` can be evaluated twice instead of being stored in a
+/// temporary variable.
+fn can_be_reused(e: &Expr) -> bool {
+    let is_small = match *e {
+        // Unresolved references may be globals with getters.
+        Expr::Ident(ref i) => i.span.ctxt() != SyntaxContext::empty(),
+        Expr::Lit(..) | Expr::This(..) => true,
+        Expr::Unary(UnaryExpr { ref arg, .. }) => match **arg {
+            Expr::Lit(..) => true,
+            _ => false,
+        },
+        _ => false,
+    };
+
+    is_small && !e.may_have_side_effects()
+}
+
 fn can_be_null(e: &Expr) -> bool {
     match *e {
         Expr::Lit(Lit::Null(..))
@@ -1067,10 +1228,39 @@ where
     v.found
 }
 
+/// Finds patterns which should be lowered by the statement list containing
+/// them.
+///
+/// Bodies of functions are not visited, because patterns in them are lowered
+/// while folding the function.
 struct DestructuringVisitor {
     found: bool,
 }
 
+impl Visit<Function> for DestructuringVisitor {
+    fn visit(&mut self, f: &Function) {
+        f.params.visit_with(self);
+    }
+}
+
+impl Visit<Constructor> for DestructuringVisitor {
+    fn visit(&mut self, c: &Constructor) {
+        c.params.visit_with(self);
+    }
+}
+
+impl Visit<ArrowExpr> for DestructuringVisitor {
+    fn visit(&mut self, f: &ArrowExpr) {
+        f.params.visit_with(self);
+
+        // An expression body is not a statement list.
+        match f.body {
+            BlockStmtOrExpr::Expr(ref e) => e.visit_with(self),
+            BlockStmtOrExpr::BlockStmt(..) => {}
+        }
+    }
+}
+
 impl Visit<Pat> for DestructuringVisitor {
     fn visit(&mut self, node: &Pat) {
         node.visit_children(self);
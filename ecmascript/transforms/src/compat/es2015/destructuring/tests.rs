@@ -15,7 +15,10 @@ fn syntax() -> Syntax {
 }
 
 fn tr() -> impl Fold<Module> {
-    destructuring(Config { loose: true })
+    destructuring(Config {
+        loose: true,
+        ..Default::default()
+    })
 }
 
 test!(
@@ -133,7 +136,10 @@ console.log((ref = [123], x = ref[0], ref));"#
 
 test_exec!(
     syntax(),
-    |_| destructuring(Config { loose: true }),
+    |_| destructuring(Config {
+        loose: true,
+        ..Default::default()
+    }),
     chained,
     r#"var a, b, c, d;
 ({ a, b } = ({ c, d } = { a: 1, b: 2, c: 3, d: 4}));
@@ -275,7 +281,10 @@ test!(
 test_exec!(
     ignore,
     syntax(),
-    |_| destructuring(Config { loose: true }),
+    |_| destructuring(Config {
+        loose: true,
+        ..Default::default()
+    }),
     fn_key_with_obj_rest_spread,
     r#"const { [(() => 1)()]: a, ...rest } = { 1: "a" };
 
@@ -300,7 +309,10 @@ for(let i = 0, length = list.length; i < length; i++){
 
 test_exec!(
     syntax(),
-    |_| destructuring(Config { loose: true }),
+    |_| destructuring(Config {
+        loose: true,
+        ..Default::default()
+    }),
     babel_issue_5090,
     r#"const assign = function([...arr], index, value) {
   arr[index] = value;
@@ -391,7 +403,10 @@ var x = coords.x, y = coords.y, foo = 'bar';"#
 test_exec!(
     ignore,
     syntax(),
-    |_| destructuring(Config { loose: true }),
+    |_| destructuring(Config {
+        loose: true,
+        ..Default::default()
+    }),
     number_key_with_object_spread,
     r#"const foo = {
   1: "a",
@@ -408,7 +423,10 @@ expect(rest).toEqual({ 2: "b", 3: "c" });"#
 test_exec!(
     ignore,
     syntax(),
-    |_| destructuring(Config { loose: true }),
+    |_| destructuring(Config {
+        loose: true,
+        ..Default::default()
+    }),
     spread_generator,
     r#"function* f() {
   for (var i = 0; i < 3; i++) {
@@ -436,6 +454,38 @@ test!(
 }"#
 );
 
+test!(
+    syntax(),
+    |_| tr(),
+    param_use_strict,
+    r#"function foo({ a, b }) {
+  "use strict";
+  return a + b;
+}"#,
+    r#"function foo(ref) {
+    "use strict";
+    let a = ref.a, b = ref.b;
+    return a + b;
+}"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    param_directives,
+    r#"function foo([x, y]) {
+  "use asm";
+  "use strict";
+  return x;
+}"#,
+    r#"function foo(ref) {
+    "use asm";
+    "use strict";
+    let x = ref[0], y = ref[1];
+    return x;
+}"#
+);
+
 test!(
     syntax(),
     |_| tr(),
@@ -540,7 +590,10 @@ function foo(bar) {
 // destructuring_function_key_with_object_rest_spread
 test_exec!(
     syntax(),
-    |_| chain!(object_rest_spread(), destructuring(Default::default())),
+    |_| chain!(
+        object_rest_spread(Default::default()),
+        destructuring(Default::default())
+    ),
     destructuring_function_key_with_object_rest_spread_exec,
     r#"
 const { [(() => 1)()]: a, ...rest } = { 1: "a" };
@@ -575,6 +628,36 @@ function isBetween(x, a, b) {
 );
 
 // destructuring_for_of
+test!(
+    syntax(),
+    |_| destructuring(Config {
+        default_check: DefaultCheck::StrictVoid0,
+        ..Default::default()
+    }),
+    default_check_void_0,
+    "let { a = 1 } = foo;
+function f({ a = 1 }) {}",
+    "let _a = foo.a, a = _a === void 0 ? 1 : _a;
+function f(ref) {
+    let _a = ref.a, a = _a === void 0 ? 1 : _a;
+}"
+);
+
+test!(
+    syntax(),
+    |_| destructuring(Config {
+        default_check: DefaultCheck::StrictUndefined,
+        ..Default::default()
+    }),
+    default_check_undefined,
+    "let { a = 1 } = foo;
+function f({ a = 1 }) {}",
+    "let _a = foo.a, a = _a === undefined ? 1 : _a;
+function f(ref) {
+    let _a = ref.a, a = _a === undefined ? 1 : _a;
+}"
+);
+
 test!(
     syntax(),
     |_| chain!(
@@ -584,7 +667,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_for_of,
     r#"
@@ -603,7 +686,7 @@ for (var ref2 of test.expectation.registers){
 }
 var ref1;
 for (ref of test.expectation.registers){
-    ref1 = ref, name = ref1[0], before = ref1[1], after = ref1[2], ref1;
+    ref1 = _slicedToArray(ref, 3), name = ref1[0], before = ref1[1], after = ref1[2], ref1;
 }
 
 "#
@@ -619,7 +702,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_object_basic,
     r#"
@@ -645,7 +728,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_assignment_arrow_function_block,
     r#"
@@ -670,7 +753,7 @@ test_exec!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_non_iterable_exec,
     r#"
@@ -697,7 +780,7 @@ test_exec!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_empty_object_pattern_exec,
     r#"
@@ -708,6 +791,49 @@ expect(function () {
 "#
 );
 
+test!(
+    syntax(),
+    |_| destructuring(Default::default()),
+    empty_object_pattern_checked,
+    "var {} = null;",
+    "var ref = null, ref = ref !== null ? ref : _throw(new TypeError('Cannot destructure \
+     undefined'));"
+);
+
+test!(
+    syntax(),
+    |_| destructuring(Config {
+        check_empty_destructuring: false,
+        ..Default::default()
+    }),
+    empty_object_pattern_unchecked,
+    "let {} = null;
+let {} = foo;",
+    "let ref = null;
+let ref1 = foo;"
+);
+
+test_exec!(
+    syntax(),
+    |_| destructuring(Config {
+        check_empty_destructuring: false,
+        ..Default::default()
+    }),
+    empty_object_pattern_unchecked_exec,
+    r#"
+let count = 0;
+function foo() {
+  count++;
+  return null;
+}
+
+expect(function () {
+  var {} = foo();
+}).not.toThrow();
+expect(count).toBe(1);
+"#
+);
+
 // destructuring_chained
 test_exec!(
     syntax(),
@@ -718,7 +844,7 @@ test_exec!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_chained_exec,
     r#"
@@ -736,7 +862,7 @@ expect(d).toBe(4);
 test_exec!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
@@ -794,7 +920,7 @@ test_exec!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_issue_5090_exec,
     r#"
@@ -821,7 +947,7 @@ test_exec!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_default_precedence_exec,
     r#"
@@ -856,7 +982,7 @@ expect(f2({a: 1})).toEqual([1, 1, 1]);
 //    spread(spread::Config{..Default::default()}),
 //    parameters(),
 //    block_scoping(),
-//    object_rest_spread(),
+//    object_rest_spread(Default::default()),
 //  ]
 //}
 //"#),
@@ -906,7 +1032,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_parameters,
     r#"
@@ -973,7 +1099,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_array_unpack_optimisation,
     r#"
@@ -1054,7 +1180,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_known_array,
     r#"
@@ -1074,14 +1200,14 @@ var x = z[0],
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_es7_object_rest,
     r#"
@@ -1135,7 +1261,7 @@ expect(oldCourses).toEqual([]);
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
@@ -1159,7 +1285,7 @@ var tmp = z.x, ref = tmp === void 0 ? {} : tmp, y = ref.y;
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
@@ -1193,7 +1319,7 @@ var ref = [0, 1, 2, 3, 4, 5, 6],
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
@@ -1229,7 +1355,7 @@ function isSorted(param) {
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
@@ -1265,7 +1391,7 @@ test!(
             ..Default::default()
         }),
         block_scoping(),
-        object_rest_spread()
+        object_rest_spread(Default::default())
     ),
     destructuring_assignment_statement,
     r#"
@@ -1273,7 +1399,7 @@ test!(
 "#,
     r#"
             var ref;
-ref = f(), a = ref[0], b = ref[1], ref;
+ref = _slicedToArray(f(), 2), a = ref[0], b = ref[1], ref;
 "#
 );
 
@@ -1287,7 +1413,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_array,
     r#"
@@ -1312,7 +1438,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_assignment_arrow_function_no_block,
     r#"
@@ -1329,14 +1455,14 @@ var ref;
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_issue_9834,
     r#"
@@ -1371,7 +1497,10 @@ var key = prefix + 'state',
 // destructuring_number_key_with_object_rest_spread
 test_exec!(
     syntax(),
-    |_| chain!(object_rest_spread(), destructuring(Default::default())),
+    |_| chain!(
+        object_rest_spread(Default::default()),
+        destructuring(Default::default())
+    ),
     destructuring_number_key_with_object_rest_spread_exec,
     r#"
 const foo = {
@@ -1398,7 +1527,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_for_in,
     r#"
@@ -1418,7 +1547,7 @@ for(var ref2 in obj){
 }
 var ref1;
 for(ref in obj){
-    ref1 = ref, name = ref1[0], value = ref1[1], ref1;
+    ref1 = _slicedToArray(ref, 2), name = ref1[0], value = ref1[1], ref1;
     print('Name: ' + name + ', Value: ' + value);
 }"#
 );
@@ -1433,7 +1562,7 @@ test!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_issue_5744,
     r#"
@@ -1457,7 +1586,7 @@ test_exec!(
         parameters(),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_spread_generator_exec,
     r#"
@@ -1480,3 +1609,196 @@ test!(
     "var ref;
 foo((ref = [1, 2], a = ref[0], b = ref[1], ref));"
 );
+
+test!(
+    syntax(),
+    |_| tr(),
+    default_value_literal_init,
+    "var [a = 1, b = 2] = [3, 4];",
+    "var a = 3 === void 0 ? 1 : 3, b = 4 === void 0 ? 2 : 4;"
+);
+
+test_script!(
+    syntax(),
+    |_| tr(),
+    script_var_decl,
+    "var [a, b] = f();",
+    "var ref = f(), a = ref[0], b = ref[1];"
+);
+
+test_script!(
+    syntax(),
+    |_| tr(),
+    script_assign,
+    "[a, b] = f();",
+    "var ref;
+ref = f(), a = ref[0], b = ref[1], ref;"
+);
+
+test_script!(
+    syntax(),
+    |_| tr(),
+    script_ref_conflict,
+    "var ref = 1;
+var [a, b] = f();",
+    "var ref = 1;
+var ref1 = f(), a = ref1[0], b = ref1[1];"
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested_arrow_only,
+    "foo();
+bar(() => {
+    let [a, b] = f();
+    return () => [c, d] = g();
+});",
+    "foo();
+bar(() => {
+    let ref = f(), a = ref[0], b = ref[1];
+    var ref1;
+    return () => (ref1 = g(), c = ref1[0], d = ref1[1], ref1);
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested_fn_param_only,
+    "foo();
+bar(function ({ a = 1 }) {
+    return a;
+});",
+    "foo();
+bar(function (ref) {
+    let _a = ref.a, a = _a === void 0 ? 1 : _a;
+    return a;
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    vars_per_stmt,
+    "[a, b] = f();
+foo();
+[c, d] = g();",
+    "var ref;
+ref = f(), a = ref[0], b = ref[1], ref;
+foo();
+var ref1;
+ref1 = g(), c = ref1[0], d = ref1[1], ref1;"
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    separate_temps,
+    "let [a, b] = arr;
+let c = [d, e] = f();
+const g = [h] = f();",
+    "let a = arr[0], b = arr[1];
+var ref;
+let c = (ref = f(), d = ref[0], e = ref[1], ref);
+var ref1;
+const g = (ref1 = f(), h = ref1[0], ref1);"
+);
+
+test!(
+    syntax(),
+    |_| destructuring(Config {
+        loose: true,
+        inline_temps: true,
+        ..Default::default()
+    }),
+    inline_temps,
+    "let [a, b] = arr;
+let c = [d, e] = f();
+const g = [h] = f();",
+    "let a = arr[0], b = arr[1];
+let ref, c = (ref = f(), d = ref[0], e = ref[1], ref);
+var ref1;
+const g = (ref1 = f(), h = ref1[0], ref1);"
+);
+
+test!(
+    syntax(),
+    |_| destructuring(Default::default()),
+    assign_iterable,
+    "[a, b] = set;
+[c, ...d] = set;",
+    "var ref;
+ref = _slicedToArray(set, 2), a = ref[0], b = ref[1], ref;
+var ref1;
+ref1 = _toArray(set), c = ref1[0], d = ref1.slice(1), ref1;"
+);
+
+test!(
+    syntax(),
+    |_| destructuring(Default::default()),
+    assign_iterable_value,
+    "x = [a, b] = set;
+y = [c] = [1];",
+    "var ref, ref1;
+x = (ref = set, ref1 = _slicedToArray(ref, 2), a = ref1[0], b = ref1[1], ref);
+var ref2;
+y = (ref2 = [1], c = ref2[0], ref2);"
+);
+
+test_exec!(
+    syntax(),
+    |_| destructuring(Default::default()),
+    assign_iterable_exec,
+    "var set = new Set([1, 2, 3]);
+var a, b, c, rest;
+
+var value = [a, b] = set;
+expect(a).toBe(1);
+expect(b).toBe(2);
+expect(value).toBe(set);
+
+[c, ...rest] = set;
+expect(c).toBe(1);
+expect(rest).toEqual([2, 3]);"
+);
+
+#[test]
+fn invalid_pat() {
+    let stderr = ::testing::run_test(false, |cm, handler| {
+        HANDLER.set(handler, || {
+            let mut tester = crate::tests::Tester {
+                cm,
+                handler,
+                comments: Default::default(),
+            };
+
+            let mut module = tester.parse_module("input.js", "var a = foo;\nbar();")?;
+            match module.body[0] {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(ref mut var))) => {
+                    let span = var.decls[0].name.span();
+                    var.decls[0].name = Pat::Invalid(Invalid { span });
+                }
+                _ => unreachable!(),
+            }
+
+            let module = module.fold_with(&mut destructuring(Default::default()));
+            assert_eq!(module.body.len(), 2);
+
+            if handler.has_errors() {
+                Err(())
+            } else {
+                Ok(())
+            }
+        })
+    })
+    .expect_err("should report an error");
+
+    assert!(
+        stderr
+            .to_string()
+            .contains("destructuring: unexpected pattern"),
+        "{}",
+        stderr
+    );
+}
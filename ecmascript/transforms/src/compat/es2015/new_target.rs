@@ -0,0 +1,355 @@
+use crate::{
+    pass::Pass,
+    util::{undefined, ExprFactory, UsageFinder},
+};
+use ast::*;
+use std::mem::replace;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith};
+
+/// Compiles `new.target` to an es5 expression.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function Foo() {
+///     console.log(new.target);
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function Foo() {
+///     console.log(this instanceof Foo ? this.constructor : void 0);
+/// }
+/// ```
+///
+/// In class constructors and functions, `new.target` is replaced with
+/// `this.constructor` if `this` is an instance of the class or function. In
+/// methods, getters and setters, which can't be called with `new`,
+/// it's replaced with `void 0`. Anonymous function expressions are given the
+/// name of the variable or property they are assigned to, which is the name
+/// they would have inferred anyway, to refer to them.
+///
+/// The result is not exact: `this.constructor` may have been overwritten, a
+/// function called with an instance of it as `this` (e.g. `Foo.call(new
+/// Foo())`) is treated as called with `new`, and `Reflect.construct` with a
+/// different `newTarget` is not supported. `new.target` before `super()` in a
+/// derived class is not supported either. Anonymous functions which don't
+/// have a name to infer, or which refer to a variable with that name, are
+/// named `_target`.
+///
+/// This pass should be applied before `classes`.
+pub fn new_target() -> impl Pass {
+    NewTarget {
+        ctx: Ctx::Top,
+        class_name: None,
+        fn_name: None,
+        used: false,
+    }
+}
+
+#[derive(Clone)]
+struct NewTarget {
+    ctx: Ctx,
+    /// Name of the class whose body is being folded.
+    class_name: Option<Ident>,
+    /// Name inferred for the anonymous function expression being folded.
+    fn_name: Option<Ident>,
+    /// `true` if `new.target` was replaced in the current function.
+    used: bool,
+}
+
+#[derive(Clone)]
+enum Ctx {
+    /// Not in a function.
+    Top,
+    /// A function which can be called with `new`, or a class constructor.
+    Constructor(Option<Ident>),
+    /// A function which can't be called with `new`.
+    Method,
+}
+
+impl NewTarget {
+    fn fold_in<T>(&mut self, ctx: Ctx, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old_ctx = replace(&mut self.ctx, ctx);
+        let node = node.fold_children(self);
+        self.ctx = old_ctx;
+        node
+    }
+}
+
+impl Fold<FnDecl> for NewTarget {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        let old_used = replace(&mut self.used, false);
+        let f = self.fold_in(Ctx::Constructor(Some(f.ident.clone())), f);
+        self.used = old_used;
+        f
+    }
+}
+
+impl Fold<FnExpr> for NewTarget {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let fn_name = self.fn_name.take();
+        let ident = f
+            .ident
+            .clone()
+            .or_else(|| fn_name.filter(|name| !UsageFinder::find(name, &f.function)))
+            .unwrap_or_else(|| private_ident!(f.function.span, "_target"));
+
+        let old_used = replace(&mut self.used, false);
+        let f = self.fold_in(Ctx::Constructor(Some(ident.clone())), f);
+        let used = replace(&mut self.used, old_used);
+
+        match f.ident {
+            None if used => FnExpr {
+                ident: Some(ident),
+                ..f
+            },
+            _ => f,
+        }
+    }
+}
+
+impl NewTarget {
+    /// Folds `value`, naming it `name` if it's an anonymous function which
+    /// uses `new.target`.
+    fn fold_named(&mut self, name: &Ident, value: Box<Expr>) -> Box<Expr> {
+        match *value {
+            Expr::Fn(FnExpr { ident: None, .. }) => {
+                self.fn_name = Some(name.clone());
+                let value = value.fold_with(self);
+                self.fn_name = None;
+                value
+            }
+            _ => value.fold_with(self),
+        }
+    }
+}
+
+impl Fold<VarDeclarator> for NewTarget {
+    fn fold(&mut self, d: VarDeclarator) -> VarDeclarator {
+        let name = d.name.fold_with(self);
+
+        let init = match name {
+            Pat::Ident(ref i) => d.init.map(|init| self.fold_named(i, init)),
+            _ => d.init.fold_with(self),
+        };
+
+        VarDeclarator { name, init, ..d }
+    }
+}
+
+impl Fold<AssignExpr> for NewTarget {
+    fn fold(&mut self, e: AssignExpr) -> AssignExpr {
+        let left = e.left.fold_with(self);
+
+        let right = match left {
+            PatOrExpr::Pat(box Pat::Ident(ref i)) | PatOrExpr::Expr(box Expr::Ident(ref i))
+                if e.op == op!("=") =>
+            {
+                self.fold_named(i, e.right)
+            }
+            _ => e.right.fold_with(self),
+        };
+
+        AssignExpr { left, right, ..e }
+    }
+}
+
+impl Fold<KeyValueProp> for NewTarget {
+    fn fold(&mut self, p: KeyValueProp) -> KeyValueProp {
+        let key = p.key.fold_with(self);
+
+        let value = match key {
+            PropName::Ident(ref i)
+                if !i.is_reserved_for_es3() && i.sym != *"await" && i.sym != *"eval" =>
+            {
+                // Keys are not bindings, so the function gets its own one.
+                let name = private_ident!(i.span, i.sym.clone());
+                self.fold_named(&name, p.value)
+            }
+            _ => p.value.fold_with(self),
+        };
+
+        KeyValueProp { key, value }
+    }
+}
+
+impl Fold<ClassDecl> for NewTarget {
+    fn fold(&mut self, c: ClassDecl) -> ClassDecl {
+        let old = replace(&mut self.class_name, Some(c.ident.clone()));
+        let c = c.fold_children(self);
+        self.class_name = old;
+        c
+    }
+}
+
+impl Fold<ClassExpr> for NewTarget {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        let old = replace(&mut self.class_name, c.ident.clone());
+        let c = c.fold_children(self);
+        self.class_name = old;
+        c
+    }
+}
+
+impl Fold<Constructor> for NewTarget {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        let ctx = Ctx::Constructor(self.class_name.clone());
+        self.fold_in(ctx, c)
+    }
+}
+
+macro_rules! method {
+    ($T:tt) => {
+        impl Fold<$T> for NewTarget {
+            fn fold(&mut self, m: $T) -> $T {
+                self.fold_in(Ctx::Method, m)
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(MethodProp);
+method!(GetterProp);
+method!(SetterProp);
+
+impl Fold<Expr> for NewTarget {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        let span = match e {
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("new"),
+                        span,
+                        ..
+                    },
+                prop:
+                    Ident {
+                        sym: js_word!("target"),
+                        ..
+                    },
+            }) => span,
+            _ => return e,
+        };
+
+        let this = || Expr::This(ThisExpr { span });
+        let constructor = || this().member(quote_ident!(span, "constructor"));
+
+        match self.ctx {
+            Ctx::Top => e,
+            Ctx::Constructor(Some(ref ident)) => {
+                self.used = true;
+
+                Expr::Cond(CondExpr {
+                    span,
+                    test: box this().make_bin(op!("instanceof"), ident.clone()),
+                    cons: box constructor(),
+                    alt: undefined(span),
+                })
+            }
+            Ctx::Constructor(None) => constructor(),
+            Ctx::Method => *undefined(span),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::es2015::classes;
+    use swc_common::chain;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        function,
+        "function Foo() { console.log(new.target); }
+var bar = function () { return () => new.target; };",
+        "function Foo() {
+    console.log(this instanceof Foo ? this.constructor : void 0);
+}
+var bar = function bar() {
+    return () => this instanceof bar ? this.constructor : void 0;
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        function_name_inferred,
+        "foo = function () { return new.target; };
+var obj = { bar: function () { return new.target; } };
+var baz = function () { baz(); return new.target; };
+call(function () { return new.target; });",
+        "foo = function foo() {
+    return this instanceof foo ? this.constructor : void 0;
+};
+var obj = {
+    bar: function bar() {
+        return this instanceof bar ? this.constructor : void 0;
+    }
+};
+var baz = function _target() {
+    baz();
+    return this instanceof _target ? this.constructor : void 0;
+};
+call(function _target() {
+    return this instanceof _target ? this.constructor : void 0;
+});"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        function_name_exec,
+        "var Foo = function () { this.target = new.target; };
+var obj = { Bar: function () { this.target = new.target; } };
+
+expect(Foo.name).toBe('Foo');
+expect(obj.Bar.name).toBe('Bar');
+expect(new Foo().target).toBe(Foo);
+expect(new obj.Bar().target).toBe(obj.Bar);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        class,
+        "class Foo {
+    constructor() { this.target = new.target; }
+    method() { return new.target; }
+}
+var Bar = class { constructor() { this.target = new.target; } };",
+        "class Foo {
+    constructor() { this.target = this instanceof Foo ? this.constructor : void 0; }
+    method() { return void 0; }
+}
+var Bar = class { constructor() { this.target = this.constructor; } };"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(new_target(), classes(Default::default())),
+        class_exec,
+        "class Foo {
+    constructor() { this.target = new.target; }
+}
+class Bar extends Foo {}
+function Baz() { return new.target; }
+
+expect(new Foo().target).toBe(Foo);
+expect(new Bar().target).toBe(Bar);
+expect(Baz()).toBeUndefined();"
+    );
+}
@@ -0,0 +1,666 @@
+use super::classes::get_prototype_of;
+use crate::{
+    pass::Pass,
+    util::{alias_ident_for, is_literal, ExprFactory, StmtLike},
+};
+use ast::*;
+use std::{iter, mem};
+use swc_common::{Fold, FoldWith, Span, DUMMY_SP};
+
+/// Compiles `super` in methods of object literals.
+///
+/// `super` in classes is handled by the `classes` pass. This pass should run
+/// before it, as the class pass does not know about object literals in class
+/// methods.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var obj = {
+///   foo() {
+///     super.foo = 1;
+///     return super.foo();
+///   }
+/// };
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _obj;
+/// var obj = _obj = {
+///   foo() {
+///     _set(_getPrototypeOf(_obj), "foo", 1, this, true);
+///     return _get(_getPrototypeOf(_obj), "foo", this).call(this);
+///   }
+/// };
+/// ```
+pub fn object_super() -> impl Pass {
+    ObjectSuper
+}
+
+#[derive(Clone, Copy)]
+struct ObjectSuper;
+
+impl<T: StmtLike + FoldWith<ObjectFolder>> Fold<Vec<T>> for ObjectSuper
+where
+    Vec<T>: FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let stmts = stmts.fold_children(self);
+
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts {
+            let mut folder = ObjectFolder::default();
+            let stmt = stmt.fold_with(&mut folder);
+
+            if !folder.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: folder.vars,
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+}
+
+/// Binds object literals which use `super` to a variable.
+#[derive(Default)]
+struct ObjectFolder {
+    vars: Vec<VarDeclarator>,
+}
+
+/// Function bodies are handled by [ObjectSuper].
+impl Fold<Function> for ObjectFolder {
+    fn fold(&mut self, f: Function) -> Function {
+        Function {
+            params: f.params.fold_with(self),
+            decorators: f.decorators.fold_with(self),
+            ..f
+        }
+    }
+}
+
+impl Fold<Expr> for ObjectFolder {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Object(ObjectLit { span, props }) => {
+                let home = private_ident!("_obj");
+
+                let mut replacer = SuperReplacer {
+                    home: &home,
+                    outer_vars: &mut self.vars,
+                    vars: vec![],
+                    found: false,
+                };
+                let props = props
+                    .into_iter()
+                    .map(|prop| match prop {
+                        PropOrSpread::Prop(box prop) => {
+                            PropOrSpread::Prop(box replacer.fold_method(prop))
+                        }
+                        _ => prop,
+                    })
+                    .collect();
+                let obj = Expr::Object(ObjectLit { span, props });
+
+                if !replacer.found {
+                    return obj;
+                }
+
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(home.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                Expr::Assign(AssignExpr {
+                    span,
+                    left: PatOrExpr::Pat(box Pat::Ident(home)),
+                    op: op!("="),
+                    right: box obj,
+                })
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Replaces `super` in a method with the prototype of `home`.
+struct SuperReplacer<'a> {
+    home: &'a Ident,
+    /// Variables of the statement containing the object, used by parameters.
+    outer_vars: &'a mut Vec<VarDeclarator>,
+    /// Variables of the current method.
+    vars: Vec<VarDeclarator>,
+    /// True if `super` is used.
+    found: bool,
+}
+
+/// `super` in a nested function or class does not refer to the object.
+impl<'a> Fold<Function> for SuperReplacer<'a> {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl<'a> Fold<Class> for SuperReplacer<'a> {
+    fn fold(&mut self, c: Class) -> Class {
+        c
+    }
+}
+
+impl<'a> Fold<Expr> for SuperReplacer<'a> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(super_token),
+                        prop,
+                        computed,
+                        ..
+                    })),
+                args,
+                type_args,
+            }) => {
+                let key = prop_key(prop.fold_with(self), computed);
+                let callee = self.get_call(super_token.span, key);
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: callee.member(quote_ident!("call")).as_callee(),
+                    args: iter::once(ThisExpr { span: DUMMY_SP }.as_arg())
+                        .chain(args.fold_with(self))
+                        .collect(),
+                    type_args,
+                })
+            }
+
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Super(super_token),
+                prop,
+                computed,
+                ..
+            }) => {
+                let key = prop_key(prop.fold_with(self), computed);
+                self.get_call(super_token.span, key)
+            }
+
+            Expr::Assign(AssignExpr {
+                span,
+                left:
+                    PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(super_token),
+                        prop,
+                        computed,
+                        ..
+                    })),
+                op,
+                right,
+            })
+            | Expr::Assign(AssignExpr {
+                span,
+                left:
+                    PatOrExpr::Pat(box Pat::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(super_token),
+                        prop,
+                        computed,
+                        ..
+                    }))),
+                op,
+                right,
+            }) => {
+                let key = prop_key(prop.fold_with(self), computed);
+                let right = right.fold_with(self);
+
+                let op = match op {
+                    op!("=") => return self.set_call(span, super_token.span, key, right),
+
+                    // `super.x ||= v` is `_get(.., _ref = x, this) || _set(.., _ref, v, ..)`,
+                    // so `v` is evaluated only if required.
+                    op!("&&=") | op!("||=") | op!("??=") => {
+                        let (key, key_ref) = self.memoize_key(key);
+                        let get = self.get_call(super_token.span, key);
+                        let set = self.set_call(DUMMY_SP, super_token.span, key_ref, right);
+
+                        return Expr::Bin(BinExpr {
+                            span,
+                            left: box get,
+                            op: match op {
+                                op!("&&=") => op!("&&"),
+                                op!("||=") => op!("||"),
+                                _ => op!("??"),
+                            },
+                            right: box set,
+                        });
+                    }
+
+                    op!("+=") => op!(bin, "+"),
+                    op!("-=") => op!(bin, "-"),
+                    op!("*=") => op!("*"),
+                    op!("/=") => op!("/"),
+                    op!("%=") => op!("%"),
+                    op!("<<=") => op!("<<"),
+                    op!(">>=") => op!(">>"),
+                    op!(">>>=") => op!(">>>"),
+                    op!("|=") => op!("|"),
+                    op!("&=") => op!("&"),
+                    op!("^=") => op!("^"),
+                    op!("**=") => op!("**"),
+                    op!("&&=") | op!("||=") | op!("??=") => unreachable!(),
+                };
+
+                let (key, key_ref) = self.memoize_key(key);
+                let value = box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: box self.get_call(super_token.span, key_ref),
+                    op,
+                    right,
+                });
+
+                self.set_call(span, super_token.span, key, value)
+            }
+
+            Expr::Update(UpdateExpr {
+                span,
+                arg:
+                    box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(super_token),
+                        prop,
+                        computed,
+                        ..
+                    }),
+                op,
+                prefix,
+            }) => {
+                let key = prop_key(prop.fold_with(self), computed);
+                let (key, key_ref) = self.memoize_key(key);
+
+                // `+` converts the old value to a number, like `++` does.
+                let old = box Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!(unary, "+"),
+                    arg: box self.get_call(super_token.span, key_ref),
+                });
+                let op = match op {
+                    op!("++") => op!(bin, "+"),
+                    op!("--") => op!(bin, "-"),
+                };
+                let one = box Expr::Lit(Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: 1.0,
+                }));
+
+                if prefix {
+                    let value = box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: old,
+                        op,
+                        right: one,
+                    });
+                    return self.set_call(span, super_token.span, key, value);
+                }
+
+                let old_ref = self.declare(alias_ident_for(&old, "_old"));
+                let value = box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(old_ref.clone())),
+                        op: op!("="),
+                        right: old,
+                    }),
+                    op,
+                    right: one,
+                });
+
+                Expr::Seq(SeqExpr {
+                    span,
+                    exprs: vec![
+                        box self.set_call(DUMMY_SP, super_token.span, key, value),
+                        box Expr::Ident(old_ref),
+                    ],
+                })
+            }
+
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl<'a> SuperReplacer<'a> {
+    /// Replaces `super` in a method of the object.
+    fn fold_method(&mut self, prop: Prop) -> Prop {
+        match prop {
+            Prop::Method(MethodProp { key, function }) => {
+                let params = function.params.fold_with(self);
+                self.outer_vars.extend(self.vars.drain(..));
+
+                Prop::Method(MethodProp {
+                    key,
+                    function: Function {
+                        params,
+                        body: self.fold_body(function.body),
+                        ..function
+                    },
+                })
+            }
+            Prop::Getter(GetterProp { span, key, body }) => Prop::Getter(GetterProp {
+                span,
+                key,
+                body: self.fold_body(body),
+            }),
+            Prop::Setter(SetterProp {
+                span,
+                key,
+                param,
+                body,
+            }) => {
+                let param = param.fold_with(self);
+                self.outer_vars.extend(self.vars.drain(..));
+
+                Prop::Setter(SetterProp {
+                    span,
+                    key,
+                    param,
+                    body: self.fold_body(body),
+                })
+            }
+            _ => prop,
+        }
+    }
+
+    /// Replaces `super` in the body of a method, and declares temporary
+    /// variables in it.
+    ///
+    /// The variables are local to a call, so they are not shared by calls of
+    /// an async method which run at the same time.
+    fn fold_body(&mut self, body: Option<BlockStmt>) -> Option<BlockStmt> {
+        let mut body = body.fold_with(self)?;
+
+        if !self.vars.is_empty() {
+            let decls = mem::replace(&mut self.vars, vec![]);
+            body.stmts.insert(
+                0,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls,
+                    declare: false,
+                })),
+            );
+        }
+
+        Some(body)
+    }
+
+    /// `_getPrototypeOf(_obj)`
+    fn proto(&mut self) -> ExprOrSpread {
+        self.found = true;
+
+        get_prototype_of(&Expr::Ident(self.home.clone())).as_arg()
+    }
+
+    /// `_get(_getPrototypeOf(_obj), key, this)`
+    fn get_call(&mut self, super_token: Span, key: Expr) -> Expr {
+        Expr::Call(CallExpr {
+            span: super_token,
+            callee: helper!(get, "get"),
+            args: vec![
+                self.proto(),
+                key.as_arg(),
+                ThisExpr { span: super_token }.as_arg(),
+            ],
+            type_args: Default::default(),
+        })
+    }
+
+    /// `_set(_getPrototypeOf(_obj), key, value, this, true)`
+    fn set_call(&mut self, span: Span, super_token: Span, key: Expr, value: Box<Expr>) -> Expr {
+        Expr::Call(CallExpr {
+            span,
+            callee: helper!(set, "set"),
+            args: vec![
+                self.proto(),
+                key.as_arg(),
+                value.as_arg(),
+                ThisExpr { span: super_token }.as_arg(),
+                // strict
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: true,
+                })
+                .as_arg(),
+            ],
+            type_args: Default::default(),
+        })
+    }
+
+    /// Returns `(_ref = key, _ref)` if `key` should be evaluated only once.
+    fn memoize_key(&mut self, key: Expr) -> (Expr, Expr) {
+        if is_literal(&key) {
+            return (key.clone(), key);
+        }
+
+        let alias = self.declare(alias_ident_for(&key, "_ref"));
+        (
+            Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                op: op!("="),
+                right: box key,
+            }),
+            Expr::Ident(alias),
+        )
+    }
+
+    fn declare(&mut self, ident: Ident) -> Ident {
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(ident.clone()),
+            init: None,
+            definite: false,
+        });
+        ident
+    }
+}
+
+/// Converts `foo` of `super.foo` to a string literal.
+fn prop_key(prop: Box<Expr>, computed: bool) -> Expr {
+    match *prop {
+        Expr::Ident(Ident { sym, span, .. }) if !computed => Expr::Lit(Lit::Str(Str {
+            span,
+            value: sym,
+            has_escape: false,
+        })),
+        prop => prop,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        call,
+        "var obj = {
+  foo() {
+    return super.foo(1, 2);
+  }
+};",
+        "var _obj;
+var obj = _obj = {
+  foo() {
+    return _get(_getPrototypeOf(_obj), 'foo', this).call(this, 1, 2);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        assign,
+        "var obj = {
+  foo(x) {
+    super.foo = x;
+    super[bar()] += 1;
+  }
+};",
+        "var _obj;
+var obj = _obj = {
+  foo(x) {
+    var _ref;
+    _set(_getPrototypeOf(_obj), 'foo', x, this, true);
+    _set(_getPrototypeOf(_obj), _ref = bar(), _get(_getPrototypeOf(_obj), _ref, this) + 1, this, \
+         true);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        update,
+        "var obj = {
+  foo() {
+    return super.count++;
+  }
+};",
+        "var _obj;
+var obj = _obj = {
+  foo() {
+    var _old;
+    return _set(_getPrototypeOf(_obj), 'count', (_old = +_get(_getPrototypeOf(_obj), 'count', \
+         this)) + 1, this, true), _old;
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            logical_assignment: true,
+            ..Default::default()
+        }),
+        |_| object_super(),
+        logical_assign,
+        "var obj = {
+  foo(k) {
+    super.x ||= 1;
+    super.y &&= 2;
+    super[k] ??= 3;
+  }
+};",
+        "var _obj;
+var obj = _obj = {
+  foo(k) {
+    var _k;
+    _get(_getPrototypeOf(_obj), 'x', this) || _set(_getPrototypeOf(_obj), 'x', 1, this, true);
+    _get(_getPrototypeOf(_obj), 'y', this) && _set(_getPrototypeOf(_obj), 'y', 2, this, true);
+    _get(_getPrototypeOf(_obj), _k = k, this) ?? _set(_getPrototypeOf(_obj), _k, 3, this, true);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        async_method,
+        "var obj = {
+  async foo(k) {
+    super[k] += await v;
+  }
+};",
+        "var _obj;
+var obj = _obj = {
+  async foo(k) {
+    var _k;
+    _set(_getPrototypeOf(_obj), _k = k, _get(_getPrototypeOf(_obj), _k, this) + await v, this, \
+         true);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        getter_and_arrow,
+        "var obj = {
+  get foo() {
+    return () => super.foo;
+  }
+};",
+        "var _obj;
+var obj = _obj = {
+  get foo() {
+    return () => _get(_getPrototypeOf(_obj), 'foo', this);
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        nested_class,
+        "var obj = {
+  foo() {
+    return class extends Base {
+      foo() {
+        return super.foo();
+      }
+    };
+  }
+};",
+        "var obj = {
+  foo() {
+    return class extends Base {
+      foo() {
+        return super.foo();
+      }
+    };
+  }
+};"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        exec,
+        "var base = {
+  foo() {
+    return 'base';
+  },
+  count: 1
+};
+var obj = {
+  __proto__: base,
+  foo() {
+    return super.foo() + '!';
+  },
+  bump() {
+    super.count += 1;
+    return super.count++;
+  }
+};
+
+expect(obj.foo()).toBe('base!');
+expect(obj.bump()).toBe(1);
+expect(obj.count).toBe(2);
+expect(base.count).toBe(1);"
+    );
+}
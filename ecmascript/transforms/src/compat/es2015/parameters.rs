@@ -1,11 +1,18 @@
-use crate::util::{prepend_stmts, ExprFactory};
+use crate::util::{prepend_stmts, undefined, ExprFactory};
 use arrayvec::ArrayVec;
 use ast::*;
-use swc_common::{Fold, FoldWith, Mark, Spanned, DUMMY_SP};
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, Mark, Span, Spanned, SyntaxContext, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
 
+/// Compiles default and rest parameters to statements in the function body.
+///
+/// Parameters of functions starting from the first one with a default value
+/// are read from `arguments`, so that the `length` of functions is preserved.
+/// Arrow functions and setters don't have their own `arguments` or can't
+/// omit parameters, so all of their parameters are kept.
 pub fn parameters() -> Params {
     Params
 }
@@ -14,10 +21,264 @@ pub fn parameters() -> Params {
 pub struct Params;
 // prevent_recurse!(Params, Pat);
 
+/// `arguments[i]`
+fn arguments_at(span: Span, i: usize) -> Expr {
+    let span = span.with_ctxt(SyntaxContext::empty());
+    quote_ident!(span, "arguments").computed_member(Expr::Lit(Lit::Num(Number {
+        span,
+        value: i as f64,
+    })))
+}
+
+/// `arguments.length > i`
+fn arguments_len_gt(span: Span, i: usize) -> Expr {
+    let span = span.with_ctxt(SyntaxContext::empty());
+    Expr::Bin(BinExpr {
+        span,
+        left: member_expr!(span, arguments.length),
+        op: op!(">"),
+        right: box Expr::Lit(Lit::Num(Number {
+            span,
+            value: i as f64,
+        })),
+    })
+}
+
+/// Returns the syntax context of `arguments` if it's bound by `p`.
+fn arguments_in_pat(p: &Pat) -> Option<SyntaxContext> {
+    match *p {
+        Pat::Ident(ref i) if i.sym == js_word!("arguments") => Some(i.span.ctxt()),
+        Pat::Array(ArrayPat { ref elems, .. }) => elems
+            .iter()
+            .filter_map(Option::as_ref)
+            .filter_map(arguments_in_pat)
+            .next(),
+        Pat::Object(ObjectPat { ref props, .. }) => props
+            .iter()
+            .filter_map(|prop| match *prop {
+                ObjectPatProp::KeyValue(KeyValuePatProp { ref value, .. }) => {
+                    arguments_in_pat(value)
+                }
+                ObjectPatProp::Assign(AssignPatProp { ref key, .. }) => {
+                    if key.sym == js_word!("arguments") {
+                        Some(key.span.ctxt())
+                    } else {
+                        None
+                    }
+                }
+                ObjectPatProp::Rest(RestPat { ref arg, .. }) => arguments_in_pat(arg),
+            })
+            .next(),
+        Pat::Rest(RestPat { ref arg, .. }) => arguments_in_pat(arg),
+        Pat::Assign(AssignPat { ref left, .. }) => arguments_in_pat(left),
+        _ => None,
+    }
+}
+
+/// Returns the syntax context of `arguments` if it's bound by a parameter or a
+/// declaration in the function body, which shadows the `arguments` object.
+fn arguments_binding(ps: &[Pat], body: &BlockStmt) -> Option<SyntaxContext> {
+    ps.iter().filter_map(arguments_in_pat).next().or_else(|| {
+        body.stmts
+            .iter()
+            .filter_map(|s| match *s {
+                Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. }))
+                | Stmt::Decl(Decl::Class(ClassDecl { ref ident, .. })) => {
+                    if ident.sym == js_word!("arguments") {
+                        Some(ident.span.ctxt())
+                    } else {
+                        None
+                    }
+                }
+                // `var arguments` is initialized with the `arguments` object.
+                Stmt::Decl(Decl::Var(ref v)) if v.kind != VarDeclKind::Var => v
+                    .decls
+                    .iter()
+                    .filter_map(|d| arguments_in_pat(&d.name))
+                    .next(),
+                _ => None,
+            })
+            .next()
+    })
+}
+
+/// Applies `mark` to a user binding named `arguments`, so that injected
+/// statements can refer to the `arguments` object. The binding is renamed by
+/// hygiene.
+struct ArgumentsRenamer {
+    ctxt: SyntaxContext,
+    mark: Mark,
+}
+
+impl ArgumentsRenamer {
+    fn is_binding(&self, i: &Ident) -> bool {
+        i.sym == js_word!("arguments") && i.span.ctxt() == self.ctxt
+    }
+}
+
+impl Fold<Ident> for ArgumentsRenamer {
+    fn fold(&mut self, i: Ident) -> Ident {
+        if self.is_binding(&i) {
+            Ident {
+                span: i.span.apply_mark(self.mark),
+                ..i
+            }
+        } else {
+            i
+        }
+    }
+}
+
+impl Fold<MemberExpr> for ArgumentsRenamer {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+impl Fold<PropName> for ArgumentsRenamer {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(..) => n.fold_children(self),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<Prop> for ArgumentsRenamer {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            // { arguments } => { arguments: arguments1 }
+            Prop::Shorthand(i) => {
+                if self.is_binding(&i) {
+                    Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(i.clone()),
+                        value: box Expr::Ident(i.fold_with(self)),
+                    })
+                } else {
+                    Prop::Shorthand(i)
+                }
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ObjectPatProp> for ArgumentsRenamer {
+    fn fold(&mut self, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            // { arguments = v } => { arguments: arguments1 = v }
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                if !self.is_binding(&key) {
+                    return ObjectPatProp::Assign(AssignPatProp {
+                        span,
+                        key,
+                        value: value.fold_with(self),
+                    });
+                }
+
+                let binding = box Pat::Ident(key.clone().fold_with(self));
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(key),
+                    value: match value {
+                        Some(right) => box Pat::Assign(AssignPat {
+                            span,
+                            left: binding,
+                            right: right.fold_with(self),
+                            type_ann: None,
+                        }),
+                        None => binding,
+                    },
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+/// Functions have their own `arguments` object.
+macro_rules! own_arguments {
+    ($T:ty) => {
+        impl Fold<$T> for ArgumentsRenamer {
+            fn fold(&mut self, n: $T) -> $T {
+                n
+            }
+        }
+    };
+}
+
+own_arguments!(Function);
+own_arguments!(Constructor);
+own_arguments!(GetterProp);
+own_arguments!(SetterProp);
+
 impl Params {
+    fn fold_fn(&mut self, ps: Vec<Pat>, body: BlockStmt) -> (Vec<Pat>, BlockStmt) {
+        self.fold_params(ps, body, true)
+    }
+
     fn fold_fn_like(&mut self, ps: Vec<Pat>, body: BlockStmt) -> (Vec<Pat>, BlockStmt) {
+        self.fold_params(ps, body, false)
+    }
+
+    /// Setters must have exactly one parameter, so parameters of class setters
+    /// are kept like parameters of arrow functions.
+    fn fold_setter(&mut self, f: Function) -> Function {
+        if f.body.is_none() {
+            return f;
+        }
+
+        let f = f.fold_children(self);
+        let (params, body) = self.fold_fn_like(f.params, f.body.unwrap());
+
+        validate!(Function {
+            params,
+            body: Some(body),
+            ..f
+        })
+    }
+
+    /// If `has_arguments` is true, parameters starting from the first one with
+    /// a default value are removed and read from `arguments`.
+    fn fold_params(
+        &mut self,
+        ps: Vec<Pat>,
+        body: BlockStmt,
+        has_arguments: bool,
+    ) -> (Vec<Pat>, BlockStmt) {
         let body = validate!(body);
 
+        let first_default = if has_arguments {
+            ps.iter().position(|p| match *p {
+                Pat::Assign(..) => true,
+                _ => false,
+            })
+        } else {
+            None
+        };
+
+        let uses_arguments = first_default.is_some()
+            || ps.iter().any(|p| match *p {
+                Pat::Rest(..) => true,
+                _ => false,
+            });
+        let (ps, body) = match arguments_binding(&ps, &body) {
+            Some(ctxt) if uses_arguments => {
+                let mut renamer = ArgumentsRenamer {
+                    ctxt,
+                    mark: Mark::fresh(Mark::root()),
+                };
+                (ps.fold_with(&mut renamer), body.fold_with(&mut renamer))
+            }
+            _ => (ps, body),
+        };
+
         let mut params = vec![];
         let mut decls = vec![];
         let mut unpack_rest = None;
@@ -26,6 +287,53 @@ impl Params {
         for (i, param) in ps.into_iter().enumerate() {
             let span = param.span();
 
+            if first_default.map_or(false, |first| i >= first) {
+                match param {
+                    Pat::Rest(..) => {}
+                    Pat::Assign(AssignPat { left, right, .. }) => {
+                        // a = arguments.length > i && arguments[i] !== void 0
+                        //      ? arguments[i] : right
+                        decls.push(VarDeclarator {
+                            span,
+                            name: *left,
+                            init: Some(box Expr::Cond(CondExpr {
+                                span,
+                                test: box Expr::Bin(BinExpr {
+                                    span,
+                                    left: box arguments_len_gt(span, i),
+                                    op: op!("&&"),
+                                    right: box Expr::Bin(BinExpr {
+                                        span,
+                                        left: box arguments_at(span, i),
+                                        op: op!("!=="),
+                                        right: undefined(span),
+                                    }),
+                                }),
+                                cons: box arguments_at(span, i),
+                                alt: right,
+                            })),
+                            definite: false,
+                        });
+                        continue;
+                    }
+                    _ => {
+                        // a = arguments.length > i ? arguments[i] : void 0
+                        decls.push(VarDeclarator {
+                            span,
+                            name: param,
+                            init: Some(box Expr::Cond(CondExpr {
+                                span,
+                                test: box arguments_len_gt(span, i),
+                                cons: box arguments_at(span, i),
+                                alt: undefined(span),
+                            })),
+                            definite: false,
+                        });
+                        continue;
+                    }
+                }
+            }
+
             match param {
                 Pat::Ident(..) => params.push(param),
                 Pat::Array(..) | Pat::Object(..) => {
@@ -236,4 +544,25 @@ impl Params {
     }
 }
 
-impl_fold_fn!(Params);
+impl_fold_fn!(Params, fold_fn);
+
+macro_rules! fold_setter {
+    ($T:ident) => {
+        impl Fold<$T> for Params {
+            fn fold(&mut self, m: $T) -> $T {
+                if m.kind != MethodKind::Setter {
+                    return m.fold_children(self);
+                }
+
+                $T {
+                    key: m.key.fold_with(self),
+                    function: self.fold_setter(m.function),
+                    ..m
+                }
+            }
+        }
+    };
+}
+
+fold_setter!(ClassMethod);
+fold_setter!(PrivateMethod);
@@ -18,7 +18,7 @@ fn tr() -> impl Fold<Module> {
     chain!(
         resolver(),
         Params,
-        crate::compat::es2015::destructuring(destructuring::Config { loose: false }),
+        crate::compat::es2015::destructuring(Default::default()),
         crate::compat::es2015::block_scoping(),
     )
 }
@@ -65,8 +65,8 @@ class Foo {
 "#,
     r#"
 class Foo{
-     func(a, param) {
-        var b = param === void 0 ? Date.now() : param
+     func(a) {
+        var b = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : Date.now();
         return {
             a
         };
@@ -96,8 +96,8 @@ test!(
     |_| tr(),
     default_before_last,
     r#"function foo(a = "foo", b) {}"#,
-    r#"function foo(param, b) {
-    var a = param === void 0 ? 'foo' : param;
+    r#"function foo() {
+    var a = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : 'foo', b = arguments.length > 1 ? arguments[1] : void 0;
 }"#
 );
 
@@ -186,9 +186,9 @@ test!(
   }
 }
 Ref.nextID = 0"#,
-    r#"var Ref = function Ref(param) {
+    r#"var Ref = function Ref() {
         'use strict';
-        var id = param === void 0 ? ++Ref.nextID : param;
+        var id = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : ++Ref.nextID;
         _classCallCheck(this, Ref);
         this.id = id;
     };
@@ -227,15 +227,15 @@ class X {
     this.x = x
   }
 }"#,
-    r#"var Ref = function Ref(param) {
+    r#"var Ref = function Ref() {
         'use strict';
-        var ref = param === void 0 ? Ref : param;
+        var ref = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : Ref;
         _classCallCheck(this, Ref);
         this.ref = ref;
     }
-var X = function X(param) {
+var X = function X() {
         'use strict';
-        var x = param === void 0 ? foo : param;
+        var x = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : foo;
         _classCallCheck(this, X);
         this.x = x;
     };
@@ -266,12 +266,12 @@ test!(
 var a = function (e, f = 5) {
   return e + " bar " + f;
 };"#,
-    "var t = function(param, param1) {
-    var e = param === void 0 ? 'foo' : param, f = param1 === void 0 ? 5 : param1;
+    "var t = function() {
+    var e = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : 'foo', f = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : 5;
     return e + ' bar ' + f;
 };
-var a = function(e, param) {
-    var f = param === void 0 ? 5 : param;
+var a = function(e) {
+    var f = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : 5;
     return e + ' bar ' + f;
 };
 "
@@ -287,10 +287,9 @@ test!(
   {a3, a4},
   a5,
   {a6, a7} = {}) {}"#,
-    "function fn(a1, param, param1, a5, param2) {
-    var a2 = param === void 0 ? 4 : param, a3 = param1.a3, a4 = param1.a4, ref = param2 === void 0 \
-     ? {
-    } : param2, a6 = ref.a6, a7 = ref.a7;
+    "function fn(a1) {
+    var a2 = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : 4, ref = arguments.length > 2 ? arguments[2] : void 0, a3 = ref.a3, a4 = ref.a4, a5 = arguments.length > 3 ? arguments[3] : void 0, ref1 = arguments.length > 4 && arguments[4] !== void 0 ? arguments[4] : {
+    }, a6 = ref1.a6, a7 = ref1.a7;
 }
 "
 );
@@ -305,8 +304,8 @@ function rest(b = a, ...a) {
 }
 rest(undefined, 2)"#,
     r#"var a = 1;
-function rest(param) {
-    var b = param === void 0 ? a : param;
+function rest() {
+    var b = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : a;
     for(var _len = arguments.length, a1 = new Array(_len > 1 ? _len - 1 : 0), _key = 1; _key < _len; _key++){
         a1[_key - 1] = arguments[_key];
     }
@@ -325,8 +324,8 @@ test!(
 }
 rest2(undefined, 2);"#,
     r#"var a = 1;
-function rest2(param) {
-    var b = param === void 0 ? a : param;
+function rest2() {
+    var b = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : a;
     for(var _len = arguments.length, a1 = new Array(_len > 1 ? _len - 1 : 0), _key = 1; _key < _len; _key++){
         a1[_key - 1] = arguments[_key];
     }
@@ -345,8 +344,8 @@ test!(
 }
 rest3(undefined, 2)"#,
     r#"var a = 1;
-function rest3(param) {
-    var b = param === void 0 ? a : param;
+function rest3() {
+    var b = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : a;
     for(var _len = arguments.length, a1 = new Array(_len > 1 ? _len - 1 : 0), _key = 1; _key < _len; _key++){
         a1[_key - 1] = arguments[_key];
     }
@@ -416,12 +415,112 @@ test!(
     r#"var t = function (f = "foo") {
   return f + " bar";
 };"#,
-    r#"var t = function(param) {
-    var f = param === void 0 ? 'foo' : param;
+    r#"var t = function() {
+    var f = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : 'foo';
     return f + ' bar';
 };"#
 );
 
+test!(
+    syntax(),
+    |_| tr(),
+    default_after_param,
+    r#"function f(a, b = 1, c) {
+  return a + b + c;
+}"#,
+    r#"function f(a) {
+    var b = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : 1, c = arguments.length > 2 ? arguments[2] : void 0;
+    return a + b + c;
+}"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    default_rest,
+    r#"function f(a, b = 1, ...c) {
+  return c;
+}"#,
+    r#"function f(a) {
+    var b = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : 1;
+    for(var _len = arguments.length, c = new Array(_len > 2 ? _len - 2 : 0), _key = 2; _key < _len; _key++){
+        c[_key - 2] = arguments[_key];
+    }
+    return c;
+}"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    default_length_exec,
+    r#"function f(a, b = 1, c) {
+  return [a, b, c];
+}
+function g(a = 1, b) {}
+function h(a, ...b) {}
+class A {
+  set x(v = 1) {
+    this.v = v;
+  }
+}
+
+expect(f.length).toBe(1);
+expect(g.length).toBe(0);
+expect(h.length).toBe(1);
+expect(f(1, undefined, 3)).toEqual([1, 1, 3]);
+expect(f(1, 2)).toEqual([1, 2, undefined]);
+
+const a = new A();
+a.x = undefined;
+expect(a.v).toBe(1);"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    rest_only,
+    r#"function f(...rest) {
+  return rest;
+}"#,
+    r#"function f() {
+    for(var _len = arguments.length, rest = new Array(_len), _key = 0; _key < _len; _key++){
+        rest[_key] = arguments[_key];
+    }
+    return rest;
+}"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    rest_after_params,
+    r#"function f(a, b, ...rest) {
+  return rest;
+}"#,
+    r#"function f(a, b) {
+    for(var _len = arguments.length, rest = new Array(_len > 2 ? _len - 2 : 0), _key = 2; _key < _len; _key++){
+        rest[_key - 2] = arguments[_key];
+    }
+    return rest;
+}"#
+);
+
+test_script!(
+    syntax(),
+    |_| Params,
+    rest_arguments_binding,
+    r#"function f(arguments, ...rest) {
+  return { arguments, rest };
+}"#,
+    r#"function f(arguments1) {
+    for(let _len = arguments.length, rest = new Array(_len > 1 ? _len - 1 : 0), _key = 1; _key < _len; _key++){
+        rest[_key - 1] = arguments[_key];
+    }
+    return { arguments: arguments1, rest };
+}"#
+);
+
 test!(
     syntax(),
     |_| tr(),
@@ -431,8 +530,8 @@ function t(x = "default", { a, b }, ...args) {
   console.log(x, a, b, args);
 }"#,
     "// #3861
-function t(param, param1) {
-    var x = param === void 0 ? 'default' : param, a = param1.a, b = param1.b;
+function t() {
+    var x = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : 'default', ref = arguments.length > 1 ? arguments[1] : void 0, a = ref.a, b = ref.b;
     for(var _len = arguments.length, args = new Array(_len > 2 ? _len - 2 : 0), _key = 2; _key < \
      _len; _key++){
         args[_key - 2] = arguments[_key];
@@ -1552,8 +1651,8 @@ function () {
     }
   }, {
     key: "completelyUnrelated",
-    value: function completelyUnrelated(param) {
-      var copy = param === void 0 ? 123 : param;
+    value: function completelyUnrelated() {
+      var copy = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : 123;
     }
   }]);
 
@@ -1676,3 +1775,22 @@ function foo() {
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| tr(),
+    use_asm_directive,
+    r#"
+function foo(a, b = 1) {
+  "use asm";
+  return a + b;
+}
+"#,
+    r#"
+function foo(a) {
+  "use asm";
+  var b = arguments.length > 1 && arguments[1] !== void 0 ? arguments[1] : 1;
+  return a + b;
+}
+"#
+);
@@ -1,3 +1,4 @@
+use crate::util::{is_valid_ident, UsageFinder};
 use ast::*;
 use swc_common::{Fold, FoldWith};
 
@@ -30,14 +31,43 @@ use swc_common::{Fold, FoldWith};
 ///## Out
 ///```js
 /// var cat = {
-///   getName: function () {
+///   getName: function getName() {
 ///     return name;
 ///   }
 /// };
 /// ```
+///
+/// Methods become function expressions named after the key, so that they are
+/// shown in stack traces. The function is not named if the key is not a
+/// valid identifier, or if the name would shadow a variable used in the
+/// method. Generator and async methods become generator and async functions,
+/// which are lowered by the passes for them.
 #[derive(Default, Clone, Copy)]
 pub struct Shorthand;
 
+/// Returns the name of a function for a method named `key`, if it can be
+/// named.
+fn fn_name(key: &PropName, function: &Function) -> Option<Ident> {
+    let i = match *key {
+        PropName::Ident(ref i) => i.clone(),
+        PropName::Str(Str {
+            ref value, span, ..
+        }) if is_valid_ident(value) => Ident::new(value.clone(), span),
+        _ => return None,
+    };
+
+    if i.is_reserved_for_es3()
+        || i.sym == *"await"
+        || i.sym == *"eval"
+        || i.sym == *"arguments"
+        || UsageFinder::find(&i, function)
+    {
+        return None;
+    }
+
+    Some(i)
+}
+
 impl Fold<Prop> for Shorthand {
     fn fold(&mut self, prop: Prop) -> Prop {
         let prop = prop.fold_children(self);
@@ -48,11 +78,11 @@ impl Fold<Prop> for Shorthand {
                 value: box quote_ident!(span, sym).into(),
             }),
             Prop::Method(MethodProp { key, function }) => Prop::KeyValue(KeyValueProp {
-                key,
                 value: box Expr::Fn(FnExpr {
-                    ident: None,
+                    ident: fn_name(&key, &function),
                     function,
                 }),
+                key,
             }),
             _ => prop,
         }
@@ -73,12 +103,48 @@ mod tests {
   }
 };",
         "var obj = {
-  method: function () {
+  method: function method() {
     return 5 + 5;
   }
 };"
     );
 
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        method_named,
+        "var obj = { foo(a) { return a; }, 'bar'() {}, *baz() { yield 1; } };",
+        "var obj = {
+    foo: function foo(a) { return a; },
+    'bar': function bar() {},
+    baz: function* baz() { yield 1; }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        method_computed_key,
+        "var obj = { [foo]() {}, ['bar']() {} };",
+        "var obj = { [foo]: function () {}, ['bar']: function () {} };"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        method_reserved_word,
+        "var obj = { default() {}, class() {}, 'foo-bar'() {} };",
+        "var obj = { default: function () {}, class: function () {}, 'foo-bar': function () {} };"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        method_shadow,
+        "var foo = 1; var obj = { foo() { return foo; } };",
+        "var foo = 1; var obj = { foo: function () { return foo; } };"
+    );
+
     test!(
         ::swc_ecma_parser::Syntax::default(),
         |_| Shorthand,
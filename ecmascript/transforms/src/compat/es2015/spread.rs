@@ -1,12 +1,16 @@
 use crate::{
     pass::Pass,
-    util::{alias_ident_for, is_literal, prepend, undefined, ExprFactory, StmtLike},
+    util::{
+        alias_ident_for, free_vars, is_literal, prepend, undefined, ExprFactory, FreeVarCollector,
+        Id, StmtLike, HANDLER,
+    },
 };
 use ast::*;
+use indexmap::IndexSet;
 use serde::Deserialize;
 use std::mem;
 use swc_atoms::js_word;
-use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Span, Spanned, DUMMY_SP};
+use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Span, Spanned, VisitWith, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -18,6 +22,11 @@ pub fn spread(c: Config) -> impl Pass {
 #[derive(Debug, Clone, Copy, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
+    /// Assume that spread values are arrays.
+    ///
+    /// Values known to be other iterables (strings, and `new Set()` and
+    /// `new Map()` of the global `Set` and `Map`) are still converted with `_toConsumableArray`, and a
+    /// warning is reported for them.
     pub loose: bool,
 }
 
@@ -31,16 +40,23 @@ struct Spread {
 struct ActualFolder {
     c: Config,
     vars: Vec<VarDeclarator>,
+    /// Variables which are not declared in the program.
+    globals: IndexSet<Id>,
 }
 
 impl<T> Fold<Vec<T>> for Spread
 where
-    T: StmtLike + FoldWith<ActualFolder> + FoldWith<Self>,
+    T: StmtLike + FoldWith<ActualFolder> + FoldWith<Self> + VisitWith<FreeVarCollector>,
 {
     fn fold(&mut self, items: Vec<T>) -> Vec<T> {
         let mut folder = ActualFolder {
             c: self.c,
             vars: vec![],
+            globals: if self.c.loose {
+                free_vars(&items)
+            } else {
+                Default::default()
+            },
         };
         let mut items = items.move_map(|item| item.fold_with(&mut folder));
         if !folder.vars.is_empty() {
@@ -212,6 +228,29 @@ impl Fold<Expr> for ActualFolder {
 }
 
 impl ActualFolder {
+    /// Returns true if `e` should be treated as an array instead of calling
+    /// `_toConsumableArray`.
+    fn assumes_array(&self, e: &Expr) -> bool {
+        if !self.c.loose {
+            return false;
+        }
+
+        if is_non_array_iterable(e, &self.globals) {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_warn(
+                        e.span(),
+                        "spreading a value which is not an array, so it's converted using a \
+                         helper even in loose mode",
+                    )
+                    .emit()
+            });
+            return false;
+        }
+
+        true
+    }
+
     fn concat_args(
         &self,
         span: Span,
@@ -286,7 +325,7 @@ impl ActualFolder {
                             }
                             _ => {
                                 if args_len == 1 && !need_array {
-                                    return if self.c.loose {
+                                    return if self.assumes_array(&expr) {
                                         *expr
                                     } else {
                                         Expr::Call(CallExpr {
@@ -302,7 +341,7 @@ impl ActualFolder {
                                 }
                                 // [].concat(arr) is shorter than _toConsumableArray(arr)
                                 if args_len == 1 {
-                                    return if self.c.loose {
+                                    return if self.assumes_array(&expr) {
                                         Expr::Call(CallExpr {
                                             span: DUMMY_SP,
                                             callee: ArrayLit {
@@ -399,6 +438,23 @@ impl ActualFolder {
     }
 }
 
+/// Returns true if `e` is known to be an iterable which is not an array.
+///
+/// `Set` and `Map` are only known if they are in `globals`, as they may be
+/// shadowed by a local variable.
+fn is_non_array_iterable(e: &Expr, globals: &IndexSet<Id>) -> bool {
+    match *e {
+        Expr::Lit(Lit::Str(..)) | Expr::Tpl(..) => true,
+        Expr::New(NewExpr { ref callee, .. }) => match **callee {
+            Expr::Ident(Ident {
+                ref sym, ref span, ..
+            }) if *sym == *"Set" || *sym == *"Map" => globals.contains(&(sym.clone(), span.ctxt())),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 fn expand_literal_args(
     args: impl ExactSizeIterator + Iterator<Item = Option<ExprOrSpread>>,
 ) -> Vec<Option<ExprOrSpread>> {
@@ -1,5 +1,5 @@
 use super::*;
-use crate::{compat::es2015::block_scoping, modules::common_js::common_js};
+use crate::{compat::es2015::block_scoping, modules::common_js::common_js, resolver};
 use swc_common::chain;
 
 fn syntax() -> ::swc_ecma_parser::Syntax {
@@ -913,3 +913,47 @@ foo("foo", "bar");
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| spread(Config { loose: true }),
+    loose_non_array,
+    "var a = [...new Set(b)];
+var c = [...'abc'];
+var d = [...e];",
+    "var a = _toConsumableArray(new Set(b));
+var c = _toConsumableArray('abc');
+var d = [].concat(e);"
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), spread(Config { loose: true })),
+    loose_shadowed_set,
+    "var a = [...new Set(b)];
+function foo(Set) {
+    return [...new Set(b)];
+}",
+    "var a = _toConsumableArray(new Set(b));
+function foo(Set) {
+    return [].concat(new Set(b));
+}"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    spread_set_exec,
+    "var set = new Set([1, 2, 2, 3]);
+expect([...set]).toEqual([1, 2, 3]);
+expect([0, ...set]).toEqual([0, 1, 2, 3]);"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    spread_string_exec,
+    "var s = 'abc';
+expect([...s]).toEqual(['a', 'b', 'c']);
+expect(Math.max(...'123')).toBe(3);"
+);
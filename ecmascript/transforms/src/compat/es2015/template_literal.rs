@@ -1,13 +1,53 @@
-use crate::util::{is_literal, prepend_stmts, ExprFactory, StmtLike};
+use crate::{
+    pass::Pass,
+    util::{is_literal, prepend_stmts, ExprFactory, StmtLike},
+};
 use ast::*;
+use serde::Deserialize;
 use std::{iter, mem};
 use swc_atoms::js_word;
 use swc_common::{BytePos, Fold, FoldWith, Spanned, DUMMY_SP};
 #[cfg(test)]
 mod tests;
 
+/// Compiles template literals to string concatenation.
+///
+/// The strings array of a tagged template is created once per call site and
+/// cached in a hoisted function, so evaluating the tagged template again
+/// passes the same object to the tag.
+pub fn template_literal(c: Config) -> impl Pass {
+    TemplateLiteral {
+        c,
+        added: Default::default(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Freeze the strings array of tagged templates and its `raw` property
+    /// with `Object.freeze`, as required by the spec.
+    ///
+    /// Defaults to `true`.
+    #[serde(default = "default_spec")]
+    pub spec: bool,
+}
+
+const fn default_spec() -> bool {
+    true
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            spec: default_spec(),
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct TemplateLiteral {
+    c: Config,
     added: Vec<Stmt>,
 }
 
@@ -223,10 +263,14 @@ impl Fold<Expr> for TemplateLiteral {
                                 definite: false,
                                 init: Some(box Expr::Call(CallExpr {
                                     span: DUMMY_SP,
-                                    callee: helper!(
-                                        tagged_template_literal,
-                                        "taggedTemplateLiteral"
-                                    ),
+                                    callee: if self.c.spec {
+                                        helper!(tagged_template_literal, "taggedTemplateLiteral")
+                                    } else {
+                                        helper!(
+                                            tagged_template_literal_loose,
+                                            "taggedTemplateLiteralLoose"
+                                        )
+                                    },
                                     args: {
                                         let has_escape = quasis.iter().any(|s| {
                                             s.cooked.as_ref().map(|s| s.has_escape).unwrap_or(true)
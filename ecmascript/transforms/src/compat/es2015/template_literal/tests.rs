@@ -10,6 +10,10 @@ fn tr(_: ()) -> impl Pass {
     TemplateLiteral::default()
 }
 
+fn loose() -> impl Pass {
+    template_literal(Config { spec: false })
+}
+
 test_exec!(
     syntax(),
     |_| tr(Default::default()),
@@ -766,3 +770,58 @@ var bar = tag(_templateObject1(), 1);
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| loose(),
+    loose_tagged,
+    "var foo = tag`first${1}second`;",
+    r#"
+function _templateObject() {
+  const data = _taggedTemplateLiteralLoose(["first", "second"]);
+
+  _templateObject = function () {
+    return data;
+  };
+
+  return data;
+}
+
+var foo = tag(_templateObject(), 1);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    tagged_in_loop,
+    "
+const tag = strings => strings;
+const seen = [];
+for (let i = 0; i < 3; i++) {
+  seen.push(tag`a${i}b`);
+}
+
+expect(seen[1]).toBe(seen[0]);
+expect(seen[2]).toBe(seen[0]);
+expect(Object.isFrozen(seen[0])).toBe(true);
+expect(Object.isFrozen(seen[0].raw)).toBe(true);
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| loose(),
+    loose_tagged_in_loop,
+    "
+const tag = strings => strings;
+const seen = [];
+for (let i = 0; i < 3; i++) {
+  seen.push(tag`a${i}b`);
+}
+
+expect(seen[1]).toBe(seen[0]);
+expect(seen[0].raw).toEqual(['a', 'b']);
+expect(Object.isFrozen(seen[0])).toBe(false);
+"
+);
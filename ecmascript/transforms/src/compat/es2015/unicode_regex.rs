@@ -0,0 +1,245 @@
+use crate::util::HANDLER;
+use ast::*;
+use std::{char, fmt::Write, iter::Peekable, str::Chars};
+use swc_common::{Fold, FoldWith};
+
+/// Compile ES2015 unicode regex to an ES5 regex
+///
+///# Example
+///## In
+///
+/// ```js
+/// /\u{1F600}+/u;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /(?:\uD83D\uDE00)+/
+/// ```
+///
+/// Code points are converted to surrogate pairs, which are grouped so that
+/// quantifiers apply to both of them. Patterns which match differently
+/// without the `u` flag (e.g. `.`, negated character classes or case
+/// insensitive matching) are not changed, and a warning is emitted instead.
+#[derive(Clone, Copy)]
+pub struct UnicodeRegex;
+
+impl Fold<Expr> for UnicodeRegex {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(Regex {
+                span,
+                exp,
+                flags: Some(flags),
+            })) => {
+                if !flags.value.contains('u') {
+                    return Expr::Lit(Lit::Regex(Regex {
+                        span,
+                        exp,
+                        flags: Some(flags),
+                    }));
+                }
+
+                let lowered = if flags.value.contains('i') {
+                    Err("case insensitive matching")
+                } else {
+                    lower(&exp.value)
+                };
+
+                match lowered {
+                    Ok(pattern) => {
+                        let flags: String = flags.value.chars().filter(|&c| c != 'u').collect();
+
+                        Expr::Lit(Lit::Regex(Regex {
+                            span,
+                            exp: Str {
+                                value: pattern.into(),
+                                ..exp
+                            },
+                            flags: if flags.is_empty() {
+                                None
+                            } else {
+                                Some(quote_str!(span, flags))
+                            },
+                        }))
+                    }
+                    Err(reason) => {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_warn(
+                                    span,
+                                    &format!(
+                                        "regular expression with the `u` flag can't be compiled \
+                                         to es5 because it uses {}",
+                                        reason
+                                    ),
+                                )
+                                .emit()
+                        });
+
+                        Expr::Lit(Lit::Regex(Regex {
+                            span,
+                            exp,
+                            flags: Some(flags),
+                        }))
+                    }
+                }
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Converts `pattern` of a regex with the `u` flag to a pattern which
+/// matches the same strings without it.
+fn lower(pattern: &str) -> Result<String, &'static str> {
+    let mut buf = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('u') if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    let mut hex = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => hex.push(c),
+                            None => return Err("an invalid escape"),
+                        }
+                    }
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or("an invalid escape")?;
+                    push_char(&mut buf, c, in_class)?;
+                }
+                Some('u') => {
+                    let unit = hex_unit(&mut chars).ok_or("an invalid escape")?;
+                    // `\uD83D\uDE00` is a single code point with the `u` flag.
+                    let c = if (0xd800..0xdc00).contains(&unit) {
+                        let mut next = chars.clone();
+                        let low = match (next.next(), next.next()) {
+                            (Some('\\'), Some('u')) => {
+                                hex_unit(&mut next).filter(|low| (0xdc00..0xe000).contains(low))
+                            }
+                            _ => None,
+                        };
+                        match low {
+                            Some(low) => {
+                                chars = next;
+                                0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00)
+                            }
+                            None => unit,
+                        }
+                    } else {
+                        unit
+                    };
+                    match char::from_u32(c) {
+                        Some(c) => push_char(&mut buf, c, in_class)?,
+                        // A lone surrogate.
+                        None => write!(buf, "\\u{:04X}", c).unwrap(),
+                    }
+                }
+                Some('p') | Some('P') => return Err("a unicode property escape"),
+                Some('D') | Some('S') | Some('W') => return Err("a negated character class"),
+                Some(c) => {
+                    buf.push('\\');
+                    buf.push(c);
+                }
+                None => buf.push('\\'),
+            },
+            '[' if !in_class => {
+                if chars.peek() == Some(&'^') {
+                    return Err("a negated character class");
+                }
+                in_class = true;
+                buf.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                buf.push(c);
+            }
+            '.' if !in_class => return Err("`.`"),
+            _ if (c as u32) > 0xffff => push_char(&mut buf, c, in_class)?,
+            _ => buf.push(c),
+        }
+    }
+
+    Ok(buf)
+}
+
+/// Parses the four hex digits of a `\uXXXX` escape.
+fn hex_unit(chars: &mut Peekable<Chars>) -> Option<u32> {
+    let mut unit = 0;
+    for _ in 0..4 {
+        unit = unit * 16 + chars.next()?.to_digit(16)?;
+    }
+    Some(unit)
+}
+
+/// Pushes an escape sequence matching `c`.
+fn push_char(buf: &mut String, c: char, in_class: bool) -> Result<(), &'static str> {
+    let mut units = [0; 2];
+    let units = c.encode_utf16(&mut units);
+    if units.len() == 1 {
+        write!(buf, "\\u{:04X}", units[0]).unwrap();
+    } else {
+        if in_class {
+            return Err("a code point above U+FFFF in a character class");
+        }
+        write!(buf, "(?:\\u{:04X}\\u{:04X})", units[0], units[1]).unwrap();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        code_point,
+        r"var re = /\u{1F600}+a\u{62}/gu;",
+        r"var re = /(?:\uD83D\uDE00)+a\u0062/g;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        class,
+        r"var re = /[\u{61}-\u{7A}]/u;",
+        r"var re = /[\u0061-\u007A]/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        escaped_surrogate_pair,
+        r"var a = /\uD83D\uDE00+/u, b = /[\uD83D\uDE00]/u, c = /\uD83D+/u;",
+        r"var a = /(?:\uD83D\uDE00)+/, b = /[\uD83D\uDE00]/u, c = /\uD83D+/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        unsupported,
+        r"var a = /./u, b = /[^a]/u, c = /[\u{1F600}]/u, d = /a/iu;",
+        r"var a = /./u, b = /[^a]/u, c = /[\u{1F600}]/u, d = /a/iu;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        ignore_non_unicode,
+        r"var re = /\u{1F600}./g;",
+        r"var re = /\u{1F600}./g;"
+    );
+}
@@ -1,6 +1,6 @@
 use crate::{
     pass::Pass,
-    util::{ExprFactory, StmtLike},
+    util::{alias_if_required, is_literal, ExprFactory, StmtLike},
 };
 use ast::*;
 use swc_common::{Fold, FoldWith, Span, Spanned, Visit, VisitWith, DUMMY_SP};
@@ -24,6 +24,9 @@ use swc_common::{Fold, FoldWith, Span, Spanned, Visit, VisitWith, DUMMY_SP};
 ///
 /// x = Math.pow(x, 3);
 /// ```
+///
+/// `Math.pow` does not accept BigInts, so `**` is left as is if an operand is
+/// known to be a BigInt, like `2n ** 3n`.
 pub fn exponentation() -> impl Pass {
     Exponentation
 }
@@ -46,21 +49,21 @@ impl Fold<Expr> for AssignFolder {
                 op: op!("**="),
                 right,
             }) => {
+                if is_bigint(&right) {
+                    return Expr::Assign(AssignExpr {
+                        span,
+                        left,
+                        op: op!("**="),
+                        right,
+                    });
+                }
+
                 let lhs: Ident = match left {
                     PatOrExpr::Pat(box Pat::Ident(ref i))
                     | PatOrExpr::Expr(box Expr::Ident(ref i)) => i.clone(),
 
-                    // unimplemented
-                    PatOrExpr::Expr(ref e) => {
-                        let ref_ident = private_ident!(e.span(), "ref");
-
-                        self.vars.push(VarDeclarator {
-                            span: DUMMY_SP,
-                            name: ref_ident.clone().into(),
-                            init: Some(e.clone()),
-                            definite: false,
-                        });
-                        ref_ident
+                    PatOrExpr::Expr(box Expr::Member(e)) => {
+                        return self.fold_member_assign(span, e, right)
                     }
 
                     left => {
@@ -84,12 +87,81 @@ impl Fold<Expr> for AssignFolder {
                 left,
                 op: op!("**"),
                 right,
-            }) => mk_call(span, left, right),
+            }) => {
+                if is_bigint(&left) || is_bigint(&right) {
+                    return Expr::Bin(BinExpr {
+                        span,
+                        left,
+                        op: op!("**"),
+                        right,
+                    });
+                }
+
+                mk_call(span, left, right)
+            }
             _ => e,
         }
     }
 }
 
+impl AssignFolder {
+    /// `obj[prop] **= right` => `_obj = obj, _prop = prop, _obj[_prop] =
+    /// Math.pow(_obj[_prop], right)`
+    ///
+    /// The object and the computed property are evaluated once and in order,
+    /// using temporaries declared before the statement.
+    fn fold_member_assign(&mut self, span: Span, e: MemberExpr, right: Box<Expr>) -> Expr {
+        let mut exprs = vec![];
+
+        let obj = match e.obj {
+            ExprOrSuper::Expr(obj) => {
+                ExprOrSuper::Expr(box Expr::Ident(self.memoize(&mut exprs, obj, "_ref")))
+            }
+            ExprOrSuper::Super(s) => ExprOrSuper::Super(s),
+        };
+        let prop = if e.computed && !is_literal(&e.prop) {
+            box Expr::Ident(self.memoize(&mut exprs, e.prop, "_prop"))
+        } else {
+            e.prop
+        };
+
+        let member = MemberExpr { obj, prop, ..e };
+        exprs.push(box Expr::Assign(AssignExpr {
+            span,
+            left: PatOrExpr::Expr(box Expr::Member(member.clone())),
+            op: op!("="),
+            right: box mk_call(span, box Expr::Member(member), right),
+        }));
+
+        if exprs.len() == 1 {
+            *exprs.pop().unwrap()
+        } else {
+            Expr::Seq(SeqExpr { span, exprs })
+        }
+    }
+
+    /// Returns an identifier which holds the value of `e`.
+    fn memoize(&mut self, exprs: &mut Vec<Box<Expr>>, e: Box<Expr>, default: &str) -> Ident {
+        let (alias, aliased) = alias_if_required(&e, default);
+        if aliased {
+            self.vars.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: alias.clone().into(),
+                init: None,
+                definite: false,
+            });
+            exprs.push(box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                op: op!("="),
+                right: e,
+            }));
+        }
+
+        alias
+    }
+}
+
 impl<T: StmtLike + VisitWith<ShouldFold>> Fold<Vec<T>> for Exponentation
 where
     Vec<T>: FoldWith<Self>,
@@ -140,6 +212,65 @@ fn mk_call(span: Span, left: Box<Expr>, right: Box<Expr>) -> Expr {
     })
 }
 
+/// Returns true if `e` is known to be a BigInt.
+///
+/// BigInts can't be mixed with numbers in arithmetic, so an arithmetic
+/// expression with a BigInt operand is a BigInt, unless it's a string
+/// concatenation.
+fn is_bigint(e: &Expr) -> bool {
+    match *e {
+        Expr::Lit(Lit::BigInt(..)) => true,
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_bigint(expr),
+        Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            ref arg,
+            ..
+        })
+        | Expr::Unary(UnaryExpr {
+            op: op!("~"),
+            ref arg,
+            ..
+        }) => is_bigint(arg),
+        Expr::Bin(BinExpr {
+            op: op!(bin, "+"),
+            ref left,
+            ref right,
+            ..
+        }) => !is_str(left) && !is_str(right) && (is_bigint(left) || is_bigint(right)),
+        Expr::Bin(BinExpr {
+            op,
+            ref left,
+            ref right,
+            ..
+        }) => match op {
+            op!(bin, "-")
+            | op!("*")
+            | op!("/")
+            | op!("%")
+            | op!("**")
+            | op!("&")
+            | op!("|")
+            | op!("^")
+            | op!("<<")
+            | op!(">>") => is_bigint(left) || is_bigint(right),
+            _ => false,
+        },
+        Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+            ..
+        }) => callee.sym == *"BigInt",
+        _ => false,
+    }
+}
+
+fn is_str(e: &Expr) -> bool {
+    match *e {
+        Expr::Lit(Lit::Str(..)) | Expr::Tpl(..) => true,
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_str(expr),
+        _ => false,
+    }
+}
+
 fn should_fold<N>(node: &N) -> bool
 where
     N: VisitWith<ShouldFold>,
@@ -233,6 +364,60 @@ expect(counters).toBe(1);"#
         ok_if_code_eq
     );
 
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Exponentation,
+        assign_member,
+        r#"o.p.q **= 2"#,
+        r#"var _p;
+_p = o.p, _p.q = Math.pow(_p.q, 2);"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Exponentation,
+        assign_member_ident,
+        r#"o.q **= 2"#,
+        r#"o.q = Math.pow(o.q, 2);"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Exponentation,
+        assign_member_conditional,
+        r#"if (foo) o.p.q **= 2"#,
+        r#"var _p;
+if (foo) _p = o.p, _p.q = Math.pow(_p.q, 2);"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Exponentation,
+        bigint,
+        "a = 2n ** 3n;
+b = (-2n) ** x;
+c = BigInt(x) ** 2n;
+d = (1n + 2n) ** 2n;
+x **= 2n;",
+        "a = 2n ** 3n;
+b = (-2n) ** x;
+c = BigInt(x) ** 2n;
+d = (1n + 2n) ** 2n;
+x **= 2n;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Exponentation,
+        bigint_mixed_with_number,
+        "a = 2 ** 3;
+b = ('a' + 1n) ** 2;
+c = 2n ** 3n + 2 ** 3;",
+        "a = Math.pow(2, 3);
+b = Math.pow('a' + 1n, 2);
+c = 2n ** 3n + Math.pow(2, 3);"
+    );
+
     //     test!(::swc_ecma_parser::Syntax::default(),
     //         |_| Exponentation,
     //         babel_4403,
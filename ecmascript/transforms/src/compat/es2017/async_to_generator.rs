@@ -1,11 +1,11 @@
 use crate::{
-    compat::es2015::arrow,
+    compat::{es2015::arrow, es2018::lower_for_await},
     pass::Pass,
-    util::{contains_ident_ref, contains_this_expr, ExprFactory, StmtLike},
+    util::{contains_ident_ref, contains_this_expr, undefined, ExprFactory, StmtLike, HANDLER},
 };
 use ast::*;
 use std::iter;
-use swc_common::{Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, Span, Spanned, Visit, VisitWith, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -30,6 +30,16 @@ mod tests;
 ///   yield bar();
 /// });
 /// ```
+///
+/// # Top level await
+///
+/// Statements of a module using top-level `await` are wrapped in an async
+/// function which is called immediately, as the target can't run it as-is.
+/// The function is called with `undefined` as `this`, like the top level of a
+/// module. Imports are kept at the top level. Modules with exports are
+/// reported as errors, as they can't be moved into a function. Top-level
+/// `for await` loops are converted like those in async functions, because
+/// `async_generator` only converts loops in functions.
 pub fn async_to_generator() -> impl Pass {
     AsyncToGenerator
 }
@@ -73,6 +83,132 @@ where
     }
 }
 
+impl Fold<Module> for AsyncToGenerator {
+    fn fold(&mut self, module: Module) -> Module {
+        let module = module.fold_children(self);
+
+        wrap_top_level_await(module)
+    }
+}
+
+/// Wraps statements of `module` in an async iife if it uses top-level
+/// `await`.
+fn wrap_top_level_await(module: Module) -> Module {
+    let await_span = match find_top_level_await(&module) {
+        Some(span) => span,
+        None => return module,
+    };
+
+    let has_exports = module.body.iter().any(|item| match *item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(..)) | ModuleItem::Stmt(..) => false,
+        _ => true,
+    });
+    if has_exports {
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_err(
+                    await_span,
+                    "top level await can not be lowered in a module with exports",
+                )
+                .emit()
+        });
+        return module;
+    }
+
+    let Module {
+        span,
+        body: items,
+        shebang,
+    } = module;
+
+    let mut body = Vec::with_capacity(items.len());
+    let mut stmts = vec![];
+    for item in items {
+        match item {
+            ModuleItem::Stmt(stmt) => stmts.push(stmt),
+            _ => body.push(item),
+        }
+    }
+
+    let function = Function {
+        span: DUMMY_SP,
+        params: vec![],
+        decorators: Default::default(),
+        body: Some(lower_for_await(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        })),
+        is_async: true,
+        is_generator: false,
+        type_params: Default::default(),
+        return_type: Default::default(),
+    };
+    let iife = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: make_fn_ref(FnExpr {
+            ident: None,
+            function,
+        })
+        .member(quote_ident!("call"))
+        .as_callee(),
+        args: vec![undefined(DUMMY_SP).as_arg()],
+        type_args: Default::default(),
+    });
+    body.push(ModuleItem::Stmt(Stmt::Expr(box iife)));
+
+    Module {
+        span,
+        body,
+        shebang,
+    }
+}
+
+/// Returns the span of the first `await` expression or `for await` statement
+/// which is not in a function.
+fn find_top_level_await(module: &Module) -> Option<Span> {
+    struct Finder {
+        span: Option<Span>,
+    }
+
+    impl Visit<AwaitExpr> for Finder {
+        fn visit(&mut self, e: &AwaitExpr) {
+            if self.span.is_none() {
+                self.span = Some(e.span);
+            }
+
+            e.visit_children(self)
+        }
+    }
+
+    impl Visit<ForOfStmt> for Finder {
+        fn visit(&mut self, s: &ForOfStmt) {
+            if self.span.is_none() {
+                self.span = s.await_token;
+            }
+
+            s.visit_children(self)
+        }
+    }
+
+    macro_rules! noop {
+        ($T:path) => {
+            impl Visit<$T> for Finder {
+                /// Don't recurse into functions.
+                fn visit(&mut self, _: &$T) {}
+            }
+        };
+    }
+    noop!(Function);
+    noop!(ArrowExpr);
+    noop!(Constructor);
+    noop!(GetterProp);
+    noop!(SetterProp);
+
+    let mut v = Finder { span: None };
+    module.visit_with(&mut v);
+    v.span
+}
+
 impl Fold<MethodProp> for Actual {
     fn fold(&mut self, prop: MethodProp) -> MethodProp {
         let prop = validate!(prop);
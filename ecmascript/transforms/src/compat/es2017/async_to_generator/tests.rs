@@ -5,7 +5,7 @@ use crate::{
     resolver,
 };
 use swc_common::chain;
-use swc_ecma_parser::Syntax;
+use swc_ecma_parser::{EsConfig, Syntax};
 
 struct ParenRemover;
 impl Fold<Expr> for ParenRemover {
@@ -30,12 +30,19 @@ fn syntax() -> Syntax {
     Syntax::default()
 }
 
+fn tla_syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        top_level_await: true,
+        ..Default::default()
+    })
+}
+
 fn tr() -> impl Fold<Module> {
     chain!(
         ParenRemover,
         validating!(arrow()),
         validating!(parameters()),
-        validating!(destructuring(destructuring::Config { loose: false })),
+        validating!(destructuring(Default::default())),
         validating!(function_name()),
         AsyncToGenerator {},
         fixer()
@@ -1424,7 +1431,7 @@ test!(
     |_| chain!(
         AsyncToGenerator {},
         parameters(),
-        destructuring(destructuring::Config { loose: false }),
+        destructuring(Default::default()),
     ),
     regression_4943,
     r#"
@@ -2092,3 +2099,98 @@ async function foo() {}
 
 "#
 );
+
+test!(
+    tla_syntax(),
+    |_| tr(),
+    top_level_await,
+    r#"
+import foo from 'foo';
+const bar = await foo();
+function baz() {
+    return bar;
+}
+console.log(baz());
+"#,
+    r#"
+import foo from 'foo';
+_asyncToGenerator(function*() {
+    const bar = yield foo();
+    function baz() {
+        return bar;
+    }
+    console.log(baz());
+}).call(void 0);
+"#
+);
+
+test!(
+    tla_syntax(),
+    |_| tr(),
+    top_level_for_await,
+    r#"
+for await (const x of xs) {
+    console.log(x);
+}
+"#,
+    r#"
+_asyncToGenerator(function*() {
+    try {
+        var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+        for(var _iterator = _asyncIterator(xs), _step, _value; _step = yield _iterator.next(), _iteratorNormalCompletion = _step.done, _value = yield _step.value, !_iteratorNormalCompletion; _iteratorNormalCompletion = true){
+            const x = _value;
+            console.log(x);
+        }
+    } catch (err) {
+        _didIteratorError = true;
+        _iteratorError = err;
+    } finally{
+        try {
+            if (!_iteratorNormalCompletion && _iterator.return != null) {
+                yield _iterator.return();
+            }
+        } finally{
+            if (_didIteratorError) {
+                throw _iteratorError;
+            }
+        }
+    }
+}).call(void 0);
+"#
+);
+
+test!(
+    tla_syntax(),
+    |_| tr(),
+    top_level_await_this,
+    r#"
+await foo(this);
+"#,
+    r#"
+_asyncToGenerator((function*() {
+    yield foo(this);
+}).bind(this)).call(void 0);
+"#
+);
+
+test!(
+    tla_syntax(),
+    |_| tr(),
+    top_level_await_in_fn,
+    r#"
+async function foo(bar) {
+  bar && await bar();
+}
+"#,
+    r#"
+function _foo() {
+    _foo = _asyncToGenerator(function*(bar) {
+        bar && (yield bar());
+    });
+    return _foo.apply(this, arguments);
+}
+function foo(bar) {
+    return _foo.apply(this, arguments);
+}
+"#
+);
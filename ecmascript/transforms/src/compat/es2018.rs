@@ -1,12 +1,27 @@
 pub use self::{
-    object_rest_spread::object_rest_spread, optional_catch_binding::optional_catch_binding,
+    async_generator::async_generator, object_rest_spread::object_rest_spread,
+    optional_catch_binding::optional_catch_binding,
 };
+pub(crate) use self::async_generator::lower_for_await;
 use crate::pass::Pass;
 use ast::Module;
+use serde::Deserialize;
 
-mod object_rest_spread;
+mod async_generator;
+pub mod object_rest_spread;
 mod optional_catch_binding;
 
-pub fn es2018() -> impl Pass {
-    chain_at!(Module, object_rest_spread(), optional_catch_binding())
+pub fn es2018(c: Config) -> impl Pass {
+    chain_at!(
+        Module,
+        async_generator(),
+        object_rest_spread(c.object_rest_spread),
+        optional_catch_binding()
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub object_rest_spread: object_rest_spread::Config,
 }
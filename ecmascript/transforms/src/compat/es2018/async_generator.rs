@@ -0,0 +1,746 @@
+use crate::{pass::Pass, util::ExprFactory};
+use ast::*;
+use swc_common::{Fold, FoldWith, Mark, Span, Spanned, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-proposal-async-generator-functions`
+///
+/// ## In
+///
+/// ```js
+/// async function* foo(a) {
+///     yield await a;
+///     yield* bar();
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo() {
+///     return _wrapAsyncGenerator(function* (a) {
+///         yield yield _awaitAsyncGenerator(a);
+///         yield* _asyncGeneratorDelegate(_asyncIterator(bar()), _awaitAsyncGenerator);
+///     }).apply(this, arguments);
+/// }
+/// ```
+///
+/// `for await` loops in async functions and async generators are converted to
+/// `for` loops which await each step of the iterator, and close it if the loop
+/// is exited early.
+///
+/// Async generator methods using `super` are left as is, because `super`
+/// can't be used in the generator function.
+pub fn async_generator() -> impl Pass {
+    AsyncGenerator
+}
+
+#[derive(Clone, Copy)]
+struct AsyncGenerator;
+
+impl Fold<Function> for AsyncGenerator {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+
+        if !f.is_async || f.body.is_none() {
+            return f;
+        }
+        if !f.is_generator {
+            return Function {
+                body: f.body.fold_with(&mut AsyncBody),
+                ..f
+            };
+        }
+        if contains_super(&f.body) {
+            return f;
+        }
+
+        let body = f.body.fold_with(&mut AsyncGeneratorBody);
+        let generator = FnExpr {
+            ident: None,
+            function: Function {
+                params: f.params,
+                decorators: vec![],
+                span: DUMMY_SP,
+                body,
+                is_generator: true,
+                is_async: false,
+                type_params: None,
+                return_type: None,
+            },
+        };
+
+        // _wrapAsyncGenerator(function* () {}).apply(this, arguments)
+        let call = CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(wrap_async_generator, "wrapAsyncGenerator"),
+            args: vec![generator.as_arg()],
+            type_args: None,
+        }
+        .apply(
+            DUMMY_SP,
+            box Expr::This(ThisExpr { span: DUMMY_SP }),
+            vec![quote_ident!("arguments").as_arg()],
+        );
+
+        Function {
+            params: vec![],
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box call),
+                })],
+            }),
+            is_generator: false,
+            is_async: false,
+            ..f
+        }
+    }
+}
+
+impl Fold<ArrowExpr> for AsyncGenerator {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let f = f.fold_children(self);
+
+        if !f.is_async {
+            return f;
+        }
+
+        ArrowExpr {
+            body: f.body.fold_with(&mut AsyncBody),
+            ..f
+        }
+    }
+}
+
+/// Converts `for await` in the body of an async function.
+struct AsyncBody;
+
+/// Converts `for await` loops in `body`, which is the body of an async
+/// function.
+pub(crate) fn lower_for_await(body: BlockStmt) -> BlockStmt {
+    body.fold_with(&mut AsyncBody)
+}
+
+impl Fold<Stmt> for AsyncBody {
+    fn fold(&mut self, s: Stmt) -> Stmt {
+        fold_stmt(self, s, |arg| {
+            Expr::Await(AwaitExpr {
+                span: DUMMY_SP,
+                arg,
+            })
+        })
+    }
+}
+
+/// Converts `await`, `yield*` and `for await` in the body of an async
+/// generator.
+struct AsyncGeneratorBody;
+
+/// `yield _awaitAsyncGenerator(arg)`
+fn await_in_generator(span: Span, arg: Box<Expr>) -> Expr {
+    Expr::Yield(YieldExpr {
+        span,
+        arg: Some(box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(await_async_generator, "awaitAsyncGenerator"),
+            args: vec![arg.as_arg()],
+            type_args: None,
+        })),
+        delegate: false,
+    })
+}
+
+impl Fold<Expr> for AsyncGeneratorBody {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Await(AwaitExpr { span, arg }) => await_in_generator(span, arg),
+
+            // yield* _asyncGeneratorDelegate(_asyncIterator(arg), _awaitAsyncGenerator)
+            Expr::Yield(YieldExpr {
+                span,
+                arg: Some(arg),
+                delegate: true,
+            }) => {
+                let iterator = CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(async_iterator, "asyncIterator"),
+                    args: vec![arg.as_arg()],
+                    type_args: None,
+                };
+
+                Expr::Yield(YieldExpr {
+                    span,
+                    arg: Some(box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: helper!(async_generator_delegate, "asyncGeneratorDelegate"),
+                        args: vec![
+                            iterator.as_arg(),
+                            helper_expr!(await_async_generator, "awaitAsyncGenerator").as_arg(),
+                        ],
+                        type_args: None,
+                    })),
+                    delegate: true,
+                })
+            }
+
+            _ => e,
+        }
+    }
+}
+
+impl Fold<Stmt> for AsyncGeneratorBody {
+    fn fold(&mut self, s: Stmt) -> Stmt {
+        fold_stmt(self, s, |arg| await_in_generator(DUMMY_SP, arg))
+    }
+}
+
+/// Converts `s` if it's a `for await` loop, which may be labeled.
+fn fold_stmt<V>(v: &mut V, s: Stmt, await_expr: fn(Box<Expr>) -> Expr) -> Stmt
+where
+    Stmt: FoldWith<V>,
+    ForOfStmt: FoldWith<V>,
+{
+    match s {
+        Stmt::Labeled(LabeledStmt {
+            label,
+            body:
+                box Stmt::ForOf(
+                    s @ ForOfStmt {
+                        await_token: Some(..),
+                        ..
+                    },
+                ),
+            ..
+        }) => for_await(Some(label), s.fold_children(v), await_expr),
+
+        Stmt::ForOf(
+            s @ ForOfStmt {
+                await_token: Some(..),
+                ..
+            },
+        ) => for_await(None, s.fold_children(v), await_expr),
+
+        _ => s.fold_children(v),
+    }
+}
+
+/// `await` in these nodes belongs to another function.
+macro_rules! noop {
+    ($F:ty, $T:ty) => {
+        impl Fold<$T> for $F {
+            fn fold(&mut self, n: $T) -> $T {
+                n
+            }
+        }
+    };
+}
+
+noop!(AsyncBody, Function);
+noop!(AsyncBody, Constructor);
+noop!(AsyncBody, ArrowExpr);
+noop!(AsyncGeneratorBody, Function);
+noop!(AsyncGeneratorBody, Constructor);
+noop!(AsyncGeneratorBody, ArrowExpr);
+
+/// Converts a `for await` loop to a `for` loop, awaiting values with
+/// `await_expr`.
+///
+/// ```js
+/// try {
+///     var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+///     for (var _iterator = _asyncIterator(xs), _step, _value; _step = await _iterator.next(), _iteratorNormalCompletion = _step.done, _value = await _step.value, !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+///         const x = _value;
+///     }
+/// } catch (err) {
+///     _didIteratorError = true;
+///     _iteratorError = err;
+/// } finally {
+///     try {
+///         if (!_iteratorNormalCompletion && _iterator.return != null) {
+///             await _iterator.return();
+///         }
+///     } finally {
+///         if (_didIteratorError) {
+///             throw _iteratorError;
+///         }
+///     }
+/// }
+/// ```
+///
+/// The flags are initialized in the `try` block, so that the `finally` block
+/// does not see the state of a previous run of the loop.
+fn for_await(label: Option<Ident>, s: ForOfStmt, await_expr: fn(Box<Expr>) -> Expr) -> Stmt {
+    let ForOfStmt {
+        span,
+        left,
+        right,
+        body,
+        ..
+    } = s;
+    let var_span = left.span().apply_mark(Mark::fresh(Mark::root()));
+
+    let iterator = quote_ident!(var_span, "_iterator");
+    let step = quote_ident!(var_span, "_step");
+    let value = quote_ident!(var_span, "_value");
+    let normal_completion = quote_ident!(var_span, "_iteratorNormalCompletion");
+    let did_error = quote_ident!(var_span, "_didIteratorError");
+    let error = quote_ident!(var_span, "_iteratorError");
+    let err = quote_ident!(var_span, "err");
+
+    let assign = |left: &Ident, right: Expr| {
+        Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            left: PatOrExpr::Pat(box Pat::Ident(left.clone())),
+            op: op!("="),
+            right: box right,
+        })
+    };
+    let bool_lit = |value: bool| {
+        Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value,
+        }))
+    };
+    let declarator = |name: &Ident, init: Option<Expr>| VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(name.clone()),
+        init: init.map(Box::new),
+        definite: false,
+    };
+    let call = |callee: Expr| {
+        box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: callee.as_callee(),
+            args: vec![],
+            type_args: None,
+        })
+    };
+
+    let mut body = match *body {
+        Stmt::Block(block) => block,
+        body => BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![body],
+        },
+    };
+    body.stmts.insert(
+        0,
+        match left {
+            VarDeclOrPat::VarDecl(mut var) => {
+                assert_eq!(var.decls.len(), 1);
+                Stmt::Decl(Decl::Var(VarDecl {
+                    decls: vec![VarDeclarator {
+                        init: Some(box Expr::Ident(value.clone())),
+                        ..var.decls.pop().unwrap()
+                    }],
+                    ..var
+                }))
+            }
+            VarDeclOrPat::Pat(pat) => Stmt::Expr(box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box pat),
+                op: op!("="),
+                right: box Expr::Ident(value.clone()),
+            })),
+        },
+    );
+
+    let for_stmt = Stmt::For(ForStmt {
+        span,
+        init: Some(VarDeclOrExpr::VarDecl(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![
+                declarator(
+                    &iterator,
+                    Some(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: helper!(async_iterator, "asyncIterator"),
+                        args: vec![right.as_arg()],
+                        type_args: None,
+                    })),
+                ),
+                declarator(&step, None),
+                declarator(&value, None),
+            ],
+        })),
+        // _step = await _iterator.next(), _iteratorNormalCompletion = _step.done,
+        // _value = await _step.value, !_iteratorNormalCompletion
+        test: Some(box Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs: vec![
+                box assign(
+                    &step,
+                    await_expr(call(iterator.clone().member(quote_ident!("next")))),
+                ),
+                box assign(
+                    &normal_completion,
+                    step.clone().member(quote_ident!("done")),
+                ),
+                box assign(
+                    &value,
+                    await_expr(box step.clone().member(quote_ident!("value"))),
+                ),
+                box Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!("!"),
+                    arg: box Expr::Ident(normal_completion.clone()),
+                }),
+            ],
+        })),
+        update: Some(box assign(&normal_completion, bool_lit(true))),
+        body: box Stmt::Block(body),
+    });
+    let for_stmt = match label {
+        Some(label) => Stmt::Labeled(LabeledStmt {
+            span,
+            label,
+            body: box for_stmt,
+        }),
+        None => for_stmt,
+    };
+
+    // if (!_iteratorNormalCompletion && _iterator.return != null) {
+    //     await _iterator.return();
+    // }
+    let close = Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: box Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            left: box Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("!"),
+                arg: box Expr::Ident(normal_completion.clone()),
+            }),
+            op: op!("&&"),
+            right: box Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: box iterator.clone().member(quote_ident!("return")),
+                op: op!("!="),
+                right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+            }),
+        }),
+        cons: box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Expr(box await_expr(call(
+                iterator.member(quote_ident!("return")),
+            )))],
+        }),
+        alt: None,
+    });
+    // if (_didIteratorError) {
+    //     throw _iteratorError;
+    // }
+    let rethrow = Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: box Expr::Ident(did_error.clone()),
+        cons: box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Throw(ThrowStmt {
+                span: DUMMY_SP,
+                arg: box Expr::Ident(error.clone()),
+            })],
+        }),
+        alt: None,
+    });
+
+    Stmt::Try(TryStmt {
+        span: DUMMY_SP,
+        block: BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vec![
+                        declarator(&normal_completion, Some(bool_lit(true))),
+                        declarator(&did_error, Some(bool_lit(false))),
+                        declarator(&error, None),
+                    ],
+                })),
+                for_stmt,
+            ],
+        },
+        handler: Some(CatchClause {
+            span: DUMMY_SP,
+            param: Some(Pat::Ident(err.clone())),
+            body: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![
+                    Stmt::Expr(box assign(&did_error, bool_lit(true))),
+                    Stmt::Expr(box assign(&error, Expr::Ident(err))),
+                ],
+            },
+        }),
+        finalizer: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Try(TryStmt {
+                span: DUMMY_SP,
+                block: BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![close],
+                },
+                handler: None,
+                finalizer: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![rethrow],
+                }),
+            })],
+        }),
+    })
+}
+
+fn contains_super<N>(node: &N) -> bool
+where
+    N: VisitWith<SuperFinder>,
+{
+    let mut v = SuperFinder { found: false };
+    node.visit_with(&mut v);
+    v.found
+}
+
+struct SuperFinder {
+    found: bool,
+}
+
+impl Visit<Super> for SuperFinder {
+    fn visit(&mut self, _: &Super) {
+        self.found = true;
+    }
+}
+
+/// `super` in functions other than arrows refers to another object.
+impl Visit<Function> for SuperFinder {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<Constructor> for SuperFinder {
+    fn visit(&mut self, _: &Constructor) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        yield_await,
+        "async function* foo(a) {
+    const b = await a;
+    yield b;
+    yield await bar(b);
+}",
+        "function foo() {
+    return _wrapAsyncGenerator(function* (a) {
+        const b = yield _awaitAsyncGenerator(a);
+        yield b;
+        yield yield _awaitAsyncGenerator(bar(b));
+    }).apply(this, arguments);
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        yield_delegate,
+        "const foo = async function* () { yield* bar(); };",
+        "const foo = function () {
+    return _wrapAsyncGenerator(function* () {
+        yield* _asyncGeneratorDelegate(_asyncIterator(bar()), _awaitAsyncGenerator);
+    }).apply(this, arguments);
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        for_await_in_generator,
+        "async function* foo(xs) { for await (const x of xs) yield x; }",
+        "function foo() {
+    return _wrapAsyncGenerator(function* (xs) {
+        try {
+            var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+            for (var _iterator = _asyncIterator(xs), _step, _value; _step = yield \
+         _awaitAsyncGenerator(_iterator.next()), _iteratorNormalCompletion = _step.done, _value = \
+         yield _awaitAsyncGenerator(_step.value), !_iteratorNormalCompletion; \
+         _iteratorNormalCompletion = true) {
+                const x = _value;
+                yield x;
+            }
+        } catch (err) {
+            _didIteratorError = true;
+            _iteratorError = err;
+        } finally {
+            try {
+                if (!_iteratorNormalCompletion && _iterator.return != null) {
+                    yield _awaitAsyncGenerator(_iterator.return());
+                }
+            } finally {
+                if (_didIteratorError) {
+                    throw _iteratorError;
+                }
+            }
+        }
+    }).apply(this, arguments);
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        for_await_in_async_fn,
+        "async function foo(xs) {
+    outer: for await (const { a, b } of xs) {
+        if (a) continue outer;
+        use(b);
+    }
+}",
+        "async function foo(xs) {
+    try {
+        var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+        outer: for (var _iterator = _asyncIterator(xs), _step, _value; _step = await \
+         _iterator.next(), _iteratorNormalCompletion = _step.done, _value = await _step.value, \
+         !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+            const { a, b } = _value;
+            if (a) continue outer;
+            use(b);
+        }
+    } catch (err) {
+        _didIteratorError = true;
+        _iteratorError = err;
+    } finally {
+        try {
+            if (!_iteratorNormalCompletion && _iterator.return != null) {
+                await _iterator.return();
+            }
+        } finally {
+            if (_didIteratorError) {
+                throw _iteratorError;
+            }
+        }
+    }
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        for_await_in_async_arrow,
+        "const foo = async (xs) => { for await (x of xs); };",
+        "const foo = async (xs) => {
+    try {
+        var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+        for (var _iterator = _asyncIterator(xs), _step, _value; _step = await _iterator.next(), \
+         _iteratorNormalCompletion = _step.done, _value = await _step.value, \
+         !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+            x = _value;
+            ;
+        }
+    } catch (err) {
+        _didIteratorError = true;
+        _iteratorError = err;
+    } finally {
+        try {
+            if (!_iteratorNormalCompletion && _iterator.return != null) {
+                await _iterator.return();
+            }
+        } finally {
+            if (_didIteratorError) {
+                throw _iteratorError;
+            }
+        }
+    }
+};"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        for_await_break_exec,
+        "let closed = false;
+const iterable = {
+    [Symbol.asyncIterator]() {
+        let i = 0;
+        return {
+            next() {
+                i += 1;
+                return Promise.resolve({ value: i, done: false });
+            },
+            return() {
+                closed = true;
+                return Promise.resolve({ done: true });
+            },
+        };
+    },
+};
+
+async function take(n) {
+    const values = [];
+    for await (const x of iterable) {
+        values.push(x);
+        if (values.length === n) break;
+    }
+    return values;
+}
+
+return take(3).then((values) => {
+    expect(values).toEqual([1, 2, 3]);
+    expect(closed).toBe(true);
+});"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        not_async_generator,
+        "async function foo() { await a; }
+function* bar() { yield* a; }
+async function* baz() { const f = async () => await a; }",
+        "async function foo() { await a; }
+function* bar() { yield* a; }
+function baz() {
+    return _wrapAsyncGenerator(function* () {
+        const f = async () => await a;
+    }).apply(this, arguments);
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        method_with_super,
+        "class Foo extends Bar { async *foo() { yield super.foo(); } }",
+        "class Foo extends Bar { async *foo() { yield super.foo(); } }"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| async_generator(),
+        yield_await_exec,
+        "async function* numbers() {
+    yield await Promise.resolve(1);
+    yield 2;
+    yield* (async function* () { yield 3; })();
+}
+
+async function* doubled() {
+    for await (const n of numbers()) {
+        yield n * 2;
+    }
+}
+
+return (async () => {
+    const values = [];
+    for await (const n of doubled()) {
+        values.push(n);
+    }
+    expect(values).toEqual([2, 4, 6]);
+})();"
+    );
+}
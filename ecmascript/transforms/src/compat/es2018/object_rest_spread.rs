@@ -1,10 +1,12 @@
 use crate::{
     pass::Pass,
     util::{
-        alias_ident_for, alias_if_required, is_literal, var::VarCollector, ExprFactory, StmtLike,
+        alias_ident_for, alias_if_required, is_literal, prepend, var::VarCollector, ExprFactory,
+        StmtLike,
     },
 };
 use ast::*;
+use serde::Deserialize;
 use std::{iter, mem};
 use swc_common::{
     chain, util::move_map::MoveMap, Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP,
@@ -14,8 +16,20 @@ use swc_common::{
 mod tests;
 
 /// `@babel/plugin-proposal-object-rest-spread`
-pub fn object_rest_spread() -> impl Pass {
-    chain!(ObjectRest, ObjectSpread)
+pub fn object_rest_spread(c: Config) -> impl Pass {
+    chain!(ObjectRest, ObjectSpread { c })
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Use `Object.assign` instead of the `_objectSpread` helper for spread
+    /// in object literals.
+    ///
+    /// `Object.assign` requires es2015, and sets properties instead of
+    /// defining them, so setters of `Object.prototype` are invoked.
+    #[serde(default)]
+    pub use_builtins: bool,
 }
 
 struct ObjectRest;
@@ -657,25 +671,21 @@ impl RestFolder {
             })
             .collect();
 
-        (
-            params,
-            BlockStmt {
-                stmts: if self.vars.is_empty() {
-                    None
-                } else {
-                    Some(Stmt::Decl(Decl::Var(VarDecl {
-                        span: DUMMY_SP,
-                        kind: VarDeclKind::Var,
-                        decls: mem::replace(&mut self.vars, vec![]),
-                        declare: false,
-                    })))
-                }
-                .into_iter()
-                .chain(body.stmts)
-                .collect(),
-                ..body
-            },
-        )
+        let mut stmts = body.stmts;
+        if !self.vars.is_empty() {
+            // Directives like `"use asm"` must stay first.
+            prepend(
+                &mut stmts,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: mem::replace(&mut self.vars, vec![]),
+                    declare: false,
+                })),
+            );
+        }
+
+        (params, BlockStmt { stmts, ..body })
     }
 
     fn fold_rest(
@@ -1007,7 +1017,9 @@ fn simplify_pat(pat: Pat) -> Pat {
     pat.fold_with(&mut PatSimplifier)
 }
 
-struct ObjectSpread;
+struct ObjectSpread {
+    c: Config,
+}
 
 impl Fold<Expr> for ObjectSpread {
     fn fold(&mut self, expr: Expr) -> Expr {
@@ -1068,7 +1080,11 @@ impl Fold<Expr> for ObjectSpread {
 
                 Expr::Call(CallExpr {
                     span,
-                    callee: helper!(object_spread, "objectSpread"),
+                    callee: if self.c.use_builtins {
+                        member_expr!(DUMMY_SP, Object.assign).as_callee()
+                    } else {
+                        helper!(object_spread, "objectSpread")
+                    },
                     args,
                     type_args: Default::default(),
                 })
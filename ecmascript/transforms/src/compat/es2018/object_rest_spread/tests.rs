@@ -1,4 +1,4 @@
-use super::object_rest_spread;
+use super::{object_rest_spread, Config};
 use crate::{
     compat::es2015::{destructuring, spread},
     modules::common_js::common_js,
@@ -13,7 +13,7 @@ fn syntax() -> Syntax {
 }
 
 fn tr() -> impl Fold<Module> {
-    object_rest_spread()
+    object_rest_spread(Default::default())
 }
 
 test!(
@@ -1236,6 +1236,24 @@ test!(
     r#"var z = _objectSpread({}, x);"#
 );
 
+test!(
+    syntax(),
+    |_| tr(),
+    spread_helper,
+    r#"var z = { a, ...x, b: 1 };"#,
+    r#"var z = _objectSpread({ a }, x, { b: 1 });"#
+);
+
+test!(
+    syntax(),
+    |_| object_rest_spread(Config { use_builtins: true }),
+    spread_use_builtins,
+    r#"var z = { a, ...x, b: 1 };
+var y = { ...x };"#,
+    r#"var z = Object.assign({ a }, x, { b: 1 });
+var y = Object.assign({}, x);"#
+);
+
 // object_spread_assignment
 test!(
     syntax(),
@@ -2116,7 +2134,7 @@ test!(
     |_| chain!(
         resolver(),
         tr(),
-        destructuring(destructuring::Config { loose: false }),
+        destructuring(Default::default()),
         common_js(Default::default()),
     ),
     regression_t7178,
@@ -2784,3 +2802,22 @@ const _ref2 = foo((_param)=>{
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| tr(),
+    use_asm_directive,
+    r#"
+function foo({...bar}) {
+  "use asm";
+  return bar;
+}
+"#,
+    r#"
+function foo(_param) {
+  "use asm";
+  var bar = _extends({}, _param);
+  return bar;
+}
+"#
+);
@@ -0,0 +1,19 @@
+pub use self::big_int::big_int;
+use crate::pass::Pass;
+use serde::Deserialize;
+
+pub mod big_int;
+
+/// Compiles es2020 to es2019.
+///
+/// Numeric separators (es2021) don't need a pass, because the parser drops
+/// them and the code generator prints the plain number.
+pub fn es2020(c: Config) -> impl Pass {
+    big_int(c.big_int)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub big_int: big_int::Config,
+}
@@ -0,0 +1,139 @@
+use crate::{pass::Pass, util::HANDLER};
+use ast::*;
+use serde::{Deserialize, Serialize};
+use swc_common::{Fold, FoldWith};
+
+/// Reports BigInt literals, which can not be lowered faithfully for targets
+/// without BigInt.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// const big = 10n;
+/// ```
+///
+/// ## Out
+///
+/// ```text
+/// error: BigInt literals are not supported by the target
+/// ```
+pub fn big_int(c: Config) -> impl Pass {
+    BigIntLit { c }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub big_int: Mode,
+}
+
+/// What to do with BigInt literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Mode {
+    /// Report an error.
+    Error,
+    /// Leave them as-is, e.g. if the runtime supports BigInt.
+    Preserve,
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Mode::Error
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BigIntLit {
+    c: Config,
+}
+
+impl Fold<Lit> for BigIntLit {
+    fn fold(&mut self, lit: Lit) -> Lit {
+        match lit {
+            Lit::BigInt(BigInt { span, .. }) if self.c.big_int == Mode::Error => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(span, "BigInt literals are not supported by the target")
+                        .emit()
+                });
+            }
+            _ => {}
+        }
+
+        lit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use swc_ecma_parser::{EsConfig, Syntax};
+
+    /// Returns the stderr of applying the pass to `src`.
+    fn run(c: Config, src: &str) -> Option<String> {
+        ::testing::run_test(false, |cm, handler| {
+            HANDLER.set(handler, || {
+                let mut tester = Tester {
+                    cm,
+                    handler,
+                    comments: Default::default(),
+                };
+
+                let stmts = tester.parse_stmts("input.js", src)?;
+                let expected = stmts.clone();
+                let stmts = stmts.fold_with(&mut big_int(c));
+                assert_eq!(stmts, expected);
+
+                if handler.has_errors() {
+                    Err(())
+                } else {
+                    Ok(())
+                }
+            })
+        })
+        .err()
+        .map(|stderr| stderr.to_string())
+    }
+
+    #[test]
+    fn big_int_error() {
+        let stderr = run(Default::default(), "foo(1n);").expect("should fail");
+
+        assert!(stderr.contains("BigInt literals are not supported by the target"));
+        assert!(stderr.contains("input.js:1:5"), "{}", stderr);
+    }
+
+    #[test]
+    fn big_int_preserve() {
+        assert_eq!(
+            run(
+                Config {
+                    big_int: Mode::Preserve
+                },
+                "foo(1n, 0x1fn);"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn no_big_int() {
+        assert_eq!(run(Default::default(), "foo(1);"), None);
+    }
+
+    test!(
+        Syntax::Es(EsConfig {
+            num_sep: true,
+            ..Default::default()
+        }),
+        |_| big_int(Default::default()),
+        num_sep,
+        "foo(1_000, 0x1_0, 1_0.0_1);",
+        "foo(1000, 0x10, 10.01);"
+    );
+}
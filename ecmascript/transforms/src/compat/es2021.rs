@@ -0,0 +1,8 @@
+pub use self::logical_assign::logical_assign;
+use crate::pass::Pass;
+
+mod logical_assign;
+
+pub fn es2021() -> impl Pass {
+    logical_assign()
+}
@@ -0,0 +1,408 @@
+use crate::{
+    normalize_pat::strip_parens,
+    pass::Pass,
+    util::{alias_ident_for, alias_if_required, is_literal, undefined, StmtLike, HANDLER},
+};
+use ast::*;
+use swc_common::{Fold, FoldWith, Span, Spanned, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-proposal-logical-assignment-operators`
+///
+/// The assignment is skipped entirely if the left hand side short-circuits,
+/// so setters are not called in that case.
+///
+/// Targets other than identifiers and member expressions, which are possibly
+/// parenthesized, are reported as errors.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// a ||= b;
+/// obj[key()] &&= c;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _prop;
+/// a || (a = b);
+/// obj[_prop = key()] && (obj[_prop] = c);
+/// ```
+pub fn logical_assign() -> impl Pass {
+    LogicalAssign
+}
+
+#[derive(Clone, Copy)]
+struct LogicalAssign;
+
+#[derive(Default)]
+struct AssignFolder {
+    vars: Vec<VarDeclarator>,
+}
+
+impl Fold<Expr> for AssignFolder {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Assign(AssignExpr {
+                span,
+                left,
+                op: op @ op!("&&="),
+                right,
+            })
+            | Expr::Assign(AssignExpr {
+                span,
+                left,
+                op: op @ op!("||="),
+                right,
+            })
+            | Expr::Assign(AssignExpr {
+                span,
+                left,
+                op: op @ op!("??="),
+                right,
+            }) => {
+                let left = match left {
+                    PatOrExpr::Pat(box Pat::Expr(e)) | PatOrExpr::Expr(e) => {
+                        PatOrExpr::Expr(strip_parens(e))
+                    }
+                    _ => left,
+                };
+                let (read, target) = match left {
+                    PatOrExpr::Pat(box Pat::Ident(i)) | PatOrExpr::Expr(box Expr::Ident(i)) => {
+                        (box Expr::Ident(i.clone()), box Expr::Ident(i))
+                    }
+
+                    PatOrExpr::Expr(box Expr::Member(e)) => self.memoize_member(e),
+
+                    left => {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    left.span(),
+                                    "invalid target of a logical assignment",
+                                )
+                                .emit()
+                        });
+
+                        return Expr::Assign(AssignExpr {
+                            span,
+                            left,
+                            op,
+                            right,
+                        });
+                    }
+                };
+
+                let assign = box Expr::Assign(AssignExpr {
+                    span,
+                    left: PatOrExpr::Expr(target),
+                    op: op!("="),
+                    right,
+                });
+
+                match op {
+                    op!("&&=") => mk_bin(span, read, op!("&&"), assign),
+                    op!("||=") => mk_bin(span, read, op!("||"), assign),
+                    _ => self.mk_nullish(span, read, assign),
+                }
+            }
+            _ => e,
+        }
+    }
+}
+
+impl AssignFolder {
+    /// Returns `(read, target)` for `obj[prop]`, where `read` evaluates `obj`
+    /// and `prop` once and stores them in temporaries, and `target` uses the
+    /// temporaries.
+    ///
+    /// e.g. `(_ref = a.b)[_prop = key()]` and `_ref[_prop]`
+    fn memoize_member(&mut self, e: MemberExpr) -> (Box<Expr>, Box<Expr>) {
+        let (obj, obj_alias) = match e.obj {
+            ExprOrSuper::Expr(box Expr::This(this)) => {
+                (ExprOrSuper::Expr(box Expr::This(this)), None)
+            }
+            ExprOrSuper::Expr(obj) => {
+                let (obj, alias) = self.memoize(obj, "_ref");
+                (ExprOrSuper::Expr(obj), alias)
+            }
+            ExprOrSuper::Super(s) => (ExprOrSuper::Super(s), None),
+        };
+        let (prop, prop_alias) = if e.computed && !is_literal(&e.prop) {
+            self.memoize(e.prop, "_prop")
+        } else {
+            (e.prop, None)
+        };
+
+        let target = MemberExpr {
+            span: e.span,
+            obj: match obj_alias {
+                Some(ref alias) => ExprOrSuper::Expr(box Expr::Ident(alias.clone())),
+                None => obj.clone(),
+            },
+            prop: match prop_alias {
+                Some(ref alias) => box Expr::Ident(alias.clone()),
+                None => prop.clone(),
+            },
+            computed: e.computed,
+        };
+        let read = MemberExpr {
+            span: e.span,
+            obj,
+            prop,
+            computed: e.computed,
+        };
+
+        (box Expr::Member(read), box Expr::Member(target))
+    }
+
+    /// Returns `e` or `_alias = e`, and the alias if one is declared.
+    fn memoize(&mut self, e: Box<Expr>, default: &str) -> (Box<Expr>, Option<Ident>) {
+        let (alias, aliased) = alias_if_required(&e, default);
+        if !aliased {
+            return (e, None);
+        }
+
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: alias.clone().into(),
+            init: None,
+            definite: false,
+        });
+        (
+            box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                op: op!("="),
+                right: e,
+            }),
+            Some(alias),
+        )
+    }
+
+    /// `(_ref = read) !== null && _ref !== void 0 ? _ref : assign`
+    fn mk_nullish(&mut self, span: Span, read: Box<Expr>, assign: Box<Expr>) -> Expr {
+        let ident = match *read {
+            Expr::Ident(ref i) => Some(i.clone()),
+            _ => None,
+        };
+        let (init, value) = match ident {
+            Some(i) => (read, Expr::Ident(i)),
+            None => {
+                let alias = alias_ident_for(&read, "_ref");
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: alias.clone().into(),
+                    init: None,
+                    definite: false,
+                });
+                (
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                        op: op!("="),
+                        right: read,
+                    }),
+                    Expr::Ident(alias),
+                )
+            }
+        };
+
+        Expr::Cond(CondExpr {
+            span,
+            test: box mk_bin(
+                DUMMY_SP,
+                box mk_bin(
+                    DUMMY_SP,
+                    init,
+                    op!("!=="),
+                    box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                ),
+                op!("&&"),
+                box mk_bin(DUMMY_SP, box value.clone(), op!("!=="), undefined(DUMMY_SP)),
+            ),
+            cons: box value,
+            alt: assign,
+        })
+    }
+}
+
+impl<T: StmtLike + VisitWith<ShouldFold>> Fold<Vec<T>> for LogicalAssign
+where
+    Vec<T>: FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        if !should_fold(&stmts) {
+            return stmts;
+        }
+        let stmts = stmts.fold_children(self);
+
+        let mut buf = vec![];
+
+        for stmt in stmts {
+            match stmt.try_into_stmt() {
+                Err(module_item) => buf.push(module_item),
+                Ok(stmt) => {
+                    let mut folder = AssignFolder::default();
+                    let stmt = stmt.fold_with(&mut folder);
+
+                    if !folder.vars.is_empty() {
+                        buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                            span: DUMMY_SP,
+                            kind: VarDeclKind::Var,
+                            decls: folder.vars,
+                            declare: false,
+                        }))));
+                    }
+
+                    buf.push(T::from_stmt(stmt));
+                }
+            }
+        }
+
+        buf
+    }
+}
+
+fn mk_bin(span: Span, left: Box<Expr>, op: BinaryOp, right: Box<Expr>) -> Expr {
+    Expr::Bin(BinExpr {
+        span,
+        left,
+        op,
+        right,
+    })
+}
+
+fn should_fold<N>(node: &N) -> bool
+where
+    N: VisitWith<ShouldFold>,
+{
+    let mut v = ShouldFold { found: false };
+    node.visit_with(&mut v);
+    v.found
+}
+
+struct ShouldFold {
+    found: bool,
+}
+
+impl Visit<AssignExpr> for ShouldFold {
+    fn visit(&mut self, e: &AssignExpr) {
+        match e.op {
+            op!("&&=") | op!("||=") | op!("??=") => self.found = true,
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_ecma_parser::{EsConfig, Syntax};
+
+    fn syntax() -> Syntax {
+        Syntax::Es(EsConfig {
+            logical_assignment: true,
+            ..Default::default()
+        })
+    }
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        and_ident,
+        "a &&= b;",
+        "a && (a = b);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        or_ident,
+        "a ||= b;",
+        "a || (a = b);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        nullish_ident,
+        "a ??= b;",
+        "a !== null && a !== void 0 ? a : a = b;"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        paren_target,
+        "(obj.a) ||= b;
+(c) &&= d;",
+        "obj.a || (obj.a = b);
+c && (c = d);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        and_computed_member,
+        "obj[key()] &&= v;",
+        "var _prop;
+obj[_prop = key()] && (obj[_prop] = v);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        or_computed_member,
+        "a.b[key()] ||= v;",
+        "var _b, _prop;
+(_b = a.b)[_prop = key()] || (_b[_prop] = v);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        nullish_computed_member,
+        "obj[key()] ??= v;",
+        "var _prop, _ref;
+(_ref = obj[_prop = key()]) !== null && _ref !== void 0 ? _ref : obj[_prop] = v;"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assign(),
+        nested_fn,
+        "function f() { return this.x ||= 1; }",
+        "function f() { return this.x || (this.x = 1); }"
+    );
+
+    test_exec!(
+        syntax(),
+        |_| logical_assign(),
+        setter_not_called,
+        "let calls = 0, keys = 0;
+const obj = {
+  get x() { return 1; },
+  set x(v) { calls++; },
+  y: null,
+};
+function key() { keys++; return 'x'; }
+
+obj[key()] ||= 2;
+obj[key()] ??= 2;
+expect(calls).toBe(0);
+expect(keys).toBe(2);
+
+obj[key()] &&= 2;
+expect(calls).toBe(1);
+
+obj.y ??= 3;
+expect(obj.y).toBe(3);
+obj.y &&= 0;
+expect(obj.y).toBe(0);"
+    );
+}
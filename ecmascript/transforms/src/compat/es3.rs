@@ -1,19 +1,28 @@
 pub use self::{
-    member_expr_lits::MemberExprLit, prop_lits::PropertyLiteral, reserved_word::ReservedWord,
+    member_expr_lits::MemberExprLit, prop_lits::PropertyLiteral,
+    property_mutators::PropertyMutators, reserved_word::ReservedWord,
 };
-use crate::pass::Pass;
+use crate::{compat::es2015::Shorthand, pass::Pass};
 use ast::Expr;
+use swc_common::chain;
 
 mod member_expr_lits;
 mod prop_lits;
+mod property_mutators;
 mod reserved_word;
 
 /// Make output es3-compatible.
 pub fn es3(preserve_import: bool) -> impl Pass {
-    chain_at!(
-        Expr,
-        PropertyLiteral,
-        MemberExprLit,
-        ReservedWord { preserve_import }
+    chain!(
+        // Declares temporaries in statement lists, so it can't be chained at
+        // `Expr`.
+        PropertyMutators,
+        chain_at!(
+            Expr,
+            PropertyLiteral,
+            MemberExprLit,
+            Shorthand,
+            ReservedWord { preserve_import }
+        )
     )
 }
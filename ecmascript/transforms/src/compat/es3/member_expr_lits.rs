@@ -19,6 +19,9 @@ use swc_common::{Fold, FoldWith};
 /// obj["const"] = "isKeyword";
 /// obj["var"] = "isKeyword";
 /// ```
+///
+/// Optional member accesses like `obj?.["foo"]` are handled in the same way,
+/// so this pass can be applied before or after `optional_chaining`.
 #[derive(Default, Clone, Copy)]
 pub struct MemberExprLit;
 
@@ -73,6 +76,15 @@ impl Fold<MemberExpr> for MemberExprLit {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{tests::Tester, typescript::optional_chaining};
+    use swc_common::chain;
+    use swc_ecma_parser::{Syntax, TsConfig};
+
+    fn ts() -> Syntax {
+        Syntax::Typescript(TsConfig {
+            ..Default::default()
+        })
+    }
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
@@ -103,4 +115,66 @@ obj["var"] = "isKeyword";"#
         "_query[idx]=$this.attr('data-ref');",
         "_query[idx]=$this.attr('data-ref');"
     );
+
+    #[test]
+    fn optional_member() {
+        Tester::run(|tester| {
+            let module = tester
+                .with_parser("input.ts", ts(), r#"obj?.["foo"]; obj?.["var"];"#, |p| {
+                    p.parse_module().map_err(|mut e| {
+                        e.emit();
+                    })
+                })?
+                .fold_with(&mut MemberExprLit);
+
+            let props: Vec<_> = module
+                .body
+                .iter()
+                .map(|item| match *item {
+                    ModuleItem::Stmt(Stmt::Expr(box Expr::TsOptChain(TsOptChain {
+                        expr: box Expr::Member(ref e),
+                        ..
+                    }))) => e,
+                    _ => panic!("unexpected item: {:?}", item),
+                })
+                .collect();
+
+            match *props[0] {
+                MemberExpr {
+                    computed: false,
+                    prop: box Expr::Ident(ref i),
+                    ..
+                } if &*i.sym == "foo" => {}
+                ref e => panic!("`obj?.foo` expected, got {:?}", e),
+            }
+            match *props[1] {
+                MemberExpr {
+                    computed: true,
+                    prop: box Expr::Lit(Lit::Str(ref s)),
+                    ..
+                } if &*s.value == "var" => {}
+                ref e => panic!("`obj?.[\"var\"]` expected, got {:?}", e),
+            }
+
+            Ok(())
+        });
+    }
+
+    test!(
+        ts(),
+        |_| chain!(MemberExprLit, optional_chaining()),
+        optional_member_before_lowering,
+        r#"obj?.["foo"]; obj?.["var"];"#,
+        r#"obj === null || obj === void 0 ? void 0 : obj.foo;
+obj === null || obj === void 0 ? void 0 : obj["var"];"#
+    );
+
+    test!(
+        ts(),
+        |_| chain!(optional_chaining(), MemberExprLit),
+        optional_member_after_lowering,
+        r#"obj?.["foo"]; obj?.["var"];"#,
+        r#"obj === null || obj === void 0 ? void 0 : obj.foo;
+obj === null || obj === void 0 ? void 0 : obj["var"];"#
+    );
 }
@@ -43,6 +43,10 @@ impl Fold<PropName> for PropertyLiteral {
         let n = validate!(n.fold_children(self));
 
         match n {
+            PropName::Str(ref s) if s.is_index_key() => PropName::Num(Number {
+                span: s.span,
+                value: s.value.parse().unwrap(),
+            }),
             PropName::Str(Str {
                 value: sym, span, ..
             }) => {
@@ -93,7 +97,7 @@ mod tests {
 };"#,
         r#"var foo = {
   bar: function () {},
-  '1': function () {},
+  1: function () {},
 
   "default": 1,
   [a]: 2,
@@ -116,4 +120,12 @@ var x = {
 };"#,
         ok_if_code_eq
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyLiteral,
+        num_key,
+        r#"var x = { '0': a, '1.5': b, '00': c };"#,
+        r#"var x = { 0: a, '1.5': b, '00': c };"#
+    );
 }
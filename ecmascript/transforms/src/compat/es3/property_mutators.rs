@@ -0,0 +1,415 @@
+use crate::util::{alias_ident_for, is_literal, ExprFactory, StmtLike};
+use ast::*;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Span, DUMMY_SP};
+
+/// babel: `transform-property-mutators`
+///
+/// # Input
+/// ```js
+/// var foo = {
+///   get bar() {
+///     return this._bar;
+///   },
+///   set bar(value) {
+///     this._bar = value;
+///   },
+///   baz: 1
+/// };
+/// ```
+///
+/// # Output
+/// ```js
+/// var foo = Object.defineProperty({
+///   baz: 1
+/// }, "bar", {
+///   get: function () {
+///     return this._bar;
+///   },
+///   set: function (value) {
+///     this._bar = value;
+///   },
+///   enumerable: true,
+///   configurable: true
+/// });
+/// ```
+///
+/// A getter and a setter are grouped if their keys are identifiers or
+/// strings with the same value. Accessors are defined after other properties,
+/// so an accessor overridden by a later property is dropped.
+///
+/// If an accessor has a computed key, computed keys of the object are stored
+/// in temporary variables in source order, so they are evaluated in the same
+/// order as in the object literal.
+#[derive(Default, Clone, Copy)]
+pub struct PropertyMutators;
+
+impl Fold<Vec<ModuleItem>> for PropertyMutators {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        fold_stmts(items.fold_children(self))
+    }
+}
+
+impl Fold<Vec<Stmt>> for PropertyMutators {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        fold_stmts(stmts.fold_children(self))
+    }
+}
+
+fn fold_stmts<T>(stmts: Vec<T>) -> Vec<T>
+where
+    T: StmtLike + FoldWith<ObjectFolder>,
+{
+    let mut buf = Vec::with_capacity(stmts.len());
+
+    for stmt in stmts {
+        let mut folder = ObjectFolder::default();
+        let stmt = stmt.fold_with(&mut folder);
+
+        // Declare temporaries used for keys, e.g. `var _key;`
+        if !folder.vars.is_empty() {
+            buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: folder.vars,
+                declare: false,
+            }))));
+        }
+
+        buf.push(stmt);
+    }
+
+    buf
+}
+
+#[derive(Default)]
+struct ObjectFolder {
+    vars: Vec<VarDeclarator>,
+}
+
+struct Accessor {
+    /// Name used to group a getter and a setter.
+    name: Option<JsWord>,
+    key: Box<Expr>,
+    getter: Option<Function>,
+    setter: Option<Function>,
+}
+
+/// Returns the name of a property if it's statically known.
+fn prop_name(key: &PropName) -> Option<JsWord> {
+    match *key {
+        PropName::Ident(ref i) => Some(i.sym.clone()),
+        PropName::Str(ref s) => Some(s.value.clone()),
+        _ => None,
+    }
+}
+
+fn key_expr(key: PropName) -> Box<Expr> {
+    match key {
+        PropName::Ident(i) => box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym))),
+        PropName::Str(s) => box Expr::Lit(Lit::Str(s)),
+        PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
+        PropName::Computed(c) => c.expr,
+    }
+}
+
+/// Returns true if `key` is evaluated to a value which is not known
+/// statically.
+fn is_dynamic_key(key: &PropName) -> bool {
+    match *key {
+        PropName::Computed(ref c) => !is_literal(&c.expr),
+        _ => false,
+    }
+}
+
+fn function(span: Span, params: Vec<Pat>, body: Option<BlockStmt>) -> Function {
+    Function {
+        params,
+        decorators: Default::default(),
+        span,
+        body,
+        is_generator: false,
+        is_async: false,
+        type_params: Default::default(),
+        return_type: Default::default(),
+    }
+}
+
+fn descriptor_prop(name: &str, value: Expr) -> PropOrSpread {
+    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(quote_ident!(name)),
+        value: box value,
+    }))
+}
+
+impl ObjectFolder {
+    /// Stores the value of a computed `key` in a temporary variable.
+    fn hoist_key(&mut self, exprs: &mut Vec<Box<Expr>>, key: PropName) -> PropName {
+        match key {
+            PropName::Computed(ComputedPropName { span, expr }) if !is_literal(&expr) => {
+                let alias = alias_ident_for(&expr, "_key");
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: alias.clone().into(),
+                    init: None,
+                    definite: false,
+                });
+                exprs.push(box Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                    op: op!("="),
+                    right: expr,
+                }));
+
+                PropName::Computed(ComputedPropName {
+                    span,
+                    expr: box Expr::Ident(alias),
+                })
+            }
+            _ => key,
+        }
+    }
+}
+
+impl Fold<Expr> for ObjectFolder {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        let ObjectLit { span, props } = match e {
+            Expr::Object(obj) => obj,
+            _ => return e,
+        };
+
+        let hoist_keys = props.iter().any(|prop| match *prop {
+            PropOrSpread::Prop(box Prop::Getter(GetterProp { ref key, .. }))
+            | PropOrSpread::Prop(box Prop::Setter(SetterProp { ref key, .. })) => {
+                is_dynamic_key(key)
+            }
+            _ => false,
+        });
+        let mut exprs = vec![];
+
+        let mut accessors: Vec<Accessor> = vec![];
+        let mut rest = Vec::with_capacity(props.len());
+
+        for prop in props {
+            let prop = if hoist_keys {
+                match prop {
+                    PropOrSpread::Prop(box prop) => PropOrSpread::Prop(box match prop {
+                        Prop::KeyValue(p) => Prop::KeyValue(KeyValueProp {
+                            key: self.hoist_key(&mut exprs, p.key),
+                            ..p
+                        }),
+                        Prop::Method(p) => Prop::Method(MethodProp {
+                            key: self.hoist_key(&mut exprs, p.key),
+                            ..p
+                        }),
+                        Prop::Getter(p) => Prop::Getter(GetterProp {
+                            key: self.hoist_key(&mut exprs, p.key),
+                            ..p
+                        }),
+                        Prop::Setter(p) => Prop::Setter(SetterProp {
+                            key: self.hoist_key(&mut exprs, p.key),
+                            ..p
+                        }),
+                        _ => prop,
+                    }),
+                    _ => prop,
+                }
+            } else {
+                prop
+            };
+
+            let (key, getter, setter) = match prop {
+                PropOrSpread::Prop(box Prop::Getter(GetterProp { span, key, body })) => {
+                    (key, Some(function(span, vec![], body)), None)
+                }
+                PropOrSpread::Prop(box Prop::Setter(SetterProp {
+                    span,
+                    key,
+                    param,
+                    body,
+                })) => (key, None, Some(function(span, vec![param], body))),
+                _ => {
+                    let name = match prop {
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { ref key, .. }))
+                        | PropOrSpread::Prop(box Prop::Method(MethodProp { ref key, .. })) => {
+                            prop_name(key)
+                        }
+                        PropOrSpread::Prop(box Prop::Shorthand(ref i)) => Some(i.sym.clone()),
+                        _ => None,
+                    };
+                    if let Some(name) = name {
+                        accessors.retain(|a| a.name.as_ref() != Some(&name));
+                    }
+                    rest.push(prop);
+                    continue;
+                }
+            };
+
+            let name = prop_name(&key);
+            match accessors
+                .iter_mut()
+                .find(|a| a.name.is_some() && a.name == name)
+            {
+                Some(a) => {
+                    if getter.is_some() {
+                        a.getter = getter;
+                    }
+                    if setter.is_some() {
+                        a.setter = setter;
+                    }
+                }
+                None => accessors.push(Accessor {
+                    name,
+                    key: key_expr(key),
+                    getter,
+                    setter,
+                }),
+            }
+        }
+
+        if accessors.is_empty() {
+            return Expr::Object(ObjectLit { span, props: rest });
+        }
+
+        let obj = accessors
+            .into_iter()
+            .fold(Expr::Object(ObjectLit { span, props: rest }), |obj, a| {
+                let mut desc = vec![];
+                if let Some(getter) = a.getter {
+                    desc.push(descriptor_prop(
+                        "get",
+                        Expr::Fn(FnExpr {
+                            ident: None,
+                            function: getter,
+                        }),
+                    ));
+                }
+                if let Some(setter) = a.setter {
+                    desc.push(descriptor_prop(
+                        "set",
+                        Expr::Fn(FnExpr {
+                            ident: None,
+                            function: setter,
+                        }),
+                    ));
+                }
+                for &name in &["enumerable", "configurable"] {
+                    desc.push(descriptor_prop(
+                        name,
+                        Expr::Lit(Lit::Bool(Bool {
+                            span: DUMMY_SP,
+                            value: true,
+                        })),
+                    ));
+                }
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: member_expr!(DUMMY_SP, Object.defineProperty).as_callee(),
+                    args: vec![
+                        obj.as_arg(),
+                        a.key.as_arg(),
+                        ObjectLit {
+                            span: DUMMY_SP,
+                            props: desc,
+                        }
+                        .as_arg(),
+                    ],
+                    type_args: Default::default(),
+                })
+            });
+
+        if exprs.is_empty() {
+            obj
+        } else {
+            exprs.push(box obj);
+            Expr::Seq(SeqExpr { span, exprs })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyMutators,
+        getter,
+        "var foo = { a: 1, get bar() { return this.a; } };",
+        "var foo = Object.defineProperty({ a: 1 }, 'bar', {
+    get: function () { return this.a; },
+    enumerable: true,
+    configurable: true
+});"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyMutators,
+        setter,
+        "var foo = { set 'bar'(value) { this.a = value; } };",
+        "var foo = Object.defineProperty({}, 'bar', {
+    set: function (value) { this.a = value; },
+    enumerable: true,
+    configurable: true
+});"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyMutators,
+        getter_setter_pair,
+        "var foo = {
+    get bar() { return this._bar; },
+    [baz]: 1,
+    set bar(value) { this._bar = value; },
+    get [qux]() { return 2; }
+};",
+        "var _baz, _qux;
+var foo = (_baz = baz, _qux = qux, Object.defineProperty(Object.defineProperty({ [_baz]: 1 }, 'bar', {
+    get: function () { return this._bar; },
+    set: function (value) { this._bar = value; },
+    enumerable: true,
+    configurable: true
+}), _qux, {
+    get: function () { return 2; },
+    enumerable: true,
+    configurable: true
+}));"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyMutators,
+        computed_key_order,
+        "
+const order = [];
+const key = k => {
+    order.push(k);
+    return k;
+};
+const obj = {
+    [key('a')]: 1,
+    get [key('b')]() { return 2; },
+    [key('c')]: 3,
+    set [key('d')](v) {}
+};
+
+expect(order).toEqual(['a', 'b', 'c', 'd']);
+expect(obj.a).toBe(1);
+expect(obj.b).toBe(2);
+expect(obj.c).toBe(3);
+"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyMutators,
+        overridden,
+        "var foo = { get bar() { return 1; }, bar: 2 };",
+        "var foo = { bar: 2 };"
+    );
+}
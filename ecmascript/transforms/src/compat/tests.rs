@@ -0,0 +1,84 @@
+//! Tests for passes which are applied together.
+
+use super::{es2015::spread, es2016::exponentation};
+use swc_common::chain;
+
+fn syntax() -> ::swc_ecma_parser::Syntax {
+    Default::default()
+}
+
+// Exponentiation, then spread (the order used by presets).
+
+test!(
+    syntax(),
+    |_| chain!(exponentation(), spread(Default::default())),
+    exponentation_spread_new,
+    "new Foo(...args) ** 2;",
+    "Math.pow(_construct(Foo, _toConsumableArray(args)), 2);"
+);
+
+test!(
+    syntax(),
+    |_| chain!(exponentation(), spread(Default::default())),
+    exponentation_spread_assign,
+    "x **= f(...a);",
+    "x = Math.pow(x, f.apply(void 0, _toConsumableArray(a)));"
+);
+
+test!(
+    syntax(),
+    |_| chain!(exponentation(), spread(Default::default())),
+    exponentation_spread_assign_member,
+    "g(...a).x **= f(...b);",
+    "var _ref;
+_ref = g.apply(void 0, _toConsumableArray(a)), _ref.x = Math.pow(_ref.x, f.apply(void 0, \
+     _toConsumableArray(b)));"
+);
+
+test!(
+    syntax(),
+    |_| chain!(exponentation(), spread(Default::default())),
+    exponentation_spread_assign_computed,
+    "foo(); o[k(...a)] **= 2;",
+    "foo();
+var _prop;
+_prop = k.apply(void 0, _toConsumableArray(a)), o[_prop] = Math.pow(o[_prop], 2);"
+);
+
+// Spread, then exponentiation.
+
+test!(
+    syntax(),
+    |_| chain!(spread(Default::default()), exponentation()),
+    spread_exponentation_new,
+    "new Foo(...args) ** 2;",
+    "Math.pow(_construct(Foo, _toConsumableArray(args)), 2);"
+);
+
+test!(
+    syntax(),
+    |_| chain!(spread(Default::default()), exponentation()),
+    spread_exponentation_assign,
+    "x **= f(...a);",
+    "x = Math.pow(x, f.apply(void 0, _toConsumableArray(a)));"
+);
+
+test!(
+    syntax(),
+    |_| chain!(spread(Default::default()), exponentation()),
+    spread_exponentation_assign_member,
+    "g(...a).x **= f(...b);",
+    "var _ref;
+_ref = g.apply(void 0, _toConsumableArray(a)), _ref.x = Math.pow(_ref.x, f.apply(void 0, \
+     _toConsumableArray(b)));"
+);
+
+test!(
+    syntax(),
+    |_| chain!(spread(Default::default()), exponentation()),
+    spread_exponentation_assign_computed,
+    "foo(); o[k(...a)] **= 2;",
+    "foo();
+var _prop;
+_prop = k.apply(void 0, _toConsumableArray(a)), o[_prop] = Math.pow(o[_prop], 2);"
+);
@@ -0,0 +1,203 @@
+use crate::{pass::Pass, util::StmtLike};
+use ast::*;
+use std::mem;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith};
+
+/// Removes duplicate directives from directive prologues, which may appear
+/// after passes prepend directives or merge bodies.
+///
+/// Only the first occurrence of each directive is kept. String literals after
+/// the prologue are not directives, so they are left as-is.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// 'use strict';
+/// 'use strict';
+/// foo();
+/// 'use strict';
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// 'use strict';
+/// foo();
+/// 'use strict';
+/// ```
+pub fn directives() -> impl Pass {
+    Directives
+}
+
+#[derive(Clone, Copy)]
+struct Directives;
+
+/// Returns the directive `stmt` would be if it's in a directive prologue.
+fn as_directive(stmt: Option<&Stmt>) -> Option<(&JsWord, bool)> {
+    match stmt {
+        Some(&Stmt::Expr(ref e)) => match **e {
+            Expr::Lit(Lit::Str(Str {
+                ref value,
+                has_escape,
+                ..
+            })) => Some((value, has_escape)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn dedupe<T>(stmts: Vec<T>) -> Vec<T>
+where
+    T: StmtLike,
+{
+    let prologue_len = stmts
+        .iter()
+        .take_while(|stmt| as_directive(stmt.as_stmt()).is_some())
+        .count();
+    if prologue_len < 2 {
+        return stmts;
+    }
+
+    let mut seen = Vec::with_capacity(prologue_len);
+    let mut buf = Vec::with_capacity(stmts.len());
+    for (i, stmt) in stmts.into_iter().enumerate() {
+        if i < prologue_len {
+            let (value, has_escape) = as_directive(stmt.as_stmt()).unwrap();
+            let directive = (value.clone(), has_escape);
+            if seen.contains(&directive) {
+                continue;
+            }
+            seen.push(directive);
+        }
+
+        buf.push(stmt);
+    }
+
+    buf
+}
+
+impl Fold<Module> for Directives {
+    fn fold(&mut self, m: Module) -> Module {
+        let m = m.fold_children(self);
+
+        Module {
+            body: dedupe(m.body),
+            ..m
+        }
+    }
+}
+
+impl Fold<Script> for Directives {
+    fn fold(&mut self, s: Script) -> Script {
+        let s = s.fold_children(self);
+
+        Script {
+            body: dedupe(s.body),
+            ..s
+        }
+    }
+}
+
+/// Bodies of functions, arrow functions, constructors, getters and setters.
+macro_rules! impl_for_body {
+    ($T:ty) => {
+        impl Fold<$T> for Directives {
+            fn fold(&mut self, f: $T) -> $T {
+                let mut f = f.fold_children(self);
+
+                if let Some(ref mut body) = f.body {
+                    body.stmts = dedupe(mem::replace(&mut body.stmts, vec![]));
+                }
+
+                f
+            }
+        }
+    };
+}
+
+impl_for_body!(Function);
+impl_for_body!(Constructor);
+impl_for_body!(GetterProp);
+impl_for_body!(SetterProp);
+
+impl Fold<ArrowExpr> for Directives {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let mut f = f.fold_children(self);
+
+        if let BlockStmtOrExpr::BlockStmt(ref mut body) = f.body {
+            body.stmts = dedupe(mem::replace(&mut body.stmts, vec![]));
+        }
+
+        f
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| directives(),
+        doubled_use_strict,
+        "'use strict';
+'use strict';
+foo();",
+        "'use strict';
+foo();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| directives(),
+        fn_body,
+        "function foo() {
+    'use strict';
+    'use asm';
+    'use strict';
+    return 1;
+}
+var bar = () => {
+    'use strict';
+    'use strict';
+};",
+        "function foo() {
+    'use strict';
+    'use asm';
+    return 1;
+}
+var bar = () => {
+    'use strict';
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| directives(),
+        after_code,
+        "'use strict';
+foo();
+'use strict';",
+        "'use strict';
+foo();
+'use strict';"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| directives(),
+        block,
+        "{
+    'use strict';
+    'use strict';
+}",
+        "{
+    'use strict';
+    'use strict';
+}"
+    );
+}
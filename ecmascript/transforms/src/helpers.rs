@@ -164,6 +164,7 @@ define_helpers!(Helpers {
     class_private_method_set: (),
     class_static_private_field_spec_get: (),
     class_static_private_field_spec_set: (),
+    class_static_private_method_get: (),
     construct: (set_prototype_of),
     create_class: (),
     decorate: (to_array, to_property_key),
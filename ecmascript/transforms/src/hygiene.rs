@@ -9,7 +9,7 @@ use std::cell::RefCell;
 use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith, Span, SyntaxContext};
 
-mod ops;
+pub(crate) mod ops;
 #[cfg(test)]
 mod tests;
 
@@ -130,14 +130,31 @@ pub fn hygiene() -> impl Pass + 'static {
         }
     }
 
-    chain_at!(
-        Module,
-        Hygiene {
-            current: Default::default(),
-            ident_type: IdentType::Ref,
-        },
-        MarkClearer
-    )
+    /// Runs hygiene on both of modules and scripts.
+    #[derive(Clone, Copy)]
+    struct Runner;
+    impl Fold<Module> for Runner {
+        fn fold(&mut self, module: Module) -> Module {
+            module
+                .fold_with(&mut Hygiene {
+                    current: Default::default(),
+                    ident_type: IdentType::Ref,
+                })
+                .fold_with(&mut MarkClearer)
+        }
+    }
+    impl Fold<Script> for Runner {
+        fn fold(&mut self, script: Script) -> Script {
+            script
+                .fold_with(&mut Hygiene {
+                    current: Default::default(),
+                    ident_type: IdentType::Ref,
+                })
+                .fold_with(&mut MarkClearer)
+        }
+    }
+
+    Runner
 }
 
 impl<'a> Hygiene<'a> {
@@ -162,6 +179,14 @@ impl<'a> Fold<Module> for Hygiene<'a> {
     }
 }
 
+impl<'a> Fold<Script> for Hygiene<'a> {
+    fn fold(&mut self, script: Script) -> Script {
+        let script = script.fold_children(self);
+
+        self.apply_ops(script)
+    }
+}
+
 impl<'a> Fold<TryStmt> for Hygiene<'a> {
     fn fold(&mut self, node: TryStmt) -> TryStmt {
         TryStmt {
@@ -3,14 +3,14 @@ use swc_atoms::JsWord;
 use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned, SyntaxContext, DUMMY_SP};
 
 #[derive(Debug)]
-pub(super) enum ScopeOp {
+pub(crate) enum ScopeOp {
     Rename {
         from: (JsWord, SyntaxContext),
         to: JsWord,
     },
 }
 
-pub(super) struct Operator<'a>(pub &'a [ScopeOp]);
+pub(crate) struct Operator<'a>(pub &'a [ScopeOp]);
 
 impl<'a> Fold<Vec<ModuleItem>> for Operator<'a> {
     fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
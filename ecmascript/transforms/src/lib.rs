@@ -9,8 +9,16 @@
 #![recursion_limit = "1024"]
 
 pub use self::{
-    const_modules::const_modules, fixer::fixer, hygiene::hygiene, inline_globals::InlineGlobals,
-    resolver::resolver, simplify::simplifier,
+    const_modules::const_modules,
+    directives::directives,
+    fixer::fixer,
+    hygiene::hygiene,
+    inline_globals::InlineGlobals,
+    normalize_pat::normalize_pat,
+    registry::{registry, PassFactory},
+    resolver::{resolver, resolver_with_mark},
+    simplify::simplifier,
+    strict_mode::strict_mode,
 };
 
 #[macro_use]
@@ -24,18 +32,24 @@ mod quote;
 pub mod helpers;
 #[macro_use]
 mod hygiene;
+pub mod analysis;
+pub mod bundler;
 pub mod compat;
 mod const_modules;
 pub mod debug;
+mod directives;
 mod fixer;
 mod inline_globals;
 pub mod modules;
+mod normalize_pat;
 pub mod optimization;
 pub mod pass;
 pub mod proposals;
 pub mod react;
+mod registry;
 mod resolver;
 pub mod scope;
 mod simplify;
+pub mod strict_mode;
 pub mod typescript;
 pub mod util;
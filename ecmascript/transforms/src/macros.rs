@@ -1,8 +1,16 @@
 #[cfg(test)]
 use crate::pass::Pass;
 
+/// Implements `Fold` for function-like nodes using `fold_fn_like`.
+///
+/// If `$fold_fn` is given, it's used instead of `fold_fn_like` for functions
+/// and constructors, which have their own `arguments`.
 macro_rules! impl_fold_fn {
     ($T:path) => {
+        impl_fold_fn!($T, fold_fn_like);
+    };
+
+    ($T:path, $fold_fn:ident) => {
         impl Fold<Function> for $T {
             fn fold(&mut self, f: Function) -> Function {
                 if f.body.is_none() {
@@ -12,7 +20,7 @@ macro_rules! impl_fold_fn {
                 let f = validate!(f);
                 let f = f.fold_children(self);
 
-                let (params, body) = self.fold_fn_like(f.params, f.body.unwrap());
+                let (params, body) = self.$fold_fn(f.params, f.body.unwrap());
 
                 validate!(Function {
                     params,
@@ -146,7 +154,7 @@ macro_rules! impl_fold_fn {
                     })
                     .collect();
 
-                let (params, body) = self.fold_fn_like(params, f.body.unwrap());
+                let (params, body) = self.$fold_fn(params, f.body.unwrap());
 
                 validate!(Constructor {
                     params: params.into_iter().map(PatOrTsParamProp::Pat).collect(),
@@ -103,8 +103,8 @@ test!(
         decorators(Default::default()),
         class_properties(),
         export(),
-        simplifier(),
-        compat::es2018(),
+        simplifier(Default::default()),
+        compat::es2018(Default::default()),
         compat::es2017(),
         compat::es2016(),
         compat::es2015(Default::default()),
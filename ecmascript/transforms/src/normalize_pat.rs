@@ -0,0 +1,177 @@
+use crate::pass::Pass;
+use ast::*;
+use swc_common::{Fold, FoldWith};
+
+/// Normalizes assignment targets left by other passes to the form the parser
+/// produces.
+///
+///  - Parentheses around a target are removed.
+///  - `Pat::Expr` wrapping an identifier becomes `Pat::Ident`.
+///  - The left hand side of an assignment is `PatOrExpr::Expr` if it's a
+///    member expression, or an identifier of a compound assignment like `a +=
+///    1`. Identifiers assigned with `=` are `PatOrExpr::Pat`.
+///
+/// Array and object patterns are left as-is, even if they have a single
+/// element, because destructuring calls the iterator of the value or checks
+/// that it's not `null`.
+pub fn normalize_pat() -> impl Pass {
+    NormalizePat
+}
+
+#[derive(Clone, Copy)]
+struct NormalizePat;
+
+pub(crate) fn strip_parens(e: Box<Expr>) -> Box<Expr> {
+    match e {
+        box Expr::Paren(ParenExpr { expr, .. }) => strip_parens(expr),
+        _ => e,
+    }
+}
+
+impl Fold<Pat> for NormalizePat {
+    fn fold(&mut self, p: Pat) -> Pat {
+        let p = p.fold_children(self);
+
+        match p {
+            Pat::Expr(e) => match strip_parens(e) {
+                box Expr::Ident(i) => Pat::Ident(i),
+                e => Pat::Expr(e),
+            },
+            _ => p,
+        }
+    }
+}
+
+impl Fold<AssignExpr> for NormalizePat {
+    fn fold(&mut self, e: AssignExpr) -> AssignExpr {
+        let e = e.fold_children(self);
+        let is_assign = e.op == op!("=");
+
+        let left = match e.left {
+            PatOrExpr::Pat(box Pat::Expr(left)) => PatOrExpr::Expr(left),
+            PatOrExpr::Pat(box Pat::Ident(i)) => ident_target(i, is_assign),
+            PatOrExpr::Expr(left) => match strip_parens(left) {
+                box Expr::Ident(i) => ident_target(i, is_assign),
+                left => PatOrExpr::Expr(left),
+            },
+            left => left,
+        };
+
+        AssignExpr { left, ..e }
+    }
+}
+
+fn ident_target(i: Ident, is_assign: bool) -> PatOrExpr {
+    if is_assign {
+        PatOrExpr::Pat(box Pat::Ident(i))
+    } else {
+        PatOrExpr::Expr(box Expr::Ident(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use swc_common::DUMMY_SP;
+
+    fn member(obj: &str, prop: &str) -> Box<Expr> {
+        box Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(box Expr::Ident(quote_ident!(obj))),
+            prop: box Expr::Ident(quote_ident!(prop)),
+            computed: false,
+        })
+    }
+
+    fn paren(expr: Box<Expr>) -> Box<Expr> {
+        box Expr::Paren(ParenExpr {
+            span: DUMMY_SP,
+            expr,
+        })
+    }
+
+    fn assign(op: AssignOp, left: PatOrExpr) -> Stmt {
+        Stmt::Expr(box Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op,
+            left,
+            right: box Expr::Ident(quote_ident!("c")),
+        }))
+    }
+
+    #[test]
+    fn member_target() {
+        Tester::run(|tester| {
+            let module = Module {
+                span: DUMMY_SP,
+                body: vec![
+                    ModuleItem::Stmt(assign(
+                        op!("="),
+                        PatOrExpr::Pat(box Pat::Expr(paren(member("a", "b")))),
+                    )),
+                    ModuleItem::Stmt(assign(
+                        op!("="),
+                        PatOrExpr::Pat(box Pat::Array(ArrayPat {
+                            span: DUMMY_SP,
+                            elems: vec![
+                                Some(Pat::Expr(paren(member("a", "b")))),
+                                Some(Pat::Expr(paren(box Expr::Ident(quote_ident!("d"))))),
+                            ],
+                            type_ann: None,
+                        })),
+                    )),
+                    ModuleItem::Stmt(assign(
+                        op!("+="),
+                        PatOrExpr::Pat(box Pat::Ident(quote_ident!("d"))),
+                    )),
+                ],
+                shebang: None,
+            };
+
+            let module = module.fold_with(&mut normalize_pat());
+
+            let expected = tester.parse_module("expected.js", "a.b = c; [a.b, d] = c; d += c;")?;
+            assert_eq!(tester.print(&module), tester.print(&expected));
+
+            match module.body[0] {
+                ModuleItem::Stmt(Stmt::Expr(box Expr::Assign(AssignExpr {
+                    left: PatOrExpr::Expr(box Expr::Member(..)),
+                    ..
+                }))) => {}
+                ref item => panic!("unexpected target: {:?}", item),
+            }
+            match module.body[1] {
+                ModuleItem::Stmt(Stmt::Expr(box Expr::Assign(AssignExpr {
+                    left: PatOrExpr::Pat(box Pat::Array(ref arr)),
+                    ..
+                }))) => match (&arr.elems[0], &arr.elems[1]) {
+                    (&Some(Pat::Expr(box Expr::Member(..))), &Some(Pat::Ident(..))) => {}
+                    elems => panic!("unexpected elements: {:?}", elems),
+                },
+                ref item => panic!("unexpected target: {:?}", item),
+            }
+            match module.body[2] {
+                ModuleItem::Stmt(Stmt::Expr(box Expr::Assign(AssignExpr {
+                    left: PatOrExpr::Expr(box Expr::Ident(..)),
+                    ..
+                }))) => {}
+                ref item => panic!("unexpected target: {:?}", item),
+            }
+
+            Ok(())
+        });
+    }
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| normalize_pat(),
+        member_target_exec,
+        "const o = {};
+let d;
+[o.a, d] = [1, 2];
+o.b = 3;
+o.b += d;
+expect(o).toEqual({ a: 1, b: 5 });"
+    );
+}
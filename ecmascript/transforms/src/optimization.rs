@@ -1,3 +1,21 @@
-pub use self::json_parse::JsonParse;
+pub use self::{
+    arguments_to_rest::arguments_to_rest, const_props::inline_const_props, dot_access::dot_access,
+    drop_console::drop_console, fn_decl::fn_decl, fold_pow::fold_pow,
+    hoist_functions::hoist_functions, iife_inline::iife_inline, inline::inline,
+    json_parse::JsonParse, remove_empty::remove_empty, simplify_compare::simplify_compare,
+    undefined::normalize_undefined,
+};
 
+mod arguments_to_rest;
+mod const_props;
+pub mod dot_access;
+pub mod drop_console;
+mod fn_decl;
+mod fold_pow;
+mod hoist_functions;
+mod iife_inline;
+mod inline;
 mod json_parse;
+mod remove_empty;
+mod simplify_compare;
+pub mod undefined;
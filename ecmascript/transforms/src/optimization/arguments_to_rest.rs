@@ -0,0 +1,436 @@
+use crate::{pass::Pass, util::ExprFactory};
+use ast::*;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+
+/// Replaces `arguments` with a rest parameter if it's used only as an
+/// array-like object.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function foo() {
+///     var rest = Array.prototype.slice.call(arguments, 1);
+///     return arguments.length + arguments[0] + rest.length;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo(...args) {
+///     var rest = args.slice(1);
+///     return args.length + args[0] + rest.length;
+/// }
+/// ```
+///
+/// Only functions without parameters are converted, so `arguments` is not
+/// mapped to any parameter. `arguments` may be used to read `length`, read or
+/// write elements and with `Array.prototype.slice.call` or `[].slice.call`.
+/// Functions which use it in any other way, like `arguments.callee` or
+/// passing it to another function, are left as is. So are functions using
+/// `eval` or starting with `'use strict'`, as a function with a rest
+/// parameter can't have it.
+pub fn arguments_to_rest() -> impl Pass {
+    ArgumentsToRest
+}
+
+#[derive(Clone, Copy)]
+struct ArgumentsToRest;
+
+impl Fold<Function> for ArgumentsToRest {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+
+        if !f.params.is_empty() {
+            return f;
+        }
+        let body = match f.body {
+            Some(ref body) if !has_use_strict(&body.stmts) => body,
+            _ => return f,
+        };
+
+        let mut v = ArgumentsUsage {
+            found: false,
+            unsafe_use: false,
+        };
+        body.visit_with(&mut v);
+        if !v.found || v.unsafe_use {
+            return f;
+        }
+
+        let args = private_ident!("args");
+        let body = f.body.fold_with(&mut Replacer { args: args.clone() });
+
+        Function {
+            params: vec![Pat::Rest(RestPat {
+                dot3_token: DUMMY_SP,
+                arg: box Pat::Ident(args),
+                type_ann: None,
+            })],
+            body,
+            ..f
+        }
+    }
+}
+
+/// Getters can't have parameters.
+macro_rules! method {
+    ($T:ident) => {
+        impl Fold<$T> for ArgumentsToRest {
+            fn fold(&mut self, m: $T) -> $T {
+                match m.kind {
+                    MethodKind::Getter => {}
+                    _ => return m.fold_children(self),
+                }
+
+                let key = m.key.fold_with(self);
+                let function = m.function.fold_children(self);
+
+                $T { key, function, ..m }
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(PrivateMethod);
+
+fn has_use_strict(stmts: &[Stmt]) -> bool {
+    stmts
+        .iter()
+        .take_while(|stmt| match **stmt {
+            Stmt::Expr(box Expr::Lit(Lit::Str(..))) => true,
+            _ => false,
+        })
+        .any(|stmt| match *stmt {
+            Stmt::Expr(box Expr::Lit(Lit::Str(Str { ref value, .. }))) => *value == *"use strict",
+            _ => false,
+        })
+}
+
+fn is_arguments(e: &Expr) -> bool {
+    match *e {
+        Expr::Ident(Ident {
+            sym: js_word!("arguments"),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+fn is_ident(e: &Expr, name: &str) -> bool {
+    match *e {
+        Expr::Ident(Ident { ref sym, .. }) => *sym == *name,
+        _ => false,
+    }
+}
+
+/// Returns the object of `e` if `e` is a non-computed member expression with
+/// the property `prop`.
+fn member_of<'a>(e: &'a Expr, prop: &str) -> Option<&'a Expr> {
+    match *e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            prop: ref p,
+            computed: false,
+            ..
+        }) if is_ident(p, prop) => Some(obj),
+        _ => None,
+    }
+}
+
+/// Returns true if `e` is `Array.prototype.slice.call` or `[].slice.call`.
+fn is_slice_call(e: &Expr) -> bool {
+    let array = match member_of(e, "call").and_then(|e| member_of(e, "slice")) {
+        Some(array) => array,
+        None => return false,
+    };
+
+    match *array {
+        Expr::Array(ArrayLit { ref elems, .. }) => elems.is_empty(),
+        _ => member_of(array, "prototype").map_or(false, |e| is_ident(e, "Array")),
+    }
+}
+
+/// Returns the rest of the arguments if `e` is a call to `slice` with
+/// `arguments` as `this`.
+fn sliced_arguments(e: &CallExpr) -> Option<&[ExprOrSpread]> {
+    match e.callee {
+        ExprOrSuper::Expr(ref callee) if is_slice_call(callee) => {}
+        _ => return None,
+    }
+
+    match e.args.first() {
+        Some(ExprOrSpread { spread: None, expr }) if is_arguments(expr) => Some(&e.args[1..]),
+        _ => None,
+    }
+}
+
+struct ArgumentsUsage {
+    found: bool,
+    /// Set if `arguments` is used in a way an array does not support.
+    unsafe_use: bool,
+}
+
+impl Visit<Expr> for ArgumentsUsage {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(ref obj),
+                ref prop,
+                computed,
+                ..
+            }) if is_arguments(obj) => {
+                self.found = true;
+                if computed {
+                    // `arguments['callee']`
+                    match **prop {
+                        Expr::Lit(Lit::Str(..)) => self.unsafe_use = true,
+                        _ => prop.visit_with(self),
+                    }
+                } else if !is_ident(prop, "length") {
+                    self.unsafe_use = true;
+                }
+            }
+
+            Expr::Call(ref call) => match sliced_arguments(call) {
+                Some(args) => {
+                    self.found = true;
+                    args.visit_with(self);
+                }
+                None => e.visit_children(self),
+            },
+
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Ident> for ArgumentsUsage {
+    fn visit(&mut self, i: &Ident) {
+        match i.sym {
+            js_word!("arguments") | js_word!("eval") => self.unsafe_use = true,
+            _ => {}
+        }
+    }
+}
+
+impl Visit<AssignExpr> for ArgumentsUsage {
+    fn visit(&mut self, e: &AssignExpr) {
+        // Arrays are truncated by assigning to `length`.
+        let left = match e.left {
+            PatOrExpr::Expr(ref left) | PatOrExpr::Pat(box Pat::Expr(ref left)) => Some(left),
+            _ => None,
+        };
+        if left.map_or(false, |left| {
+            member_of(left, "length").map_or(false, is_arguments)
+        }) {
+            self.unsafe_use = true;
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<MemberExpr> for ArgumentsUsage {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for ArgumentsUsage {
+    fn visit(&mut self, n: &PropName) {
+        match *n {
+            PropName::Computed(ref e) => e.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+/// Functions have their own `arguments`.
+impl Visit<Function> for ArgumentsUsage {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<Constructor> for ArgumentsUsage {
+    fn visit(&mut self, _: &Constructor) {}
+}
+
+/// Accessors of object literals have their own `arguments`, too.
+impl Visit<GetterProp> for ArgumentsUsage {
+    fn visit(&mut self, p: &GetterProp) {
+        p.key.visit_with(self);
+    }
+}
+
+impl Visit<SetterProp> for ArgumentsUsage {
+    fn visit(&mut self, p: &SetterProp) {
+        p.key.visit_with(self);
+    }
+}
+
+struct Replacer {
+    args: Ident,
+}
+
+impl Fold<Expr> for Replacer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        if is_arguments(&e) {
+            return Expr::Ident(self.args.clone());
+        }
+
+        match e {
+            Expr::Call(call) => match sliced_arguments(&call).map(|args| args.to_vec()) {
+                Some(args) => Expr::Call(CallExpr {
+                    span: call.span,
+                    callee: self.args.clone().member(quote_ident!("slice")).as_callee(),
+                    args: args.fold_with(self),
+                    type_args: None,
+                }),
+                None => Expr::Call(call.fold_children(self)),
+            },
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Replacer {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+impl Fold<Function> for Replacer {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<Constructor> for Replacer {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        c
+    }
+}
+
+impl Fold<GetterProp> for Replacer {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        GetterProp {
+            key: p.key.fold_with(self),
+            ..p
+        }
+    }
+}
+
+impl Fold<SetterProp> for Replacer {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        SetterProp {
+            key: p.key.fold_with(self),
+            ..p
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| arguments_to_rest(),
+        slice,
+        "function foo() {
+    var rest = Array.prototype.slice.call(arguments, 1);
+    return arguments.length + arguments[0] + rest.length;
+}
+var bar = function () { return [].slice.call(arguments); };",
+        "function foo(...args) {
+    var rest = args.slice(1);
+    return args.length + args[0] + rest.length;
+}
+var bar = function (...args) { return args.slice(); };"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| arguments_to_rest(),
+        arrow,
+        "function foo() { return () => arguments[0]; }
+function bar() { return function () { return arguments[0]; }; }",
+        "function foo(...args) { return () => args[0]; }
+function bar() { return function (...args) { return args[0]; }; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| arguments_to_rest(),
+        callee,
+        "function foo() { return arguments.length ? arguments.callee() : 0; }
+function bar() { return arguments['callee']; }",
+        "function foo() { return arguments.length ? arguments.callee() : 0; }
+function bar() { return arguments['callee']; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| arguments_to_rest(),
+        unsafe_use,
+        "function a() { return baz(arguments); }
+function b() { arguments = []; return arguments.length; }
+function c() { arguments.length = 0; }
+function d() { eval('x'); return arguments[0]; }
+function e() { 'use strict'; return arguments[0]; }
+function f(x) { return arguments[0]; }",
+        "function a() { return baz(arguments); }
+function b() { arguments = []; return arguments.length; }
+function c() { arguments.length = 0; }
+function d() { eval('x'); return arguments[0]; }
+function e() { 'use strict'; return arguments[0]; }
+function f(x) { return arguments[0]; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| arguments_to_rest(),
+        getter,
+        "class Foo {
+    get a() { return arguments.length; }
+    b() { return arguments.length; }
+}",
+        "class Foo {
+    get a() { return arguments.length; }
+    b(...args) { return args.length; }
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| arguments_to_rest(),
+        object_accessor,
+        "function foo() {
+    return {
+        get [arguments[0]]() { return arguments.length; },
+        set b(v) { arguments.length; },
+    };
+}",
+        "function foo(...args) {
+    return {
+        get [args[0]]() { return arguments.length; },
+        set b(v) { arguments.length; },
+    };
+}"
+    );
+}
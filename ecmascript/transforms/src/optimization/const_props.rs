@@ -0,0 +1,389 @@
+use super::inline::is_const;
+use crate::{pass::Pass, util::StmtLike};
+use ast::*;
+use hashbrown::HashMap;
+use std::mem;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, SyntaxContext, Visit, VisitWith, DUMMY_SP};
+
+/// Inlines properties of `const` objects which are used like enums.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// const Color = { Red: 0, Green: 1 };
+/// paint(Color.Red, Color['Green']);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// paint(0, 1);
+/// ```
+///
+/// An object is inlined only if all of its property values are literals and
+/// the binding is used only to read its properties. The object is removed,
+/// as it's not used after inlining. Objects which are passed around,
+/// modified or used with an unknown key are left as is.
+///
+/// Bindings are compared using their syntax context, so this pass should be
+/// applied after `resolver`.
+pub fn inline_const_props() -> impl Pass {
+    ConstProps
+}
+
+#[derive(Clone, Copy)]
+struct ConstProps;
+
+impl<T> Fold<Vec<T>> for ConstProps
+where
+    T: StmtLike + VisitWith<UsageCollector> + FoldWith<Replacer>,
+    Vec<T>: FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut stmts = stmts.fold_children(self);
+
+        let mut i = 0;
+        while i < stmts.len() {
+            if try_inline(&mut stmts, i) {
+                stmts.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        stmts
+    }
+}
+
+/// Inlines properties of the object declared by `stmts[idx]` if possible.
+///
+/// Returns true if `stmts[idx]` should be removed.
+fn try_inline<T>(stmts: &mut [T], idx: usize) -> bool
+where
+    T: StmtLike + VisitWith<UsageCollector> + FoldWith<Replacer>,
+{
+    let (name, props) = match stmts[idx].as_stmt().and_then(candidate) {
+        Some(v) => v,
+        None => return false,
+    };
+
+    let mut v = UsageCollector {
+        sym: name.sym.clone(),
+        ctxt: name.span.ctxt(),
+        props,
+        escaped: false,
+    };
+    stmts[..idx].visit_with(&mut v);
+    stmts[idx + 1..].visit_with(&mut v);
+    if v.escaped {
+        return false;
+    }
+
+    let mut replacer = Replacer {
+        sym: name.sym,
+        ctxt: name.span.ctxt(),
+        props: v.props,
+    };
+    for (i, stmt) in stmts.iter_mut().enumerate() {
+        if i == idx {
+            continue;
+        }
+        let s = mem::replace(
+            stmt,
+            T::from_stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP })),
+        );
+        *stmt = s.fold_with(&mut replacer);
+    }
+
+    true
+}
+
+/// Returns the name and the properties if `stmt` declares only a `const`
+/// object with literal values.
+fn candidate(stmt: &Stmt) -> Option<(Ident, HashMap<JsWord, Expr>)> {
+    let (name, props) = match *stmt {
+        Stmt::Decl(Decl::Var(VarDecl {
+            kind: VarDeclKind::Const,
+            ref decls,
+            declare: false,
+            ..
+        })) if decls.len() == 1 => match decls[0] {
+            VarDeclarator {
+                name: Pat::Ident(ref name),
+                init: Some(box Expr::Object(ObjectLit { ref props, .. })),
+                ..
+            } if name.type_ann.is_none() => (name, props),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let mut values = HashMap::default();
+    for prop in props {
+        match *prop {
+            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { ref key, ref value }))
+                if is_const(value) =>
+            {
+                let key = match *key {
+                    PropName::Ident(ref i) => i.sym.clone(),
+                    PropName::Str(ref s) => s.value.clone(),
+                    _ => return None,
+                };
+                // `__proto__` sets the prototype instead of a property.
+                if key == *"__proto__" {
+                    return None;
+                }
+                values.insert(key, (**value).clone());
+            }
+            _ => return None,
+        }
+    }
+
+    Some((name.clone(), values))
+}
+
+/// Returns the key of `e` if it's statically known.
+fn prop_key(e: &MemberExpr) -> Option<&JsWord> {
+    match *e.prop {
+        Expr::Ident(ref i) if !e.computed => Some(&i.sym),
+        Expr::Lit(Lit::Str(ref s)) if e.computed => Some(&s.value),
+        _ => None,
+    }
+}
+
+/// Checks if a binding is used only to read known properties.
+struct UsageCollector {
+    sym: JsWord,
+    ctxt: SyntaxContext,
+    props: HashMap<JsWord, Expr>,
+    /// Set if the binding is used in any other way.
+    escaped: bool,
+}
+
+impl UsageCollector {
+    fn is_target(&self, e: &Expr) -> bool {
+        match *e {
+            Expr::Ident(ref i) => i.sym == self.sym && i.span.ctxt() == self.ctxt,
+            _ => false,
+        }
+    }
+
+    /// Marks the binding as escaped if `e` is a property of it.
+    fn escape_if_member(&mut self, e: &Expr) {
+        match *e {
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(ref obj),
+                ..
+            }) if self.is_target(obj) => self.escaped = true,
+            _ => {}
+        }
+    }
+}
+
+impl Visit<Ident> for UsageCollector {
+    fn visit(&mut self, i: &Ident) {
+        if i.sym == self.sym && i.span.ctxt() == self.ctxt {
+            self.escaped = true;
+        }
+    }
+}
+
+impl Visit<MemberExpr> for UsageCollector {
+    fn visit(&mut self, e: &MemberExpr) {
+        match e.obj {
+            ExprOrSuper::Expr(ref obj) if self.is_target(obj) => {
+                match prop_key(e) {
+                    Some(key) if self.props.contains_key(key) => {}
+                    _ => self.escaped = true,
+                }
+                if e.computed {
+                    e.prop.visit_with(self);
+                }
+            }
+            _ => {
+                e.obj.visit_with(self);
+                if e.computed {
+                    e.prop.visit_with(self);
+                }
+            }
+        }
+    }
+}
+
+impl Visit<PropName> for UsageCollector {
+    fn visit(&mut self, n: &PropName) {
+        match *n {
+            PropName::Computed(ref e) => e.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+impl Visit<AssignExpr> for UsageCollector {
+    fn visit(&mut self, e: &AssignExpr) {
+        match e.left {
+            PatOrExpr::Expr(ref left) => self.escape_if_member(left),
+            PatOrExpr::Pat(..) => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<Pat> for UsageCollector {
+    fn visit(&mut self, p: &Pat) {
+        match *p {
+            Pat::Expr(ref e) => self.escape_if_member(e),
+            _ => {}
+        }
+
+        p.visit_children(self);
+    }
+}
+
+impl Visit<UpdateExpr> for UsageCollector {
+    fn visit(&mut self, e: &UpdateExpr) {
+        self.escape_if_member(&e.arg);
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UnaryExpr> for UsageCollector {
+    fn visit(&mut self, e: &UnaryExpr) {
+        if e.op == op!("delete") {
+            self.escape_if_member(&e.arg);
+        }
+        e.visit_children(self);
+    }
+}
+
+impl Visit<CallExpr> for UsageCollector {
+    fn visit(&mut self, e: &CallExpr) {
+        // The object would be `this` of the call.
+        match e.callee {
+            ExprOrSuper::Expr(ref callee) => self.escape_if_member(callee),
+            ExprOrSuper::Super(..) => {}
+        }
+        e.visit_children(self);
+    }
+}
+
+impl Visit<TaggedTpl> for UsageCollector {
+    fn visit(&mut self, e: &TaggedTpl) {
+        self.escape_if_member(&e.tag);
+        e.visit_children(self);
+    }
+}
+
+/// Replaces reads of properties with their values.
+struct Replacer {
+    sym: JsWord,
+    ctxt: SyntaxContext,
+    props: HashMap<JsWord, Expr>,
+}
+
+impl Fold<Expr> for Replacer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        let value = match e {
+            Expr::Member(ref m) => match m.obj {
+                ExprOrSuper::Expr(box Expr::Ident(ref i))
+                    if i.sym == self.sym && i.span.ctxt() == self.ctxt =>
+                {
+                    prop_key(m).and_then(|key| self.props.get(key)).cloned()
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        value.unwrap_or(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver;
+    use swc_common::chain;
+
+    fn tr() -> impl Pass {
+        chain!(resolver(), inline_const_props())
+    }
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        inline,
+        "const C = { A: 1, 'B': 'b', D: -2 };
+use(C.A, C['B'], C.D);
+function f() { return C.A; }",
+        "use(1, 'b', -2);
+function f() { return 1; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        passed_to_function,
+        "const C = { A: 1 };
+use(C.A);
+register(C);",
+        "const C = { A: 1 };
+use(C.A);
+register(C);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        mutated,
+        "const C = { A: 1 }; C.A = 2; use(C.A);
+const D = { A: 1 }; D.A++;
+const E = { A: 1 }; delete E.A;
+const F = { A: 1 }; [F.A] = [2];",
+        "const C = { A: 1 }; C.A = 2; use(C.A);
+const D = { A: 1 }; D.A++;
+const E = { A: 1 }; delete E.A;
+const F = { A: 1 }; [F.A] = [2];"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        unknown_key,
+        "const C = { A: 1 }; use(C.B);
+const D = { A: 1 }; use(D[key]);
+const E = { A: 1 }; E.toString();",
+        "const C = { A: 1 }; use(C.B);
+const D = { A: 1 }; use(D[key]);
+const E = { A: 1 }; E.toString();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        not_literal,
+        "const C = { A: f() }; use(C.A);
+let D = { A: 1 }; use(D.A);
+const E = { A: 1, ...F }; use(E.A);",
+        "const C = { A: f() }; use(C.A);
+let D = { A: 1 }; use(D.A);
+const E = { A: 1, ...F }; use(E.A);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        shadowed,
+        "const C = { A: 1 };
+function f(C) { return C.B; }
+use(C.A);",
+        "function f(C) { return C.B; }
+use(1);"
+    );
+}
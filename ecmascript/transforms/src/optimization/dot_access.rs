@@ -0,0 +1,118 @@
+use crate::{pass::Pass, util::is_valid_ident};
+use ast::*;
+use serde::{Deserialize, Serialize};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Spanned};
+
+/// Converts computed member expressions with a constant string key to dot
+/// access.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// obj["foo"];
+/// obj["fo" + "o"];
+/// obj["foo-bar"];
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// obj.foo;
+/// obj.foo;
+/// obj["foo-bar"];
+/// ```
+///
+/// Only string literals and concatenations of them are evaluated, so this
+/// pass should be applied after the simplifier to handle other constant
+/// expressions.
+pub fn dot_access(c: Config) -> impl Pass {
+    DotAccess { c }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Keep keys which are reserved words in es3, like `var`, computed.
+    #[serde(default)]
+    pub es3: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DotAccess {
+    c: Config,
+}
+
+/// Returns the value of `e` if it's a string literal or a concatenation of
+/// them.
+fn const_str(e: &Expr) -> Option<String> {
+    match *e {
+        Expr::Lit(Lit::Str(Str { ref value, .. })) => Some(value.to_string()),
+        Expr::Bin(BinExpr {
+            op: op!(bin, "+"),
+            ref left,
+            ref right,
+            ..
+        }) => {
+            let mut s = const_str(left)?;
+            s.push_str(&const_str(right)?);
+            Some(s)
+        }
+        Expr::Paren(ParenExpr { ref expr, .. }) => const_str(expr),
+        _ => None,
+    }
+}
+
+impl Fold<MemberExpr> for DotAccess {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let e = e.fold_children(self);
+        if !e.computed {
+            return e;
+        }
+
+        let sym: JsWord = match const_str(&e.prop) {
+            Some(s) => s.into(),
+            None => return e,
+        };
+        if !is_valid_ident(&sym) || (self.c.es3 && sym.is_reserved_for_es3()) {
+            return e;
+        }
+
+        MemberExpr {
+            computed: false,
+            prop: box Expr::Ident(quote_ident!(e.prop.span(), sym)),
+            ..e
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_access(Default::default()),
+        concat,
+        r#"x = obj["fo" + "o"]; y = obj["foo"]; z = obj["va" + "r"];"#,
+        "x = obj.foo; y = obj.foo; z = obj.var;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_access(Config { es3: true }),
+        es3_reserved,
+        r#"x = obj["fo" + "o"]; z = obj["va" + "r"];"#,
+        r#"x = obj.foo; z = obj["va" + "r"];"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_access(Default::default()),
+        not_ident,
+        r#"x = obj["foo-" + "bar"]; y = obj["1" + "a"]; z = obj[foo + "a"];"#,
+        r#"x = obj["foo-" + "bar"]; y = obj["1" + "a"]; z = obj[foo + "a"];"#
+    );
+}
@@ -0,0 +1,196 @@
+use crate::{
+    pass::Pass,
+    util::{free_vars, undefined, ExprExt, Id, StmtLike},
+};
+use ast::*;
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+use swc_atoms::JsWord;
+use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes `debugger` statements and, if configured, calls to `console.*`.
+///
+/// Only calls on the global `console` are removed, not on a local variable
+/// named `console`.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// debugger;
+/// console.log(foo);
+/// var a = console.log(bar());
+/// ```
+///
+/// ## Out (with `drop_console_but_keep_side_effects`)
+///
+/// ```js
+/// var a = (bar(), void 0);
+/// ```
+pub fn drop_console(config: Config) -> impl Pass {
+    DropConsole {
+        config,
+        globals: Default::default(),
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Remove calls to `console.*`.
+    #[serde(default)]
+    pub drop_console: bool,
+
+    /// Methods of `console` to remove. Calls to all methods are removed if
+    /// this is empty.
+    #[serde(default)]
+    pub methods: Vec<JsWord>,
+
+    /// Evaluate arguments of removed calls if they may have side effects.
+    #[serde(default)]
+    pub drop_console_but_keep_side_effects: bool,
+}
+
+#[derive(Debug, Clone)]
+struct DropConsole {
+    config: Config,
+    /// Variables which are not declared in the program.
+    globals: IndexSet<Id>,
+}
+
+impl DropConsole {
+    /// Returns arguments of `e` if it's a call to `console.*` which should be
+    /// removed.
+    fn take_console_args(&self, e: &mut Expr) -> Option<Vec<ExprOrSpread>> {
+        if !self.config.drop_console {
+            return None;
+        }
+
+        let call = match *e {
+            Expr::Call(ref mut call) => call,
+            _ => return None,
+        };
+
+        let method = match call.callee {
+            ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+                ref prop,
+                computed,
+                ..
+            })) if &*obj.sym == "console"
+                && self.globals.contains(&(obj.sym.clone(), obj.span.ctxt())) =>
+            {
+                match **prop {
+                    Expr::Ident(ref prop) if !computed => &prop.sym,
+                    Expr::Lit(Lit::Str(ref prop)) if computed => &prop.value,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        if !self.config.methods.is_empty() && !self.config.methods.contains(method) {
+            return None;
+        }
+
+        Some(call.args.drain(..).collect())
+    }
+
+    /// Returns expressions which should be evaluated instead of the removed
+    /// call.
+    fn side_effects(&self, args: Vec<ExprOrSpread>) -> Vec<Box<Expr>> {
+        if !self.config.drop_console_but_keep_side_effects {
+            return vec![];
+        }
+
+        args.into_iter()
+            .filter_map(|arg| match arg.spread {
+                // Spreading invokes the iterator protocol.
+                Some(..) => Some(box Expr::Array(ArrayLit {
+                    span: arg.expr.span(),
+                    elems: vec![Some(arg)],
+                })),
+                None if arg.expr.may_have_side_effects() => Some(arg.expr),
+                None => None,
+            })
+            .collect()
+    }
+}
+
+impl Fold<Module> for DropConsole {
+    fn fold(&mut self, m: Module) -> Module {
+        self.globals = free_vars(&m);
+        m.fold_children(self)
+    }
+}
+
+impl Fold<Script> for DropConsole {
+    fn fold(&mut self, s: Script) -> Script {
+        self.globals = free_vars(&s);
+        s.fold_children(self)
+    }
+}
+
+impl Fold<Expr> for DropConsole {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let mut e = e.fold_children(self);
+        let span = e.span();
+
+        let args = match self.take_console_args(&mut e) {
+            Some(args) => args,
+            None => return e,
+        };
+
+        let mut exprs = self.side_effects(args);
+        exprs.push(undefined(span));
+
+        if exprs.len() == 1 {
+            *exprs.pop().unwrap()
+        } else {
+            Expr::Seq(SeqExpr { span, exprs })
+        }
+    }
+}
+
+impl Fold<Stmt> for DropConsole {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        match stmt {
+            Stmt::Debugger(DebuggerStmt { span }) => Stmt::Empty(EmptyStmt { span }),
+            Stmt::Expr(mut expr) => {
+                let span = expr.span();
+                match self.take_console_args(&mut expr) {
+                    Some(args) => {
+                        let args = args.fold_with(self);
+                        let mut exprs = self.side_effects(args);
+                        match exprs.len() {
+                            0 => Stmt::Empty(EmptyStmt { span }),
+                            1 => Stmt::Expr(exprs.pop().unwrap()),
+                            _ => Stmt::Expr(box Expr::Seq(SeqExpr { span, exprs })),
+                        }
+                    }
+                    None => Stmt::Expr(expr.fold_with(self)),
+                }
+            }
+            _ => stmt.fold_children(self),
+        }
+    }
+}
+
+impl<T> Fold<Vec<T>> for DropConsole
+where
+    T: StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, items: Vec<T>) -> Vec<T> {
+        items.move_flat_map(|item| {
+            let item = item.fold_with(self);
+            match item.as_stmt() {
+                Some(Stmt::Empty(..)) => None,
+                _ => Some(item),
+            }
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+use crate::resolver;
+use swc_common::chain;
+
+fn tr(drop_console_but_keep_side_effects: bool) -> impl Pass {
+    drop_console(Config {
+        drop_console: true,
+        drop_console_but_keep_side_effects,
+        ..Default::default()
+    })
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(false),
+    stmt,
+    "console.log(foo);
+bar();",
+    "bar();"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(false),
+    expr,
+    "var a = console.log(foo);",
+    "var a = void 0;"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(false),
+    single_stmt_body,
+    "if (a) console.log(foo);",
+    "if (a) ;"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(false),
+    debugger,
+    "function foo() {
+    debugger;
+    return 1;
+}",
+    "function foo() {
+    return 1;
+}"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| drop_console(Default::default()),
+    keep_console_by_default,
+    "debugger;
+console.log(foo);",
+    "console.log(foo);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(true),
+    keep_side_effects_stmt,
+    "console.log(foo, bar(), 1);",
+    "bar();"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(true),
+    keep_side_effects_expr,
+    "var a = console.log(foo, bar());",
+    "var a = (bar(), void 0);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(true),
+    keep_side_effects_spread,
+    "console.log(...foo);",
+    "[...foo];"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| drop_console(Config {
+        drop_console: true,
+        methods: vec!["log".into()],
+        ..Default::default()
+    }),
+    methods,
+    "console.log(foo);
+console.error(foo);
+console['log'](foo);",
+    "console.error(foo);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| chain!(resolver(), tr(false)),
+    local_console,
+    "console.log(foo);
+function foo(console) {
+    console.log(foo);
+}",
+    "function foo(console) {
+    console.log(foo);
+}"
+);
@@ -0,0 +1,349 @@
+use crate::{pass::Pass, util::StmtLike};
+use ast::*;
+use std::mem;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, SyntaxContext, Visit, VisitWith, DUMMY_SP};
+
+/// Converts function expressions assigned to variables into function
+/// declarations.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var f = function f() {};
+/// var g = function () {};
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function f() {}
+/// function g() {}
+/// ```
+///
+/// Only statements directly in a module, a script or a function body are
+/// converted. As a function declaration is hoisted along with its value, a
+/// variable is left as-is if it's used before the declaration or from another
+/// function declaration. Reassigned variables are left as-is, too.
+pub fn fn_decl() -> impl Pass {
+    FnToDecl
+}
+
+struct FnToDecl;
+
+impl Fold<Module> for FnToDecl {
+    fn fold(&mut self, m: Module) -> Module {
+        let m = m.fold_children(self);
+
+        Module {
+            body: convert(m.body),
+            ..m
+        }
+    }
+}
+
+impl Fold<Script> for FnToDecl {
+    fn fold(&mut self, s: Script) -> Script {
+        let s = s.fold_children(self);
+
+        Script {
+            body: convert(s.body),
+            ..s
+        }
+    }
+}
+
+impl Fold<Function> for FnToDecl {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+
+        Function {
+            body: f.body.map(|body| BlockStmt {
+                stmts: convert(body.stmts),
+                ..body
+            }),
+            ..f
+        }
+    }
+}
+
+fn convert<T>(mut stmts: Vec<T>) -> Vec<T>
+where
+    T: StmtLike + VisitWith<Finder>,
+{
+    for i in 0..stmts.len() {
+        let name = match stmts[i].as_stmt().and_then(candidate) {
+            Some(name) => name.clone(),
+            None => continue,
+        };
+
+        if !can_convert(&stmts, i, &name) {
+            continue;
+        }
+
+        let stmt = mem::replace(
+            &mut stmts[i],
+            T::from_stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP })),
+        );
+        let function = match stmt.try_into_stmt() {
+            Ok(Stmt::Decl(Decl::Var(VarDecl { mut decls, .. }))) => match decls.pop() {
+                Some(VarDeclarator {
+                    init: Some(box Expr::Fn(FnExpr { function, .. })),
+                    ..
+                }) => function,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        stmts[i] = T::from_stmt(Stmt::Decl(Decl::Fn(FnDecl {
+            ident: name,
+            declare: false,
+            function,
+        })));
+    }
+
+    stmts
+}
+
+/// Returns the name of the variable if `stmt` is `var f = function f() {}` or
+/// `var f = function () {}`.
+fn candidate(stmt: &Stmt) -> Option<&Ident> {
+    let decls = match *stmt {
+        Stmt::Decl(Decl::Var(VarDecl {
+            kind: VarDeclKind::Var,
+            declare: false,
+            ref decls,
+            ..
+        })) if decls.len() == 1 => decls,
+        _ => return None,
+    };
+
+    match decls[0] {
+        VarDeclarator {
+            name: Pat::Ident(ref name),
+            init: Some(box Expr::Fn(FnExpr { ref ident, .. })),
+            ..
+        } if name.type_ann.is_none() => match *ident {
+            None => Some(name),
+            // Inside of the function, `f` refers to the function itself.
+            Some(ref ident) if ident.sym == name.sym && ident.span.ctxt() == name.span.ctxt() => {
+                Some(name)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn can_convert<T>(stmts: &[T], idx: usize, name: &Ident) -> bool
+where
+    T: StmtLike + VisitWith<Finder>,
+{
+    for (i, stmt) in stmts.iter().enumerate() {
+        let mut v = Finder::new(name);
+
+        if i == idx {
+            match *stmt.as_stmt().unwrap() {
+                Stmt::Decl(Decl::Var(VarDecl { ref decls, .. })) => {
+                    decls[0].init.visit_with(&mut v)
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            stmt.visit_with(&mut v);
+        }
+
+        if (i < idx && v.used) || v.used_in_fn_decl || v.reassigned {
+            return false;
+        }
+    }
+
+    true
+}
+
+struct Finder {
+    sym: JsWord,
+    ctxt: SyntaxContext,
+    used: bool,
+    /// Set if the variable is used from a function declaration.
+    used_in_fn_decl: bool,
+    /// Set if the variable is assigned or declared again.
+    reassigned: bool,
+}
+
+impl Finder {
+    fn new(i: &Ident) -> Self {
+        Finder {
+            sym: i.sym.clone(),
+            ctxt: i.span.ctxt(),
+            used: false,
+            used_in_fn_decl: false,
+            reassigned: false,
+        }
+    }
+
+    fn is_target(&self, i: &Ident) -> bool {
+        i.sym == self.sym && i.span.ctxt() == self.ctxt
+    }
+
+    fn contains<N>(&self, node: &N) -> bool
+    where
+        N: VisitWith<Self>,
+    {
+        let mut v = Finder {
+            sym: self.sym.clone(),
+            ctxt: self.ctxt,
+            used: false,
+            used_in_fn_decl: false,
+            reassigned: false,
+        };
+        node.visit_with(&mut v);
+        v.used
+    }
+}
+
+impl Visit<Ident> for Finder {
+    fn visit(&mut self, i: &Ident) {
+        if self.is_target(i) {
+            self.used = true;
+        }
+    }
+}
+
+impl Visit<MemberExpr> for Finder {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<FnDecl> for Finder {
+    fn visit(&mut self, f: &FnDecl) {
+        if self.is_target(&f.ident) {
+            self.reassigned = true;
+        }
+
+        let used = self.used;
+        self.used = false;
+        f.function.visit_with(self);
+        if self.used {
+            self.used_in_fn_decl = true;
+        }
+        self.used |= used;
+    }
+}
+
+impl Visit<ClassDecl> for Finder {
+    fn visit(&mut self, c: &ClassDecl) {
+        if self.is_target(&c.ident) {
+            self.reassigned = true;
+        }
+
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<VarDeclarator> for Finder {
+    fn visit(&mut self, d: &VarDeclarator) {
+        if self.contains(&d.name) {
+            self.reassigned = true;
+        }
+
+        d.init.visit_with(self);
+    }
+}
+
+impl Visit<AssignExpr> for Finder {
+    fn visit(&mut self, e: &AssignExpr) {
+        if self.contains(&e.left) {
+            self.reassigned = true;
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UpdateExpr> for Finder {
+    fn visit(&mut self, e: &UpdateExpr) {
+        if self.contains(&e.arg) {
+            self.reassigned = true;
+        }
+
+        e.visit_children(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        matching_name,
+        "var f = function f() { return f; }; f();",
+        "function f() { return f; } f();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        anonymous,
+        "foo(); var f = function () {}; f();",
+        "foo(); function f() {} f();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        function_body,
+        "function foo() { var f = function () {}; return f; }",
+        "function foo() { function f() {} return f; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        different_name,
+        "var f = function g() { return g; };",
+        "var f = function g() { return g; };"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        used_before,
+        "if (f) foo(); var f = function () {};",
+        "if (f) foo(); var f = function () {};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        used_in_hoisted_fn,
+        "g(); var f = function () {}; function g() { f(); }",
+        "g(); var f = function () {}; function g() { f(); }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        reassigned,
+        "var f = function () {}; if (foo) f = bar;",
+        "var f = function () {}; if (foo) f = bar;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fn_decl(),
+        conditional,
+        "if (foo) { var f = function () {}; }",
+        "if (foo) { var f = function () {}; }"
+    );
+}
@@ -0,0 +1,204 @@
+use crate::pass::Pass;
+use ast::*;
+use swc_common::{Fold, FoldWith, Span, Spanned};
+
+/// Folds exponentiations of integer literals, like `Math.pow` calls emitted
+/// by the `exponentiation` pass.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// x = Math.pow(2, 10);
+/// y = Math.pow(Math.pow(2, 3), 2) + 3 ** 2 ** 2;
+/// z = Math.pow(a, 2);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// x = 1024;
+/// y = 64 + 81;
+/// z = Math.pow(a, 2);
+/// ```
+///
+/// Floating point exponentiation is not exact, so an exponentiation is folded
+/// only if the base is an integer, the exponent is a non-negative integer and
+/// the result is a safe integer. Nested exponentiations are folded from the
+/// inside out.
+///
+/// `Math` is assumed to be the global object.
+pub fn fold_pow() -> impl Pass {
+    FoldPow
+}
+
+#[derive(Clone, Copy)]
+struct FoldPow;
+
+impl Fold<Expr> for FoldPow {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        let value = match e {
+            Expr::Bin(BinExpr {
+                op: op!("**"),
+                ref left,
+                ref right,
+                ..
+            }) => pow(left, right),
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(ref callee),
+                ref args,
+                ..
+            }) if is_math_pow(callee) => match **args {
+                [ExprOrSpread {
+                    spread: None,
+                    expr: ref base,
+                }, ExprOrSpread {
+                    spread: None,
+                    expr: ref exp,
+                }] => pow(base, exp),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match value {
+            Some(value) => num(e.span(), value),
+            None => e,
+        }
+    }
+}
+
+fn is_math_pow(e: &Expr) -> bool {
+    match *e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+            prop: box Expr::Ident(ref prop),
+            computed: false,
+            ..
+        }) => obj.sym == *"Math" && prop.sym == *"pow",
+        _ => false,
+    }
+}
+
+/// Largest integer `n` such that `n` and `n + 1` are exactly representable.
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// Returns the value of `e` if it's an integer literal, optionally negated.
+fn int_lit(e: &Expr) -> Option<i128> {
+    match *e {
+        Expr::Paren(ParenExpr { ref expr, .. }) => int_lit(expr),
+        Expr::Lit(Lit::Num(Number { value, .. }))
+            if value.fract() == 0.0 && value <= MAX_SAFE_INTEGER as f64 =>
+        {
+            Some(value as i128)
+        }
+        // `-0` is not an integer for us, as `(-0) ** 1` is `-0`.
+        Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            ref arg,
+            ..
+        }) => int_lit(arg).filter(|&v| v != 0).map(|v| -v),
+        _ => None,
+    }
+}
+
+/// Computes `base ** exp` if the result is exact.
+fn pow(base: &Expr, exp: &Expr) -> Option<i128> {
+    let base = int_lit(base)?;
+    let exp = int_lit(exp)?;
+    if exp < 0 || exp > u32::max_value() as i128 {
+        return None;
+    }
+
+    base.checked_pow(exp as u32)
+        .filter(|v| v.abs() <= MAX_SAFE_INTEGER)
+}
+
+fn num(span: Span, value: i128) -> Expr {
+    let lit = Expr::Lit(Lit::Num(Number {
+        span,
+        value: value.abs() as f64,
+    }));
+
+    if value < 0 {
+        Expr::Unary(UnaryExpr {
+            span,
+            op: op!(unary, "-"),
+            arg: box lit,
+        })
+    } else {
+        lit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fold_pow(),
+        math_pow,
+        "x = Math.pow(2, 10);
+y = Math.pow(-3, 3);
+z = Math.pow(0, 0);",
+        "x = 1024;
+y = -27;
+z = 1;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fold_pow(),
+        nested,
+        "x = Math.pow(Math.pow(2, 3), 2);
+y = 2 ** 3 ** 2;
+z = (-2) ** Math.pow(2, 2);",
+        "x = 64;
+y = 512;
+z = 16;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fold_pow(),
+        non_literal_base,
+        "x = Math.pow(a, 2);
+y = Math.pow(2, a);
+z = a ** 2;",
+        "x = Math.pow(a, 2);
+y = Math.pow(2, a);
+z = a ** 2;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fold_pow(),
+        inexact,
+        "a = Math.pow(2, 0.5);
+b = Math.pow(10, -1);
+c = Math.pow(1.5, 2);
+d = Math.pow(3, 40);
+e = Math.pow(-0, 1);",
+        "a = Math.pow(2, 0.5);
+b = Math.pow(10, -1);
+c = Math.pow(1.5, 2);
+d = Math.pow(3, 40);
+e = Math.pow(-0, 1);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| fold_pow(),
+        other_calls,
+        "a = Math.pow(2, 3, f());
+b = Math.pow(...args);
+c = obj.pow(2, 3);",
+        "a = Math.pow(2, 3, f());
+b = Math.pow(...args);
+c = obj.pow(2, 3);"
+    );
+}
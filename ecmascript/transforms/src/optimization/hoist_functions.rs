@@ -0,0 +1,193 @@
+use crate::{
+    pass::Pass,
+    util::{prepend_stmts, StmtLike},
+};
+use ast::*;
+use swc_common::{Fold, FoldWith};
+
+/// Moves function declarations to the top of their function or module, like
+/// the engine does while instantiating the scope.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// 'use strict';
+/// foo();
+/// function foo() {}
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// 'use strict';
+/// function foo() {}
+/// foo();
+/// ```
+///
+/// Declarations are moved after directives, keeping their relative order.
+/// Function declarations in blocks are left in place, as they are scoped to
+/// the block in strict mode, and assigned to the outer variable only when
+/// evaluated in sloppy mode. Exported declarations are left in place, too.
+pub fn hoist_functions() -> impl Pass {
+    HoistFunctions
+}
+
+#[derive(Clone, Copy)]
+struct HoistFunctions;
+
+fn hoist<T>(stmts: Vec<T>) -> Vec<T>
+where
+    T: StmtLike,
+{
+    let (fns, mut rest): (Vec<_>, Vec<_>) =
+        stmts.into_iter().partition(|stmt| match stmt.as_stmt() {
+            Some(&Stmt::Decl(Decl::Fn(..))) => true,
+            _ => false,
+        });
+
+    prepend_stmts(&mut rest, fns.into_iter());
+    rest
+}
+
+fn hoist_body(body: Option<BlockStmt>) -> Option<BlockStmt> {
+    body.map(|body| BlockStmt {
+        stmts: hoist(body.stmts),
+        ..body
+    })
+}
+
+impl Fold<Module> for HoistFunctions {
+    fn fold(&mut self, m: Module) -> Module {
+        let m = m.fold_children(self);
+
+        Module {
+            body: hoist(m.body),
+            ..m
+        }
+    }
+}
+
+impl Fold<Script> for HoistFunctions {
+    fn fold(&mut self, s: Script) -> Script {
+        let s = s.fold_children(self);
+
+        Script {
+            body: hoist(s.body),
+            ..s
+        }
+    }
+}
+
+impl Fold<Function> for HoistFunctions {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+
+        Function {
+            body: hoist_body(f.body),
+            ..f
+        }
+    }
+}
+
+impl Fold<Constructor> for HoistFunctions {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        let c = c.fold_children(self);
+
+        Constructor {
+            body: hoist_body(c.body),
+            ..c
+        }
+    }
+}
+
+impl Fold<GetterProp> for HoistFunctions {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        let p = p.fold_children(self);
+
+        GetterProp {
+            body: hoist_body(p.body),
+            ..p
+        }
+    }
+}
+
+impl Fold<SetterProp> for HoistFunctions {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let p = p.fold_children(self);
+
+        SetterProp {
+            body: hoist_body(p.body),
+            ..p
+        }
+    }
+}
+
+impl Fold<ArrowExpr> for HoistFunctions {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let f = f.fold_children(self);
+
+        match f.body {
+            BlockStmtOrExpr::BlockStmt(body) => ArrowExpr {
+                body: BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    stmts: hoist(body.stmts),
+                    ..body
+                }),
+                ..f
+            },
+            _ => f,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| hoist_functions(),
+        called_before_decl,
+        "'use strict';
+foo();
+function foo() { return bar(); function bar() {} }
+var a = 1;
+function baz() {}",
+        "'use strict';
+function foo() { function bar() {} return bar(); }
+function baz() {}
+foo();
+var a = 1;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| hoist_functions(),
+        block_in_strict_mode,
+        "'use strict';
+foo();
+{
+    bar();
+    function bar() {}
+}
+function foo() {}",
+        "'use strict';
+function foo() {}
+foo();
+{
+    bar();
+    function bar() {}
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| hoist_functions(),
+        arrow_and_method,
+        "var f = () => { g(); function g() {} };
+var o = { get a() { return g(); function g() {} } };",
+        "var f = () => { function g() {} g(); };
+var o = { get a() { function g() {} return g(); } };"
+    );
+}
@@ -0,0 +1,265 @@
+use crate::{pass::Pass, util::undefined};
+use ast::*;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, Visit, VisitWith};
+
+/// Inlines immediately invoked function expressions which only return a
+/// value.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var a = (function () { return foo(); })();
+/// var b = (() => bar)();
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var a = foo();
+/// var b = bar;
+/// ```
+///
+/// Only functions without parameters which are called without arguments are
+/// inlined. A function is inlined only if its body starts with a `return`
+/// statement and the rest of the body doesn't declare anything, as a hoisted
+/// declaration would shadow variables used by the returned value. Functions
+/// using `this`, `arguments` or `new.target`, and named function expressions
+/// which refer to themselves are left as-is.
+pub fn iife_inline() -> impl Pass {
+    IifeInline
+}
+
+#[derive(Clone, Copy)]
+struct IifeInline;
+
+impl Fold<Expr> for IifeInline {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Call(CallExpr {
+                span,
+                callee: ExprOrSuper::Expr(ref callee),
+                ref args,
+                ..
+            }) if args.is_empty() => match inline(callee) {
+                Some(Some(ret)) => ret,
+                Some(None) => *undefined(span),
+                None => e,
+            },
+            _ => e,
+        }
+    }
+}
+
+/// Returns the value returned by `callee`, if it can be inlined.
+fn inline(callee: &Expr) -> Option<Option<Expr>> {
+    match *callee {
+        Expr::Paren(ParenExpr { ref expr, .. }) => inline(expr),
+
+        Expr::Fn(FnExpr {
+            ref ident,
+            ref function,
+        }) => {
+            if !is_simple(
+                function.params.is_empty(),
+                function.is_async,
+                function.is_generator,
+            ) || !function.decorators.is_empty()
+            {
+                return None;
+            }
+            let body = function.body.as_ref()?;
+
+            let mut v = FnScopeFinder { found: false };
+            body.visit_with(&mut v);
+            if v.found {
+                return None;
+            }
+            if let Some(ref ident) = *ident {
+                let mut v = IdentFinder {
+                    ident,
+                    found: false,
+                };
+                body.visit_with(&mut v);
+                if v.found {
+                    return None;
+                }
+            }
+
+            returned_value(&body.stmts)
+        }
+
+        Expr::Arrow(ArrowExpr {
+            ref params,
+            ref body,
+            is_async,
+            is_generator,
+            ..
+        }) => {
+            if !is_simple(params.is_empty(), is_async, is_generator) {
+                return None;
+            }
+
+            match *body {
+                BlockStmtOrExpr::Expr(ref e) => Some(Some((**e).clone())),
+                BlockStmtOrExpr::BlockStmt(ref body) => returned_value(&body.stmts),
+            }
+        }
+
+        _ => None,
+    }
+}
+
+fn is_simple(no_params: bool, is_async: bool, is_generator: bool) -> bool {
+    no_params && !is_async && !is_generator
+}
+
+fn returned_value(stmts: &[Stmt]) -> Option<Option<Expr>> {
+    let (first, rest) = stmts.split_first()?;
+
+    let mut v = DeclFinder { found: false };
+    for stmt in rest {
+        stmt.visit_with(&mut v);
+    }
+    if v.found {
+        return None;
+    }
+
+    match *first {
+        Stmt::Return(ReturnStmt { ref arg, .. }) => Some(arg.as_ref().map(|arg| (**arg).clone())),
+        _ => None,
+    }
+}
+
+/// Finds usages of `this`, `arguments` and `new.target` which belong to the
+/// function.
+struct FnScopeFinder {
+    found: bool,
+}
+
+impl Visit<ThisExpr> for FnScopeFinder {
+    fn visit(&mut self, _: &ThisExpr) {
+        self.found = true;
+    }
+}
+
+impl Visit<MetaPropExpr> for FnScopeFinder {
+    fn visit(&mut self, _: &MetaPropExpr) {
+        self.found = true;
+    }
+}
+
+impl Visit<Ident> for FnScopeFinder {
+    fn visit(&mut self, i: &Ident) {
+        if i.sym == js_word!("arguments") {
+            self.found = true;
+        }
+    }
+}
+
+impl Visit<Function> for FnScopeFinder {
+    /// Don't recurse into fn
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<Constructor> for FnScopeFinder {
+    /// Don't recurse into constructor
+    fn visit(&mut self, _: &Constructor) {}
+}
+
+struct IdentFinder<'a> {
+    ident: &'a Ident,
+    found: bool,
+}
+
+impl<'a> Visit<Ident> for IdentFinder<'a> {
+    fn visit(&mut self, i: &Ident) {
+        if i.sym == self.ident.sym && i.span.ctxt() == self.ident.span.ctxt() {
+            self.found = true;
+        }
+    }
+}
+
+/// Finds declarations which are scoped to the function.
+struct DeclFinder {
+    found: bool,
+}
+
+impl Visit<Decl> for DeclFinder {
+    fn visit(&mut self, _: &Decl) {
+        self.found = true;
+    }
+}
+
+impl Visit<VarDecl> for DeclFinder {
+    fn visit(&mut self, _: &VarDecl) {
+        self.found = true;
+    }
+}
+
+impl Visit<Function> for DeclFinder {
+    /// Don't recurse into fn
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for DeclFinder {
+    /// Don't recurse into arrow
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| iife_inline(),
+        return_value,
+        "var a = (function () { return 1; })();
+var b = (() => foo())();
+var c = (function () { return; })();
+var d = (function () { return bar; foo(); })();",
+        "var a = 1;
+var b = foo();
+var c = void 0;
+var d = bar;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| iife_inline(),
+        hoisted_var,
+        "var a = (function () { return b; var b = 1; })();
+var c = (function () { return d; function d() {} })();",
+        "var a = (function () { return b; var b = 1; })();
+var c = (function () { return d; function d() {} })();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| iife_inline(),
+        fn_scope,
+        "var a = (function () { return this; })();
+var b = (function () { return arguments; })();
+var c = (function f() { return f; })();
+var d = (function (e) { return e; })();
+var e = (function () { return 1; })(foo());",
+        "var a = (function () { return this; })();
+var b = (function () { return arguments; })();
+var c = (function f() { return f; })();
+var d = (function (e) { return e; })();
+var e = (function () { return 1; })(foo());"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| iife_inline(),
+        precedence,
+        "var a = (function () { return b + c; })() * 2;",
+        "var a = (b + c) * 2;"
+    );
+}
@@ -0,0 +1,628 @@
+use crate::{
+    pass::Pass,
+    util::{Assumptions, ExprExt, StmtLike},
+};
+use ast::*;
+use std::mem;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, SyntaxContext, Visit, VisitWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// Inlines `const` and `let` bindings which are used only once.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// const a = 1;
+/// const b = g();
+/// f(a, b);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// f(1, g());
+/// ```
+///
+/// Initializers which consist only of literals are inlined wherever the
+/// binding is used. Other initializers are moved only into the next statement,
+/// and only if nothing with a side effect is evaluated before the usage. As a
+/// result, an initializer is never moved into a function, a loop or a
+/// conditionally evaluated expression.
+///
+/// Bindings are compared using their syntax context, so this pass should be
+/// applied after `resolver`.
+pub fn inline(assumptions: Assumptions) -> impl Pass {
+    Inline { assumptions }
+}
+
+struct Inline {
+    assumptions: Assumptions,
+}
+
+impl<T> Fold<Vec<T>> for Inline
+where
+    T: StmtLike + VisitWith<Counter> + FoldWith<Replacer>,
+    Vec<T>: FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut stmts = stmts.fold_children(self);
+
+        let mut i = 0;
+        while i < stmts.len() {
+            if self.try_inline(&mut stmts, i) {
+                stmts.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        stmts
+    }
+}
+
+impl Inline {
+    /// Inlines the initializer of `stmts[idx]` if possible.
+    ///
+    /// Returns true if `stmts[idx]` should be removed.
+    fn try_inline<T>(&self, stmts: &mut [T], idx: usize) -> bool
+    where
+        T: StmtLike + VisitWith<Counter> + FoldWith<Replacer>,
+    {
+        let (name, init) = match stmts[idx].as_stmt().and_then(candidate) {
+            Some((name, init)) => (name.clone(), init),
+            None => return false,
+        };
+
+        let mut v = Counter::new(&name);
+        init.visit_with(&mut v);
+        stmts[..idx].visit_with(&mut v);
+        if v.total != 0 {
+            return false;
+        }
+
+        stmts[idx + 1..].visit_with(&mut v);
+        if v.reassigned || v.total != 1 || v.in_expr != 1 {
+            return false;
+        }
+
+        let is_const = is_const(init);
+        if !is_const {
+            match stmts.get(idx + 1).and_then(|stmt| stmt.as_stmt()) {
+                Some(stmt) => {
+                    if self.search_stmt(stmt, &name) != Search::Found {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        let init = match stmts[idx].as_stmt() {
+            Some(Stmt::Decl(Decl::Var(VarDecl { ref decls, .. }))) => decls[0].init.clone(),
+            _ => unreachable!(),
+        };
+        let mut replacer = Replacer {
+            sym: name.sym,
+            ctxt: name.span.ctxt(),
+            init,
+        };
+
+        for stmt in stmts[idx + 1..].iter_mut() {
+            let s = mem::replace(
+                stmt,
+                T::from_stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP })),
+            );
+            *stmt = s.fold_with(&mut replacer);
+            if replacer.init.is_none() {
+                break;
+            }
+        }
+        debug_assert!(replacer.init.is_none(), "failed to inline {}", name.sym);
+
+        true
+    }
+
+    /// Searches for `name` in the order of evaluation.
+    fn search_stmt(&self, stmt: &Stmt, name: &Ident) -> Search {
+        match *stmt {
+            Stmt::Expr(ref e)
+            | Stmt::Return(ReturnStmt {
+                arg: Some(ref e), ..
+            })
+            | Stmt::Throw(ThrowStmt { arg: ref e, .. })
+            | Stmt::If(IfStmt { test: ref e, .. })
+            | Stmt::Switch(SwitchStmt {
+                discriminant: ref e,
+                ..
+            }) => self.search(e, name),
+
+            Stmt::Decl(Decl::Var(VarDecl { ref decls, .. })) => {
+                for decl in decls {
+                    match decl.name {
+                        Pat::Ident(..) => {}
+                        // Destructuring may invoke getters.
+                        _ => return Search::Blocked,
+                    }
+
+                    match decl.init {
+                        Some(ref init) => match self.search(init, name) {
+                            Search::Continue => {}
+                            res => return res,
+                        },
+                        None => {}
+                    }
+                }
+
+                Search::Continue
+            }
+
+            _ => Search::Blocked,
+        }
+    }
+
+    fn search(&self, e: &Expr, name: &Ident) -> Search {
+        if !Counter::contains(name, e) {
+            return if e.may_have_side_effects_with(&self.assumptions) {
+                Search::Blocked
+            } else {
+                Search::Continue
+            };
+        }
+
+        match *e {
+            Expr::Ident(..) => Search::Found,
+
+            Expr::Paren(ParenExpr { ref expr, .. })
+            | Expr::Unary(UnaryExpr { arg: ref expr, .. })
+            | Expr::Await(AwaitExpr { arg: ref expr, .. })
+            | Expr::Yield(YieldExpr {
+                arg: Some(ref expr),
+                ..
+            })
+            | Expr::TsAs(TsAsExpr { ref expr, .. })
+            | Expr::TsNonNull(TsNonNullExpr { ref expr, .. })
+            | Expr::TsTypeAssertion(TsTypeAssertion { ref expr, .. })
+            | Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. }) => self.search(expr, name),
+
+            // The right operand is evaluated conditionally.
+            Expr::Bin(BinExpr {
+                op: op!("||"),
+                ref left,
+                ..
+            })
+            | Expr::Bin(BinExpr {
+                op: op!("&&"),
+                ref left,
+                ..
+            })
+            | Expr::Cond(CondExpr { test: ref left, .. }) => match self.search(left, name) {
+                Search::Continue => Search::Blocked,
+                res => res,
+            },
+            Expr::Bin(BinExpr {
+                ref left,
+                ref right,
+                ..
+            }) => self.search_all(vec![&**left, &**right], name),
+
+            Expr::Seq(SeqExpr { ref exprs, .. }) | Expr::Tpl(Tpl { ref exprs, .. }) => {
+                self.search_all(exprs.iter().map(|e| &**e), name)
+            }
+
+            // Inlining a member expression into the callee changes `this`.
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(box Expr::Ident(ref i)),
+                ..
+            })
+            | Expr::TaggedTpl(TaggedTpl {
+                tag: box Expr::Ident(ref i),
+                ..
+            }) if Counter::contains(name, i) => Search::Blocked,
+
+            Expr::TaggedTpl(TaggedTpl {
+                ref tag, ref exprs, ..
+            }) => self.search_all(
+                Some(&**tag).into_iter().chain(exprs.iter().map(|e| &**e)),
+                name,
+            ),
+
+            Expr::Member(ref e) => self.search_member(e, name),
+
+            Expr::Assign(AssignExpr {
+                ref left,
+                ref right,
+                ..
+            }) => {
+                let res = match *left {
+                    PatOrExpr::Expr(box Expr::Member(ref e)) => self.search_member(e, name),
+                    PatOrExpr::Pat(box Pat::Expr(box Expr::Member(ref e))) => {
+                        self.search_member(e, name)
+                    }
+                    // Default values of patterns are evaluated after the right hand side.
+                    _ if Counter::contains(name, left) => Search::Blocked,
+                    _ => Search::Continue,
+                };
+
+                match res {
+                    Search::Continue => self.search(right, name),
+                    res => res,
+                }
+            }
+
+            Expr::Call(CallExpr {
+                ref callee,
+                ref args,
+                ..
+            }) => {
+                let res = match *callee {
+                    ExprOrSuper::Expr(ref callee) => self.search(callee, name),
+                    ExprOrSuper::Super(..) => Search::Continue,
+                };
+                match res {
+                    Search::Continue => self.search_args(args.iter().map(Some), name),
+                    res => res,
+                }
+            }
+            Expr::New(NewExpr {
+                ref callee,
+                ref args,
+                ..
+            }) => match self.search(callee, name) {
+                Search::Continue => {
+                    self.search_args(args.iter().flat_map(|args| args.iter()).map(Some), name)
+                }
+                res => res,
+            },
+            Expr::Array(ArrayLit { ref elems, .. }) => {
+                self.search_args(elems.iter().map(|e| e.as_ref()), name)
+            }
+
+            Expr::Object(ObjectLit { ref props, .. }) => {
+                for prop in props {
+                    let res = match *prop {
+                        PropOrSpread::Prop(ref prop) => match **prop {
+                            Prop::Shorthand(ref i) => {
+                                if Counter::contains(name, i) {
+                                    Search::Found
+                                } else {
+                                    Search::Continue
+                                }
+                            }
+                            Prop::KeyValue(KeyValueProp { ref key, ref value }) => {
+                                match self.search_prop_name(key, name) {
+                                    Search::Continue => self.search(value, name),
+                                    res => res,
+                                }
+                            }
+                            Prop::Getter(GetterProp { ref key, .. })
+                            | Prop::Setter(SetterProp { ref key, .. })
+                            | Prop::Method(MethodProp { ref key, .. }) => {
+                                match self.search_prop_name(key, name) {
+                                    Search::Continue if Counter::contains(name, &**prop) => {
+                                        Search::Blocked
+                                    }
+                                    res => res,
+                                }
+                            }
+                            Prop::Assign(..) => Search::Blocked,
+                        },
+                        PropOrSpread::Spread(SpreadElement { ref expr, .. }) => {
+                            match self.search(expr, name) {
+                                // Spreading invokes getters.
+                                Search::Continue if !self.assumptions.pure_getters => {
+                                    Search::Blocked
+                                }
+                                res => res,
+                            }
+                        }
+                    };
+
+                    match res {
+                        Search::Continue => {}
+                        res => return res,
+                    }
+                }
+
+                Search::Continue
+            }
+
+            // Functions are not invoked immediately, and the initializer should not be
+            // evaluated more than once.
+            _ => Search::Blocked,
+        }
+    }
+
+    fn search_all<'a, I>(&self, exprs: I, name: &Ident) -> Search
+    where
+        I: IntoIterator<Item = &'a Expr>,
+    {
+        for e in exprs {
+            match self.search(e, name) {
+                Search::Continue => {}
+                res => return res,
+            }
+        }
+
+        Search::Continue
+    }
+
+    fn search_args<'a, I>(&self, args: I, name: &Ident) -> Search
+    where
+        I: IntoIterator<Item = Option<&'a ExprOrSpread>>,
+    {
+        for arg in args.into_iter().filter_map(|arg| arg) {
+            match self.search(&arg.expr, name) {
+                // Spreading invokes the iterator protocol.
+                Search::Continue if arg.spread.is_some() => return Search::Blocked,
+                Search::Continue => {}
+                res => return res,
+            }
+        }
+
+        Search::Continue
+    }
+
+    fn search_member(&self, e: &MemberExpr, name: &Ident) -> Search {
+        let res = match e.obj {
+            ExprOrSuper::Expr(ref obj) => self.search(obj, name),
+            ExprOrSuper::Super(..) => Search::Continue,
+        };
+
+        match res {
+            Search::Continue if e.computed => self.search(&e.prop, name),
+            res => res,
+        }
+    }
+
+    fn search_prop_name(&self, key: &PropName, name: &Ident) -> Search {
+        match *key {
+            PropName::Computed(ref e) => self.search(&e.expr, name),
+            _ => Search::Continue,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Search {
+    /// The binding is used before any side effect.
+    Found,
+    /// An expression with a side effect is evaluated before the binding is
+    /// used.
+    Blocked,
+    /// The expression does not use the binding and it does not have side
+    /// effects.
+    Continue,
+}
+
+/// Returns the name and the initializer if `stmt` declares only one binding
+/// using `const` or `let`.
+fn candidate(stmt: &Stmt) -> Option<(&Ident, &Expr)> {
+    match *stmt {
+        Stmt::Decl(Decl::Var(VarDecl {
+            kind: VarDeclKind::Const,
+            ref decls,
+            declare: false,
+            ..
+        }))
+        | Stmt::Decl(Decl::Var(VarDecl {
+            kind: VarDeclKind::Let,
+            ref decls,
+            declare: false,
+            ..
+        })) if decls.len() == 1 => match decls[0] {
+            VarDeclarator {
+                name: Pat::Ident(ref name),
+                init: Some(ref init),
+                ..
+            } if name.type_ann.is_none() => Some((name, init)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns true if `e` consists only of literals, so it can be evaluated at
+/// any time.
+pub(super) fn is_const(e: &Expr) -> bool {
+    match *e {
+        // Each evaluation of a regular expression literal creates a new object.
+        Expr::Lit(Lit::Regex(..)) => false,
+        Expr::Lit(..) => true,
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_const(expr),
+        Expr::Unary(UnaryExpr {
+            op: op!("delete"), ..
+        }) => false,
+        Expr::Unary(UnaryExpr { ref arg, .. }) => is_const(arg),
+        Expr::Bin(BinExpr {
+            ref left,
+            ref right,
+            ..
+        }) => is_const(left) && is_const(right),
+        Expr::Tpl(Tpl { ref exprs, .. }) => exprs.iter().all(|e| is_const(e)),
+        _ => false,
+    }
+}
+
+/// Counts usages of a binding.
+struct Counter {
+    sym: JsWord,
+    ctxt: SyntaxContext,
+    total: usize,
+    /// Usages which can be replaced with an expression.
+    in_expr: usize,
+    /// Set if the binding is assigned or deleted.
+    reassigned: bool,
+}
+
+impl Counter {
+    fn new(i: &Ident) -> Self {
+        Counter {
+            sym: i.sym.clone(),
+            ctxt: i.span.ctxt(),
+            total: 0,
+            in_expr: 0,
+            reassigned: false,
+        }
+    }
+
+    fn contains<N>(i: &Ident, node: &N) -> bool
+    where
+        N: VisitWith<Self>,
+    {
+        let mut v = Counter::new(i);
+        node.visit_with(&mut v);
+        v.total != 0
+    }
+
+    fn is_target(&self, i: &Ident) -> bool {
+        i.sym == self.sym && i.span.ctxt() == self.ctxt
+    }
+}
+
+impl Visit<Ident> for Counter {
+    fn visit(&mut self, i: &Ident) {
+        if self.is_target(i) {
+            self.total += 1;
+        }
+    }
+}
+
+impl Visit<Expr> for Counter {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Ident(ref i) if self.is_target(i) => self.in_expr += 1,
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<Prop> for Counter {
+    fn visit(&mut self, p: &Prop) {
+        match *p {
+            Prop::Shorthand(ref i) if self.is_target(i) => self.in_expr += 1,
+            _ => {}
+        }
+
+        p.visit_children(self);
+    }
+}
+
+impl Visit<PropName> for Counter {
+    fn visit(&mut self, n: &PropName) {
+        match *n {
+            PropName::Computed(ref e) => e.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+impl Visit<MemberExpr> for Counter {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<AssignExpr> for Counter {
+    fn visit(&mut self, e: &AssignExpr) {
+        match e.left {
+            PatOrExpr::Pat(box Pat::Ident(ref i)) | PatOrExpr::Expr(box Expr::Ident(ref i))
+                if self.is_target(i) =>
+            {
+                self.reassigned = true
+            }
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UnaryExpr> for Counter {
+    fn visit(&mut self, e: &UnaryExpr) {
+        match *e {
+            UnaryExpr {
+                op: op!("delete"),
+                arg: box Expr::Ident(ref i),
+                ..
+            } if self.is_target(i) => self.reassigned = true,
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UpdateExpr> for Counter {
+    fn visit(&mut self, e: &UpdateExpr) {
+        match *e.arg {
+            Expr::Ident(ref i) if self.is_target(i) => self.reassigned = true,
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+/// Replaces the usage of a binding with `init`.
+struct Replacer {
+    sym: JsWord,
+    ctxt: SyntaxContext,
+    init: Option<Box<Expr>>,
+}
+
+impl Replacer {
+    fn is_target(&self, i: &Ident) -> bool {
+        i.sym == self.sym && i.span.ctxt() == self.ctxt
+    }
+}
+
+impl Fold<Expr> for Replacer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        if self.init.is_none() {
+            return e;
+        }
+
+        match e {
+            Expr::Ident(ref i) if self.is_target(i) => *self.init.take().unwrap(),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for Replacer {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) if self.init.is_some() && self.is_target(&i) => {
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: self.init.take().unwrap(),
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Replacer {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+fn tr() -> impl Pass {
+    inline(Default::default())
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    literal,
+    "const a = 1;
+f(a);",
+    "f(1);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    literal_into_closure,
+    "const a = 'foo' + 1;
+function f() { return a; }",
+    "function f() { return 'foo' + 1; }"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    chained,
+    "const a = 1;
+const b = a + 2;
+f(b);",
+    "f(1 + 2);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    impure_next_stmt,
+    "const a = g();
+f(a);",
+    "f(g());"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    used_twice,
+    "const a = g();
+f(a);
+h(a);",
+    "const a = g();
+f(a);
+h(a);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    cross_stmt,
+    "const a = g();
+b();
+f(a);",
+    "const a = g();
+b();
+f(a);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    cross_side_effect,
+    "const a = g();
+f(b(), a);",
+    "const a = g();
+f(b(), a);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    conditional,
+    "const a = g();
+x && f(a);",
+    "const a = g();
+x && f(a);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    fn_expr_in_closure,
+    "const f = function () {};
+foo(() => f);",
+    "const f = function () {};
+foo(() => f);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    reassigned,
+    "let a = 1;
+a = 2;",
+    "let a = 1;
+a = 2;"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    member_callee,
+    "const a = obj.method;
+a();",
+    "const a = obj.method;
+a();"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    member_access_default,
+    "const a = g();
+console.log(a);",
+    "const a = g();
+console.log(a);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| inline(Assumptions { pure_getters: true }),
+    member_access_pure_getters,
+    "const a = g();
+console.log(a);",
+    "console.log(g());"
+);
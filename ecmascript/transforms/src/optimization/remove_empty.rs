@@ -0,0 +1,293 @@
+use crate::{
+    pass::Pass,
+    util::{ExprExt, StmtLike},
+};
+use ast::*;
+use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned, Visit, VisitWith};
+
+/// Removes empty blocks and statements which have no effect because their
+/// bodies are empty.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function foo() {
+///     {}
+///     if (a) {}
+///     if (bar()) ;
+///     switch (b) {
+///         case 1:
+///             baz();
+///         case 2:
+///     }
+///     switch (c) {
+///         default:
+///             qux();
+///             break;
+///     }
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo() {
+///     bar();
+///     switch (b) {
+///         case 1:
+///             baz();
+///     }
+///     {
+///         qux();
+///     }
+/// }
+/// ```
+///
+/// Conditions and discriminants which may have side effects are kept as
+/// expression statements. Empty bodies of loops are replaced with `;`, as
+/// they can't be removed.
+pub fn remove_empty() -> impl Pass {
+    RemoveEmpty
+}
+
+#[derive(Clone, Copy)]
+struct RemoveEmpty;
+
+fn is_empty(s: &Stmt) -> bool {
+    match *s {
+        Stmt::Empty(..) => true,
+        _ => false,
+    }
+}
+
+/// Returns `e` as a statement if it may have side effects.
+fn effects(e: Box<Expr>) -> Stmt {
+    if e.may_have_side_effects() {
+        Stmt::Expr(e)
+    } else {
+        Stmt::Empty(EmptyStmt { span: e.span() })
+    }
+}
+
+fn is_break(s: &Stmt) -> bool {
+    match *s {
+        Stmt::Break(BreakStmt { label: None, .. }) => true,
+        _ => false,
+    }
+}
+
+/// Removes cases at the end of `cases` which do nothing.
+fn remove_trailing_cases(cases: &mut Vec<SwitchCase>) {
+    // Tests are evaluated until a case matches.
+    let is_removable = |case: &SwitchCase| {
+        (case.cons.is_empty() || (case.cons.len() == 1 && is_break(&case.cons[0])))
+            && case
+                .test
+                .as_ref()
+                .map_or(true, |test| !test.may_have_side_effects())
+    };
+    let mut len = cases.len();
+    while len > 0 && is_removable(&cases[len - 1]) {
+        len -= 1;
+    }
+    // If `default` comes before the removed cases, values matching them would
+    // fall back to `default`.
+    if cases[..len].iter().all(|case| case.test.is_some()) {
+        cases.truncate(len);
+    }
+
+    if let Some(last) = cases.last_mut() {
+        if last.cons.last().map_or(false, is_break) {
+            last.cons.pop();
+        }
+    }
+}
+
+impl Fold<Stmt> for RemoveEmpty {
+    fn fold(&mut self, s: Stmt) -> Stmt {
+        let s = s.fold_children(self);
+
+        match s {
+            Stmt::Block(BlockStmt { span, ref stmts }) if stmts.is_empty() => {
+                Stmt::Empty(EmptyStmt { span })
+            }
+
+            Stmt::Labeled(LabeledStmt {
+                span,
+                body: box Stmt::Empty(..),
+                ..
+            }) => Stmt::Empty(EmptyStmt { span }),
+
+            Stmt::If(IfStmt {
+                span,
+                test,
+                cons,
+                alt,
+            }) => {
+                let alt = alt.filter(|alt| !is_empty(alt));
+                if alt.is_none() && is_empty(&cons) {
+                    return effects(test);
+                }
+
+                Stmt::If(IfStmt {
+                    span,
+                    test,
+                    cons,
+                    alt,
+                })
+            }
+
+            Stmt::Switch(SwitchStmt {
+                span,
+                discriminant,
+                mut cases,
+            }) => {
+                remove_trailing_cases(&mut cases);
+
+                if cases.is_empty() {
+                    return effects(discriminant);
+                }
+
+                let is_default_only = cases.len() == 1 && cases[0].test.is_none();
+                if is_default_only && !BreakFinder::find(&cases[0].cons) {
+                    let mut stmts = vec![];
+                    if discriminant.may_have_side_effects() {
+                        stmts.push(Stmt::Expr(discriminant));
+                    }
+                    stmts.extend(cases.pop().unwrap().cons);
+
+                    return Stmt::Block(BlockStmt { span, stmts });
+                }
+
+                Stmt::Switch(SwitchStmt {
+                    span,
+                    discriminant,
+                    cases,
+                })
+            }
+
+            _ => s,
+        }
+    }
+}
+
+impl<T> Fold<Vec<T>> for RemoveEmpty
+where
+    T: StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, items: Vec<T>) -> Vec<T> {
+        items.move_flat_map(|item| {
+            let item = item.fold_with(self);
+            match item.as_stmt() {
+                Some(Stmt::Empty(..)) => None,
+                _ => Some(item),
+            }
+        })
+    }
+}
+
+/// Finds a `break` without a label which targets the enclosing statement.
+struct BreakFinder {
+    found: bool,
+}
+
+impl BreakFinder {
+    fn find(stmts: &[Stmt]) -> bool {
+        let mut v = BreakFinder { found: false };
+        stmts.visit_with(&mut v);
+        v.found
+    }
+}
+
+impl Visit<BreakStmt> for BreakFinder {
+    fn visit(&mut self, s: &BreakStmt) {
+        if s.label.is_none() {
+            self.found = true;
+        }
+    }
+}
+
+/// `break` in these nodes targets them or is not allowed.
+macro_rules! break_target {
+    ($T:ty) => {
+        impl Visit<$T> for BreakFinder {
+            fn visit(&mut self, _: &$T) {}
+        }
+    };
+}
+
+break_target!(ForStmt);
+break_target!(ForInStmt);
+break_target!(ForOfStmt);
+break_target!(WhileStmt);
+break_target!(DoWhileStmt);
+break_target!(SwitchStmt);
+break_target!(Function);
+break_target!(ArrowExpr);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| remove_empty(),
+        empty_block,
+        "function foo() { a(); {} { {} } while (b) {} }",
+        "function foo() { a(); while (b); }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| remove_empty(),
+        empty_if,
+        "if (a) {} if (b()) ; if (c) {} else {} if (d) {} else e();",
+        "b(); if (d) ; else e();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| remove_empty(),
+        empty_cases,
+        "switch (a) { case 1: b(); case 2: case 3: break; }
+switch (c) { case d(): }
+switch (e()) {}",
+        "switch (a) { case 1: b(); }
+switch (c) { case d(): }
+e();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| remove_empty(),
+        empty_cases_after_default,
+        "switch (a) { default: x(); case 1: }
+switch (b) { case 1: x(); default: case 2: break; }",
+        "switch (a) { default: x(); case 1: }
+switch (b) { case 1: x(); }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| remove_empty(),
+        default_only,
+        "switch (a) { default: b(); break; }
+switch (c()) { default: let d; }
+switch (e) { default: if (f) break; g(); }
+switch (h) { default: for (;;) break; }",
+        "{ b(); }
+{ c(); let d; }
+switch (e) { default: if (f) break; g(); }
+{ for (;;) break; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| remove_empty(),
+        labeled,
+        "a: {} b: for (;;) { c: {} continue b; }",
+        "b: for (;;) { continue b; }"
+    );
+}
@@ -0,0 +1,268 @@
+use crate::{
+    pass::Pass,
+    util::{BoolType, ExprExt, Known},
+};
+use ast::*;
+use swc_common::{Fold, FoldWith, Span};
+
+/// Simplifies negated comparisons and double negations, which are often
+/// produced by other passes.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// if (!!a) foo(!(b === c), !(d != e));
+/// x = (f < g) === true;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// if (a) foo(b !== c, d == e);
+/// x = f < g;
+/// ```
+///
+/// Only equality operators are inverted, as `!(a < b)` differs from
+/// `a >= b` if an operand is `NaN`. A comparison with `true` or `false` is
+/// removed only if the other operand is known to be a boolean, because `==`
+/// coerces it and `===` fails for other truthy values. `!!x` is replaced
+/// with `x` only if the value is used as a condition.
+pub fn simplify_compare() -> impl Pass {
+    SimplifyCompare
+}
+
+#[derive(Clone, Copy)]
+struct SimplifyCompare;
+
+/// Returns the inverse of an equality operator.
+fn invert(op: BinaryOp) -> Option<BinaryOp> {
+    Some(match op {
+        op!("===") => op!("!=="),
+        op!("!==") => op!("==="),
+        op!("==") => op!("!="),
+        op!("!=") => op!("=="),
+        _ => return None,
+    })
+}
+
+fn unwrap_paren(e: Box<Expr>) -> Box<Expr> {
+    match e {
+        box Expr::Paren(ParenExpr { expr, .. }) => unwrap_paren(expr),
+        _ => e,
+    }
+}
+
+fn is_bool(e: &Expr) -> bool {
+    match *e {
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_bool(expr),
+        _ => e.get_type() == Known(BoolType),
+    }
+}
+
+/// Returns `!arg`, inverting `arg` if it's an equality.
+fn negate(span: Span, arg: Box<Expr>) -> Expr {
+    let arg = unwrap_paren(arg);
+
+    match arg {
+        box Expr::Bin(BinExpr {
+            span: bin_span,
+            op,
+            left,
+            right,
+        }) => match invert(op) {
+            Some(op) => Expr::Bin(BinExpr {
+                span: bin_span,
+                op,
+                left,
+                right,
+            }),
+            None => Expr::Unary(UnaryExpr {
+                span,
+                op: op!("!"),
+                arg: box Expr::Bin(BinExpr {
+                    span: bin_span,
+                    op,
+                    left,
+                    right,
+                }),
+            }),
+        },
+        // The result of `!` is only used as a condition of itself.
+        _ => Expr::Unary(UnaryExpr {
+            span,
+            op: op!("!"),
+            arg: condition(arg),
+        }),
+    }
+}
+
+/// Simplifies `e`, whose value is only used as a condition.
+fn condition(e: Box<Expr>) -> Box<Expr> {
+    match e {
+        box Expr::Unary(UnaryExpr {
+            op: op!("!"),
+            arg: box Expr::Unary(UnaryExpr {
+                op: op!("!"), arg, ..
+            }),
+            ..
+        }) => condition(arg),
+        box Expr::Paren(ParenExpr { span, expr }) => box Expr::Paren(ParenExpr {
+            span,
+            expr: condition(expr),
+        }),
+        box Expr::Bin(BinExpr {
+            span,
+            op,
+            left,
+            right,
+        }) if op == op!("&&") || op == op!("||") => box Expr::Bin(BinExpr {
+            span,
+            op,
+            left: condition(left),
+            right: condition(right),
+        }),
+        _ => e,
+    }
+}
+
+impl Fold<Expr> for SimplifyCompare {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Unary(UnaryExpr {
+                span,
+                op: op!("!"),
+                arg,
+            }) => negate(span, arg),
+
+            Expr::Bin(BinExpr {
+                span,
+                op,
+                left,
+                right,
+            }) => {
+                let value = |e: &Expr| match *e {
+                    Expr::Lit(Lit::Bool(Bool { value, .. })) => Some(value),
+                    _ => None,
+                };
+                let is_equality = invert(op).is_some();
+                let (value, other) = match (value(&left), value(&right)) {
+                    (None, Some(v)) if is_equality && is_bool(&left) => (v, left),
+                    (Some(v), None) if is_equality && is_bool(&right) => (v, right),
+                    _ => {
+                        return Expr::Bin(BinExpr {
+                            span,
+                            op,
+                            left,
+                            right,
+                        })
+                    }
+                };
+                let other = unwrap_paren(other);
+
+                let is_eq = op == op!("===") || op == op!("==");
+                if is_eq == value {
+                    *other
+                } else {
+                    negate(span, other)
+                }
+            }
+
+            _ => e,
+        }
+    }
+}
+
+impl Fold<IfStmt> for SimplifyCompare {
+    fn fold(&mut self, s: IfStmt) -> IfStmt {
+        let s = s.fold_children(self);
+        IfStmt {
+            test: condition(s.test),
+            ..s
+        }
+    }
+}
+
+impl Fold<WhileStmt> for SimplifyCompare {
+    fn fold(&mut self, s: WhileStmt) -> WhileStmt {
+        let s = s.fold_children(self);
+        WhileStmt {
+            test: condition(s.test),
+            ..s
+        }
+    }
+}
+
+impl Fold<DoWhileStmt> for SimplifyCompare {
+    fn fold(&mut self, s: DoWhileStmt) -> DoWhileStmt {
+        let s = s.fold_children(self);
+        DoWhileStmt {
+            test: condition(s.test),
+            ..s
+        }
+    }
+}
+
+impl Fold<ForStmt> for SimplifyCompare {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        let s = s.fold_children(self);
+        ForStmt {
+            test: s.test.map(condition),
+            ..s
+        }
+    }
+}
+
+impl Fold<CondExpr> for SimplifyCompare {
+    fn fold(&mut self, e: CondExpr) -> CondExpr {
+        let e = e.fold_children(self);
+        CondExpr {
+            test: condition(e.test),
+            ..e
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| simplify_compare(),
+        negated_equality,
+        "a = !(b === c); d = !(e !== f); g = !(h == null); i = !(j != k);",
+        "a = b !== c; d = e === f; g = h != null; i = j == k;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| simplify_compare(),
+        double_negation,
+        "if (!!a) b = !!c;
+while (!!d && !(!!e || f)) g = !!h ? 1 : 2;
+x = !!!y;",
+        "if (a) b = !!c;
+while (d && !(e || f)) g = h ? 1 : 2;
+x = !y;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| simplify_compare(),
+        compare_bool,
+        "a = (b < c) === true; d = !e === false; f = true !== (g in h); i = (j == k) != true;",
+        "a = b < c; d = !!e; f = !(g in h); i = j != k;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| simplify_compare(),
+        coercion,
+        "a = b === true; c = d == false; e = !(f < g); h = !(i >= j); k = l == null;",
+        "a = b === true; c = d == false; e = !(f < g); h = !(i >= j); k = l == null;"
+    );
+}
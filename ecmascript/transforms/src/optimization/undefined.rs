@@ -0,0 +1,201 @@
+use crate::{pass::Pass, util};
+use ast::*;
+use serde::{Deserialize, Serialize};
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, SyntaxContext, Visit, VisitWith};
+
+/// Rewrites `void 0` to `undefined` or vice versa.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// if (a === void 0) a = undefined;
+/// ```
+///
+/// ## Out (with `UndefinedForm::Undefined`)
+///
+/// ```js
+/// if (a === undefined) a = undefined;
+/// ```
+///
+/// `undefined` is treated as the global only if its syntax context is empty,
+/// so this pass should be applied after `resolver`. `void 0` is not rewritten
+/// to `undefined` if `undefined` is declared anywhere in the module.
+pub fn normalize_undefined(c: Config) -> impl Pass {
+    NormalizeUndefined { c, shadowed: false }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub undefined_form: UndefinedForm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UndefinedForm {
+    /// Rewrite `undefined` to `void 0`.
+    Void0,
+    /// Rewrite `void 0` to `undefined`.
+    Undefined,
+}
+
+impl Default for UndefinedForm {
+    fn default() -> Self {
+        UndefinedForm::Void0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NormalizeUndefined {
+    c: Config,
+    /// `true` if `undefined` is declared in the module.
+    shadowed: bool,
+}
+
+impl Fold<Module> for NormalizeUndefined {
+    fn fold(&mut self, m: Module) -> Module {
+        let mut v = LocalUndefined { found: false };
+        m.visit_with(&mut v);
+        self.shadowed = v.found;
+
+        m.fold_children(self)
+    }
+}
+
+impl Fold<Script> for NormalizeUndefined {
+    fn fold(&mut self, s: Script) -> Script {
+        let mut v = LocalUndefined { found: false };
+        s.visit_with(&mut v);
+        self.shadowed = v.found;
+
+        s.fold_children(self)
+    }
+}
+
+impl Fold<MemberExpr> for NormalizeUndefined {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        // `obj.undefined` is a property name, not a reference.
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+impl Fold<Expr> for NormalizeUndefined {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match (self.c.undefined_form, e) {
+            (
+                UndefinedForm::Void0,
+                Expr::Ident(Ident {
+                    span,
+                    sym: js_word!("undefined"),
+                    ..
+                }),
+            ) if span.ctxt() == SyntaxContext::empty() => *util::undefined(span),
+
+            (
+                UndefinedForm::Undefined,
+                Expr::Unary(UnaryExpr {
+                    span,
+                    op: op!("void"),
+                    arg: box Expr::Lit(Lit::Num(..)),
+                }),
+            ) if !self.shadowed => Expr::Ident(Ident::new(js_word!("undefined"), span)),
+
+            (_, e) => e,
+        }
+    }
+}
+
+impl Fold<UpdateExpr> for NormalizeUndefined {
+    fn fold(&mut self, e: UpdateExpr) -> UpdateExpr {
+        // `undefined++` can't be rewritten to `void 0++`.
+        match *e.arg {
+            Expr::Ident(..) => e,
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// Finds references to a non-global `undefined`.
+struct LocalUndefined {
+    found: bool,
+}
+
+impl Visit<Ident> for LocalUndefined {
+    fn visit(&mut self, i: &Ident) {
+        if i.sym == js_word!("undefined") && i.span.ctxt() != SyntaxContext::empty() {
+            self.found = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver;
+    use swc_common::chain;
+
+    fn tr(undefined_form: UndefinedForm) -> impl Pass {
+        chain!(resolver(), normalize_undefined(Config { undefined_form }))
+    }
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(UndefinedForm::Undefined),
+        void_0_to_undefined,
+        "if (a === void 0) a = void 0;",
+        "if (a === undefined) a = undefined;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(UndefinedForm::Undefined),
+        shadowed,
+        "function f(undefined) { return undefined; } if (a === void 0) f();",
+        "function f(undefined) { return undefined; } if (a === void 0) f();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(UndefinedForm::Void0),
+        undefined_to_void_0,
+        "if (a === undefined) a = typeof undefined;",
+        "if (a === void 0) a = typeof void 0;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(UndefinedForm::Void0),
+        local_undefined,
+        "function f(undefined) { return undefined; } f(undefined);",
+        "function f(undefined) { return undefined; } f(void 0);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(UndefinedForm::Void0),
+        member_prop,
+        "a.undefined = a[undefined];",
+        "a.undefined = a[void 0];"
+    );
+
+    test_script!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(UndefinedForm::Undefined),
+        shadowed_script,
+        "function f(undefined) { return undefined; } if (a === void 0) f();",
+        "function f(undefined) { return undefined; } if (a === void 0) f();"
+    );
+}
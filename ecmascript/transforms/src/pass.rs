@@ -1,7 +1,18 @@
+pub use self::{
+    cache::{caching, CachingPass, PassCache},
+    features::{
+        skip_unless, FeatureFinder, FeatureFlags, FeatureScope, SharedFeatures, SkipUnless,
+    },
+    repeat::{repeat, Repeat},
+};
 use ast::*;
 use std::marker::PhantomData;
 use swc_common::{Fold, FoldWith};
 
+mod cache;
+mod features;
+mod repeat;
+
 pub fn noop() -> impl Pass {
     struct Noop;
     impl<T> Fold<T> for Noop
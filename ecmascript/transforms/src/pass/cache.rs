@@ -0,0 +1,420 @@
+use crate::pass::Pass;
+use ast::Module;
+use hashbrown::HashMap;
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+use swc_common::{
+    hygiene::Transparency, BytePos, Fold, FoldWith, Mark, SourceMap, Span, SyntaxContext, Visit,
+    VisitWith,
+};
+
+/// Wraps `pass` so that its output is reused for source files which are not
+/// modified since the last run.
+///
+/// Output is keyed on [SourceFile::src_hash] of the input module and
+/// `config_hash`, which should change whenever the configuration of `pass`
+/// changes.
+///
+/// # Hygiene
+///
+/// Marks allocated by `pass` are replaced with fresh ones whenever a module is
+/// reused, so modules with the same source don't share syntax contexts, and
+/// `cache` can be used with another `GLOBALS`. Marks which existed before
+/// `pass` ran are kept as-is, so `pass` should be applied to modules which are
+/// not marked yet, e.g. freshly parsed ones.
+///
+/// [SourceFile::src_hash]:swc_common::SourceFile
+pub fn caching<P>(cm: Arc<SourceMap>, cache: PassCache, config_hash: u64, pass: P) -> CachingPass<P>
+where
+    P: Pass,
+{
+    CachingPass {
+        cm,
+        cache,
+        config_hash,
+        pass,
+    }
+}
+
+#[derive(Clone)]
+pub struct CachingPass<P: Pass> {
+    cm: Arc<SourceMap>,
+    cache: PassCache,
+    config_hash: u64,
+    pass: P,
+}
+
+/// Storage of [CachingPass].
+///
+/// Clones share the same storage. If it's full, the oldest entry is evicted.
+#[derive(Debug, Clone)]
+pub struct PassCache {
+    storage: Arc<Mutex<Storage>>,
+    capacity: usize,
+}
+
+impl Default for PassCache {
+    fn default() -> Self {
+        PassCache::with_capacity(256)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Storage {
+    entries: HashMap<(u128, u64), Entry>,
+    /// Keys of `entries`, from the oldest one.
+    order: VecDeque<(u128, u64)>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    module: Module,
+    /// Start position of the source file `module` was parsed from.
+    start_pos: BytePos,
+    /// Marks allocated while running the pass.
+    marks: Range<u32>,
+    /// Parents of `marks`.
+    parents: Vec<Mark>,
+    /// Marks of syntax contexts used in `module`, to recreate them in the
+    /// current hygiene table.
+    ctxts: HashMap<SyntaxContext, Vec<(Mark, Transparency)>>,
+}
+
+impl PassCache {
+    /// Creates a cache which holds up to `capacity` modules.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PassCache {
+            storage: Default::default(),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.storage.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut storage = self.storage.lock().unwrap();
+        storage.entries.clear();
+        storage.order.clear();
+    }
+
+    fn get(&self, key: (u128, u64), start_pos: BytePos) -> Option<Module> {
+        let mut storage = self.storage.lock().unwrap();
+        let valid = match storage.entries.get(&key) {
+            // Marks which were not allocated by the pass are kept, so they should
+            // exist in the current hygiene table.
+            Some(entry) => entry.ctxts.values().flatten().all(|&(mark, _)| {
+                entry.marks.contains(&mark.as_u32()) || mark.as_u32() < Mark::count()
+            }),
+            None => return None,
+        };
+        if !valid {
+            storage.entries.remove(&key);
+            storage.order.retain(|k| *k != key);
+            return None;
+        }
+
+        let entry = &storage.entries[&key];
+        if entry.start_pos == start_pos && entry.ctxts.is_empty() {
+            return Some(entry.module.clone());
+        }
+
+        // Parents are allocated before their children, so they are already
+        // replaced.
+        let mut marks = HashMap::with_capacity(entry.parents.len());
+        for (mark, &parent) in entry.marks.clone().zip(&entry.parents) {
+            let parent = marks.get(&parent).cloned().unwrap_or(parent);
+            marks.insert(Mark::from_u32(mark), Mark::fresh(parent));
+        }
+
+        let ctxts = entry
+            .ctxts
+            .iter()
+            .map(|(&ctxt, ctxt_marks)| {
+                let mut new = SyntaxContext::empty();
+                for &(mark, transparency) in ctxt_marks {
+                    let mark = marks.get(&mark).cloned().unwrap_or(mark);
+                    new = new.apply_mark_with_transparency(mark, transparency);
+                }
+                (ctxt, new)
+            })
+            .collect();
+
+        Some(entry.module.clone().fold_with(&mut Rebase {
+            from: entry.start_pos,
+            to: start_pos,
+            len: (entry.module.span.hi() - entry.start_pos).0,
+            ctxts,
+        }))
+    }
+
+    fn insert(&self, key: (u128, u64), entry: Entry) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut storage = self.storage.lock().unwrap();
+        if storage.entries.insert(key, entry).is_none() {
+            storage.order.push_back(key);
+        }
+        while storage.entries.len() > self.capacity {
+            let oldest = match storage.order.pop_front() {
+                Some(oldest) => oldest,
+                None => break,
+            };
+            storage.entries.remove(&oldest);
+        }
+    }
+}
+
+impl<P> Fold<Module> for CachingPass<P>
+where
+    P: Pass,
+{
+    fn fold(&mut self, module: Module) -> Module {
+        if module.span.is_dummy() {
+            return self.pass.fold(module);
+        }
+
+        let fm = self.cm.lookup_byte_offset(module.span.lo()).sf;
+        let key = (fm.src_hash, self.config_hash);
+
+        if let Some(module) = self.cache.get(key, fm.start_pos) {
+            return module;
+        }
+
+        let start = Mark::count();
+        let module = self.pass.fold(module);
+        let end = Mark::count();
+
+        let mut ctxts = CtxtCollector {
+            ctxts: Default::default(),
+        };
+        module.visit_with(&mut ctxts);
+
+        self.cache.insert(
+            key,
+            Entry {
+                module: module.clone(),
+                start_pos: fm.start_pos,
+                marks: start..end,
+                parents: (start..end).map(|m| Mark::from_u32(m).parent()).collect(),
+                ctxts: ctxts.ctxts,
+            },
+        );
+
+        module
+    }
+}
+
+/// Records marks of syntax contexts used in a module.
+struct CtxtCollector {
+    ctxts: HashMap<SyntaxContext, Vec<(Mark, Transparency)>>,
+}
+
+impl Visit<Span> for CtxtCollector {
+    fn visit(&mut self, span: &Span) {
+        let ctxt = span.ctxt();
+        if ctxt != SyntaxContext::empty() && !self.ctxts.contains_key(&ctxt) {
+            self.ctxts.insert(ctxt, ctxt.marks());
+        }
+    }
+}
+
+/// Moves spans pointing to `from..from + len` so that they point to
+/// `to..to + len`, and replaces syntax contexts with those in `ctxts`.
+struct Rebase {
+    from: BytePos,
+    to: BytePos,
+    len: u32,
+    ctxts: HashMap<SyntaxContext, SyntaxContext>,
+}
+
+impl Fold<Span> for Rebase {
+    fn fold(&mut self, span: Span) -> Span {
+        let ctxt = self.ctxts.get(&span.ctxt()).cloned().unwrap_or(span.ctxt());
+
+        if span.is_dummy() || span.lo() < self.from || span.hi().0 > self.from.0 + self.len {
+            return span.with_ctxt(ctxt);
+        }
+
+        let lo = BytePos(span.lo().0 - self.from.0 + self.to.0);
+        let hi = BytePos(span.hi().0 - self.from.0 + self.to.0);
+        Span::new(lo, hi, ctxt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone)]
+    struct Counting(Arc<AtomicUsize>);
+
+    impl Fold<Module> for Counting {
+        fn fold(&mut self, module: Module) -> Module {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            module
+        }
+    }
+
+    #[test]
+    fn reuse_for_same_source() {
+        Tester::run(|tester| {
+            let count = Arc::new(AtomicUsize::new(0));
+            let cache = PassCache::default();
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 0, Counting(count.clone()));
+
+            let first = tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+            let second = tester
+                .parse_module("b.js", "let a = 1;")?
+                .fold_with(&mut pass);
+
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+            assert_eq!(cache.len(), 1);
+            assert_ne!(first.span, second.span);
+            assert_eq!(
+                first.fold_with(&mut ::testing::DropSpan),
+                second.fold_with(&mut ::testing::DropSpan)
+            );
+
+            Ok(())
+        });
+    }
+
+    /// Marks the module with a fresh mark.
+    #[derive(Clone)]
+    struct Marking;
+
+    impl Fold<Module> for Marking {
+        fn fold(&mut self, module: Module) -> Module {
+            let span = module.span.apply_mark(Mark::fresh(Mark::root()));
+            Module { span, ..module }
+        }
+    }
+
+    #[test]
+    fn fresh_marks_for_same_source() {
+        Tester::run(|tester| {
+            let mut pass = caching(tester.cm.clone(), PassCache::default(), 0, Marking);
+
+            let first = tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+            let second = tester
+                .parse_module("b.js", "let a = 1;")?
+                .fold_with(&mut pass);
+
+            assert_ne!(first.span.ctxt(), second.span.ctxt());
+            assert_eq!(second.span.ctxt().marks().len(), 1);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reuse_in_another_session() {
+        let cache = PassCache::default();
+
+        Tester::run(|tester| {
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 0, Marking);
+            tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+
+            Ok(())
+        });
+
+        Tester::run(|tester| {
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 0, Marking);
+            let module = tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+
+            let marks = module.span.ctxt().marks();
+            assert_eq!(marks.len(), 1);
+            assert_eq!(marks[0].0.parent(), Mark::root());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn invalidate_on_change() {
+        Tester::run(|tester| {
+            let count = Arc::new(AtomicUsize::new(0));
+            let cache = PassCache::default();
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 0, Counting(count.clone()));
+
+            tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+            tester
+                .parse_module("a.js", "let a = 2;")?
+                .fold_with(&mut pass);
+
+            assert_eq!(count.load(Ordering::SeqCst), 2);
+            assert_eq!(cache.len(), 2);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn invalidate_on_config_change() {
+        Tester::run(|tester| {
+            let count = Arc::new(AtomicUsize::new(0));
+            let cache = PassCache::default();
+
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 0, Counting(count.clone()));
+            tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 1, Counting(count.clone()));
+            tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+
+            assert_eq!(count.load(Ordering::SeqCst), 2);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn evict_oldest() {
+        Tester::run(|tester| {
+            let count = Arc::new(AtomicUsize::new(0));
+            let cache = PassCache::with_capacity(1);
+            let mut pass = caching(tester.cm.clone(), cache.clone(), 0, Counting(count.clone()));
+
+            tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+            tester
+                .parse_module("b.js", "let b = 1;")?
+                .fold_with(&mut pass);
+            assert_eq!(cache.len(), 1);
+
+            tester
+                .parse_module("a.js", "let a = 1;")?
+                .fold_with(&mut pass);
+            assert_eq!(count.load(Ordering::SeqCst), 3);
+
+            Ok(())
+        });
+    }
+}
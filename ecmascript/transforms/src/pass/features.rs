@@ -0,0 +1,254 @@
+use crate::pass::Pass;
+use ast::*;
+use bitflags::bitflags;
+use std::{cell::Cell, rc::Rc};
+use swc_common::{Fold, Visit, VisitWith};
+
+bitflags! {
+    /// Syntax features used by a module.
+    pub struct FeatureFlags: u32 {
+        const CLASSES = 1 << 0;
+        const ARROWS = 1 << 1;
+        /// Array and object patterns.
+        const DESTRUCTURING = 1 << 2;
+        /// Spread in array literals, calls and `new` expressions.
+        const SPREAD = 1 << 3;
+        /// Template literals, including tagged ones.
+        const TEMPLATE_LITERALS = 1 << 4;
+        const FOR_OF = 1 << 5;
+    }
+}
+
+impl FeatureFlags {
+    /// Returns features used by `node`.
+    pub fn of<N>(node: &N) -> Self
+    where
+        N: VisitWith<FeatureFinder>,
+    {
+        let mut v = FeatureFinder {
+            flags: FeatureFlags::empty(),
+        };
+        node.visit_with(&mut v);
+        v.flags
+    }
+}
+
+/// Collects [FeatureFlags] of a node in a single visit.
+pub struct FeatureFinder {
+    flags: FeatureFlags,
+}
+
+macro_rules! feature {
+    ($T:ty, $flag:ident) => {
+        impl Visit<$T> for FeatureFinder {
+            fn visit(&mut self, n: &$T) {
+                self.flags |= FeatureFlags::$flag;
+                n.visit_children(self);
+            }
+        }
+    };
+}
+
+feature!(Class, CLASSES);
+feature!(ArrowExpr, ARROWS);
+feature!(ArrayPat, DESTRUCTURING);
+feature!(ObjectPat, DESTRUCTURING);
+feature!(Tpl, TEMPLATE_LITERALS);
+feature!(TaggedTpl, TEMPLATE_LITERALS);
+feature!(ForOfStmt, FOR_OF);
+
+impl Visit<ExprOrSpread> for FeatureFinder {
+    fn visit(&mut self, n: &ExprOrSpread) {
+        if n.spread.is_some() {
+            self.flags |= FeatureFlags::SPREAD;
+        }
+        n.visit_children(self);
+    }
+}
+
+/// Wraps `pass` so that it's applied only to modules using one of `features`.
+///
+/// Features are computed each time the pass is applied, so features
+/// introduced by passes applied before it are taken into account. Use
+/// [SharedFeatures] to compute them once for several passes.
+pub fn skip_unless<P>(features: FeatureFlags, pass: P) -> SkipUnless<P>
+where
+    P: Pass,
+{
+    SkipUnless {
+        features,
+        shared: None,
+        pass,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SkipUnless<P: Pass> {
+    features: FeatureFlags,
+    shared: Option<SharedFeatures>,
+    pass: P,
+}
+
+impl<P> Fold<Module> for SkipUnless<P>
+where
+    P: Pass,
+{
+    fn fold(&mut self, module: Module) -> Module {
+        let used = match self.shared.as_ref().and_then(|shared| shared.0.get()) {
+            Some(used) => used,
+            None => FeatureFlags::of(&module),
+        };
+
+        if used.intersects(self.features) {
+            self.pass.fold(module)
+        } else {
+            module
+        }
+    }
+}
+
+/// Features of a module, computed once and shared by the passes created by
+/// [SharedFeatures::skip_unless].
+///
+/// Features introduced by passes inside [SharedFeatures::scope] are not taken
+/// into account. Clones share the same features.
+#[derive(Debug, Clone, Default)]
+pub struct SharedFeatures(Rc<Cell<Option<FeatureFlags>>>);
+
+impl SharedFeatures {
+    /// Wraps `pass` so that features of a module are computed before it's
+    /// applied.
+    pub fn scope<P>(&self, pass: P) -> FeatureScope<P>
+    where
+        P: Pass,
+    {
+        FeatureScope {
+            shared: self.clone(),
+            pass,
+        }
+    }
+
+    /// Like [skip_unless], but uses the features computed by
+    /// [SharedFeatures::scope], if any.
+    pub fn skip_unless<P>(&self, features: FeatureFlags, pass: P) -> SkipUnless<P>
+    where
+        P: Pass,
+    {
+        SkipUnless {
+            features,
+            shared: Some(self.clone()),
+            pass,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeatureScope<P: Pass> {
+    shared: SharedFeatures,
+    pass: P,
+}
+
+impl<P> Fold<Module> for FeatureScope<P>
+where
+    P: Pass,
+{
+    fn fold(&mut self, module: Module) -> Module {
+        let prev = self.shared.0.replace(Some(FeatureFlags::of(&module)));
+        let module = self.pass.fold(module);
+        self.shared.0.set(prev);
+
+        module
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use swc_common::{chain, FoldWith};
+
+    fn flags(src: &str) -> FeatureFlags {
+        let mut flags = None;
+        Tester::run(|tester| {
+            flags = Some(FeatureFlags::of(&tester.parse_module("input.js", src)?));
+            Ok(())
+        });
+        flags.unwrap()
+    }
+
+    #[test]
+    fn accurate() {
+        assert_eq!(
+            flags("function foo(a, b) { return a + b; }"),
+            FeatureFlags::empty()
+        );
+        assert_eq!(
+            flags("foo(function () { class A {} });"),
+            FeatureFlags::CLASSES
+        );
+        assert_eq!(flags("var a = () => 1;"), FeatureFlags::ARROWS);
+        assert_eq!(
+            flags("var { a } = b; function foo([c]) {}"),
+            FeatureFlags::DESTRUCTURING
+        );
+        assert_eq!(flags("foo(...a); new Foo(...b);"), FeatureFlags::SPREAD);
+        assert_eq!(flags("var a = [...b];"), FeatureFlags::SPREAD);
+        assert_eq!(flags("var a = { ...b };"), FeatureFlags::empty());
+        assert_eq!(flags("tag`a${b}`;"), FeatureFlags::TEMPLATE_LITERALS);
+        assert_eq!(flags("for (const a of b);"), FeatureFlags::FOR_OF);
+        assert_eq!(
+            flags("class A { foo() { return () => `${this.a}`; } }"),
+            FeatureFlags::CLASSES | FeatureFlags::ARROWS | FeatureFlags::TEMPLATE_LITERALS
+        );
+    }
+
+    #[derive(Clone)]
+    struct Counting(Rc<Cell<usize>>);
+
+    impl Fold<Module> for Counting {
+        fn fold(&mut self, module: Module) -> Module {
+            self.0.set(self.0.get() + 1);
+            module
+        }
+    }
+
+    #[test]
+    fn skip() {
+        Tester::run(|tester| {
+            let count = Rc::new(Cell::new(0));
+            let mut pass = skip_unless(FeatureFlags::CLASSES, Counting(count.clone()));
+
+            tester
+                .parse_module("a.js", "var a = () => 1;")?
+                .fold_with(&mut pass);
+            assert_eq!(count.get(), 0);
+
+            tester
+                .parse_module("b.js", "var A = class {};")?
+                .fold_with(&mut pass);
+            assert_eq!(count.get(), 1);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn shared() {
+        Tester::run(|tester| {
+            let count = Rc::new(Cell::new(0));
+            let features = SharedFeatures::default();
+            let mut pass = features.scope(chain!(
+                features.skip_unless(FeatureFlags::CLASSES, Counting(count.clone())),
+                features.skip_unless(FeatureFlags::ARROWS, Counting(count.clone())),
+            ));
+
+            tester
+                .parse_module("a.js", "var a = () => 1;")?
+                .fold_with(&mut pass);
+            assert_eq!(count.get(), 1);
+            assert_eq!(features.0.get(), None);
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{pass::Pass, util::structural_hash};
+use ast::Module;
+use swc_common::Fold;
+
+/// Runs `pass` until it stops changing the module, but at most `max_iters`
+/// times.
+///
+/// This is useful for optimization passes which expose new opportunities for
+/// each other, like inlining and constant folding. A run changes the module if
+/// the [structural_hash] of its output differs from the one of its input, so
+/// changes to positions are ignored and `pass` should not make changes which
+/// are undone by the next run.
+pub fn repeat<P>(pass: P, max_iters: usize) -> Repeat<P>
+where
+    P: Pass,
+{
+    Repeat { pass, max_iters }
+}
+
+#[derive(Debug, Clone)]
+pub struct Repeat<P: Pass> {
+    pass: P,
+    max_iters: usize,
+}
+
+impl<P> Fold<Module> for Repeat<P>
+where
+    P: Pass,
+{
+    fn fold(&mut self, mut module: Module) -> Module {
+        let mut hash = structural_hash(&module);
+        for _ in 0..self.max_iters {
+            module = self.pass.fold(module);
+
+            let prev = hash;
+            hash = structural_hash(&module);
+            if hash == prev {
+                break;
+            }
+        }
+
+        module
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{optimization::inline, simplifier, tests::Tester};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use swc_common::{chain, FoldWith, DUMMY_SP};
+
+    fn tr(max_iters: usize) -> impl Pass {
+        repeat(
+            chain!(simplifier(Default::default()), inline(Default::default())),
+            max_iters,
+        )
+    }
+
+    // `a` is inlined after `1 + 2` is folded, so `3 * 2` is folded by the second
+    // run.
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(5),
+        fixpoint,
+        "const a = 1 + 2; b = a * 2;",
+        "b = 6;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(1),
+        max_iters,
+        "const a = 1 + 2; b = a * 2;",
+        "b = 3 * 2;"
+    );
+
+    #[derive(Clone)]
+    struct Counting(Arc<AtomicUsize>);
+
+    impl Fold<Module> for Counting {
+        fn fold(&mut self, module: Module) -> Module {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            module
+        }
+    }
+
+    #[test]
+    fn stop_if_unchanged() {
+        Tester::run(|tester| {
+            let count = Arc::new(AtomicUsize::new(0));
+            tester
+                .parse_module("input.js", "let a = 1;")?
+                .fold_with(&mut repeat(Counting(count.clone()), 5));
+
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+            Ok(())
+        })
+    }
+
+    #[derive(Clone)]
+    struct DropSpan(Arc<AtomicUsize>);
+
+    impl Fold<Module> for DropSpan {
+        fn fold(&mut self, module: Module) -> Module {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Module {
+                span: DUMMY_SP,
+                ..module
+            }
+        }
+    }
+
+    #[test]
+    fn ignore_span_changes() {
+        Tester::run(|tester| {
+            let count = Arc::new(AtomicUsize::new(0));
+            tester
+                .parse_module("input.js", "let a = 1;")?
+                .fold_with(&mut repeat(DropSpan(count.clone()), 5));
+
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+            Ok(())
+        })
+    }
+}
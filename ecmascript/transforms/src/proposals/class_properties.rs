@@ -267,11 +267,40 @@ impl ClassProperties {
         let mut used_names = vec![];
         let mut used_key_names = vec![];
         let mut statics = HashSet::default();
+        let mut methods = HashSet::default();
+        let mut accessors = HashSet::default();
+        let mut has_private_methods = false;
+        let mut private_fns = vec![];
 
         for member in class.body {
             match member {
-                ClassMember::PrivateMethod(..) | ClassMember::TsIndexSignature(..) => {
-                    members.push(member)
+                ClassMember::TsIndexSignature(..) => members.push(member),
+
+                ClassMember::PrivateMethod(method) => {
+                    // Private accessors are not lowered yet, so they (and
+                    // accesses to them) are left untouched.
+                    if method.kind != MethodKind::Method {
+                        accessors.insert(method.key.id.sym.clone());
+                        members.push(ClassMember::PrivateMethod(method));
+                        continue;
+                    }
+                    if method.is_static {
+                        statics.insert(method.key.id.sym.clone());
+                    } else {
+                        has_private_methods = true;
+                    }
+                    methods.insert(method.key.id.sym.clone());
+
+                    // The method is moved out of the class, and calls become
+                    // `_classPrivateMethodGet(this, _brand, _foo).call(this)`.
+                    private_fns.push(Stmt::Decl(Decl::Fn(FnDecl {
+                        ident: Ident::new(
+                            format!("_{}", method.key.id.sym).into(),
+                            method.key.span.apply_mark(self.mark),
+                        ),
+                        function: method.function,
+                        declare: false,
+                    })));
                 }
 
                 ClassMember::Method(method) => {
@@ -473,19 +502,58 @@ impl ClassProperties {
             }
         }
 
+        let brand = private_ident!("_brand");
+        if has_private_methods {
+            // Private methods are installed before fields are initialized.
+            constructor_exprs.insert(
+                0,
+                box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: brand.clone().member(quote_ident!("add")).as_callee(),
+                    args: vec![ThisExpr { span: DUMMY_SP }.as_arg()],
+                    type_args: Default::default(),
+                }),
+            );
+
+            extra_stmts.insert(
+                0,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        definite: false,
+                        name: Pat::Ident(brand.clone()),
+                        init: Some(box Expr::New(NewExpr {
+                            span: DUMMY_SP,
+                            callee: box Expr::Ident(quote_ident!("WeakSet")),
+                            args: Some(vec![]),
+                            type_args: Default::default(),
+                        })),
+                    }],
+                })),
+            );
+        }
+
         let constructor =
             self.process_constructor(constructor, has_super, &used_names, constructor_exprs);
         if let Some(c) = constructor {
             members.push(ClassMember::Constructor(c));
         }
 
-        let members = members.fold_with(&mut FieldAccessFolder {
+        let mut folder = FieldAccessFolder {
             mark: self.mark,
             statics: &statics,
+            methods: &methods,
+            accessors: &accessors,
+            brand: &brand,
             vars: vec![],
             class_name: &ident,
             in_assign_pat: false,
-        });
+        };
+        let members = members.fold_with(&mut folder);
+        extra_stmts.extend(private_fns.fold_with(&mut folder));
 
         (
             vars,
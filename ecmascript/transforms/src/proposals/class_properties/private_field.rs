@@ -10,6 +10,12 @@ pub(super) struct FieldAccessFolder<'a> {
     pub class_name: &'a Ident,
     pub vars: Vec<VarDeclarator>,
     pub statics: &'a HashSet<JsWord>,
+    /// Names of private methods.
+    pub methods: &'a HashSet<JsWord>,
+    /// Names of private getters and setters, which are left as-is.
+    pub accessors: &'a HashSet<JsWord>,
+    /// `WeakSet` of instances which have private methods.
+    pub brand: &'a Ident,
     pub in_assign_pat: bool,
 }
 
@@ -23,7 +29,7 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                 arg: box Expr::Member(arg),
             }) => {
                 let n = match *arg.prop {
-                    Expr::PrivateName(ref n) => n,
+                    Expr::PrivateName(ref n) if !self.accessors.contains(&n.id.sym) => n,
                     _ => {
                         return Expr::Update(UpdateExpr {
                             span,
@@ -171,7 +177,7 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                 right,
             }) => {
                 let n = match *left.prop {
-                    Expr::PrivateName(ref n) => n.clone(),
+                    Expr::PrivateName(ref n) if !self.accessors.contains(&n.id.sym) => n.clone(),
                     _ => {
                         return Expr::Assign(AssignExpr {
                             span,
@@ -196,8 +202,25 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                         })
                         .fold_children(self);
                     }
-                    ExprOrSuper::Expr(ref obj) => obj.clone(),
+                    ExprOrSuper::Expr(ref obj) => obj.clone().fold_with(self),
                 };
+                let right = right.fold_with(self);
+
+                if self.methods.contains(&n.id.sym) {
+                    // Private methods are not writable.
+                    return Expr::Seq(SeqExpr {
+                        span,
+                        exprs: vec![
+                            right,
+                            box Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: helper!(class_private_method_set, "classPrivateMethodSet"),
+                                args: vec![],
+                                type_args: Default::default(),
+                            }),
+                        ],
+                    });
+                }
 
                 let is_static = self.statics.contains(&n.id.sym);
                 let ident = Ident::new(
@@ -230,7 +253,38 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                     .as_arg()
                 };
 
-                let value = if op == op!("=") {
+                // `a.#x ||= b` is `get(a, _x) || set(a, _x, b)`, so `b` is
+                // evaluated only if required.
+                let (this, logical) = match op {
+                    op!("&&=") | op!("||=") | op!("??=") => {
+                        let get = self.fold_private_get(left.clone(), Some(var.clone())).0;
+                        let is_this = match *this.expr {
+                            Expr::This(..) => true,
+                            _ => false,
+                        };
+                        let (get, this) = if is_this {
+                            (get, this)
+                        } else {
+                            (
+                                Expr::Seq(SeqExpr {
+                                    span: DUMMY_SP,
+                                    exprs: vec![this.expr, box get],
+                                }),
+                                var.clone().as_arg(),
+                            )
+                        };
+                        let op = match op {
+                            op!("&&=") => op!("&&"),
+                            op!("||=") => op!("||"),
+                            _ => op!("??"),
+                        };
+
+                        (this, Some((op, get)))
+                    }
+                    _ => (this, None),
+                };
+
+                let value = if op == op!("=") || logical.is_some() {
                     right.as_arg()
                 } else {
                     let left = box self.fold_private_get(left, Some(var)).0;
@@ -253,13 +307,14 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                             op!("&=") => op!("&"),
                             op!("^=") => op!("^"),
                             op!("**=") => op!("**"),
+                            op!("&&=") | op!("||=") | op!("??=") => unreachable!(),
                         },
                         right,
                     }
                     .as_arg()
                 };
 
-                if is_static {
+                let set = if is_static {
                     Expr::Call(CallExpr {
                         span: DUMMY_SP,
                         callee: helper!(
@@ -285,6 +340,16 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
 
                         type_args: Default::default(),
                     })
+                };
+
+                match logical {
+                    Some((op, get)) => Expr::Bin(BinExpr {
+                        span,
+                        left: box get,
+                        op,
+                        right: box set,
+                    }),
+                    None => set,
                 }
             }
 
@@ -332,6 +397,51 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                 }
             }
             Expr::Member(e) => self.fold_private_get(e, None).0,
+
+            // `#x in obj`
+            Expr::Bin(BinExpr {
+                span,
+                op: op!("in"),
+                left: box Expr::PrivateName(n),
+                right,
+            }) => {
+                let right = right.fold_with(self);
+
+                if self.accessors.contains(&n.id.sym) {
+                    return Expr::Bin(BinExpr {
+                        span,
+                        op: op!("in"),
+                        left: box Expr::PrivateName(n),
+                        right,
+                    });
+                }
+
+                if self.statics.contains(&n.id.sym) {
+                    // Only the class itself has static private names.
+                    return Expr::Bin(BinExpr {
+                        span,
+                        op: op!("==="),
+                        left: right,
+                        right: box Expr::Ident(self.class_name.clone()),
+                    });
+                }
+
+                let set = if self.methods.contains(&n.id.sym) {
+                    self.brand.clone()
+                } else {
+                    Ident::new(
+                        format!("_{}", n.id.sym).into(),
+                        n.id.span.apply_mark(self.mark),
+                    )
+                };
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: set.member(quote_ident!("has")).as_callee(),
+                    args: vec![right.as_arg()],
+                    type_args: Default::default(),
+                })
+            }
             _ => e.fold_children(self),
         }
     }
@@ -375,6 +485,16 @@ impl<'a> FieldAccessFolder<'a> {
             Expr::PrivateName(n) => n,
             _ => return (Expr::Member(e), None),
         };
+        if self.accessors.contains(&n.id.sym) {
+            return (
+                Expr::Member(MemberExpr {
+                    obj: e.obj.fold_with(self),
+                    prop: box Expr::PrivateName(n),
+                    ..e
+                }),
+                None,
+            );
+        }
 
         let obj = match e.obj {
             ExprOrSuper::Super(..) => {
@@ -390,16 +510,24 @@ impl<'a> FieldAccessFolder<'a> {
         };
 
         let is_static = self.statics.contains(&n.id.sym);
+        let is_method = self.methods.contains(&n.id.sym);
         let ident = Ident::new(
             format!("_{}", n.id.sym).into(),
             n.id.span.apply_mark(self.mark),
         );
 
         if is_static {
-            let get = helper!(
-                class_static_private_field_spec_get,
-                "classStaticPrivateFieldSpecGet"
-            );
+            let get = if is_method {
+                helper!(
+                    class_static_private_method_get,
+                    "classStaticPrivateMethodGet"
+                )
+            } else {
+                helper!(
+                    class_static_private_field_spec_get,
+                    "classStaticPrivateFieldSpecGet"
+                )
+            };
 
             (
                 Expr::Call(CallExpr {
@@ -415,7 +543,7 @@ impl<'a> FieldAccessFolder<'a> {
                 Some(Expr::Ident(self.class_name.clone())),
             )
         } else {
-            if self.in_assign_pat {
+            if self.in_assign_pat && !is_method {
                 let set = helper!(
                     class_private_field_destructure,
                     "classPrivateFieldDestructureSet"
@@ -438,20 +566,8 @@ impl<'a> FieldAccessFolder<'a> {
                 };
             }
 
-            let get = helper!(class_private_field_get, "classPrivateFieldGet");
-
-            match *obj {
-                Expr::This(this) => (
-                    CallExpr {
-                        span: DUMMY_SP,
-                        callee: get,
-                        args: vec![this.as_arg(), ident.as_arg()],
-
-                        type_args: Default::default(),
-                    }
-                    .into(),
-                    Some(Expr::This(this)),
-                ),
+            let (receiver, this) = match *obj {
+                Expr::This(this) => (this.as_arg(), Expr::This(this)),
                 _ => {
                     let mut aliased = false;
                     let var = obj_alias.unwrap_or_else(|| {
@@ -469,35 +585,49 @@ impl<'a> FieldAccessFolder<'a> {
                     });
 
                     (
-                        CallExpr {
-                            span: DUMMY_SP,
-                            callee: get,
-                            args: vec![
-                                if is_alias_initialized {
-                                    var.clone().as_arg()
-                                } else {
-                                    if aliased {
-                                        AssignExpr {
-                                            span: DUMMY_SP,
-                                            left: PatOrExpr::Pat(box Pat::Ident(var.clone())),
-                                            op: op!("="),
-                                            right: obj,
-                                        }
-                                        .as_arg()
-                                    } else {
-                                        var.clone().as_arg()
-                                    }
-                                },
-                                ident.as_arg(),
-                            ],
-
-                            type_args: Default::default(),
-                        }
-                        .into(),
-                        Some(Expr::Ident(var)),
+                        if is_alias_initialized {
+                            var.clone().as_arg()
+                        } else {
+                            if aliased {
+                                AssignExpr {
+                                    span: DUMMY_SP,
+                                    left: PatOrExpr::Pat(box Pat::Ident(var.clone())),
+                                    op: op!("="),
+                                    right: obj,
+                                }
+                                .as_arg()
+                            } else {
+                                var.clone().as_arg()
+                            }
+                        },
+                        Expr::Ident(var),
                     )
                 }
-            }
+            };
+
+            let (get, args) = if is_method {
+                (
+                    helper!(class_private_method_get, "classPrivateMethodGet"),
+                    vec![receiver, self.brand.clone().as_arg(), ident.as_arg()],
+                )
+            } else {
+                (
+                    helper!(class_private_field_get, "classPrivateFieldGet"),
+                    vec![receiver, ident.as_arg()],
+                )
+            };
+
+            (
+                CallExpr {
+                    span: DUMMY_SP,
+                    callee: get,
+                    args,
+
+                    type_args: Default::default(),
+                }
+                .into(),
+                Some(this),
+            )
         }
     }
 }
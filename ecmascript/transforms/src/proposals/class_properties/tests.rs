@@ -21,6 +21,7 @@ fn ts() -> Syntax {
 fn syntax() -> Syntax {
     Syntax::Es(EsConfig {
         class_private_props: true,
+        class_private_methods: true,
         class_props: true,
         ..Default::default()
     })
@@ -2057,6 +2058,59 @@ var _foo = new WeakMap();
 "#
 );
 
+test!(
+    Syntax::Es(EsConfig {
+        class_private_props: true,
+        logical_assignment: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    private_logical_assignment,
+    r#"
+class Foo {
+  #foo = 0;
+
+  test(other) {
+    this.#foo ||= 1;
+    this.#foo &&= 2;
+    other.obj.#foo ??= 3;
+  }
+}
+
+"#,
+    r#"
+var Foo =
+/*#__PURE__*/
+function () {
+  'use strict';
+
+  function Foo() {
+    _classCallCheck(this, Foo);
+
+    _foo.set(this, {
+      writable: true,
+      value: 0
+    });
+  }
+
+  _createClass(Foo, [{
+    key: "test",
+    value: function test(other) {
+      var _obj;
+
+      _classPrivateFieldGet(this, _foo) || _classPrivateFieldSet(this, _foo, 1);
+      _classPrivateFieldGet(this, _foo) && _classPrivateFieldSet(this, _foo, 2);
+      (_obj = other.obj, _classPrivateFieldGet(_obj, _foo)) ?? _classPrivateFieldSet(_obj, _foo, 3);
+    }
+  }]);
+  return Foo;
+}();
+
+var _foo = new WeakMap();
+
+"#
+);
+
 test_exec!(
     syntax(),
     |_| tr(),
@@ -4849,3 +4903,170 @@ expect(foo.y).toBe('bar');
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_field_read_write,
+    r#"
+class Foo {
+  #x = 1;
+  add(other) {
+    this.#x = this.#x + other.#x;
+  }
+}
+"#,
+    r#"
+class Foo {
+  add(other) {
+    _classPrivateFieldSet(this, _x, _classPrivateFieldGet(this, _x) + _classPrivateFieldGet(other, _x));
+  }
+  constructor() {
+    _x.set(this, {
+      writable: true,
+      value: 1
+    });
+  }
+}
+var _x = new WeakMap();
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_method,
+    r#"
+class Foo {
+  #x = 1;
+  #get() {
+    return this.#x;
+  }
+  sum(other) {
+    return this.#get() + other.#get();
+  }
+}
+"#,
+    r#"
+class Foo {
+  sum(other) {
+    return _classPrivateMethodGet(this, _brand, _get).call(this) +
+        _classPrivateMethodGet(other, _brand, _get).call(other);
+  }
+  constructor() {
+    _brand.add(this);
+    _x.set(this, {
+      writable: true,
+      value: 1
+    });
+  }
+}
+var _brand = new WeakSet();
+var _x = new WeakMap();
+function _get() {
+  return _classPrivateFieldGet(this, _x);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_in,
+    r#"
+class Foo {
+  #x;
+  #m() {}
+  static #s = 1;
+  static check(o) {
+    return #x in o && #m in o && #s in o;
+  }
+}
+"#,
+    r#"
+class Foo {
+  static check(o) {
+    return _x.has(o) && _brand.has(o) && o === Foo;
+  }
+  constructor() {
+    _brand.add(this);
+    _x.set(this, {
+      writable: true,
+      value: void 0
+    });
+  }
+}
+var _brand = new WeakSet();
+var _x = new WeakMap();
+var _s = {
+  writable: true,
+  value: 1
+};
+function _m() {}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    private_method_exec,
+    r#"
+class Foo {
+  #x = 2;
+  #double(y) {
+    return this.#x * y;
+  }
+  static #triple(y) {
+    return y * 3;
+  }
+  run(y) {
+    return this.#double(y) + Foo.#triple(y);
+  }
+  static has(o) {
+    return #double in o;
+  }
+}
+
+const foo = new Foo();
+expect(foo.run(1)).toBe(5);
+expect(Foo.has(foo)).toBe(true);
+expect(Foo.has({})).toBe(false);
+expect(() => foo.run.call({}, 1)).toThrow();
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_accessor_untouched,
+    r#"
+class Foo {
+  #y = 1;
+  get #x() {
+    return this.#y;
+  }
+  set #x(v) {}
+  run() {
+    this.#x = this.#x;
+  }
+}
+"#,
+    r#"
+class Foo {
+  get #x() {
+    return _classPrivateFieldGet(this, _y);
+  }
+  set #x(v) {}
+  run() {
+    this.#x = this.#x;
+  }
+  constructor() {
+    _y.set(this, {
+      writable: true,
+      value: 1
+    });
+  }
+}
+var _y = new WeakMap();
+"#
+);
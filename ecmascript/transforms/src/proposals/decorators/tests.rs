@@ -51,6 +51,56 @@ let A = _decorate([dec()], function (_initialize) {
   };
 });
 
+"#
+);
+// transformation_export_decl_after_export
+test!(
+    syntax(false),
+    |_| transformation(),
+    transformation_export_decl_after_export,
+    r#"
+export @dec() class A {}
+"#,
+    r#"
+export let A = _decorate([dec()], function (_initialize) {
+  class A {
+    constructor() {
+      _initialize(this);
+    }
+
+  }
+
+  return {
+    F: A,
+    d: []
+  };
+});
+
+"#
+);
+// transformation_export_decl_before_export
+test!(
+    syntax(true),
+    |_| transformation(),
+    transformation_export_decl_before_export,
+    r#"
+@dec() export class A {}
+"#,
+    r#"
+export let A = _decorate([dec()], function (_initialize) {
+  class A {
+    constructor() {
+      _initialize(this);
+    }
+
+  }
+
+  return {
+    F: A,
+    d: []
+  };
+});
+
 "#
 );
 // transformation_initialize_after_super_multiple
@@ -0,0 +1,120 @@
+use crate::{
+    compat::{
+        es2015::*,
+        es2016::exponentation,
+        es2017::async_to_generator,
+        es2018::{async_generator, object_rest_spread, optional_catch_binding},
+        es2020::big_int,
+        es2021::logical_assign,
+        es3::{MemberExprLit, PropertyLiteral, PropertyMutators},
+    },
+    pass::Pass,
+};
+use hashbrown::HashMap;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Creates a pass from its json config.
+pub type PassFactory = fn(Value) -> Result<Box<dyn Pass>, serde_json::Error>;
+
+/// Returns constructors of passes, keyed by the name of the babel plugin they
+/// port (e.g. `transform-destructuring`).
+///
+/// The config of a pass is deserialized from the json value given to the
+/// constructor. `null` means the default config. Passes without options
+/// accept any value.
+///
+/// Passes which depend on others (e.g. `resolver` or `helpers`) are not
+/// included, and should be applied by the host.
+pub fn registry() -> HashMap<&'static str, PassFactory> {
+    let mut r: HashMap<_, PassFactory> = HashMap::default();
+
+    macro_rules! add {
+        ($name:expr, |$c:ident| $pass:expr) => {{
+            fn factory(v: Value) -> Result<Box<dyn Pass>, serde_json::Error> {
+                let $c = config(v)?;
+                Ok(box $pass)
+            }
+            r.insert($name, factory);
+        }};
+        ($name:expr, $pass:expr) => {{
+            fn factory(_: Value) -> Result<Box<dyn Pass>, serde_json::Error> {
+                Ok(box $pass)
+            }
+            r.insert($name, factory);
+        }};
+    }
+
+    // es3
+    add!("transform-member-expression-literals", MemberExprLit);
+    add!("transform-property-literals", PropertyLiteral);
+    add!("transform-property-mutators", PropertyMutators);
+
+    // es2015
+    add!("transform-arrow-functions", arrow());
+    add!("transform-block-scoped-functions", BlockScopedFns);
+    add!("transform-block-scoping", block_scoping());
+    add!("transform-classes", |c| classes(c));
+    add!("transform-computed-properties", computed_properties());
+    add!("transform-destructuring", |c| destructuring(c));
+    add!("transform-duplicate-keys", duplicate_keys());
+    add!("transform-for-of", |c| for_of(c));
+    add!("transform-function-name", function_name());
+    add!("transform-instanceof", InstanceOf);
+    add!("transform-new-target", new_target());
+    add!("transform-parameters", parameters());
+    add!("transform-shorthand-properties", Shorthand);
+    add!("transform-spread", |c| spread(c));
+    add!("transform-sticky-regex", StickyRegex);
+    add!("transform-template-literals", |c| template_literal(c));
+    add!("transform-typeof-symbol", TypeOfSymbol);
+    add!("transform-unicode-regex", UnicodeRegex);
+
+    // es2016
+    add!("transform-exponentiation-operator", exponentation());
+
+    // es2017
+    add!("transform-async-to-generator", async_to_generator());
+
+    // es2018
+    add!("proposal-async-generator-functions", async_generator());
+    add!("proposal-object-rest-spread", |c| object_rest_spread(c));
+    add!("proposal-optional-catch-binding", optional_catch_binding());
+
+    // es2020
+    add!("syntax-bigint", |c| big_int(c));
+
+    // es2021
+    add!("proposal-logical-assignment-operators", logical_assign());
+
+    r
+}
+
+fn config<C>(v: Value) -> Result<C, serde_json::Error>
+where
+    C: DeserializeOwned + Default,
+{
+    match v {
+        Value::Null => Ok(Default::default()),
+        _ => serde_json::from_value(v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| registry()["transform-destructuring"](json!({ "loose": true })).unwrap(),
+        destructuring_loose,
+        "var [a, b] = foo;",
+        "var a = foo[0], b = foo[1];"
+    );
+
+    #[test]
+    fn invalid_config() {
+        assert!(registry()["transform-destructuring"](json!({ "loose": 1 })).is_err());
+    }
+}
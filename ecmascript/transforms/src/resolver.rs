@@ -14,11 +14,16 @@ mod tests;
 const LOG: bool = false;
 
 pub fn resolver() -> impl Pass + 'static {
-    Resolver::new(
-        Mark::fresh(Mark::root()),
-        Scope::new(ScopeKind::Fn, None),
-        None,
-    )
+    resolver_with_mark(Mark::fresh(Mark::root()))
+}
+
+/// Same as [resolver], but top-level bindings are marked with
+/// `top_level_mark`.
+///
+/// This is useful if the syntax context of top-level bindings should be known,
+/// e.g. for [bundler::rename](crate::bundler::rename).
+pub fn resolver_with_mark(top_level_mark: Mark) -> impl Pass + 'static {
+    Resolver::new(top_level_mark, Scope::new(ScopeKind::Fn, None), None)
 }
 
 #[derive(Debug, Clone)]
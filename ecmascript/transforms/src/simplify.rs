@@ -8,11 +8,14 @@ mod expr;
 #[cfg(test)]
 mod tests;
 
-pub fn simplifier() -> impl Pass + 'static {
-    Simplifier
+pub fn simplifier(assumptions: Assumptions) -> impl Pass + 'static {
+    Simplifier { assumptions }
 }
 
-struct Simplifier;
+#[derive(Default)]
+struct Simplifier {
+    assumptions: Assumptions,
+}
 
 impl<T: StmtLike> Fold<Vec<T>> for Simplifier
 where
@@ -102,6 +105,11 @@ impl Fold<Stmt> for Simplifier {
                     function: Function { span, .. },
                     ..
                 }) => Stmt::Empty(EmptyStmt { span }),
+
+                // `a.b;` is useless if property access is assumed to be pure.
+                Expr::Member(ref e) if !member_may_have_side_effects(e, &self.assumptions) => {
+                    Stmt::Empty(EmptyStmt { span: e.span })
+                }
                 _ => Stmt::Expr(box node),
             },
 
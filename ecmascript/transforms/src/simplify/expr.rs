@@ -2,7 +2,7 @@ use crate::util::*;
 use ast::{Ident, Lit, *};
 use std::iter;
 use swc_atoms::{js_word, JsWord};
-use swc_common::{Fold, FoldWith, Span, Spanned};
+use swc_common::{Fold, FoldWith, Span, Spanned, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -44,6 +44,17 @@ impl Fold<Expr> for SimplifyExpr {
                         })
                     }
                 }
+                // `a ? b : b` evaluates `b` anyway.
+                _ if eq_ignore_pos(&cons, &alt) => {
+                    if test.may_have_side_effects() {
+                        Expr::Seq(SeqExpr {
+                            span,
+                            exprs: vec![test, cons],
+                        })
+                    } else {
+                        *cons
+                    }
+                }
                 _ => Expr::Cond(CondExpr {
                     span,
                     test,
@@ -484,6 +495,7 @@ impl Fold<SeqExpr> for SimplifyExpr {
         let mut e = e.fold_children(self);
 
         let last_expr = e.exprs.pop().expect("SeqExpr.exprs must not be empty");
+        let has_others = !e.exprs.is_empty();
 
         // Expressions except last one
         let mut exprs = Vec::with_capacity(e.exprs.len() + 1);
@@ -491,7 +503,12 @@ impl Fold<SeqExpr> for SimplifyExpr {
         for expr in e.exprs {
             match *expr {
                 // Drop side-effect free nodes.
-                Expr::Lit(_) => {}
+                ref e if !e.may_have_side_effects() => {}
+
+                // Merge nested sequences.
+                Expr::Seq(SeqExpr { exprs: nested, .. }) => {
+                    exprs.extend(nested.into_iter().filter(|e| e.may_have_side_effects()))
+                }
 
                 // Flatten array
                 Expr::Array(ArrayLit { span, elems }) => {
@@ -512,6 +529,22 @@ impl Fold<SeqExpr> for SimplifyExpr {
             }
         }
 
+        // `(0, a.b)()` calls `a.b` without `this`, and `(0, eval)(s)` is an
+        // indirect eval.
+        if exprs.is_empty() && has_others {
+            match *last_expr {
+                Expr::Member(..)
+                | Expr::Ident(Ident {
+                    sym: js_word!("eval"),
+                    ..
+                }) => exprs.push(box Expr::Lit(Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: 0.0,
+                }))),
+                _ => {}
+            }
+        }
+
         exprs.push(last_expr);
         exprs.shrink_to_fit();
 
@@ -531,11 +564,15 @@ fn try_fold_typeof(UnaryExpr { span, op, arg }: UnaryExpr) -> Expr {
     assert_eq!(op, op!("typeof"));
 
     let val = match *arg {
-        Expr::Fn(..) => "function",
+        Expr::Fn(..) | Expr::Arrow(..) => "function",
         Expr::Lit(Lit::Str { .. }) => "string",
         Expr::Lit(Lit::Num(..)) => "number",
+        Expr::Lit(Lit::BigInt(..)) => "bigint",
         Expr::Lit(Lit::Bool(..)) => "boolean",
-        Expr::Lit(Lit::Null(..)) | Expr::Object { .. } | Expr::Array { .. } => "object",
+        Expr::Lit(Lit::Null(..))
+        | Expr::Lit(Lit::Regex(..))
+        | Expr::Object { .. }
+        | Expr::Array { .. } => "object",
         Expr::Unary(UnaryExpr {
             op: op!("void"), ..
         })
@@ -615,12 +652,52 @@ fn fold_unary(UnaryExpr { span, op, arg }: UnaryExpr) -> Expr {
                 span,
             });
         }
+        op!("delete") if !is_reference(&arg) => {
+            let val = box Expr::Lit(Lit::Bool(Bool { value: true, span }));
+            if !may_have_side_effects {
+                return *val;
+            }
+
+            return Expr::Seq(SeqExpr {
+                span,
+                exprs: vec![arg, val],
+            });
+        }
         _ => {}
     }
 
     Expr::Unary(UnaryExpr { op, arg, span })
 }
 
+/// Returns true if `e` may be a reference, which can't be deleted without
+/// side effects.
+///
+/// `delete x` is also a syntax error in strict mode, so it should not be
+/// folded away.
+fn is_reference(e: &Expr) -> bool {
+    match *e {
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_reference(expr),
+        Expr::Lit(..)
+        | Expr::This(..)
+        | Expr::Array(..)
+        | Expr::Object(..)
+        | Expr::Fn(..)
+        | Expr::Arrow(..)
+        | Expr::Class(..)
+        | Expr::Unary(..)
+        | Expr::Update(..)
+        | Expr::Bin(..)
+        | Expr::Assign(..)
+        | Expr::Cond(..)
+        | Expr::Call(..)
+        | Expr::New(..)
+        | Expr::Seq(..)
+        | Expr::Tpl(..)
+        | Expr::TaggedTpl(..) => false,
+        _ => true,
+    }
+}
+
 /// Try to fold arithmetic binary operators
 fn perform_arithmetic_op(op: BinaryOp, left: &Expr, right: &Expr) -> Value<f64> {
     /// Replace only if it becomes shorter
@@ -47,6 +47,25 @@ fn cond_side_effect() {
     test_expr("(void fn()) ? 3 : 6", "fn(), 6");
 }
 
+#[test]
+fn cond_same_branches() {
+    test_expr("a ? b : b", "b");
+    test_expr("a ? f(1) : f(1)", "f(1)");
+    test_expr("a ? b.c : b . c", "b.c");
+    same_expr("a ? b : c");
+    same_expr("a ? f(1) : f(2)");
+    same_expr("a ? 1 : 1.5");
+    same_expr("a ? 'b' : 'c'");
+    same_expr("a ? b.c : b[c]");
+    same_expr("a ? b++ : ++b");
+}
+
+#[test]
+fn cond_same_branches_side_effect() {
+    test_expr("a() ? b : b", "a(), b");
+    test_expr("a.b ? c() : c()", "a.b, c()");
+}
+
 #[test]
 fn oror_non_bool() {
     test_expr("5 || 50", "5")
@@ -544,6 +563,46 @@ fn seq_expr_array() {
     test_expr("([foo()], x)", "foo(), x");
 }
 
+#[test]
+fn seq_expr_pure() {
+    test_expr("(1, a, foo(), b)", "foo(), b");
+    test_expr("x = (1, a, b)", "x = b");
+    test_expr("x = (a, (foo(), b), c)", "x = (foo(), c)");
+    test_expr("x = ([a], function() {}, foo())", "x = foo()");
+}
+
+#[test]
+fn seq_expr_this_less_call() {
+    test_expr("(1, a.b)()", "(0, a.b)()");
+    test_expr("(a, eval)(s)", "(0, eval)(s)");
+    same_expr("(0, a.b)()");
+    test_expr("(a, b)()", "b()");
+}
+
+#[test]
+fn unary_canonical() {
+    test_expr("x = void (1 + 2)", "x = void 0");
+    test_expr("x = void a", "x = void 0");
+    same_expr("x = void f()");
+
+    test_expr("x = typeof void 0", "x = 'undefined'");
+    test_expr("x = typeof (() => {})", "x = 'function'");
+    test_expr("x = typeof 1n", "x = 'bigint'");
+    test_expr("x = typeof /a/", "x = 'object'");
+}
+
+#[test]
+fn delete_non_reference() {
+    test_expr("x = delete 1", "x = true");
+    test_expr("x = delete f()", "x = (f(), true)");
+
+    same_expr("x = delete obj.x");
+    same_expr("x = delete obj[x]");
+    // Input is parsed as a module, so this is a strict mode error which must
+    // not be folded away.
+    same_expr("x = delete x");
+}
+
 #[test]
 #[ignore]
 fn logical_ops() {
@@ -1,9 +1,15 @@
 //! Tests related to statements.
 use super::Simplifier;
+use crate::util::Assumptions;
 
 macro_rules! test_stmt {
     ($l:expr, $r:expr) => {
-        test_transform!(::swc_ecma_parser::Syntax::default(), |_| Simplifier, $l, $r)
+        test_transform!(
+            ::swc_ecma_parser::Syntax::default(),
+            |_| Simplifier::default(),
+            $l,
+            $r
+        )
     };
     ($l:expr, $r:expr,) => {
         test_expr!($l, $r);
@@ -35,3 +41,34 @@ fn compiled_out_simple() {
     compiled_out!("8;");
     compiled_out!("8+8;");
 }
+
+#[test]
+fn pure_getters_unused_member() {
+    let pure_getters = || Simplifier {
+        assumptions: Assumptions { pure_getters: true },
+    };
+
+    test_transform!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| pure_getters(),
+        "a.b;",
+        ""
+    );
+    test_transform!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| pure_getters(),
+        "a.b.c; a[0];",
+        ""
+    );
+    test_transform!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| pure_getters(),
+        "a[b()]; a().b;",
+        "a[b()]; a().b;"
+    );
+}
+
+#[test]
+fn impure_getters_unused_member() {
+    test_stmt!("a.b;", "a.b;");
+}
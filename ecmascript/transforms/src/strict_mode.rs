@@ -0,0 +1,117 @@
+use crate::{pass::Pass, util::HANDLER};
+use ast::*;
+use serde::{Deserialize, Serialize};
+use swc_common::{BytePos, Fold, FoldWith, Span};
+
+/// Reports code which is not allowed in strict mode.
+///
+/// This should be used if the output is a module or starts with
+/// `'use strict'`, because it's an error to emit such code.
+///
+/// Currently only `with` statements are checked.
+pub fn strict_mode(c: Config) -> impl Pass {
+    StrictMode { c }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Leave `with` statements as-is instead of reporting them.
+    #[serde(default)]
+    pub allow_with: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StrictMode {
+    c: Config,
+}
+
+impl Fold<Stmt> for StrictMode {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            Stmt::With(WithStmt { span, .. }) if !self.c.allow_with => {
+                // Point at the `with` keyword.
+                let span = Span::new(span.lo(), span.lo() + BytePos(4), span.ctxt());
+
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(span, "`with` statement is not allowed in strict mode")
+                        .emit()
+                });
+            }
+            _ => {}
+        }
+
+        stmt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+
+    /// Returns the stderr of applying the pass to `src`, parsed as a script.
+    fn run(c: Config, src: &str) -> Option<String> {
+        ::testing::run_test(false, |cm, handler| {
+            HANDLER.set(handler, || {
+                let mut tester = Tester {
+                    cm,
+                    handler,
+                    comments: Default::default(),
+                };
+
+                let stmts = tester.parse_stmts("input.js", src)?;
+                let expected = stmts.clone();
+                let stmts = stmts.fold_with(&mut strict_mode(c));
+                assert_eq!(stmts, expected);
+
+                if handler.has_errors() {
+                    Err(())
+                } else {
+                    Ok(())
+                }
+            })
+        })
+        .err()
+        .map(|stderr| stderr.to_string())
+    }
+
+    #[test]
+    fn with_stmt_error() {
+        let stderr = run(Default::default(), "foo();\nwith (o) {}").expect("should fail");
+
+        assert!(stderr.contains("`with` statement is not allowed in strict mode"));
+        assert!(stderr.contains("input.js:2:1"), "{}", stderr);
+        assert!(
+            stderr
+                .lines()
+                .any(|line| line.trim_end().ends_with("| ^^^^")),
+            "span should point at `with`:\n{}",
+            stderr
+        );
+    }
+
+    #[test]
+    fn nested_with_stmt_error() {
+        let stderr =
+            run(Default::default(), "function f() {\n  with (o) {}\n}").expect("should fail");
+
+        assert!(stderr.contains("input.js:2:3"), "{}", stderr);
+    }
+
+    #[test]
+    fn allow_with() {
+        assert_eq!(
+            run(Config { allow_with: true }, "with (o) { foo(); }"),
+            None
+        );
+    }
+
+    #[test]
+    fn no_with() {
+        assert_eq!(run(Default::default(), "foo();"), None);
+    }
+}
@@ -134,14 +134,64 @@ impl<'a> Tester<'a> {
         Ok(module)
     }
 
+    pub fn apply_transform_script<T: Fold<Script>>(
+        &mut self,
+        mut tr: T,
+        name: &str,
+        syntax: Syntax,
+        src: &str,
+    ) -> Result<Script, ()> {
+        let fm = self
+            .cm
+            .new_source_file(FileName::Real(name.into()), src.into());
+
+        let script = {
+            let sess = Session {
+                handler: &self.handler,
+            };
+
+            let mut p = Parser::new(sess, syntax, SourceFileInput::from(&*fm), None);
+            p.parse_script().map_err(|mut e| {
+                e.emit();
+            })?
+        };
+
+        let script = script
+            .fold_with(&mut tr)
+            .fold_with(&mut ::testing::DropSpan)
+            .fold_with(&mut Normalizer);
+
+        Ok(script)
+    }
+
     pub fn print(&mut self, module: &Module) -> String {
+        self.print_with(Default::default(), |emitter| emitter.emit_module(module))
+    }
+
+    /// Prints `module` with the syntax contexts of identifiers, like `ref#3`.
+    pub fn print_hygiene(&mut self, module: &Module) -> String {
+        let cfg = swc_ecma_codegen::Config {
+            debug_syntax_contexts: true,
+            ..Default::default()
+        };
+        self.print_with(cfg, |emitter| emitter.emit_module(module))
+    }
+
+    pub fn print_script(&mut self, script: &Script) -> String {
+        self.print_with(Default::default(), |emitter| emitter.emit_script(script))
+    }
+
+    fn print_with<F>(&mut self, cfg: swc_ecma_codegen::Config, op: F) -> String
+    where
+        F: FnOnce(&mut Emitter<'_>) -> io::Result<()>,
+    {
         let handlers = box MyHandlers;
 
         let mut wr = Buf(Arc::new(RwLock::new(vec![])));
         {
             let mut src_map_builder = SourceMapBuilder::new(None);
             let mut emitter = Emitter {
-                cfg: Default::default(),
+                cfg,
                 cm: self.cm.clone(),
                 wr: box swc_ecma_codegen::text_writer::JsWriter::new(
                     self.cm.clone(),
@@ -154,8 +204,7 @@ impl<'a> Tester<'a> {
                 pos_of_leading_comments: Default::default(),
             };
 
-            // println!("Emitting: {:?}", module);
-            emitter.emit_module(&module).unwrap();
+            op(&mut emitter).unwrap();
         }
 
         let r = wr.0.read().unwrap();
@@ -203,7 +252,7 @@ pub(crate) fn test_transform<F, P>(
 
         match ::std::env::var("PRINT_HYGIENE") {
             Ok(ref s) if s == "1" => {
-                let hygiene_src = tester.print(&actual.clone().fold_with(&mut HygieneVisualizer));
+                let hygiene_src = tester.print_hygiene(&actual);
                 println!("----- Hygiene -----\n{}", hygiene_src);
             }
             _ => {}
@@ -280,6 +329,55 @@ macro_rules! test {
     };
 }
 
+/// Test transformation of a script.
+#[cfg(test)]
+macro_rules! test_script {
+    ($syntax:expr, $tr:expr, $test_name:ident, $input:expr, $expected:expr) => {
+        #[test]
+        fn $test_name() {
+            crate::tests::test_script_transform($syntax, $tr, $input, $expected)
+        }
+    };
+}
+
+pub(crate) fn test_script_transform<F, P>(syntax: Syntax, tr: F, input: &str, expected: &str)
+where
+    F: FnOnce(&mut Tester<'_>) -> P,
+    P: Pass,
+{
+    Tester::run(|tester| {
+        let expected =
+            tester.apply_transform_script(::testing::DropSpan, "output.js", syntax, expected)?;
+
+        let tr = make_tr("actual", tr, tester);
+        let actual = tester
+            .apply_transform_script(tr, "input.js", syntax, input)?
+            .fold_with(&mut crate::hygiene::hygiene())
+            .fold_with(&mut crate::fixer::fixer());
+
+        if actual == expected {
+            return Ok(());
+        }
+
+        let (actual_src, expected_src) =
+            (tester.print_script(&actual), tester.print_script(&expected));
+
+        if actual_src == expected_src {
+            println!(">>>>> Code <<<<<\n{}", actual_src);
+            assert_eq!(actual, expected, "different ast was detected");
+            return Err(());
+        }
+
+        println!(">>>>> Orig <<<<<\n{}", input);
+        println!(">>>>> Code <<<<<\n{}", actual_src);
+        panic!(
+            r#"assertion failed: `(left == right)`
+            {}"#,
+            ::testing::diff(&actual_src, &expected_src),
+        );
+    });
+}
+
 macro_rules! exec_tr {
     ($syntax:expr, $tr:expr, $test_name:ident, $input:expr) => {{
         crate::tests::exec_tr(stringify!($test_name), $syntax, $tr, $input);
@@ -306,7 +404,7 @@ where
         )?;
         match ::std::env::var("PRINT_HYGIENE") {
             Ok(ref s) if s == "1" => {
-                let hygiene_src = tester.print(&module.clone().fold_with(&mut HygieneVisualizer));
+                let hygiene_src = tester.print_hygiene(&module);
                 println!("----- Hygiene -----\n{}", hygiene_src);
             }
             _ => {}
@@ -408,13 +506,3 @@ impl Fold<PatOrExpr> for Normalizer {
         }
     }
 }
-
-struct HygieneVisualizer;
-impl Fold<Ident> for HygieneVisualizer {
-    fn fold(&mut self, ident: Ident) -> Ident {
-        Ident {
-            sym: format!("{}{:?}", ident.sym, ident.span.ctxt()).into(),
-            ..ident
-        }
-    }
-}
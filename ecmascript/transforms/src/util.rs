@@ -1,5 +1,11 @@
+pub(crate) use self::structural_hash::eq_ignore_pos;
 pub use self::{
+    assumptions::Assumptions,
+    bindings::{bindings, BindingCollector},
+    comments::attach_comments,
     factory::ExprFactory,
+    free_vars::{free_vars, FreeVarCollector},
+    structural_hash::structural_hash,
     value::{
         Type::{
             self, Bool as BoolType, Null as NullType, Num as NumberType, Obj as ObjectType,
@@ -19,16 +25,26 @@ use std::{
 };
 use swc_atoms::{js_word, JsWord};
 use swc_common::{
-    errors::Handler, Fold, FoldWith, Mark, Span, Spanned, Visit, VisitWith, DUMMY_SP,
+    errors::Handler, Fold, FoldWith, Mark, Span, Spanned, SyntaxContext, Visit, VisitWith, DUMMY_SP,
 };
 use unicode_xid::UnicodeXID;
 
+mod assumptions;
+mod bindings;
+mod comments;
 pub(crate) mod constructor;
 mod factory;
+mod free_vars;
 pub(crate) mod options;
+mod structural_hash;
+#[cfg(test)]
+mod tests;
 mod value;
 pub(crate) mod var;
 
+/// Identifier of a variable.
+pub type Id = (JsWord, SyntaxContext);
+
 pub(crate) struct ThisVisitor {
     found: bool,
 }
@@ -299,6 +315,10 @@ pub trait ExprExt {
                             FpCategory::Nan | FpCategory::Zero => false,
                             _ => true,
                         },
+                        // Skips the radix prefix like `0x`.
+                        Lit::BigInt(BigInt { ref value, .. }) => value
+                            .char_indices()
+                            .any(|(i, c)| c != '0' && !(i == 1 && c.is_ascii_alphabetic())),
                         Lit::Bool(b) => b.value,
                         Lit::Str(Str { ref value, .. }) => !value.is_empty(),
                         Lit::Null(..) => false,
@@ -611,90 +631,181 @@ pub trait ExprExt {
         }
     }
 
+    /// Returns true if evaluating `self` may have an observable effect other
+    /// than producing its value.
+    ///
+    /// This is conservative: `true` is returned for anything which is not
+    /// known to be pure. Coercions of operands (e.g. `valueOf` called by
+    /// `a + b`) are not considered as side effects.
     fn may_have_side_effects(&self) -> bool {
+        self.may_have_side_effects_with(&Default::default())
+    }
+
+    /// Same as [ExprExt::may_have_side_effects], but uses `assumptions` about
+    /// the input.
+    fn may_have_side_effects_with(&self, assumptions: &Assumptions) -> bool {
+        let check = |e: &Expr| e.may_have_side_effects_with(assumptions);
+
         match *self.as_expr_kind() {
             Expr::Lit(..)
             | Expr::Ident(..)
             | Expr::This(..)
             | Expr::PrivateName(..)
-            | Expr::TsConstAssertion(..) => false,
-            Expr::Paren(ref e) => e.expr.may_have_side_effects(),
+            | Expr::MetaProp(..) => false,
+
             // Function expression does not have any side effect if it's not used.
-            Expr::Fn(..) | Expr::Arrow(ArrowExpr { .. }) => false,
-            // TODO
-            Expr::Class(..) => true,
-            Expr::Array(ArrayLit { ref elems, .. }) => elems
-                .iter()
-                .filter_map(|e| e.as_ref())
-                .any(|e| e.expr.may_have_side_effects()),
-            Expr::Unary(UnaryExpr { ref arg, .. }) => arg.may_have_side_effects(),
+            Expr::Fn(..) | Expr::Arrow(..) => false,
+
+            Expr::Class(ClassExpr { ref class, .. }) => {
+                class_may_have_side_effects(class, assumptions)
+            }
+
+            Expr::Call(..)
+            | Expr::New(..)
+            | Expr::TaggedTpl(..)
+            | Expr::Assign(..)
+            | Expr::Update(..)
+            | Expr::Await(..)
+            | Expr::Yield(..) => true,
+
+            Expr::Unary(UnaryExpr {
+                op: op!("delete"), ..
+            }) => true,
+            Expr::Unary(UnaryExpr { ref arg, .. }) => check(arg),
+
             Expr::Bin(BinExpr {
                 ref left,
                 ref right,
                 ..
-            }) => left.may_have_side_effects() || right.may_have_side_effects(),
+            }) => check(left) || check(right),
 
-            //TODO
-            Expr::Tpl(_) => true,
-            Expr::TaggedTpl(_) => true,
-            Expr::MetaProp(_) => true,
+            Expr::Member(ref e) => member_may_have_side_effects(e, assumptions),
 
-            Expr::Await(_)
-            | Expr::Yield(_)
-            | Expr::Member(_)
-            | Expr::Update(_)
-            | Expr::Assign(_) => true,
+            Expr::Tpl(Tpl { ref exprs, .. }) => exprs.iter().any(|e| check(e)),
 
-            // TODO
-            Expr::New(_) => true,
-            // TODO
-            Expr::Call(_) => true,
+            Expr::Array(ArrayLit { ref elems, .. }) => elems
+                .iter()
+                .filter_map(|e| e.as_ref())
+                // Spreading invokes the iterator protocol.
+                .any(|e| e.spread.is_some() || check(&e.expr)),
 
-            Expr::Seq(SeqExpr { ref exprs, .. }) => exprs.iter().any(|e| e.may_have_side_effects()),
+            Expr::Object(ObjectLit { ref props, .. }) => props.iter().any(|prop| match *prop {
+                PropOrSpread::Prop(ref prop) => match **prop {
+                    Prop::Shorthand(..) => false,
+                    Prop::KeyValue(KeyValueProp { ref key, ref value }) => {
+                        prop_name_may_have_side_effects(key, assumptions) || check(value)
+                    }
+                    Prop::Getter(GetterProp { ref key, .. })
+                    | Prop::Setter(SetterProp { ref key, .. })
+                    | Prop::Method(MethodProp { ref key, .. }) => {
+                        prop_name_may_have_side_effects(key, assumptions)
+                    }
+                    Prop::Assign(..) => true,
+                },
+                // Spreading invokes getters of the source object.
+                PropOrSpread::Spread(SpreadElement { ref expr, .. }) => {
+                    !assumptions.pure_getters || check(expr)
+                }
+            }),
 
+            Expr::Paren(ParenExpr { ref expr, .. }) => check(expr),
+            Expr::Seq(SeqExpr { ref exprs, .. }) => exprs.iter().any(|e| check(e)),
             Expr::Cond(CondExpr {
                 ref test,
                 ref cons,
                 ref alt,
                 ..
-            }) => {
-                test.may_have_side_effects()
-                    || cons.may_have_side_effects()
-                    || alt.may_have_side_effects()
-            }
-
-            Expr::Object(ObjectLit { ref props, .. }) => props.iter().any(|node| match node {
-                PropOrSpread::Prop(box node) => match *node {
-                    Prop::Shorthand(..) => false,
-                    Prop::KeyValue(KeyValueProp { ref key, ref value }) => {
-                        let k = match *key {
-                            PropName::Computed(ref e) => e.expr.may_have_side_effects(),
-                            _ => false,
-                        };
-
-                        k || value.may_have_side_effects()
-                    }
-                    _ => true,
-                },
-                PropOrSpread::Spread(SpreadElement { expr, .. }) => expr.may_have_side_effects(),
-            }),
+            }) => check(test) || check(cons) || check(alt),
 
             Expr::JSXMebmer(..)
             | Expr::JSXNamespacedName(..)
             | Expr::JSXEmpty(..)
             | Expr::JSXElement(..)
-            | Expr::JSXFragment(..) => unreachable!("simplifying jsx"),
+            | Expr::JSXFragment(..) => true,
 
             Expr::TsAs(TsAsExpr { ref expr, .. })
             | Expr::TsNonNull(TsNonNullExpr { ref expr, .. })
             | Expr::TsTypeAssertion(TsTypeAssertion { ref expr, .. })
-            | Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. }) => expr.may_have_side_effects(),
-            Expr::TsOptChain(ref e) => e.expr.may_have_side_effects(),
+            | Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. })
+            | Expr::TsConstAssertion(TsConstAssertion { ref expr, .. }) => check(expr),
+            Expr::TsOptChain(ref e) => check(&e.expr),
 
-            Expr::Invalid(..) => unreachable!(),
+            Expr::Invalid(..) => true,
         }
     }
 }
+
+/// Returns true if reading `e` may have side effects.
+///
+/// Property accesses are pure only if `pure_getters` is assumed.
+pub fn member_may_have_side_effects(e: &MemberExpr, assumptions: &Assumptions) -> bool {
+    if !assumptions.pure_getters {
+        return true;
+    }
+
+    let obj = match e.obj {
+        ExprOrSuper::Expr(ref obj) => obj,
+        ExprOrSuper::Super(..) => return true,
+    };
+
+    obj.may_have_side_effects_with(assumptions)
+        || (e.computed && e.prop.may_have_side_effects_with(assumptions))
+}
+
+fn prop_name_may_have_side_effects(key: &PropName, assumptions: &Assumptions) -> bool {
+    match *key {
+        PropName::Computed(ref e) => e.expr.may_have_side_effects_with(assumptions),
+        _ => false,
+    }
+}
+
+/// Returns true if defining `class` may have side effects.
+fn class_may_have_side_effects(class: &Class, assumptions: &Assumptions) -> bool {
+    // `extends` throws if the super class is not a constructor.
+    if class.super_class.is_some() || !class.decorators.is_empty() {
+        return true;
+    }
+
+    class.body.iter().any(|member| match *member {
+        ClassMember::Constructor(..) | ClassMember::TsIndexSignature(..) => false,
+        ClassMember::Method(ClassMethod {
+            ref key,
+            ref function,
+            ..
+        }) => !function.decorators.is_empty() || prop_name_may_have_side_effects(key, assumptions),
+        ClassMember::PrivateMethod(PrivateMethod { ref function, .. }) => {
+            !function.decorators.is_empty()
+        }
+        ClassMember::ClassProp(ClassProp {
+            ref key,
+            computed,
+            ref value,
+            is_static,
+            ref decorators,
+            ..
+        }) => {
+            !decorators.is_empty()
+                || (computed && key.may_have_side_effects_with(assumptions))
+                // Values of static properties are evaluated with the class.
+                || (is_static
+                    && value
+                        .as_ref()
+                        .map_or(false, |v| v.may_have_side_effects_with(assumptions)))
+        }
+        ClassMember::PrivateProp(PrivateProp {
+            ref value,
+            is_static,
+            ref decorators,
+            ..
+        }) => {
+            !decorators.is_empty()
+                || (is_static
+                    && value
+                        .as_ref()
+                        .map_or(false, |v| v.may_have_side_effects_with(assumptions)))
+        }
+    })
+}
 fn and(lt: Value<Type>, rt: Value<Type>) -> Value<Type> {
     if lt == rt {
         return lt;
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Assumptions about the input which make some optimizations sound.
+///
+/// Everything is disabled by default, as each of them can change behavior of
+/// code which does not hold the assumption.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Assumptions {
+    /// Assume that reading a property does not have side effects, i.e. there
+    /// are no getters or proxies with side effects.
+    ///
+    /// This allows removing and reordering member expressions.
+    #[serde(default)]
+    pub pure_getters: bool,
+}
@@ -0,0 +1,219 @@
+use super::Id;
+use ast::*;
+use swc_common::{Span, Visit, VisitWith};
+
+/// Returns bindings introduced in `node`, with the span of their binding
+/// identifier, in source order.
+///
+/// Variables, functions, classes, parameters, catch parameters and imports
+/// are included, as well as names of function and class expressions.
+/// Bindings of nested functions are included too.
+pub fn bindings<N>(node: &N) -> Vec<(Id, Span)>
+where
+    N: VisitWith<BindingCollector>,
+{
+    let mut v = BindingCollector { bindings: vec![] };
+    node.visit_with(&mut v);
+    v.bindings
+}
+
+pub struct BindingCollector {
+    bindings: Vec<(Id, Span)>,
+}
+
+impl BindingCollector {
+    fn add(&mut self, i: &Ident) {
+        self.bindings.push(((i.sym.clone(), i.span.ctxt()), i.span));
+    }
+
+    /// Adds identifiers bound by `p`.
+    fn add_pat(&mut self, p: &Pat) {
+        match *p {
+            Pat::Ident(ref i) => self.add(i),
+            Pat::Array(ArrayPat { ref elems, .. }) => {
+                for elem in elems.iter().filter_map(Option::as_ref) {
+                    self.add_pat(elem);
+                }
+            }
+            Pat::Object(ObjectPat { ref props, .. }) => {
+                for prop in props {
+                    match *prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { ref key, ref value }) => {
+                            key.visit_with(self);
+                            self.add_pat(value);
+                        }
+                        ObjectPatProp::Assign(AssignPatProp {
+                            ref key, ref value, ..
+                        }) => {
+                            self.add(key);
+                            value.visit_with(self);
+                        }
+                        ObjectPatProp::Rest(RestPat { ref arg, .. }) => self.add_pat(arg),
+                    }
+                }
+            }
+            Pat::Rest(RestPat { ref arg, .. }) => self.add_pat(arg),
+            Pat::Assign(AssignPat {
+                ref left,
+                ref right,
+                ..
+            }) => {
+                self.add_pat(left);
+                right.visit_with(self);
+            }
+            Pat::Expr(ref e) => e.visit_with(self),
+            Pat::Invalid(..) => {}
+        }
+    }
+}
+
+impl Visit<VarDeclarator> for BindingCollector {
+    fn visit(&mut self, d: &VarDeclarator) {
+        self.add_pat(&d.name);
+        d.init.visit_with(self);
+    }
+}
+
+impl Visit<FnDecl> for BindingCollector {
+    fn visit(&mut self, f: &FnDecl) {
+        self.add(&f.ident);
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassDecl> for BindingCollector {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.add(&c.ident);
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<FnExpr> for BindingCollector {
+    fn visit(&mut self, f: &FnExpr) {
+        if let Some(ref ident) = f.ident {
+            self.add(ident);
+        }
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassExpr> for BindingCollector {
+    fn visit(&mut self, c: &ClassExpr) {
+        if let Some(ref ident) = c.ident {
+            self.add(ident);
+        }
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<Function> for BindingCollector {
+    fn visit(&mut self, f: &Function) {
+        f.decorators.visit_with(self);
+        for param in &f.params {
+            self.add_pat(param);
+        }
+        f.body.visit_with(self);
+    }
+}
+
+impl Visit<ArrowExpr> for BindingCollector {
+    fn visit(&mut self, f: &ArrowExpr) {
+        for param in &f.params {
+            self.add_pat(param);
+        }
+        f.body.visit_with(self);
+    }
+}
+
+impl Visit<SetterProp> for BindingCollector {
+    fn visit(&mut self, p: &SetterProp) {
+        p.key.visit_with(self);
+        self.add_pat(&p.param);
+        p.body.visit_with(self);
+    }
+}
+
+impl Visit<Constructor> for BindingCollector {
+    fn visit(&mut self, c: &Constructor) {
+        c.key.visit_with(self);
+        for param in &c.params {
+            match *param {
+                PatOrTsParamProp::Pat(ref p) => self.add_pat(p),
+                PatOrTsParamProp::TsParamProp(TsParamProp { ref param, .. }) => match *param {
+                    TsParamPropParam::Ident(ref i) => self.add(i),
+                    TsParamPropParam::Assign(AssignPat {
+                        ref left,
+                        ref right,
+                        ..
+                    }) => {
+                        self.add_pat(left);
+                        right.visit_with(self);
+                    }
+                },
+            }
+        }
+        c.body.visit_with(self);
+    }
+}
+
+impl Visit<CatchClause> for BindingCollector {
+    fn visit(&mut self, c: &CatchClause) {
+        if let Some(ref param) = c.param {
+            self.add_pat(param);
+        }
+        c.body.visit_with(self);
+    }
+}
+
+impl Visit<ImportDecl> for BindingCollector {
+    fn visit(&mut self, i: &ImportDecl) {
+        for s in &i.specifiers {
+            match *s {
+                ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => self.add(local),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+
+    /// Returns bindings of `src` as the source text of their spans.
+    fn texts(src: &str) -> Vec<String> {
+        let mut result = None;
+        Tester::run(|tester| {
+            let module = tester.parse_module("input.js", src)?;
+            result = Some(
+                bindings(&module)
+                    .into_iter()
+                    .map(|((sym, _), span)| {
+                        assert_eq!(tester.cm.span_to_snippet(span).unwrap(), &*sym);
+                        sym.to_string()
+                    })
+                    .collect(),
+            );
+            Ok(())
+        });
+        result.unwrap()
+    }
+
+    #[test]
+    fn destructured_param() {
+        assert_eq!(
+            texts("function f({ a, b: [c, ...d] }, e = function g(h) {}) { var i; a = j; }"),
+            vec!["f", "a", "c", "d", "e", "g", "h", "i"]
+        );
+    }
+
+    #[test]
+    fn import() {
+        assert_eq!(
+            texts("import x, { y, z as w } from 'a'; import * as ns from 'b'; try {} catch (e) {}"),
+            vec!["x", "y", "w", "ns", "e"]
+        );
+    }
+}
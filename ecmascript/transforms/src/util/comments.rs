@@ -0,0 +1,193 @@
+use ast::*;
+use hashbrown::HashMap;
+use swc_common::{
+    comments::{Comment, Comments},
+    BytePos, Span, Spanned, Visit, VisitWith,
+};
+
+/// Maps the start of statements in `module` to their leading comments.
+///
+/// A comment is attached to the nearest statement which follows it, including
+/// statements in function bodies and blocks. Comments in the middle of a
+/// statement (e.g. between arguments of a call) are attached only if the
+/// following statement is nested in that statement. Comments at the end of a
+/// file or a block are not attached to anything.
+pub fn attach_comments(module: &Module, comments: &Comments) -> HashMap<BytePos, Vec<Comment>> {
+    let mut v = StmtSpans { spans: vec![] };
+    module.visit_with(&mut v);
+    let mut spans = v.spans;
+    spans.sort_by_key(|span| span.lo());
+
+    let mut map: HashMap<_, Vec<_>> = HashMap::default();
+
+    for (pos, cmts) in comments.all_leading() {
+        let following = match spans.iter().find(|span| span.lo() >= pos) {
+            Some(span) => *span,
+            None => continue,
+        };
+
+        // The innermost statement which contains the comment.
+        let enclosing = spans
+            .iter()
+            .filter(|span| span.lo() < pos && pos < span.hi())
+            .last();
+        if let Some(enclosing) = enclosing {
+            if following.hi() > enclosing.hi() {
+                continue;
+            }
+        }
+
+        map.entry(following.lo()).or_default().extend(cmts);
+    }
+
+    map
+}
+
+struct StmtSpans {
+    spans: Vec<Span>,
+}
+
+impl StmtSpans {
+    fn add(&mut self, span: Span) {
+        if !span.is_dummy() {
+            self.spans.push(span);
+        }
+    }
+}
+
+impl Visit<ModuleDecl> for StmtSpans {
+    fn visit(&mut self, decl: &ModuleDecl) {
+        self.add(decl.span());
+        decl.visit_children(self);
+    }
+}
+
+impl Visit<Stmt> for StmtSpans {
+    fn visit(&mut self, stmt: &Stmt) {
+        self.add(stmt.span());
+        stmt.visit_children(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+
+    /// Returns trimmed texts of comments attached to `span`.
+    fn texts(map: &HashMap<BytePos, Vec<Comment>>, span: Span) -> Vec<String> {
+        map.get(&span.lo())
+            .map(|cmts| cmts.iter().map(|c| c.text.trim().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    fn count(map: &HashMap<BytePos, Vec<Comment>>) -> usize {
+        map.values().map(|cmts| cmts.len()).sum()
+    }
+
+    #[test]
+    fn function() {
+        Tester::run(|tester| {
+            let module = tester.parse_module(
+                "input.js",
+                "// header
+foo();
+
+/** doc */
+function f() {
+    // inner
+    return 1;
+}
+// dangling",
+            )?;
+            let map = attach_comments(&module, &tester.comments);
+
+            assert_eq!(texts(&map, module.body[0].span()), vec!["header"]);
+            assert_eq!(texts(&map, module.body[1].span()), vec!["doc"]);
+            let ret = match module.body[1] {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(ref f))) => {
+                    f.function.body.as_ref().unwrap().stmts[0].span()
+                }
+                _ => unreachable!(),
+            };
+            assert_eq!(texts(&map, ret), vec!["inner"]);
+            assert_eq!(count(&map), 3);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn between_stmts() {
+        Tester::run(|tester| {
+            let module = tester.parse_module(
+                "input.js",
+                "a();
+// first
+/* second */
+b();
+export default c;",
+            )?;
+            let map = attach_comments(&module, &tester.comments);
+
+            assert_eq!(texts(&map, module.body[1].span()), vec!["first", "second"]);
+            assert_eq!(count(&map), 2);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn export() {
+        Tester::run(|tester| {
+            let module = tester.parse_module(
+                "input.js",
+                "// foo
+export function foo() {}",
+            )?;
+            let map = attach_comments(&module, &tester.comments);
+
+            assert_eq!(texts(&map, module.body[0].span()), vec!["foo"]);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn in_expr() {
+        Tester::run(|tester| {
+            let module = tester.parse_module(
+                "input.js",
+                "foo(
+    // arg
+    a
+);
+bar();",
+            )?;
+            let map = attach_comments(&module, &tester.comments);
+
+            assert_eq!(count(&map), 0);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn dangling() {
+        Tester::run(|tester| {
+            let module = tester.parse_module(
+                "input.js",
+                "function f() {
+    foo();
+    // end of block
+}
+// end of file",
+            )?;
+            let map = attach_comments(&module, &tester.comments);
+
+            assert_eq!(count(&map), 0);
+
+            Ok(())
+        });
+    }
+}
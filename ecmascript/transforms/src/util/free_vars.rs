@@ -0,0 +1,465 @@
+use super::Id;
+use crate::scope::ScopeKind;
+use ast::*;
+use hashbrown::HashSet;
+use indexmap::IndexSet;
+use swc_atoms::js_word;
+use swc_common::{Visit, VisitWith};
+
+/// Returns identifiers which are referenced in `node` but not declared in it,
+/// in the order they are first referenced.
+///
+/// A reference is bound by a declaration with the same symbol and syntax
+/// context in an enclosing scope, so shadowed variables are distinguished
+/// even if the resolver is not applied. `arguments` is bound in non-arrow
+/// functions.
+pub fn free_vars<N>(node: &N) -> IndexSet<Id>
+where
+    N: VisitWith<FreeVarCollector>,
+{
+    let mut v = FreeVarCollector {
+        scopes: vec![Scope {
+            kind: ScopeKind::Fn,
+            parent: None,
+            has_arguments: false,
+            decls: Default::default(),
+        }],
+        cur: 0,
+        refs: vec![],
+    };
+    node.visit_with(&mut v);
+
+    let FreeVarCollector { scopes, refs, .. } = v;
+    refs.into_iter()
+        .filter(|&(ref id, scope)| !is_declared(&scopes, scope, id))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+fn is_declared(scopes: &[Scope], mut scope: usize, id: &Id) -> bool {
+    loop {
+        let s = &scopes[scope];
+        if s.decls.contains(id) || (s.has_arguments && id.0 == js_word!("arguments")) {
+            return true;
+        }
+        match s.parent {
+            Some(parent) => scope = parent,
+            None => return false,
+        }
+    }
+}
+
+/// Records declarations of each scope and references with the scope they
+/// occur in. References are resolved after the visit, so that hoisted
+/// declarations bind references which precede them.
+pub struct FreeVarCollector {
+    scopes: Vec<Scope>,
+    cur: usize,
+    refs: Vec<(Id, usize)>,
+}
+
+struct Scope {
+    kind: ScopeKind,
+    parent: Option<usize>,
+    /// `true` for non-arrow functions.
+    has_arguments: bool,
+    decls: HashSet<Id>,
+}
+
+impl FreeVarCollector {
+    fn with_scope<F>(&mut self, kind: ScopeKind, has_arguments: bool, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let parent = self.cur;
+        self.scopes.push(Scope {
+            kind,
+            parent: Some(parent),
+            has_arguments,
+            decls: Default::default(),
+        });
+        self.cur = self.scopes.len() - 1;
+        op(self);
+        self.cur = parent;
+    }
+
+    /// `ScopeKind::Fn` declares `i` in the nearest function scope (like
+    /// `var`), and `ScopeKind::Block` in the current scope.
+    fn declare(&mut self, i: &Ident, kind: ScopeKind) {
+        let mut scope = self.cur;
+        if kind == ScopeKind::Fn {
+            while self.scopes[scope].kind != ScopeKind::Fn {
+                scope = self.scopes[scope].parent.unwrap();
+            }
+        }
+        self.scopes[scope]
+            .decls
+            .insert((i.sym.clone(), i.span.ctxt()));
+    }
+
+    fn reference(&mut self, i: &Ident) {
+        self.refs.push(((i.sym.clone(), i.span.ctxt()), self.cur));
+    }
+
+    /// Visits a binding pattern if `decl` is `Some`, and an assignment target
+    /// otherwise.
+    fn visit_pat(&mut self, p: &Pat, decl: Option<ScopeKind>) {
+        match *p {
+            Pat::Ident(ref i) => match decl {
+                Some(kind) => self.declare(i, kind),
+                None => self.reference(i),
+            },
+            Pat::Array(ArrayPat { ref elems, .. }) => {
+                for elem in elems.iter().filter_map(Option::as_ref) {
+                    self.visit_pat(elem, decl);
+                }
+            }
+            Pat::Object(ObjectPat { ref props, .. }) => {
+                for prop in props {
+                    match *prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { ref key, ref value }) => {
+                            key.visit_with(self);
+                            self.visit_pat(value, decl);
+                        }
+                        ObjectPatProp::Assign(AssignPatProp {
+                            ref key, ref value, ..
+                        }) => {
+                            match decl {
+                                Some(kind) => self.declare(key, kind),
+                                None => self.reference(key),
+                            }
+                            value.visit_with(self);
+                        }
+                        ObjectPatProp::Rest(RestPat { ref arg, .. }) => self.visit_pat(arg, decl),
+                    }
+                }
+            }
+            Pat::Rest(RestPat { ref arg, .. }) => self.visit_pat(arg, decl),
+            Pat::Assign(AssignPat {
+                ref left,
+                ref right,
+                ..
+            }) => {
+                self.visit_pat(left, decl);
+                right.visit_with(self);
+            }
+            Pat::Expr(ref e) => e.visit_with(self),
+            Pat::Invalid(..) => {}
+        }
+    }
+
+    fn visit_fn_body(&mut self, body: &Option<BlockStmt>) {
+        if let Some(ref body) = *body {
+            body.stmts.visit_with(self);
+        }
+    }
+}
+
+impl Visit<Expr> for FreeVarCollector {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Ident(ref i) => self.reference(i),
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<MemberExpr> for FreeVarCollector {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for FreeVarCollector {
+    fn visit(&mut self, n: &PropName) {
+        if let PropName::Computed(..) = *n {
+            n.visit_children(self)
+        }
+    }
+}
+
+impl Visit<Prop> for FreeVarCollector {
+    fn visit(&mut self, p: &Prop) {
+        match *p {
+            Prop::Shorthand(ref i) => self.reference(i),
+            Prop::Getter(GetterProp {
+                ref key, ref body, ..
+            }) => {
+                key.visit_with(self);
+                self.with_scope(ScopeKind::Fn, true, |v| v.visit_fn_body(body));
+            }
+            Prop::Setter(SetterProp {
+                ref key,
+                ref param,
+                ref body,
+                ..
+            }) => {
+                key.visit_with(self);
+                self.with_scope(ScopeKind::Fn, true, |v| {
+                    v.visit_pat(param, Some(ScopeKind::Block));
+                    v.visit_fn_body(body);
+                });
+            }
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<ClassProp> for FreeVarCollector {
+    fn visit(&mut self, p: &ClassProp) {
+        p.decorators.visit_with(self);
+        if p.computed {
+            p.key.visit_with(self);
+        }
+        p.value.visit_with(self);
+    }
+}
+
+/// Assignment targets. Binding patterns are handled by their parents.
+impl Visit<Pat> for FreeVarCollector {
+    fn visit(&mut self, p: &Pat) {
+        self.visit_pat(p, None)
+    }
+}
+
+impl Visit<VarDecl> for FreeVarCollector {
+    fn visit(&mut self, v: &VarDecl) {
+        let kind = match v.kind {
+            VarDeclKind::Var => ScopeKind::Fn,
+            VarDeclKind::Let | VarDeclKind::Const => ScopeKind::Block,
+        };
+
+        for decl in &v.decls {
+            self.visit_pat(&decl.name, Some(kind));
+            decl.init.visit_with(self);
+        }
+    }
+}
+
+impl Visit<FnDecl> for FreeVarCollector {
+    fn visit(&mut self, f: &FnDecl) {
+        self.declare(&f.ident, ScopeKind::Block);
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassDecl> for FreeVarCollector {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.declare(&c.ident, ScopeKind::Block);
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<FnExpr> for FreeVarCollector {
+    fn visit(&mut self, f: &FnExpr) {
+        self.with_scope(ScopeKind::Block, false, |v| {
+            if let Some(ref ident) = f.ident {
+                v.declare(ident, ScopeKind::Block);
+            }
+            f.function.visit_with(v);
+        });
+    }
+}
+
+impl Visit<ClassExpr> for FreeVarCollector {
+    fn visit(&mut self, c: &ClassExpr) {
+        self.with_scope(ScopeKind::Block, false, |v| {
+            if let Some(ref ident) = c.ident {
+                v.declare(ident, ScopeKind::Block);
+            }
+            c.class.visit_with(v);
+        });
+    }
+}
+
+impl Visit<ExportDefaultDecl> for FreeVarCollector {
+    fn visit(&mut self, e: &ExportDefaultDecl) {
+        match e.decl {
+            DefaultDecl::Fn(FnExpr {
+                ident: Some(ref ident),
+                ref function,
+            }) => {
+                self.declare(ident, ScopeKind::Block);
+                function.visit_with(self);
+            }
+            DefaultDecl::Class(ClassExpr {
+                ident: Some(ref ident),
+                ref class,
+            }) => {
+                self.declare(ident, ScopeKind::Block);
+                class.visit_with(self);
+            }
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Function> for FreeVarCollector {
+    fn visit(&mut self, f: &Function) {
+        f.decorators.visit_with(self);
+        self.with_scope(ScopeKind::Fn, true, |v| {
+            for param in &f.params {
+                v.visit_pat(param, Some(ScopeKind::Block));
+            }
+            v.visit_fn_body(&f.body);
+        });
+    }
+}
+
+impl Visit<ArrowExpr> for FreeVarCollector {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.with_scope(ScopeKind::Fn, false, |v| {
+            for param in &f.params {
+                v.visit_pat(param, Some(ScopeKind::Block));
+            }
+            match f.body {
+                BlockStmtOrExpr::BlockStmt(ref body) => body.stmts.visit_with(v),
+                BlockStmtOrExpr::Expr(ref body) => body.visit_with(v),
+            }
+        });
+    }
+}
+
+impl Visit<Constructor> for FreeVarCollector {
+    fn visit(&mut self, c: &Constructor) {
+        c.key.visit_with(self);
+        self.with_scope(ScopeKind::Fn, true, |v| {
+            for param in &c.params {
+                match *param {
+                    PatOrTsParamProp::Pat(ref p) => v.visit_pat(p, Some(ScopeKind::Block)),
+                    PatOrTsParamProp::TsParamProp(TsParamProp { ref param, .. }) => match *param {
+                        TsParamPropParam::Ident(ref i) => v.declare(i, ScopeKind::Block),
+                        TsParamPropParam::Assign(AssignPat {
+                            ref left,
+                            ref right,
+                            ..
+                        }) => {
+                            v.visit_pat(left, Some(ScopeKind::Block));
+                            right.visit_with(v);
+                        }
+                    },
+                }
+            }
+            v.visit_fn_body(&c.body);
+        });
+    }
+}
+
+impl Visit<BlockStmt> for FreeVarCollector {
+    fn visit(&mut self, b: &BlockStmt) {
+        self.with_scope(ScopeKind::Block, false, |v| b.stmts.visit_with(v));
+    }
+}
+
+impl Visit<CatchClause> for FreeVarCollector {
+    fn visit(&mut self, c: &CatchClause) {
+        self.with_scope(ScopeKind::Block, false, |v| {
+            if let Some(ref param) = c.param {
+                v.visit_pat(param, Some(ScopeKind::Block));
+            }
+            c.body.stmts.visit_with(v);
+        });
+    }
+}
+
+macro_rules! block_scoped {
+    ($T:ty) => {
+        impl Visit<$T> for FreeVarCollector {
+            fn visit(&mut self, n: &$T) {
+                self.with_scope(ScopeKind::Block, false, |v| n.visit_children(v));
+            }
+        }
+    };
+}
+
+block_scoped!(ForStmt);
+block_scoped!(ForInStmt);
+block_scoped!(ForOfStmt);
+
+impl Visit<SwitchStmt> for FreeVarCollector {
+    fn visit(&mut self, s: &SwitchStmt) {
+        s.discriminant.visit_with(self);
+        self.with_scope(ScopeKind::Block, false, |v| s.cases.visit_with(v));
+    }
+}
+
+impl Visit<ImportDecl> for FreeVarCollector {
+    fn visit(&mut self, i: &ImportDecl) {
+        for s in &i.specifiers {
+            let local = match *s {
+                ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+            };
+            self.declare(local, ScopeKind::Block);
+        }
+    }
+}
+
+impl Visit<NamedExport> for FreeVarCollector {
+    fn visit(&mut self, e: &NamedExport) {
+        // Names exported from another module are not references.
+        if e.src.is_some() {
+            return;
+        }
+
+        for s in &e.specifiers {
+            if let ExportSpecifier::Named(NamedExportSpecifier { ref orig, .. }) = *s {
+                self.reference(orig);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Tester;
+    use swc_atoms::JsWord;
+
+    fn free<F>(src: &str, op: F) -> Vec<JsWord>
+    where
+        F: FnOnce(&Module) -> IndexSet<Id>,
+    {
+        let mut result = None;
+        Tester::run(|tester| {
+            let module = tester.parse_module("input.js", src)?;
+            result = Some(op(&module).into_iter().map(|id| id.0).collect());
+            Ok(())
+        });
+        result.unwrap()
+    }
+
+    #[test]
+    fn capture() {
+        let src = "var x = 1; function f() { return x + foo(arguments); }";
+
+        assert_eq!(
+            free(src, |m| free_vars(&m.body[1])),
+            vec![JsWord::from("x"), JsWord::from("foo")]
+        );
+        assert_eq!(free(src, free_vars), vec![JsWord::from("foo")]);
+    }
+
+    #[test]
+    fn shadow() {
+        assert_eq!(
+            free(
+                "function f() { var x; return x + y; } { let z = x; } z;",
+                free_vars
+            ),
+            vec![JsWord::from("y"), JsWord::from("x"), JsWord::from("z")]
+        );
+    }
+
+    #[test]
+    fn catch_param() {
+        assert_eq!(
+            free("try {} catch (e) { e.foo = f; } e;", free_vars),
+            vec![JsWord::from("f"), JsWord::from("e")]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use ast::*;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    mem,
+};
+use swc_atoms::JsWord;
+use swc_common::{Span, Visit, VisitWith};
+
+/// Computes a digest of the structure of `module`.
+///
+/// Spans are ignored, so modules which differ only in formatting have the
+/// same hash, but syntax contexts are not. Unlike `SourceFile::src_hash`,
+/// this can be used to detect whether a pass changed the module.
+pub fn structural_hash(module: &Module) -> u64 {
+    let mut v = StructuralHasher(DefaultHasher::new());
+    module.visit_with(&mut v);
+    v.0.finish()
+}
+
+/// Returns true if `a` and `b` are the same, ignoring their positions.
+///
+/// Like [structural_hash], syntax contexts are compared, so identifiers
+/// referring to different bindings are not the same.
+pub(crate) fn eq_ignore_pos<T>(a: &T, b: &T) -> bool
+where
+    T: VisitWith<StructuralHasher<Bytes>>,
+{
+    let mut a_bytes = StructuralHasher(Bytes::default());
+    a.visit_with(&mut a_bytes);
+
+    let mut b_bytes = StructuralHasher(Bytes::default());
+    b.visit_with(&mut b_bytes);
+
+    (a_bytes.0).0 == (b_bytes.0).0
+}
+
+/// Feeds the structure of visited nodes into a [Hasher].
+///
+/// Variants of enums, lengths of vectors, identifiers, literals and syntax
+/// contexts are written, so trees with the same output have the same
+/// structure.
+pub(crate) struct StructuralHasher<H: Hasher>(H);
+
+/// A [Hasher] which keeps everything written to it, to compare structures
+/// without collisions.
+#[derive(Default)]
+pub(crate) struct Bytes(Vec<u8>);
+
+impl Hasher for Bytes {
+    fn finish(&self) -> u64 {
+        unreachable!("Bytes is only used to compare written bytes")
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes)
+    }
+}
+
+impl<T, H: Hasher> Visit<T> for StructuralHasher<H>
+where
+    T: VisitWith<Self>,
+{
+    default fn visit(&mut self, node: &T) {
+        mem::discriminant(node).hash(&mut self.0);
+        node.visit_children(self)
+    }
+}
+
+impl<T, H: Hasher> Visit<Vec<T>> for StructuralHasher<H>
+where
+    Vec<T>: VisitWith<Self>,
+{
+    fn visit(&mut self, nodes: &Vec<T>) {
+        nodes.len().hash(&mut self.0);
+        nodes.visit_children(self)
+    }
+}
+
+impl<H: Hasher> Visit<Span> for StructuralHasher<H> {
+    fn visit(&mut self, span: &Span) {
+        span.ctxt().hash(&mut self.0)
+    }
+}
+
+impl<H: Hasher> Visit<JsWord> for StructuralHasher<H> {
+    fn visit(&mut self, s: &JsWord) {
+        (**s).hash(&mut self.0)
+    }
+}
+
+impl<H: Hasher> Visit<Ident> for StructuralHasher<H> {
+    fn visit(&mut self, i: &Ident) {
+        (*i.sym).hash(&mut self.0);
+        i.optional.hash(&mut self.0);
+        i.visit_children(self)
+    }
+}
+
+impl<H: Hasher> Visit<Number> for StructuralHasher<H> {
+    fn visit(&mut self, n: &Number) {
+        n.value.to_bits().hash(&mut self.0);
+        n.visit_children(self)
+    }
+}
+
+/// Hashes fields which are not visited, like booleans.
+macro_rules! hash_fields {
+    ($T:ty, $($field:ident),+) => {
+        impl<H: Hasher> Visit<$T> for StructuralHasher<H> {
+            fn visit(&mut self, node: &$T) {
+                $(node.$field.hash(&mut self.0);)+
+                node.visit_children(self)
+            }
+        }
+    };
+}
+
+hash_fields!(Str, has_escape);
+hash_fields!(Bool, value);
+hash_fields!(UpdateExpr, prefix);
+hash_fields!(MemberExpr, computed);
+hash_fields!(ArrowExpr, is_async, is_generator);
+hash_fields!(YieldExpr, delegate);
+hash_fields!(TplElement, tail);
+hash_fields!(Function, is_async, is_generator);
+hash_fields!(Class, is_abstract);
+hash_fields!(
+    ClassProp,
+    is_static,
+    computed,
+    is_abstract,
+    is_optional,
+    readonly,
+    definite
+);
+hash_fields!(
+    PrivateProp,
+    is_static,
+    computed,
+    is_abstract,
+    is_optional,
+    readonly,
+    definite
+);
+hash_fields!(ClassMethod, kind, is_static, is_abstract, is_optional);
+hash_fields!(PrivateMethod, kind, is_static, is_abstract, is_optional);
+hash_fields!(Constructor, is_optional);
+hash_fields!(FnDecl, declare);
+hash_fields!(ClassDecl, declare);
+hash_fields!(VarDecl, declare);
+hash_fields!(VarDeclarator, definite);
+hash_fields!(JSXOpeningElement, self_closing);
+hash_fields!(TsParamProp, readonly);
+hash_fields!(TsPropertySignature, readonly, computed, optional);
+hash_fields!(TsMethodSignature, readonly, computed, optional);
+hash_fields!(TsIndexSignature, readonly);
+hash_fields!(TsIndexedAccessType, readonly);
+hash_fields!(TsInterfaceDecl, declare);
+hash_fields!(TsTypeAliasDecl, declare);
+hash_fields!(TsEnumDecl, declare, is_const);
+hash_fields!(TsModuleDecl, declare, global);
+hash_fields!(TsNamespaceDecl, declare, global);
+hash_fields!(TsImportEqualsDecl, declare, is_export);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{resolver, tests::Tester};
+    use swc_common::FoldWith;
+
+    #[test]
+    fn ignores_spans() {
+        Tester::run(|tester| {
+            let a = tester.parse_module("a.js", "function foo(a) { return a + 1; }")?;
+            let b = tester.parse_module(
+                "b.js",
+                "function foo ( a )
+{
+    return a+1;
+}",
+            )?;
+
+            assert_eq!(structural_hash(&a), structural_hash(&b));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn detects_changes() {
+        Tester::run(|tester| {
+            let a = tester.parse_module("a.js", "function foo(a) { return a + 1; }")?;
+            let b = tester.parse_module("b.js", "function foo(a) { return a + 2; }")?;
+            let c = tester.parse_module("c.js", "function foo(a) { return a - 1; }")?;
+
+            assert_ne!(structural_hash(&a), structural_hash(&b));
+            assert_ne!(structural_hash(&a), structural_hash(&c));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn includes_syntax_context() {
+        Tester::run(|tester| {
+            let a = tester.parse_module("a.js", "let a = 1; { let a = 2; }")?;
+            let b = a.clone().fold_with(&mut resolver());
+
+            assert_ne!(structural_hash(&a), structural_hash(&b));
+            Ok(())
+        })
+    }
+}
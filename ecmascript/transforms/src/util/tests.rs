@@ -0,0 +1,80 @@
+use super::{Assumptions, ExprExt};
+use crate::tests::Tester;
+use ast::*;
+
+fn classify(src: &str, assumptions: Assumptions) -> bool {
+    let mut result = None;
+    Tester::run(|tester| {
+        let e = match tester.parse_stmt("input.js", src)? {
+            Stmt::Expr(e) => e,
+            stmt => panic!("{} is not an expression statement: {:?}", src, stmt),
+        };
+        result = Some(e.may_have_side_effects_with(&assumptions));
+        Ok(())
+    });
+    result.unwrap()
+}
+
+#[test]
+fn may_have_side_effects() {
+    let pure_getters = Assumptions { pure_getters: true };
+
+    let cases: &[(&str, bool, bool)] = &[
+        // (source, default, with pure_getters)
+        ("1", false, false),
+        ("'foo'", false, false),
+        ("null", false, false),
+        ("/foo/g", false, false),
+        ("a", false, false),
+        ("this", false, false),
+        ("1 + 2 * a", false, false),
+        ("-a", false, false),
+        ("!a", false, false),
+        ("typeof a", false, false),
+        ("void 0", false, false),
+        ("a ? b : c", false, false),
+        ("(a, b)", false, false),
+        ("`a${b}`", false, false),
+        ("[a, b]", false, false),
+        ("[...a]", true, true),
+        ("({ a: 1, b })", false, false),
+        ("({ [a]: 1, get b() {} })", false, false),
+        ("({ [f()]: 1 })", true, true),
+        ("({ ...a })", true, false),
+        ("(function () { f() })", false, false),
+        ("() => f()", false, false),
+        ("f()", true, true),
+        ("new Foo()", true, true),
+        ("a.b", true, false),
+        ("a[b]", true, false),
+        ("a.b.c", true, false),
+        ("a[f()]", true, true),
+        ("f().a", true, true),
+        ("a = 1", true, true),
+        ("a += 1", true, true),
+        ("a++", true, true),
+        ("delete a.b", true, true),
+        ("tag`a`", true, true),
+        ("(class {})", false, false),
+        ("(class { foo() {} static bar() {} })", false, false),
+        ("(class { [f()]() {} })", true, true),
+        ("(class extends A {})", true, true),
+        ("a + f()", true, true),
+        ("a ? f() : b", true, true),
+    ];
+
+    for &(src, default, with_pure_getters) in cases {
+        assert_eq!(
+            classify(src, Default::default()),
+            default,
+            "{} with default assumptions",
+            src
+        );
+        assert_eq!(
+            classify(src, pure_getters),
+            with_pure_getters,
+            "{} with pure_getters",
+            src
+        );
+    }
+}
@@ -5,7 +5,9 @@ use ecmascript::{
     ast::Program,
     parser::Syntax,
     transforms::{
-        chain_at, compat, const_modules, fixer, helpers, hygiene, modules,
+        chain_at,
+        compat::{self, es2020::big_int},
+        const_modules, fixer, helpers, hygiene, modules,
         pass::{JoinedPass, Optional, Pass},
         typescript,
     },
@@ -20,6 +22,8 @@ pub struct PassBuilder<'a, 'b, P: Pass> {
     pass: P,
     target: JscTarget,
     loose: bool,
+    spec_method_enumerability: bool,
+    big_int: big_int::Mode,
 }
 
 impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
@@ -30,6 +34,8 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             pass,
             target: JscTarget::Es5,
             loose,
+            spec_method_enumerability: true,
+            big_int: Default::default(),
         }
     }
 
@@ -41,6 +47,8 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             pass,
             target: self.target,
             loose: self.loose,
+            spec_method_enumerability: self.spec_method_enumerability,
+            big_int: self.big_int,
         }
     }
 
@@ -65,6 +73,23 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
         self
     }
 
+    /// Emit class methods as non-enumerable properties, as required by the
+    /// spec. If disabled, methods are assigned to the prototype.
+    ///
+    /// Defaults to `true`.
+    pub fn spec_method_enumerability(mut self, enabled: bool) -> Self {
+        self.spec_method_enumerability = enabled;
+        self
+    }
+
+    /// What to do with BigInt literals if the target doesn't support them.
+    ///
+    /// Defaults to [big_int::Mode::Error].
+    pub fn big_int(mut self, mode: big_int::Mode) -> Self {
+        self.big_int = mode;
+        self
+    }
+
     /// # Arguments
     /// ## module
     ///  - Use `None` if you want swc to emit import statements.
@@ -89,7 +114,23 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             Program,
             self.pass,
             // compat
-            Optional::new(compat::es2018(), self.target <= JscTarget::Es2018),
+            Optional::new(compat::es2021(), self.target <= JscTarget::Es2020),
+            Optional::new(
+                compat::es2020(compat::es2020::Config {
+                    big_int: big_int::Config {
+                        big_int: self.big_int
+                    },
+                }),
+                self.target <= JscTarget::Es2019
+            ),
+            Optional::new(
+                compat::es2018(compat::es2018::Config {
+                    object_rest_spread: compat::es2018::object_rest_spread::Config {
+                        use_builtins: self.loose && self.target >= JscTarget::Es2015
+                    },
+                }),
+                self.target <= JscTarget::Es2018
+            ),
             Optional::new(compat::es2017(), self.target <= JscTarget::Es2017),
             Optional::new(compat::es2016(), self.target <= JscTarget::Es2016),
             Optional::new(
@@ -98,7 +139,18 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
                         assume_array: self.loose
                     },
                     spread: compat::es2015::spread::Config { loose: self.loose },
-                    destructuring: compat::es2015::destructuring::Config { loose: self.loose },
+                    destructuring: compat::es2015::destructuring::Config {
+                        loose: self.loose,
+                        ..Default::default()
+                    },
+                    classes: compat::es2015::classes::Config {
+                        spec_method_enumerability: self.spec_method_enumerability
+                    },
+                    template_literal: compat::es2015::template_literal::Config {
+                        spec: !self.loose
+                    },
+                    keep_unicode_regex: self.target >= JscTarget::Es2015,
+                    keep_sticky_regex: self.target >= JscTarget::Es2015,
                 }),
                 self.target <= JscTarget::Es2015
             ),
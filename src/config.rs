@@ -5,13 +5,16 @@ use common::{errors::Handler, FileName, SourceMap};
 pub use ecmascript::parser::JscTarget;
 use ecmascript::{
     ast::{Expr, ModuleItem, Program, Stmt},
-    parser::{Parser, Session as ParseSess, SourceFileInput, Syntax},
+    codegen,
+    parser::{Parser, ProgramKind, Session as ParseSess, SourceFileInput, Syntax},
     transforms::{
         chain_at, const_modules, modules,
-        optimization::JsonParse,
+        optimization::{drop_console, JsonParse},
         pass::{noop, Optional, Pass},
         proposals::{class_properties, decorators, export},
-        react, resolver, simplifier, typescript, InlineGlobals,
+        react, resolver, simplifier, typescript,
+        util::Assumptions,
+        InlineGlobals,
     },
 };
 use hashbrown::{HashMap, HashSet};
@@ -84,6 +87,10 @@ pub struct Options {
 
     #[serde(default = "default_is_module")]
     pub is_module: bool,
+
+    /// Kind of the input program. Overrides `is_module` if set.
+    #[serde(default)]
+    pub program_kind: Option<ProgramKind>,
 }
 
 fn default_is_module() -> bool {
@@ -117,11 +124,19 @@ impl Default for InputSourceMap {
 }
 
 impl Options {
+    pub fn program_kind(&self) -> ProgramKind {
+        match self.program_kind {
+            Some(kind) => kind,
+            None if self.is_module => ProgramKind::Module,
+            None => ProgramKind::Script,
+        }
+    }
+
     pub fn build(
         &self,
         cm: &Arc<SourceMap>,
         handler: &Handler,
-        is_module: bool,
+        program_kind: ProgramKind,
         config: Option<Config>,
     ) -> BuiltConfig<impl Pass> {
         let mut config = config.unwrap_or_else(Default::default);
@@ -151,6 +166,10 @@ impl Options {
 
         let optimizer = transform.optimizer;
         let enable_optimizer = optimizer.is_some();
+        let assumptions = optimizer
+            .as_ref()
+            .map(|o| o.assumptions)
+            .unwrap_or_default();
         let json_parse_pass = {
             if let Some(ref cfg) = optimizer.as_ref().and_then(|v| v.jsonify) {
                 JsonParse {
@@ -163,6 +182,19 @@ impl Options {
             }
         };
 
+        let drop_console_pass = {
+            let enabled = optimizer
+                .as_ref()
+                .map(|o| o.drop_console.is_some())
+                .unwrap_or(false);
+            let config = optimizer
+                .as_ref()
+                .and_then(|o| o.drop_console.clone())
+                .unwrap_or_default();
+
+            Optional::new(drop_console(config), enabled)
+        };
+
         let pass = if let Some(opts) = optimizer.map(|o| o.globals.unwrap_or_else(Default::default))
         {
             opts.build(cm, handler)
@@ -186,7 +218,8 @@ impl Options {
                 export(),
                 syntax.export_default_from() || syntax.export_namespace_from()
             ),
-            Optional::new(simplifier(), enable_optimizer),
+            Optional::new(simplifier(assumptions), enable_optimizer),
+            drop_console_pass,
             json_parse_pass
         );
 
@@ -196,10 +229,15 @@ impl Options {
 
         BuiltConfig {
             minify: config.minify.unwrap_or(false),
+            line_ending: config
+                .line_ending
+                .map(From::from)
+                .unwrap_or_default(),
+            preserve_annotations: config.preserve_annotations.unwrap_or(false),
             pass,
             external_helpers,
             syntax,
-            is_module,
+            program_kind,
             source_maps: self
                 .source_maps
                 .as_ref()
@@ -327,6 +365,36 @@ pub struct Config {
 
     #[serde(default)]
     pub minify: Option<bool>,
+
+    #[serde(default)]
+    pub line_ending: Option<LineEnding>,
+
+    /// Keep license comments (`/*! ... */`, `@license` and `@preserve`), even
+    /// if `minify` is enabled.
+    #[serde(default)]
+    pub preserve_annotations: Option<bool>,
+}
+
+/// Line terminator of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineEnding {
+    #[serde(rename = "lf")]
+    Lf,
+    #[serde(rename = "crlf")]
+    CrLf,
+    /// Use the line terminator of the input file.
+    #[serde(rename = "auto")]
+    FromSource,
+}
+
+impl From<LineEnding> for codegen::LineEnding {
+    fn from(line_ending: LineEnding) -> Self {
+        match line_ending {
+            LineEnding::Lf => codegen::LineEnding::Lf,
+            LineEnding::CrLf => codegen::LineEnding::CrLf,
+            LineEnding::FromSource => codegen::LineEnding::FromSource,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -404,9 +472,11 @@ pub struct BuiltConfig<P: Pass> {
     pub pass: P,
     pub syntax: Syntax,
     pub minify: bool,
+    pub line_ending: codegen::LineEnding,
+    pub preserve_annotations: bool,
     pub external_helpers: bool,
     pub source_maps: bool,
-    pub is_module: bool,
+    pub program_kind: ProgramKind,
 }
 
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -479,6 +549,12 @@ pub struct OptimizerConfig {
 
     #[serde(default)]
     pub jsonify: Option<JsonifyOption>,
+
+    #[serde(default)]
+    pub drop_console: Option<drop_console::Config>,
+
+    #[serde(default)]
+    pub assumptions: Assumptions,
 }
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
@@ -605,7 +681,9 @@ impl Merge for Config {
     fn merge(&mut self, from: &Self) {
         self.jsc.merge(&from.jsc);
         self.module.merge(&from.module);
-        self.minify.merge(&from.minify)
+        self.minify.merge(&from.minify);
+        self.line_ending.merge(&from.line_ending);
+        self.preserve_annotations.merge(&from.preserve_annotations);
     }
 }
 
@@ -635,6 +713,12 @@ impl Merge for Option<ModuleConfig> {
     }
 }
 
+impl Merge for LineEnding {
+    fn merge(&mut self, from: &Self) {
+        *self = *from;
+    }
+}
+
 impl Merge for bool {
     fn merge(&mut self, from: &Self) {
         *self |= *from
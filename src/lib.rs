@@ -21,7 +21,7 @@ use common::{
 use ecmascript::{
     ast::Program,
     codegen::{self, Emitter},
-    parser::{Parser, Session as ParseSess, Syntax},
+    parser::{Parser, ProgramKind, Session as ParseSess, Syntax},
     transforms::{
         helpers::{self, Helpers},
         util,
@@ -69,7 +69,7 @@ impl Compiler {
         &self,
         fm: Arc<SourceFile>,
         syntax: Syntax,
-        is_module: bool,
+        program_kind: ProgramKind,
         comments: Option<&Comments>,
     ) -> Result<Program, Error> {
         self.run(|| {
@@ -77,35 +77,25 @@ impl Compiler {
                 handler: &self.handler,
             };
             let mut parser = Parser::new(session, syntax, SourceFileInput::from(&*fm), comments);
-            let program = if is_module {
-                parser
-                    .parse_module()
-                    .map_err(|mut e| {
-                        e.emit();
-                        Error::FailedToParseModule {}
-                    })
-                    .map(Program::Module)?
-            } else {
-                parser
-                    .parse_script()
-                    .map_err(|mut e| {
-                        e.emit();
-                        Error::FailedToParseModule {}
-                    })
-                    .map(Program::Script)?
-            };
+            let program = parser.parse_program(program_kind).map_err(|mut e| {
+                e.emit();
+                Error::FailedToParseModule {}
+            })?;
 
             Ok(program)
         })
     }
 
+    /// Prints `program`. `comments` are printed unless `cfg.minify` is set,
+    /// in which case only annotations are kept if `cfg.preserve_annotations`
+    /// is set.
     pub fn print(
         &self,
         program: &Program,
         fm: Arc<SourceFile>,
         comments: &Comments,
         source_map: bool,
-        minify: bool,
+        cfg: codegen::Config,
     ) -> Result<TransformOutput, Error> {
         self.run(|| {
             let mut src_map_builder = SourceMapBuilder::new(None);
@@ -122,9 +112,10 @@ impl Compiler {
                 let mut buf = vec![];
                 {
                     let handlers = box MyHandlers;
+                    let keep_comments = !cfg.minify || cfg.preserve_annotations;
                     let mut emitter = Emitter {
-                        cfg: codegen::Config { minify },
-                        comments: if minify { None } else { Some(&comments) },
+                        cfg,
+                        comments: if keep_comments { Some(&comments) } else { None },
                         cm: self.cm.clone(),
                         wr: box codegen::text_writer::JsWriter::new(
                             self.cm.clone(),
@@ -187,9 +178,9 @@ impl Compiler {
             root_mode,
             swcrc,
             config_file,
-            is_module,
             ..
         } = opts;
+        let program_kind = opts.program_kind();
         let root = root
             .clone()
             .unwrap_or_else(|| ::std::env::current_dir().unwrap());
@@ -222,7 +213,7 @@ impl Compiler {
                                 config.merge(&config_file.into_config(Some(path))?)
                             }
                             let built =
-                                opts.build(&self.cm, &self.handler, *is_module, Some(config));
+                                opts.build(&self.cm, &self.handler, program_kind, Some(config));
                             return Ok(built);
                         }
 
@@ -237,7 +228,7 @@ impl Compiler {
                     let built = opts.build(
                         &self.cm,
                         &self.handler,
-                        *is_module,
+                        program_kind,
                         Some(config_file.into_config(Some(path))?),
                     );
                     return Ok(built);
@@ -249,7 +240,7 @@ impl Compiler {
         let built = opts.build(
             &self.cm,
             &self.handler,
-            *is_module,
+            program_kind,
             match config_file {
                 Some(config_file) => Some(config_file.into_config(None)?),
                 None => None,
@@ -285,8 +276,12 @@ impl Compiler {
             let module = self.parse_js(
                 fm.clone(),
                 config.syntax,
-                config.is_module,
-                if config.minify { None } else { Some(&comments) },
+                config.program_kind,
+                if config.minify && !config.preserve_annotations {
+                    None
+                } else {
+                    Some(&comments)
+                },
             )?;
             let mut pass = config.pass;
             let module = helpers::HELPERS.set(&Helpers::new(config.external_helpers), || {
@@ -296,7 +291,19 @@ impl Compiler {
                 })
             });
 
-            self.print(&module, fm, &comments, config.source_maps, config.minify)
+            self.print(
+                &module,
+                fm,
+                &comments,
+                config.source_maps,
+                codegen::Config {
+                    minify: config.minify,
+                    preserve_annotations: config.preserve_annotations,
+                    final_newline: !config.minify,
+                    line_ending: config.line_ending,
+                    ..Default::default()
+                },
+            )
         })
     }
 }
@@ -1,5 +1,5 @@
 use std::path::Path;
-use swc::{config::Options, Compiler};
+use swc::{config::Options, ecmascript::parser::ProgramKind, Compiler};
 use testing::{NormalizedOutput, StdErr, Tester};
 use walkdir::WalkDir;
 
@@ -177,3 +177,65 @@ fn issue_467() {
 fn issue_468() {
     file("tests/projects/issue-468/input.ts").expect("failed to parse typescript");
 }
+
+/// should respect `lineEnding` in .swcrc
+#[test]
+fn line_ending_crlf() {
+    let code = Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm
+                .load_file(Path::new("tests/projects/line-ending-crlf/input.js"))
+                .expect("failed to load file");
+            c.process_js_file(
+                fm,
+                &Options {
+                    swcrc: true,
+                    is_module: true,
+                    ..Default::default()
+                },
+            )
+            .map(|v| v.code)
+            .map_err(|_| ())
+        })
+        .unwrap();
+
+    assert_eq!(code.matches("\r\n").count(), 2);
+    assert_eq!(code.matches('\n').count(), 2);
+}
+
+/// should keep license comments if `preserveAnnotations` is set
+#[test]
+fn preserve_annotations_minify() {
+    let s = file("tests/projects/preserve-annotations/input.js").unwrap();
+    println!("{}", s);
+
+    assert!(s.contains("/*! license */"));
+    assert!(!s.contains("normal"));
+}
+
+/// should allow top level `return` if `programKind` is `commonjs`
+#[test]
+fn program_kind_commonjs() {
+    let s = Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm
+                .load_file(Path::new("tests/projects/program-kind-commonjs/input.js"))
+                .expect("failed to load file");
+            c.process_js_file(
+                fm,
+                &Options {
+                    program_kind: Some(ProgramKind::CommonJs),
+                    ..Default::default()
+                },
+            )
+            .map(|v| v.code)
+            .map_err(|_| ())
+        })
+        .unwrap();
+
+    assert!(s.contains("return;"));
+}
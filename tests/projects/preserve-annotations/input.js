@@ -0,0 +1,4 @@
+/*! license */
+foo();
+/* normal */
+bar();
@@ -0,0 +1,4 @@
+if (foo) {
+    return;
+}
+bar();